@@ -0,0 +1,79 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use rutilelib::bench_utils::{gemm_flops, random_matrix_f32};
+use rutilelib::blas::{GenericBlas, NativeBlas};
+use rutilelib::gemm::{gemm_f32, gemm_f32_tiled_parallel};
+use rutilelib::layout::Layout;
+use rutilelib::shape::Shape;
+use rutilelib::tensor::Tensor;
+use rutilelib::tiled_tensor::{StaticTiler, TiledTensorView};
+use rutilelib::tuple::Tuple;
+
+const SIZES: [usize; 3] = [128, 256, 512];
+const TILES: [usize; 3] = [32, 64, 128];
+
+fn bench_gemm(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gemm_f32");
+    group.sample_size(10);
+
+    for &n in &SIZES {
+        let a = random_matrix_f32(n, n, 1);
+        let b = random_matrix_f32(n, n, 2);
+        let mut out = Tensor::new(vec![0.0; n * n], Layout::row_major(Shape::new(Tuple::int(vec![n, n]))));
+
+        group.throughput(Throughput::Elements(gemm_flops(n, n, n) as u64));
+
+        if GenericBlas::is_available() {
+            group.bench_with_input(BenchmarkId::new("generic_blas", n), &n, |bench, _| {
+                bench.iter(|| gemm_f32(&GenericBlas, &a.as_view(), &b.as_view(), &mut out.as_view_mut(), 1.0, 0.0))
+            });
+        }
+
+        group.bench_with_input(BenchmarkId::new("native", n), &n, |bench, _| {
+            bench.iter(|| gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut out.as_view_mut(), 1.0, 0.0))
+        });
+
+        for &t in TILES.iter().filter(|&&t| t < n) {
+            let tiler = Layout::row_major(Shape::new(Tuple::int(vec![t, t])));
+            let id = BenchmarkId::new(format!("tiled_parallel_native/{t}x{t}"), n);
+            group.bench_with_input(id, &n, |bench, _| {
+                bench.iter(|| {
+                    gemm_f32_tiled_parallel(
+                        &NativeBlas, &a.as_view(), &b.as_view(), &mut out.as_view_mut(), Some(&tiler), 1.0, 0.0,
+                    )
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Sum every element tile by tile: the dynamic `Tile` path against `StaticTiler`
+fn bench_tiling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tile_sum_8x8");
+    let n = 512;
+    let a = random_matrix_f32(n, n, 3);
+    group.throughput(Throughput::Elements((n * n) as u64));
+
+    group.bench_function("dynamic", |bench| {
+        bench.iter(|| {
+            let mut tiled = TiledTensorView::new(a.as_view(), StaticTiler::<8, 8>.layout());
+            tiled.tiles().map(|(_, view)| view.indexed_iter().map(|(_, x)| *x).sum::<f32>()).sum::<f32>()
+        })
+    });
+
+    group.bench_function("static", |bench| {
+        bench.iter(|| {
+            let tiled = TiledTensorView::new_static(a.as_view(), StaticTiler::<8, 8>);
+            let full: f32 = tiled.full_tiles().map(|tile| tile.load().iter().flatten().sum::<f32>()).sum();
+            let edges: f32 = tiled.edge_tiles().map(|(_, view)| view.indexed_iter().map(|(_, x)| *x).sum::<f32>()).sum();
+            full + edges
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_gemm, bench_tiling);
+criterion_main!(benches);
@@ -5,8 +5,8 @@ use rutilelib::tuple::Tuple;
 use rutilelib::blas::{BlasBackend, GenericBlas};
 use rutilelib::gemm::gemm_f32;
 use rutilelib::tiled_tensor::TiledTensorViewMut;
-
-use rand::Rng;
+use rutilelib::random::{fill_uniform, seeded};
+use rutilelib::reference;
 
 fn tiled_gemm<B: BlasBackend>(
     backend: &B,
@@ -36,20 +36,19 @@ fn tiled_gemm<B: BlasBackend>(
 fn main() {
     let (m, k, n) = (64, 32, 48);
 
-    let mut rng = rand::thread_rng();
-
-    let a_data: Vec<f32> = (0..m*k).map(|_| rng.gen_range(-1.0..1.0)).collect();
-    let b_data: Vec<f32> = (0..k*n).map(|_| rng.gen_range(-1.0..1.0)).collect();
+    let mut rng = seeded(42);
 
-    let a = Tensor::new(
-        a_data,
+    let mut a = Tensor::new(
+        vec![0.0; m*k],
         Layout::row_major(Shape::new(Tuple::int(vec![m, k]))),
     );
+    fill_uniform(&mut a.as_view_mut(), -1.0, 1.0, &mut rng);
 
-    let b = Tensor::new(
-        b_data,
+    let mut b = Tensor::new(
+        vec![0.0; k*n],
         Layout::row_major(Shape::new(Tuple::int(vec![k, n]))),
     );
+    fill_uniform(&mut b.as_view_mut(), -1.0, 1.0, &mut rng);
 
     let mut c_tiled = Tensor::new(
         vec![0.0; m*n],
@@ -64,7 +63,7 @@ fn main() {
     let backend = GenericBlas;
 
     tiled_gemm(&backend, a.as_view(), b.as_view(), c_tiled.as_view_mut(), 16, 16);
-    gemm_f32(&backend, &a.as_view(), &b.as_view(), &mut c_ref.as_view_mut(), 1.0, 0.0);
+    reference::gemm(&a.as_view(), &b.as_view(), &mut c_ref.as_view_mut(), 1.0, 0.0);
 
     unsafe {
         rutilelib::gemm::assert_close_f32(
@@ -75,6 +74,6 @@ fn main() {
         );
     }
 
-    println!("Tiled GEMM matches BLAS reference");
+    println!("Tiled GEMM matches the scalar reference");
 }
 
@@ -0,0 +1,55 @@
+// Decodes a small layout and tiler from the input and checks every
+// invariant in `rutilelib::invariants`. Panics inside the algebra and
+// `Error::Invariant` results are both reported as crashes.
+//
+//   cargo +nightly fuzz run layout_algebra
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rutilelib::error::Error;
+use rutilelib::invariants::check_layout_invariants;
+use rutilelib::layout::Layout;
+use rutilelib::shape::Shape;
+use rutilelib::tuple::Tuple;
+
+/// Extents stay below 6 so the checks, which visit every coordinate, stay fast
+fn decode(data: &[u8]) -> Option<(Layout, Layout)> {
+    let mut bytes = data.iter().copied();
+    let mut next = || bytes.next();
+    let header = next()?;
+    let rank = 1 + header as usize % 3;
+    let extents: Vec<usize> = (0..rank).map(|_| next().map(|b| b as usize % 6)).collect::<Option<_>>()?;
+
+    // Bits 2-3 of the header pick row-major, column-major or explicit strides
+    let layout = match (header >> 2) % 4 {
+        0 => Layout::row_major(extents.clone()),
+        1 => Layout::col_major(extents.clone()),
+        _ => {
+            let strides: Vec<usize> = (0..rank).map(|_| next().map(|b| b as usize % 8)).collect::<Option<_>>()?;
+            if header & 0x10 != 0 && rank >= 2 {
+                // Group the first two modes: ((e0, e1), e2, ...)
+                let group = |v: &[usize]| {
+                    let mut modes = vec![Tuple::int(v[..2].to_vec())];
+                    modes.extend(v[2..].iter().map(|&x| Tuple::int1(x)));
+                    Tuple::tup(modes)
+                };
+                Layout::row_major(Shape::new(group(&extents))).with_stride(group(&strides))
+            } else {
+                Layout::row_major(extents.clone()).with_stride(strides)
+            }
+        }
+    };
+    let flips = next()?;
+    let layout = (0..rank).filter(|m| flips & (1 << m) != 0).fold(layout, |l, m| l.flip(m));
+
+    // Tile extents of 0 exercise the error path of the divides
+    let tile: Vec<usize> = (0..rank).map(|_| next().map(|b| b as usize % 5)).collect::<Option<_>>()?;
+    Some((layout, Layout::row_major(tile)))
+}
+
+fuzz_target!(|data: &[u8]| {
+    let Some((layout, tiler)) = decode(data) else { return };
+    if let Err(e @ Error::Invariant { .. }) = check_layout_invariants(&layout, &tiler) {
+        panic!("{e}");
+    }
+});
@@ -0,0 +1,85 @@
+use std::alloc::Layout as AllocLayout;
+
+/// Source of backing memory for `Tensor::new_in`.
+///
+/// # Safety
+/// `allocate` must return memory valid for `layout` (or null on failure), and
+/// `deallocate` must accept exactly the pointers/layouts `allocate` produced.
+pub unsafe trait TensorAlloc: Send + Sync {
+    fn allocate(&self, layout: AllocLayout) -> *mut u8;
+
+    /// # Safety
+    /// `ptr` must have been returned by `allocate` with the same `layout`.
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: AllocLayout);
+
+    /// Alignment this allocator guarantees, at least the element alignment
+    fn align(&self, elem_align: usize) -> usize {
+        elem_align
+    }
+}
+
+/// Global allocator, element alignment (same as `Vec`)
+pub struct Global;
+
+unsafe impl TensorAlloc for Global {
+    fn allocate(&self, layout: AllocLayout) -> *mut u8 {
+        unsafe { std::alloc::alloc(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: AllocLayout) {
+        std::alloc::dealloc(ptr, layout)
+    }
+}
+
+pub const PAGE_SIZE: usize = 4096;
+
+/// Page-aligned allocations, suitable for DMA staging buffers
+pub struct PageAligned;
+
+unsafe impl TensorAlloc for PageAligned {
+    fn allocate(&self, layout: AllocLayout) -> *mut u8 {
+        unsafe { std::alloc::alloc(layout) }
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: AllocLayout) {
+        std::alloc::dealloc(ptr, layout)
+    }
+
+    fn align(&self, elem_align: usize) -> usize {
+        elem_align.max(PAGE_SIZE)
+    }
+}
+
+/// Page-aligned allocations locked into physical memory (`mlock`) so they
+/// cannot be paged out during transfers. Locking is best-effort: if the OS
+/// refuses (e.g. `RLIMIT_MEMLOCK`), the memory is still returned, unlocked.
+pub struct Pinned;
+
+#[cfg(unix)]
+extern "C" {
+    fn mlock(addr: *const std::ffi::c_void, len: usize) -> i32;
+    fn munlock(addr: *const std::ffi::c_void, len: usize) -> i32;
+}
+
+unsafe impl TensorAlloc for Pinned {
+    fn allocate(&self, layout: AllocLayout) -> *mut u8 {
+        let ptr = PageAligned.allocate(layout);
+        #[cfg(unix)]
+        if !ptr.is_null() {
+            unsafe {
+                mlock(ptr as *const _, layout.size());
+            }
+        }
+        ptr
+    }
+
+    unsafe fn deallocate(&self, ptr: *mut u8, layout: AllocLayout) {
+        #[cfg(unix)]
+        munlock(ptr as *const _, layout.size());
+        PageAligned.deallocate(ptr, layout)
+    }
+
+    fn align(&self, elem_align: usize) -> usize {
+        PageAligned.align(elem_align)
+    }
+}
@@ -0,0 +1,141 @@
+// src/arrow.rs
+//
+// Tensor views over the values of Arrow primitive arrays and raw buffers,
+// so columnar data reaches the GEMM / reduction kernels without a copy,
+// and the conversion of results back into Arrow arrays.
+
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, PrimitiveArray};
+use arrow_buffer::{ArrowNativeType, Buffer, ScalarBuffer};
+
+use crate::error::{Error, Result};
+use crate::layout::{Contiguity, Layout};
+use crate::shape::Shape;
+use crate::tensor::{Tensor, TensorView};
+use crate::tuple::Tuple;
+
+/// Whether every element `layout` reaches lies in `0..len`
+fn fits(layout: &Layout, len: usize) -> bool {
+    if layout.has_reversed_modes() {
+        return false;
+    }
+    let flat = layout.shape().dims.flatten();
+    if flat.contains(&0) {
+        return true;
+    }
+    let last: usize = flat.iter().zip(layout.stride().flatten()).map(|(&n, s)| (n - 1) * s).sum();
+    last < len
+}
+
+fn view_of<'a, T>(op: &'static str, values: &'a [T], layout: Layout) -> Result<TensorView<'a, T>> {
+    if !fits(&layout, values.len()) {
+        return Err(Error::ShapeMismatch {
+            op,
+            lhs: Shape::new(Tuple::int(vec![values.len()])),
+            rhs: layout.shape().clone(),
+        });
+    }
+    Ok(unsafe { TensorView::from_raw(values.as_ptr(), layout) })
+}
+
+/// The values of `array` seen through `layout`. Arrays with null slots are
+/// rejected: the values behind a null are unspecified.
+pub fn array_view<T: ArrowPrimitiveType>(array: &PrimitiveArray<T>, layout: Layout) -> Result<TensorView<'_, T::Native>> {
+    const OP: &str = "arrow::array_view";
+    if array.null_count() > 0 {
+        return Err(Error::Unsupported { op: OP, what: "an array with null slots".into() });
+    }
+    view_of(OP, array.values(), layout)
+}
+
+/// `array` as a rank-1 view of its length
+pub fn vector_view<T: ArrowPrimitiveType>(array: &PrimitiveArray<T>) -> Result<TensorView<'_, T::Native>> {
+    array_view(array, Layout::row_major([array.len()]))
+}
+
+/// `array` as a row-major `rows x cols` matrix
+pub fn matrix_view<T: ArrowPrimitiveType>(
+    array: &PrimitiveArray<T>,
+    rows: usize,
+    cols: usize,
+) -> Result<TensorView<'_, T::Native>> {
+    if array.len() != rows * cols {
+        return Err(Error::ShapeMismatch {
+            op: "arrow::matrix_view",
+            lhs: Shape::new(Tuple::int(vec![array.len()])),
+            rhs: Shape::new(Tuple::int(vec![rows, cols])),
+        });
+    }
+    array_view(array, Layout::row_major([rows, cols]))
+}
+
+/// A raw Arrow buffer read as elements of `T` through `layout`. Fails if
+/// the buffer is not aligned for `T` or too short for the layout.
+pub fn buffer_view<T: ArrowNativeType>(buffer: &Buffer, layout: Layout) -> Result<TensorView<'_, T>> {
+    const OP: &str = "arrow::buffer_view";
+    // Every bit pattern is a valid `ArrowNativeType`
+    let (head, values, _) = unsafe { buffer.as_slice().align_to::<T>() };
+    if !head.is_empty() {
+        return Err(Error::Unsupported { op: OP, what: format!("a buffer not aligned for {}", std::any::type_name::<T>()) });
+    }
+    view_of(OP, values, layout)
+}
+
+/// A primitive array of the elements of `tensor` in row-major order. The
+/// storage is handed to Arrow as-is when the tensor is already row-major.
+pub fn into_primitive_array<T: ArrowPrimitiveType>(tensor: Tensor<T::Native>) -> PrimitiveArray<T> {
+    let values = if tensor.layout().contiguity() == Some(Contiguity::RowMajor) {
+        tensor.into_vec()
+    } else {
+        tensor.as_view().to_vec()
+    };
+    PrimitiveArray::new(ScalarBuffer::from(values), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::types::Float32Type;
+    use arrow_array::Float32Array;
+    use crate::blas::NativeBlas;
+    use crate::gemm::gemm_f32;
+
+    #[test]
+    fn gemm_runs_on_arrow_columns() {
+        let a = Float32Array::from(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let b = Float32Array::from(vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        let (a, b) = (matrix_view(&a, 2, 3).unwrap(), matrix_view(&b, 3, 2).unwrap());
+
+        let mut c = Tensor::new(vec![0.0f32; 4], Layout::row_major([2, 2]));
+        gemm_f32(&NativeBlas, &a, &b, &mut c.as_view_mut(), 1.0, 0.0);
+        let out: PrimitiveArray<Float32Type> = into_primitive_array(c);
+        assert_eq!(out.values().as_ref(), &[4.0, 5.0, 10.0, 11.0]);
+    }
+
+    #[test]
+    fn views_follow_slices_and_layouts() {
+        let array = Float32Array::from((0..10).map(|x| x as f32).collect::<Vec<_>>()).slice(2, 6);
+        assert_eq!(vector_view(&array).unwrap().to_vec(), vec![2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+
+        let cols = array_view(&array, Layout::col_major([2, 3])).unwrap();
+        assert_eq!(cols.to_vec(), vec![2.0, 4.0, 6.0, 3.0, 5.0, 7.0]);
+
+        assert!(matches!(matrix_view(&array, 4, 2), Err(Error::ShapeMismatch { .. })));
+        assert!(matches!(array_view(&array, Layout::row_major([2, 3]).with_stride([4, 1])), Err(Error::ShapeMismatch { .. })));
+
+        let t = Tensor::new(vec![1.0f32, 2.0, 3.0, 4.0], Layout::col_major([2, 2]));
+        let out: PrimitiveArray<Float32Type> = into_primitive_array(t);
+        assert_eq!(out.values().as_ref(), &[1.0, 3.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn rejects_nulls_and_misaligned_buffers() {
+        let array = Float32Array::from(vec![Some(1.0), None]);
+        assert!(matches!(vector_view(&array), Err(Error::Unsupported { .. })));
+
+        let buffer = Buffer::from_slice_ref([0u32, 1, 2, 3]);
+        let view = buffer_view::<u32>(&buffer, Layout::row_major([2, 2])).unwrap();
+        assert_eq!(view.to_vec(), vec![0, 1, 2, 3]);
+        assert!(buffer_view::<u32>(&buffer.slice(1), Layout::row_major([1])).is_err());
+    }
+}
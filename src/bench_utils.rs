@@ -0,0 +1,159 @@
+use std::ops::{Add, AddAssign};
+use std::time::{Duration, Instant};
+
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::random::{fill_uniform, seeded};
+use crate::tensor::Tensor;
+use crate::tuple::Tuple;
+
+/// Wall-clock statistics over repeated kernel invocations
+#[derive(Debug, Clone, Copy)]
+pub struct KernelTiming {
+    pub iters: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+/// Run `f` `warmup` times untimed, then `iters` times timed.
+///
+/// # Panics
+/// Panics if `iters == 0`.
+pub fn time_kernel<F: FnMut()>(warmup: usize, iters: usize, mut f: F) -> KernelTiming {
+    assert!(iters > 0, "time_kernel: iters must be > 0");
+
+    for _ in 0..warmup {
+        f();
+    }
+
+    let mut samples: Vec<Duration> = (0..iters)
+        .map(|_| {
+            let t0 = Instant::now();
+            f();
+            t0.elapsed()
+        })
+        .collect();
+    samples.sort();
+
+    KernelTiming {
+        iters,
+        min: samples[0],
+        median: samples[iters / 2],
+        mean: samples.iter().sum::<Duration>() / iters as u32,
+    }
+}
+
+/// Floating point operations of one `m x k` by `k x n` GEMM
+pub fn gemm_flops(m: usize, n: usize, k: usize) -> f64 {
+    2.0 * m as f64 * n as f64 * k as f64
+}
+
+/// Throughput in GFLOP/s
+pub fn gflops(flops: f64, elapsed: Duration) -> f64 {
+    flops / elapsed.as_secs_f64() / 1e9
+}
+
+/// Work done by one driver call, as returned by `gemm_f32_tiled_parallel`,
+/// `tensor_copy_par`, `TiledReduction::gemm_f32`, ... `bytes` is the
+/// algorithmic minimum traffic: every operand read once, every result
+/// written once, regardless of how the driver tiles.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct KernelStats {
+    /// Distinct operand and result elements touched
+    pub elements: usize,
+    pub flops: usize,
+    pub bytes: usize,
+}
+
+impl KernelStats {
+    /// `C = alpha * A * B + beta * C`; C is also read when `beta != 0`
+    pub fn gemm(m: usize, n: usize, k: usize, elem_size: usize, beta: f32) -> Self {
+        let c_traffic = if beta == 0.0 { m * n } else { 2 * m * n };
+        Self {
+            elements: m * k + k * n + m * n,
+            flops: gemm_flops(m, n, k) as usize,
+            bytes: (m * k + k * n + c_traffic) * elem_size,
+        }
+    }
+
+    /// Copy of `elements` values
+    pub fn copy(elements: usize, elem_size: usize) -> Self {
+        Self { elements: 2 * elements, flops: 0, bytes: 2 * elements * elem_size }
+    }
+
+    /// Achieved GFLOP/s if the call took `elapsed`
+    pub fn gflops(&self, elapsed: Duration) -> f64 {
+        gflops(self.flops as f64, elapsed)
+    }
+
+    /// Achieved bandwidth in GB/s if the call took `elapsed`
+    pub fn gb_per_s(&self, elapsed: Duration) -> f64 {
+        self.bytes as f64 / elapsed.as_secs_f64() / 1e9
+    }
+}
+
+impl Add for KernelStats {
+    type Output = KernelStats;
+
+    fn add(self, rhs: KernelStats) -> KernelStats {
+        KernelStats {
+            elements: self.elements + rhs.elements,
+            flops: self.flops + rhs.flops,
+            bytes: self.bytes + rhs.bytes,
+        }
+    }
+}
+
+impl AddAssign for KernelStats {
+    fn add_assign(&mut self, rhs: KernelStats) {
+        *self = *self + rhs;
+    }
+}
+
+/// Row-major `rows x cols` matrix with entries uniform in [-1, 1), reproducible from `seed`
+pub fn random_matrix_f32(rows: usize, cols: usize, seed: u64) -> Tensor<f32> {
+    let mut t = Tensor::new(vec![0.0; rows * cols], Layout::row_major(Shape::new(Tuple::int(vec![rows, cols]))));
+    fill_uniform(&mut t.as_view_mut(), -1.0, 1.0, &mut seeded(seed));
+    t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_kernel_counts_calls() {
+        let mut calls = 0;
+        let t = time_kernel(2, 5, || calls += 1);
+        assert_eq!(calls, 7);
+        assert_eq!(t.iters, 5);
+        assert!(t.min <= t.median);
+    }
+
+    #[test]
+    fn kernel_stats_rates() {
+        let s = KernelStats::gemm(2, 3, 4, 4, 0.0);
+        assert_eq!(s, KernelStats { elements: 8 + 12 + 6, flops: 48, bytes: 4 * 26 });
+        assert_eq!(KernelStats::gemm(2, 3, 4, 4, 1.0).bytes, 4 * 32);
+
+        let total = s + KernelStats::copy(10, 8);
+        assert_eq!(total.bytes, 104 + 160);
+        assert!((total.gflops(Duration::from_micros(1)) - 0.048).abs() < 1e-12);
+        assert!((total.gb_per_s(Duration::from_secs(1)) - 264e-9).abs() < 1e-18);
+    }
+
+    #[test]
+    fn random_matrix_is_reproducible() {
+        let a = random_matrix_f32(3, 4, 7);
+        let b = random_matrix_f32(3, 4, 7);
+        assert_eq!(a.data(), b.data());
+        assert!(a.data().iter().all(|x| (-1.0..1.0).contains(x)));
+    }
+
+    #[test]
+    fn gflops_formula() {
+        assert_eq!(gemm_flops(2, 3, 4), 48.0);
+        assert!((gflops(2e9, Duration::from_secs(2)) - 1.0).abs() < 1e-12);
+    }
+}
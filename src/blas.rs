@@ -1,5 +1,14 @@
+#[cfg(not(target_arch = "wasm32"))]
 use libloading::Library;
+use num_complex::{Complex32, Complex64};
+use std::ffi::c_void;
+#[cfg(not(target_arch = "wasm32"))]
 use std::sync::OnceLock;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::error::Error;
+use crate::error::Result;
+use crate::kernel::NativeGemm;
+use crate::strassen::gemm_strassen_blas;
 
 /* ============================================================
    CBLAS ABI (minimal)
@@ -16,6 +25,28 @@ pub enum CBLAS_LAYOUT {
 pub enum CBLAS_TRANSPOSE {
     CblasNoTrans = 111,
     CblasTrans   = 112,
+    CblasConjTrans = 113,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum CBLAS_UPLO {
+    CblasUpper = 121,
+    CblasLower = 122,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum CBLAS_DIAG {
+    CblasNonUnit = 131,
+    CblasUnit    = 132,
+}
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub enum CBLAS_SIDE {
+    CblasLeft  = 141,
+    CblasRight = 142,
 }
 
 pub type CblasSgemm = unsafe extern "C" fn(
@@ -35,18 +66,168 @@ pub type CblasSgemm = unsafe extern "C" fn(
     ldc: i32,
 );
 
+/// `cblas_cgemm` / `cblas_zgemm`: scalars and matrices are passed by pointer
+pub type CblasComplexGemm = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    transa: CBLAS_TRANSPOSE,
+    transb: CBLAS_TRANSPOSE,
+    m: i32,
+    n: i32,
+    k: i32,
+    alpha: *const c_void,
+    a: *const c_void,
+    lda: i32,
+    b: *const c_void,
+    ldb: i32,
+    beta: *const c_void,
+    c: *mut c_void,
+    ldc: i32,
+);
+
+pub type CblasStrsm = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    side: CBLAS_SIDE,
+    uplo: CBLAS_UPLO,
+    transa: CBLAS_TRANSPOSE,
+    diag: CBLAS_DIAG,
+    m: i32,
+    n: i32,
+    alpha: f32,
+    a: *const f32,
+    lda: i32,
+    b: *mut f32,
+    ldb: i32,
+);
+
+/// `cblas_sgemm_batch_strided` (MKL, recent OpenBLAS)
+pub type CblasSgemmBatchStrided = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    transa: CBLAS_TRANSPOSE,
+    transb: CBLAS_TRANSPOSE,
+    m: i32,
+    n: i32,
+    k: i32,
+    alpha: f32,
+    a: *const f32,
+    lda: i32,
+    stride_a: i32,
+    b: *const f32,
+    ldb: i32,
+    stride_b: i32,
+    beta: f32,
+    c: *mut f32,
+    ldc: i32,
+    stride_c: i32,
+    batch: i32,
+);
+
+pub type CblasSgemv = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    trans: CBLAS_TRANSPOSE,
+    m: i32,
+    n: i32,
+    alpha: f32,
+    a: *const f32,
+    lda: i32,
+    x: *const f32,
+    incx: i32,
+    beta: f32,
+    y: *mut f32,
+    incy: i32,
+);
+
+pub type CblasSger = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    m: i32,
+    n: i32,
+    alpha: f32,
+    x: *const f32,
+    incx: i32,
+    y: *const f32,
+    incy: i32,
+    a: *mut f32,
+    lda: i32,
+);
+
+pub type CblasSsyrk = unsafe extern "C" fn(
+    layout: CBLAS_LAYOUT,
+    uplo: CBLAS_UPLO,
+    trans: CBLAS_TRANSPOSE,
+    n: i32,
+    k: i32,
+    alpha: f32,
+    a: *const f32,
+    lda: i32,
+    beta: f32,
+    c: *mut f32,
+    ldc: i32,
+);
+
 /* ============================================================
    BLAS Backend Trait
    ============================================================ */
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum BlasTranspose {
     NoTrans,
     Trans,
+    /// Conjugate transpose; the same as `Trans` for real operands
+    ConjTrans,
+}
+
+/// Which triangle of a triangular / symmetric matrix is referenced
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlasUplo {
+    Upper,
+    Lower,
+}
+
+impl BlasUplo {
+    pub fn flip(self) -> Self {
+        match self {
+            BlasUplo::Upper => BlasUplo::Lower,
+            BlasUplo::Lower => BlasUplo::Upper,
+        }
+    }
+}
+
+/// Whether a triangular matrix has an implicit unit diagonal
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlasDiag {
+    NonUnit,
+    Unit,
+}
+
+/// Side the triangular matrix is applied from in TRSM
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlasSide {
+    Left,
+    Right,
+}
+
+/// GEMM implementation a backend can be asked for with `gemm_f32_algo`,
+/// so planners and auto-tuners can choose between a backend's variants
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum GemmAlgo {
+    /// Whatever a plain `gemm_f32` call runs
+    Default,
+    /// Straight loops over C; no packing or blocking
+    Naive,
+    /// Cache-blocked over packed A/B panels and a register micro-kernel
+    Packed,
+    /// Strassen-Winograd recursion down to a base-case GEMM
+    Strassen,
 }
 
+// Methods mirror the CBLAS argument lists and take raw pointers by design
+#[allow(clippy::too_many_arguments)]
 pub trait BlasBackend {
-    fn gemm_f32(
+    /// Row-major SGEMM: `C = alpha op(A) op(B) + beta C`
+    ///
+    /// # Safety
+    /// `a`, `b` and `c` must be valid for the extents and leading dimensions
+    /// given, and C must not overlap A or B.
+    unsafe fn gemm_f32(
         &self,
         ta: BlasTranspose,
         tb: BlasTranspose,
@@ -62,37 +243,274 @@ pub trait BlasBackend {
         c: *mut f32,
         ldc: i32,
     );
-}
 
-/* ============================================================
-   Generic BLAS Loader (OpenBLAS / MKL / BLAS)
-   ============================================================ */
+    /// Row-major STRSM: solve `op(A) X = alpha B` (Left) or `X op(A) = alpha B`
+    /// (Right); `X` overwrites `B`, which is `m x n`.
+    ///
+    /// # Safety
+    /// `a` and `b` must be valid for the extents and leading dimensions given,
+    /// and B must not overlap A.
+    unsafe fn trsm_f32(
+        &self,
+        _side: BlasSide,
+        _uplo: BlasUplo,
+        _ta: BlasTranspose,
+        _diag: BlasDiag,
+        _m: i32,
+        _n: i32,
+        _alpha: f32,
+        _a: *const f32,
+        _lda: i32,
+        _b: *mut f32,
+        _ldb: i32,
+    ) {
+        panic!("trsm_f32 is not supported by this backend");
+    }
 
-struct BlasSymbols {
-    _lib: Library,
-    sgemm: CblasSgemm,
-}
+    /// `batch` row-major SGEMMs whose operands start `stride_a`, `stride_b`
+    /// and `stride_c` elements after the previous ones. The default loops
+    /// over `gemm_f32`.
+    ///
+    /// # Safety
+    /// As for `gemm_f32`, for each of the `batch` operand triples.
+    unsafe fn gemm_f32_strided_batched(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        stride_a: i32,
+        b: *const f32,
+        ldb: i32,
+        stride_b: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+        stride_c: i32,
+        batch: i32,
+    ) {
+        for i in 0..batch as usize {
+            let (a, b, c) = unsafe {
+                (a.add(i * stride_a as usize), b.add(i * stride_b as usize), c.add(i * stride_c as usize))
+            };
+            self.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc);
+        }
+    }
 
-static BLAS: OnceLock<BlasSymbols> = OnceLock::new();
+    /// Row-major SGEMV: `y = alpha op(A) x + beta y` for an `m x n` A. The
+    /// default is a plain loop, so every backend has one.
+    ///
+    /// # Safety
+    /// `a`, `x` and `y` must be valid for the extents, leading dimension and
+    /// increments given, and y must not overlap A or x.
+    unsafe fn gemv_f32(
+        &self,
+        trans: BlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        x: *const f32,
+        incx: i32,
+        beta: f32,
+        y: *mut f32,
+        incy: i32,
+    ) {
+        let (m, n, lda) = (m as usize, n as usize, lda as usize);
+        let (incx, incy) = (incx as usize, incy as usize);
+        let (rows, cols) = match trans {
+            BlasTranspose::NoTrans => (m, n),
+            BlasTranspose::Trans | BlasTranspose::ConjTrans => (n, m),
+        };
+        let a_at = |i: usize, j: usize| match trans {
+            BlasTranspose::NoTrans => i * lda + j,
+            BlasTranspose::Trans | BlasTranspose::ConjTrans => j * lda + i,
+        };
 
-fn load_blas() -> &'static BlasSymbols {
-    BLAS.get_or_init(|| unsafe {
-        let lib = Library::new("libopenblas.so")
-            .or_else(|_| Library::new("libblas.so"))
-            .expect("Failed to load BLAS library");
+        unsafe {
+            for i in 0..rows {
+                let dot: f32 = (0..cols).map(|j| *a.add(a_at(i, j)) * *x.add(j * incx)).sum();
+                let yi = y.add(i * incy);
+                *yi = alpha * dot + if beta == 0.0 { 0.0 } else { beta * *yi };
+            }
+        }
+    }
 
-        let sgemm = *lib
-            .get::<CblasSgemm>(b"cblas_sgemm\0")
-            .expect("Failed to load cblas_sgemm");
+    /// Row-major SGER: `A += alpha x y^T` for an `m x n` A. The default is
+    /// a plain loop.
+    ///
+    /// # Safety
+    /// `x`, `y` and `a` must be valid for the extents, increments and leading
+    /// dimension given, and A must not overlap x or y.
+    unsafe fn ger_f32(
+        &self,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        x: *const f32,
+        incx: i32,
+        y: *const f32,
+        incy: i32,
+        a: *mut f32,
+        lda: i32,
+    ) {
+        let (m, n, lda) = (m as usize, n as usize, lda as usize);
+        let (incx, incy) = (incx as usize, incy as usize);
+        unsafe {
+            for i in 0..m {
+                let xi = alpha * *x.add(i * incx);
+                for j in 0..n {
+                    *a.add(i * lda + j) += xi * *y.add(j * incy);
+                }
+            }
+        }
+    }
 
-        BlasSymbols { _lib: lib, sgemm }
-    })
+    /// Row-major SSYRK: `C = alpha op(A) op(A)^T + beta C` on the `uplo`
+    /// triangle of the `n x n` matrix C; `op(A)` is `n x k`.
+    ///
+    /// # Safety
+    /// `a` and `c` must be valid for the extents and leading dimensions given,
+    /// and C must not overlap A.
+    unsafe fn syrk_f32(
+        &self,
+        _uplo: BlasUplo,
+        _trans: BlasTranspose,
+        _n: i32,
+        _k: i32,
+        _alpha: f32,
+        _a: *const f32,
+        _lda: i32,
+        _beta: f32,
+        _c: *mut f32,
+        _ldc: i32,
+    ) {
+        panic!("syrk_f32 is not supported by this backend");
+    }
+
+    /// Row-major CGEMM: `C = alpha op(A) op(B) + beta C` over `Complex32`
+    ///
+    /// # Safety
+    /// As for `gemm_f32`.
+    unsafe fn gemm_c32(
+        &self,
+        _ta: BlasTranspose,
+        _tb: BlasTranspose,
+        _m: i32,
+        _n: i32,
+        _k: i32,
+        _alpha: Complex32,
+        _a: *const Complex32,
+        _lda: i32,
+        _b: *const Complex32,
+        _ldb: i32,
+        _beta: Complex32,
+        _c: *mut Complex32,
+        _ldc: i32,
+    ) {
+        panic!("gemm_c32 is not supported by this backend");
+    }
+
+    /// Row-major ZGEMM: `C = alpha op(A) op(B) + beta C` over `Complex64`
+    ///
+    /// # Safety
+    /// As for `gemm_f32`.
+    unsafe fn gemm_c64(
+        &self,
+        _ta: BlasTranspose,
+        _tb: BlasTranspose,
+        _m: i32,
+        _n: i32,
+        _k: i32,
+        _alpha: Complex64,
+        _a: *const Complex64,
+        _lda: i32,
+        _b: *const Complex64,
+        _ldb: i32,
+        _beta: Complex64,
+        _c: *mut Complex64,
+        _ldc: i32,
+    ) {
+        panic!("gemm_c64 is not supported by this backend");
+    }
+
+    /// Algorithms `gemm_f32_algo` runs as asked; always includes `Default`
+    fn gemm_algos(&self) -> &'static [GemmAlgo] {
+        &[GemmAlgo::Default]
+    }
+
+    /// `gemm_f32` with the implementation picked by `algo`. Algorithms not
+    /// listed in `gemm_algos` run `Default`.
+    ///
+    /// # Safety
+    /// As for `gemm_f32`.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gemm_f32_algo(
+        &self,
+        _algo: GemmAlgo,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        self.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc);
+    }
 }
 
-pub struct GenericBlas;
+/// A boxed backend forwards everything, so a backend picked at runtime
+/// (`config::backend`) can be handed to any driver
+impl<B: BlasBackend + ?Sized> BlasBackend for Box<B> {
+    unsafe fn gemm_f32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        (**self).gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
 
-impl BlasBackend for GenericBlas {
-    fn gemm_f32(
+    unsafe fn trsm_f32(
+        &self,
+        side: BlasSide,
+        uplo: BlasUplo,
+        ta: BlasTranspose,
+        diag: BlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *mut f32,
+        ldb: i32,
+    ) {
+        (**self).trsm_f32(side, uplo, ta, diag, m, n, alpha, a, lda, b, ldb)
+    }
+
+    unsafe fn gemm_f32_strided_batched(
         &self,
         ta: BlasTranspose,
         tb: BlasTranspose,
@@ -102,37 +520,807 @@ impl BlasBackend for GenericBlas {
         alpha: f32,
         a: *const f32,
         lda: i32,
+        stride_a: i32,
         b: *const f32,
         ldb: i32,
+        stride_b: i32,
         beta: f32,
         c: *mut f32,
         ldc: i32,
+        stride_c: i32,
+        batch: i32,
     ) {
-        let blas = load_blas();
+        (**self).gemm_f32_strided_batched(
+            ta, tb, m, n, k, alpha, a, lda, stride_a, b, ldb, stride_b, beta, c, ldc, stride_c, batch,
+        )
+    }
 
-        let transa = match ta {
-            BlasTranspose::NoTrans => CBLAS_TRANSPOSE::CblasNoTrans,
-            BlasTranspose::Trans   => CBLAS_TRANSPOSE::CblasTrans,
-        };
+    unsafe fn gemv_f32(
+        &self,
+        trans: BlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        x: *const f32,
+        incx: i32,
+        beta: f32,
+        y: *mut f32,
+        incy: i32,
+    ) {
+        (**self).gemv_f32(trans, m, n, alpha, a, lda, x, incx, beta, y, incy)
+    }
 
-        let transb = match tb {
-            BlasTranspose::NoTrans => CBLAS_TRANSPOSE::CblasNoTrans,
-            BlasTranspose::Trans   => CBLAS_TRANSPOSE::CblasTrans,
-        };
+    unsafe fn ger_f32(
+        &self,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        x: *const f32,
+        incx: i32,
+        y: *const f32,
+        incy: i32,
+        a: *mut f32,
+        lda: i32,
+    ) {
+        (**self).ger_f32(m, n, alpha, x, incx, y, incy, a, lda)
+    }
 
-        unsafe {
-            (blas.sgemm)(
-                CBLAS_LAYOUT::CblasRowMajor,
-                transa,
-                transb,
-                m, n, k,
-                alpha,
-                a, lda,
-                b, ldb,
-                beta,
-                c, ldc,
-            );
-        }
+    unsafe fn syrk_f32(
+        &self,
+        uplo: BlasUplo,
+        trans: BlasTranspose,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        (**self).syrk_f32(uplo, trans, n, k, alpha, a, lda, beta, c, ldc)
+    }
+
+    unsafe fn gemm_c32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex32,
+        a: *const Complex32,
+        lda: i32,
+        b: *const Complex32,
+        ldb: i32,
+        beta: Complex32,
+        c: *mut Complex32,
+        ldc: i32,
+    ) {
+        (**self).gemm_c32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
     }
-}
 
+    unsafe fn gemm_c64(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex64,
+        a: *const Complex64,
+        lda: i32,
+        b: *const Complex64,
+        ldb: i32,
+        beta: Complex64,
+        c: *mut Complex64,
+        ldc: i32,
+    ) {
+        (**self).gemm_c64(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+
+    fn gemm_algos(&self) -> &'static [GemmAlgo] {
+        (**self).gemm_algos()
+    }
+
+    unsafe fn gemm_f32_algo(
+        &self,
+        algo: GemmAlgo,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        (**self).gemm_f32_algo(algo, ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+}
+
+/* ============================================================
+   Generic BLAS Loader (OpenBLAS / MKL / BLAS)
+   ============================================================ */
+
+// wasm32 has no shared libraries to load: there `GenericBlas` names
+// `NativeBlas` (see below) and nothing in this section is compiled.
+
+#[cfg(not(target_arch = "wasm32"))]
+struct BlasSymbols {
+    _lib: Library,
+    sgemm: CblasSgemm,
+    strsm: Option<CblasStrsm>,
+    ssyrk: Option<CblasSsyrk>,
+    sgemm_batch_strided: Option<CblasSgemmBatchStrided>,
+    sgemv: Option<CblasSgemv>,
+    sger: Option<CblasSger>,
+    cgemm: Option<CblasComplexGemm>,
+    zgemm: Option<CblasComplexGemm>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+static BLAS: OnceLock<Option<BlasSymbols>> = OnceLock::new();
+
+#[cfg(not(target_arch = "wasm32"))]
+fn try_load_blas() -> Option<&'static BlasSymbols> {
+    BLAS.get_or_init(|| unsafe {
+        let names = ["libopenblas.so", "libblas.so"];
+        let Some((name, lib)) = names.into_iter().find_map(|name| Library::new(name).ok().map(|lib| (name, lib))) else {
+            log_debug!("no CBLAS library found (tried {names:?}); GenericBlas is unavailable");
+            return None;
+        };
+
+        let Ok(sgemm) = lib.get::<CblasSgemm>(b"cblas_sgemm\0").map(|f| *f) else {
+            log_debug!("{name} has no cblas_sgemm; GenericBlas is unavailable");
+            return None;
+        };
+        let strsm = lib.get::<CblasStrsm>(b"cblas_strsm\0").ok().map(|f| *f);
+        let ssyrk = lib.get::<CblasSsyrk>(b"cblas_ssyrk\0").ok().map(|f| *f);
+        let sgemm_batch_strided =
+            lib.get::<CblasSgemmBatchStrided>(b"cblas_sgemm_batch_strided\0").ok().map(|f| *f);
+        let sgemv = lib.get::<CblasSgemv>(b"cblas_sgemv\0").ok().map(|f| *f);
+        let sger = lib.get::<CblasSger>(b"cblas_sger\0").ok().map(|f| *f);
+        let cgemm = lib.get::<CblasComplexGemm>(b"cblas_cgemm\0").ok().map(|f| *f);
+        let zgemm = lib.get::<CblasComplexGemm>(b"cblas_zgemm\0").ok().map(|f| *f);
+        log_debug!(
+            "loaded CBLAS from {name}; strsm {}, ssyrk {}, sgemm_batch_strided {}, sgemv {}, sger {}, cgemm {}, zgemm {}",
+            strsm.is_some(),
+            ssyrk.is_some(),
+            sgemm_batch_strided.is_some(),
+            sgemv.is_some(),
+            sger.is_some(),
+            cgemm.is_some(),
+            zgemm.is_some()
+        );
+
+        Some(BlasSymbols { _lib: lib, sgemm, strsm, ssyrk, sgemm_batch_strided, sgemv, sger, cgemm, zgemm })
+    })
+    .as_ref()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_blas() -> &'static BlasSymbols {
+    try_load_blas().expect("Failed to load BLAS library")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn cblas_trans(t: BlasTranspose) -> CBLAS_TRANSPOSE {
+    match t {
+        BlasTranspose::NoTrans => CBLAS_TRANSPOSE::CblasNoTrans,
+        BlasTranspose::Trans   => CBLAS_TRANSPOSE::CblasTrans,
+        BlasTranspose::ConjTrans => CBLAS_TRANSPOSE::CblasConjTrans,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn cblas_uplo(u: BlasUplo) -> CBLAS_UPLO {
+    match u {
+        BlasUplo::Upper => CBLAS_UPLO::CblasUpper,
+        BlasUplo::Lower => CBLAS_UPLO::CblasLower,
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct GenericBlas;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl GenericBlas {
+    /// Returns true if a CBLAS library could be loaded on this machine
+    pub fn is_available() -> bool {
+        try_load_blas().is_some()
+    }
+
+    /// Handle to the system CBLAS, or `Error::BackendLoad` if none is installed
+    pub fn try_load() -> Result<Self> {
+        try_load_blas()
+            .map(|_| GenericBlas)
+            .ok_or_else(|| Error::BackendLoad("no CBLAS library (libopenblas.so / libblas.so) with cblas_sgemm".into()))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl BlasBackend for GenericBlas {
+    unsafe fn gemm_f32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        let blas = load_blas();
+        let transa = cblas_trans(ta);
+        let transb = cblas_trans(tb);
+
+        unsafe {
+            (blas.sgemm)(
+                CBLAS_LAYOUT::CblasRowMajor,
+                transa,
+                transb,
+                m, n, k,
+                alpha,
+                a, lda,
+                b, ldb,
+                beta,
+                c, ldc,
+            );
+        }
+    }
+
+    unsafe fn gemm_c32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex32,
+        a: *const Complex32,
+        lda: i32,
+        b: *const Complex32,
+        ldb: i32,
+        beta: Complex32,
+        c: *mut Complex32,
+        ldc: i32,
+    ) {
+        let cgemm = load_blas().cgemm.expect("Failed to load cblas_cgemm");
+
+        unsafe {
+            cgemm(
+                CBLAS_LAYOUT::CblasRowMajor,
+                cblas_trans(ta),
+                cblas_trans(tb),
+                m, n, k,
+                &alpha as *const Complex32 as *const c_void,
+                a as *const c_void, lda,
+                b as *const c_void, ldb,
+                &beta as *const Complex32 as *const c_void,
+                c as *mut c_void, ldc,
+            );
+        }
+    }
+
+    unsafe fn gemm_c64(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex64,
+        a: *const Complex64,
+        lda: i32,
+        b: *const Complex64,
+        ldb: i32,
+        beta: Complex64,
+        c: *mut Complex64,
+        ldc: i32,
+    ) {
+        let zgemm = load_blas().zgemm.expect("Failed to load cblas_zgemm");
+
+        unsafe {
+            zgemm(
+                CBLAS_LAYOUT::CblasRowMajor,
+                cblas_trans(ta),
+                cblas_trans(tb),
+                m, n, k,
+                &alpha as *const Complex64 as *const c_void,
+                a as *const c_void, lda,
+                b as *const c_void, ldb,
+                &beta as *const Complex64 as *const c_void,
+                c as *mut c_void, ldc,
+            );
+        }
+    }
+
+    unsafe fn trsm_f32(
+        &self,
+        side: BlasSide,
+        uplo: BlasUplo,
+        ta: BlasTranspose,
+        diag: BlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *mut f32,
+        ldb: i32,
+    ) {
+        let strsm = load_blas().strsm.expect("Failed to load cblas_strsm");
+
+        let side = match side {
+            BlasSide::Left  => CBLAS_SIDE::CblasLeft,
+            BlasSide::Right => CBLAS_SIDE::CblasRight,
+        };
+        let diag = match diag {
+            BlasDiag::NonUnit => CBLAS_DIAG::CblasNonUnit,
+            BlasDiag::Unit    => CBLAS_DIAG::CblasUnit,
+        };
+
+        unsafe {
+            strsm(
+                CBLAS_LAYOUT::CblasRowMajor,
+                side,
+                cblas_uplo(uplo),
+                cblas_trans(ta),
+                diag,
+                m, n,
+                alpha,
+                a, lda,
+                b, ldb,
+            );
+        }
+    }
+
+    unsafe fn syrk_f32(
+        &self,
+        uplo: BlasUplo,
+        trans: BlasTranspose,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        let ssyrk = load_blas().ssyrk.expect("Failed to load cblas_ssyrk");
+
+        unsafe {
+            ssyrk(
+                CBLAS_LAYOUT::CblasRowMajor,
+                cblas_uplo(uplo),
+                cblas_trans(trans),
+                n, k,
+                alpha,
+                a, lda,
+                beta,
+                c, ldc,
+            );
+        }
+    }
+
+    unsafe fn gemm_f32_strided_batched(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        stride_a: i32,
+        b: *const f32,
+        ldb: i32,
+        stride_b: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+        stride_c: i32,
+        batch: i32,
+    ) {
+        let blas = load_blas();
+        let Some(batched) = blas.sgemm_batch_strided else {
+            // Older libraries: one cblas_sgemm per matrix
+            for i in 0..batch as usize {
+                let (a, b, c) = unsafe {
+                    (a.add(i * stride_a as usize), b.add(i * stride_b as usize), c.add(i * stride_c as usize))
+                };
+                self.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc);
+            }
+            return;
+        };
+
+        unsafe {
+            batched(
+                CBLAS_LAYOUT::CblasRowMajor,
+                cblas_trans(ta),
+                cblas_trans(tb),
+                m, n, k,
+                alpha,
+                a, lda, stride_a,
+                b, ldb, stride_b,
+                beta,
+                c, ldc, stride_c,
+                batch,
+            );
+        }
+    }
+
+    unsafe fn gemv_f32(
+        &self,
+        trans: BlasTranspose,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        x: *const f32,
+        incx: i32,
+        beta: f32,
+        y: *mut f32,
+        incy: i32,
+    ) {
+        let sgemv = load_blas().sgemv.expect("Failed to load cblas_sgemv");
+
+        unsafe {
+            sgemv(
+                CBLAS_LAYOUT::CblasRowMajor,
+                cblas_trans(trans),
+                m, n,
+                alpha,
+                a, lda,
+                x, incx,
+                beta,
+                y, incy,
+            );
+        }
+    }
+
+    unsafe fn ger_f32(
+        &self,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        x: *const f32,
+        incx: i32,
+        y: *const f32,
+        incy: i32,
+        a: *mut f32,
+        lda: i32,
+    ) {
+        let sger = load_blas().sger.expect("Failed to load cblas_sger");
+
+        unsafe {
+            sger(
+                CBLAS_LAYOUT::CblasRowMajor,
+                m, n,
+                alpha,
+                x, incx,
+                y, incy,
+                a, lda,
+            );
+        }
+    }
+}
+
+
+/* ============================================================
+   Native backend (pure Rust, no external library)
+   ============================================================ */
+
+/// Portable row-major SGEMM used when no system BLAS is available.
+pub struct NativeBlas;
+
+/// On wasm32 the CBLAS loader is compiled out and `GenericBlas` is the
+/// native backend, so code written against it runs unchanged in a browser.
+#[cfg(target_arch = "wasm32")]
+pub use self::NativeBlas as GenericBlas;
+
+#[cfg(target_arch = "wasm32")]
+impl NativeBlas {
+    /// Always true: the native kernels need no library
+    pub fn is_available() -> bool {
+        true
+    }
+
+    /// Never fails; mirrors `GenericBlas::try_load` on other targets
+    pub fn try_load() -> Result<Self> {
+        Ok(NativeBlas)
+    }
+}
+
+/// Row-major complex GEMM behind `NativeBlas::gemm_c32` / `gemm_c64`;
+/// `zero` is the additive identity and `conj` conjugates an element.
+#[allow(clippy::too_many_arguments)]
+unsafe fn native_gemm_complex<T>(
+    zero: T,
+    conj: fn(T) -> T,
+    ta: BlasTranspose,
+    tb: BlasTranspose,
+    (m, n, k): (usize, usize, usize),
+    alpha: T,
+    (a, lda): (*const T, usize),
+    (b, ldb): (*const T, usize),
+    beta: T,
+    (c, ldc): (*mut T, usize),
+) where
+    T: Copy + PartialEq + std::ops::Add<Output = T> + std::ops::Mul<Output = T>,
+{
+    let op = |t: BlasTranspose, x: *const T, ld: usize, r: usize, s: usize| match t {
+        BlasTranspose::NoTrans => *x.add(r * ld + s),
+        BlasTranspose::Trans => *x.add(s * ld + r),
+        BlasTranspose::ConjTrans => conj(*x.add(s * ld + r)),
+    };
+
+    for i in 0..m {
+        for j in 0..n {
+            let mut dot = zero;
+            for p in 0..k {
+                dot = dot + op(ta, a, lda, i, p) * op(tb, b, ldb, p, j);
+            }
+            let cij = c.add(i * ldc + j);
+            *cij = alpha * dot + if beta == zero { zero } else { beta * *cij };
+        }
+    }
+}
+
+impl BlasBackend for NativeBlas {
+    unsafe fn gemm_c32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex32,
+        a: *const Complex32,
+        lda: i32,
+        b: *const Complex32,
+        ldb: i32,
+        beta: Complex32,
+        c: *mut Complex32,
+        ldc: i32,
+    ) {
+        let dims = (m as usize, n as usize, k as usize);
+        unsafe {
+            native_gemm_complex(
+                Complex32::ZERO, |x| x.conj(), ta, tb, dims, alpha,
+                (a, lda as usize), (b, ldb as usize), beta, (c, ldc as usize),
+            );
+        }
+    }
+
+    unsafe fn gemm_c64(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex64,
+        a: *const Complex64,
+        lda: i32,
+        b: *const Complex64,
+        ldb: i32,
+        beta: Complex64,
+        c: *mut Complex64,
+        ldc: i32,
+    ) {
+        let dims = (m as usize, n as usize, k as usize);
+        unsafe {
+            native_gemm_complex(
+                Complex64::ZERO, |x| x.conj(), ta, tb, dims, alpha,
+                (a, lda as usize), (b, ldb as usize), beta, (c, ldc as usize),
+            );
+        }
+    }
+
+    unsafe fn gemm_f32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        let (m, n, k) = (m as usize, n as usize, k as usize);
+        let (lda, ldb, ldc) = (lda as usize, ldb as usize, ldc as usize);
+
+        // Row-major op(X)[r][s] addressing
+        let a_at = |i: usize, p: usize| match ta {
+            BlasTranspose::NoTrans => i * lda + p,
+            BlasTranspose::Trans | BlasTranspose::ConjTrans => p * lda + i,
+        };
+        let b_at = |p: usize, j: usize| match tb {
+            BlasTranspose::NoTrans => p * ldb + j,
+            BlasTranspose::Trans | BlasTranspose::ConjTrans => j * ldb + p,
+        };
+
+        unsafe {
+            for i in 0..m {
+                let c_row = c.add(i * ldc);
+                for j in 0..n {
+                    let cij = c_row.add(j);
+                    *cij = if beta == 0.0 { 0.0 } else { beta * *cij };
+                }
+                for p in 0..k {
+                    let aip = alpha * *a.add(a_at(i, p));
+                    for j in 0..n {
+                        *c_row.add(j) += aip * *b.add(b_at(p, j));
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn trsm_f32(
+        &self,
+        side: BlasSide,
+        uplo: BlasUplo,
+        ta: BlasTranspose,
+        diag: BlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *mut f32,
+        ldb: i32,
+    ) {
+        let (m, n, lda, ldb) = (m as usize, n as usize, lda as usize, ldb as usize);
+
+        // op(A)[i][j]; transposing swaps which triangle is populated
+        let op_a = |i: usize, j: usize| unsafe {
+            match ta {
+                BlasTranspose::NoTrans => *a.add(i * lda + j),
+                BlasTranspose::Trans | BlasTranspose::ConjTrans => *a.add(j * lda + i),
+            }
+        };
+        let lower = (uplo == BlasUplo::Lower) == (ta == BlasTranspose::NoTrans);
+        let diag_at = |i: usize| if diag == BlasDiag::Unit { 1.0 } else { op_a(i, i) };
+        let bx = |i: usize, j: usize| unsafe { b.add(i * ldb + j) };
+
+        unsafe {
+            for i in 0..m {
+                for j in 0..n {
+                    *bx(i, j) *= alpha;
+                }
+            }
+
+            match side {
+                // op(A) is m x m; solve row by row
+                BlasSide::Left => {
+                    let order: Vec<usize> = if lower { (0..m).collect() } else { (0..m).rev().collect() };
+                    for &i in &order {
+                        let solved = |p: usize| if lower { p < i } else { p > i };
+                        for p in (0..m).filter(|&p| solved(p)) {
+                            let aip = op_a(i, p);
+                            for j in 0..n {
+                                *bx(i, j) -= aip * *bx(p, j);
+                            }
+                        }
+                        let d = diag_at(i);
+                        for j in 0..n {
+                            *bx(i, j) /= d;
+                        }
+                    }
+                }
+                // op(A) is n x n; solve column by column
+                BlasSide::Right => {
+                    let order: Vec<usize> = if lower { (0..n).rev().collect() } else { (0..n).collect() };
+                    for &j in &order {
+                        let solved = |p: usize| if lower { p > j } else { p < j };
+                        for p in (0..n).filter(|&p| solved(p)) {
+                            let apj = op_a(p, j);
+                            for i in 0..m {
+                                *bx(i, j) -= *bx(i, p) * apj;
+                            }
+                        }
+                        let d = diag_at(j);
+                        for i in 0..m {
+                            *bx(i, j) /= d;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn syrk_f32(
+        &self,
+        uplo: BlasUplo,
+        trans: BlasTranspose,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        let (n, k, lda, ldc) = (n as usize, k as usize, lda as usize, ldc as usize);
+
+        // op(A)[i][p], op(A) is n x k
+        let op_a = |i: usize, p: usize| unsafe {
+            match trans {
+                BlasTranspose::NoTrans => *a.add(i * lda + p),
+                BlasTranspose::Trans | BlasTranspose::ConjTrans => *a.add(p * lda + i),
+            }
+        };
+
+        unsafe {
+            for i in 0..n {
+                let cols = match uplo {
+                    BlasUplo::Lower => 0..i + 1,
+                    BlasUplo::Upper => i..n,
+                };
+                for j in cols {
+                    let dot: f32 = (0..k).map(|p| op_a(i, p) * op_a(j, p)).sum();
+                    let cij = c.add(i * ldc + j);
+                    *cij = alpha * dot + if beta == 0.0 { 0.0 } else { beta * *cij };
+                }
+            }
+        }
+    }
+
+    /// `Default` and `Naive` are the loops above; `Packed` is `NativeGemm`;
+    /// `Strassen` recurses down to these loops
+    fn gemm_algos(&self) -> &'static [GemmAlgo] {
+        &[GemmAlgo::Default, GemmAlgo::Naive, GemmAlgo::Packed, GemmAlgo::Strassen]
+    }
+
+    unsafe fn gemm_f32_algo(
+        &self,
+        algo: GemmAlgo,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        match algo {
+            GemmAlgo::Packed => NativeGemm::default().gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+            GemmAlgo::Strassen if m > 0 && n > 0 && k > 0 => unsafe {
+                gemm_strassen_blas(self, ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+            },
+            _ => self.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+// src/cast.rs
+//
+// Element type conversions between tensors. Casts run through the same
+// copy paths as `tensor_copy`, so strided views convert without first
+// being made contiguous.
+
+use half::f16;
+
+use crate::copy::map_impl;
+use crate::error::Result;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+
+/// Numeric conversion from `S`, as used by `tensor_cast` and `astype`
+pub trait CastFrom<S>: Sized {
+    fn cast_from(x: S) -> Self;
+}
+
+macro_rules! impl_cast {
+    ($($s:ty => $d:ty: |$x:ident| $e:expr),* $(,)?) => {$(
+        impl CastFrom<$s> for $d {
+            #[inline(always)]
+            fn cast_from($x: $s) -> Self {
+                $e
+            }
+        }
+    )*};
+}
+
+impl_cast!(
+    f64 => f64: |x| x,
+    f64 => f32: |x| x as f32,
+    f32 => f64: |x| x as f64,
+    f32 => f16: |x| f16::from_f32(x),
+    f16 => f32: |x| x.to_f32(),
+    f64 => f16: |x| f16::from_f64(x),
+    f16 => f64: |x| x.to_f64(),
+    i32 => f32: |x| x as f32,
+    i32 => f64: |x| x as f64,
+    i8 => f32: |x| x as f32,
+    i8 => i32: |x| x as i32,
+    // Round to nearest, saturating at the i8 range; NaN maps to 0
+    f32 => i8: |x| x.round() as i8,
+    f64 => i8: |x| x.round() as i8,
+);
+
+/// `dst = src` converted element by element
+pub fn tensor_cast<S: Copy, D: CastFrom<S>>(src: &TensorView<'_, S>, dst: &mut TensorViewMut<'_, D>) {
+    if let Err(e) = try_tensor_cast(src, dst) {
+        panic!("{e}");
+    }
+}
+
+/// `tensor_cast` returning a shape mismatch as an error
+pub fn try_tensor_cast<S: Copy, D: CastFrom<S>>(src: &TensorView<'_, S>, dst: &mut TensorViewMut<'_, D>) -> Result<()> {
+    map_impl("tensor_cast", src, dst, D::cast_from)
+}
+
+impl<T: Copy> Tensor<T> {
+    /// Copy converted to `D`, keeping the layout
+    pub fn astype<D: CastFrom<T>>(&self) -> Tensor<D> {
+        Tensor::new(self.data().iter().map(|&x| D::cast_from(x)).collect(), self.layout().clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+
+    #[test]
+    fn astype_and_strided_cast() {
+        let t = Tensor::new(vec![1.25f64, -2.5, 3.0e10, 0.1], Layout::col_major([2, 2]));
+        let f = t.astype::<f32>();
+        assert_eq!(f.layout(), t.layout());
+        assert_eq!(f.data(), &[1.25, -2.5, 3.0e10, 0.1f64 as f32]);
+        assert_eq!(f.astype::<f16>().astype::<f32>().data()[..2], [1.25, -2.5]);
+
+        let q = Tensor::new(vec![0.4f32, -0.6, 300.0, -1e9, f32::NAN, 126.5], Layout::row_major([6])).astype::<i8>();
+        assert_eq!(q.data(), &[0, -1, 127, -128, 0, 127]);
+
+        // Column-major source into a row-major destination takes the strided path
+        let src = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::col_major([2, 3]));
+        let mut dst = Tensor::new(vec![0.0f32; 6], Layout::row_major([2, 3]));
+        tensor_cast(&src.as_view(), &mut dst.as_view_mut());
+        assert_eq!(dst.data(), &[0.0, 2.0, 4.0, 1.0, 3.0, 5.0]);
+
+        let mut wrong = Tensor::new(vec![0.0f32; 6], Layout::row_major([3, 2]));
+        assert!(try_tensor_cast(&src.as_view(), &mut wrong.as_view_mut()).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+// src/compare.rs
+//
+// Elementwise comparisons producing `Tensor<bool>` masks, and `select` to
+// combine two tensors under a mask. Masks pair with `tensor_copy_masked`
+// and `fill_masked`. Results are row-major over the operands' shape;
+// operands may have any layouts.
+
+use crate::error::check_same_shape;
+use crate::layout::Layout;
+use crate::tensor::{Tensor, TensorView};
+
+/// `f` over corresponding elements of `a` and `b`
+fn zip_with<A, B, U>(op: &'static str, a: &TensorView<'_, A>, b: &TensorView<'_, B>, f: impl Fn(&A, &B) -> U) -> Tensor<U> {
+    if let Err(e) = check_same_shape(op, a.layout().shape(), b.layout().shape()) {
+        panic!("{e}");
+    }
+    // Both iterators run in coordinate order, whatever the memory order
+    let data = a.indexed_iter().zip(b.indexed_iter()).map(|((_, x), (_, y))| f(x, y)).collect();
+    Tensor::new(data, Layout::row_major(a.layout().shape().clone()))
+}
+
+macro_rules! comparisons {
+    ($($(#[$doc:meta])* $name:ident => $op:tt),* $(,)?) => {$(
+        $(#[$doc])*
+        pub fn $name<T: PartialOrd>(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Tensor<bool> {
+            zip_with(stringify!($name), a, b, |x, y| x $op y)
+        }
+    )*};
+}
+
+comparisons!(
+    /// `a > b` elementwise
+    gt => >,
+    /// `a >= b` elementwise
+    ge => >=,
+    /// `a < b` elementwise
+    lt => <,
+    /// `a <= b` elementwise
+    le => <=,
+    /// `a == b` elementwise
+    eq => ==,
+    /// `a != b` elementwise
+    ne => !=,
+);
+
+/// Mask of the NaN elements of `a` (always false for non-float types)
+#[allow(clippy::eq_op)]
+pub fn isnan<T: PartialEq>(a: &TensorView<'_, T>) -> Tensor<bool> {
+    zip_with("isnan", a, a, |x, _| x != x)
+}
+
+/// `a` where `mask` is true and `b` elsewhere (NumPy `where`)
+pub fn select<T: Copy>(mask: &TensorView<'_, bool>, a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Tensor<T> {
+    if let Err(e) = check_same_shape("select", mask.layout().shape(), a.layout().shape()) {
+        panic!("{e}");
+    }
+    let picked = zip_with("select", a, b, |&x, &y| (x, y));
+    let data = mask.indexed_iter().zip(picked.data()).map(|((_, &m), &(x, y))| if m { x } else { y }).collect();
+    Tensor::new(data, picked.layout().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn comparisons_and_select_across_layouts() {
+        let a = Tensor::new(vec![1.0, f32::NAN, 3.0, 4.0, 5.0, 6.0], Layout::row_major([2, 3]));
+        // [[6,4,2],[5,3,1]] stored column-major
+        let b = Tensor::new(vec![6.0, 5.0, 4.0, 3.0, 2.0, 1.0], Layout::col_major([2, 3]));
+
+        assert_eq!(gt(&a.as_view(), &b.as_view()).data(), &[false, false, true, false, true, true]);
+        assert_eq!(le(&a.as_view(), &b.as_view()).data(), &[true, false, false, true, false, false]);
+        assert_eq!(ne(&a.as_view(), &a.as_view()).data(), &[false, true, false, false, false, false]);
+        assert_eq!(isnan(&a.as_view()).data(), &[false, true, false, false, false, false]);
+
+        let mask = lt(&a.as_view(), &b.as_view());
+        let out = select(&mask.as_view(), &a.as_view(), &b.as_view());
+        assert_eq!(out.data(), &[1.0, 4.0, 2.0, 4.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "eq: shape mismatch")]
+    fn shapes_must_match() {
+        let a = Tensor::new(vec![0; 4], Layout::row_major([2, 2]));
+        let b = Tensor::new(vec![0; 4], Layout::row_major([4]));
+        eq(&a.as_view(), &b.as_view());
+    }
+}
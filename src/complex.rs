@@ -0,0 +1,238 @@
+// src/complex.rs
+//
+// Complex GEMM over `Complex32` / `Complex64` tensors. Operands lower to
+// BLAS exactly like `gemm_f32`; conjugation rides on the transpose flag,
+// so a conjugated operand must reach the backend transposed (ConjTrans).
+// Operands in the other order are conjugated into a transposed copy first.
+
+use num_complex::{Complex32, Complex64};
+
+use crate::blas::{BlasBackend, BlasTranspose};
+use crate::copy::tensor_copy;
+use crate::error::Result;
+use crate::gemm::{check_gemm_shapes, flip, try_lower_gemm};
+use crate::layout::Layout;
+use crate::tensor::{check_disjoint, Tensor, TensorView, TensorViewMut};
+
+pub use num_complex::Complex;
+
+/// Complex element types with a backend GEMM
+pub trait ComplexGemm: Copy + Default {
+    fn conj(self) -> Self;
+
+    /// # Safety
+    /// The pointers must address row-major operands of the given extents.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn gemm<B: BlasBackend + ?Sized>(
+        backend: &B,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Self,
+        a: *const Self,
+        lda: i32,
+        b: *const Self,
+        ldb: i32,
+        beta: Self,
+        c: *mut Self,
+        ldc: i32,
+    );
+}
+
+impl ComplexGemm for Complex32 {
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+
+    unsafe fn gemm<B: BlasBackend + ?Sized>(
+        backend: &B, ta: BlasTranspose, tb: BlasTranspose, m: i32, n: i32, k: i32,
+        alpha: Self, a: *const Self, lda: i32, b: *const Self, ldb: i32, beta: Self, c: *mut Self, ldc: i32,
+    ) {
+        backend.gemm_c32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+}
+
+impl ComplexGemm for Complex64 {
+    fn conj(self) -> Self {
+        Complex::conj(&self)
+    }
+
+    unsafe fn gemm<B: BlasBackend + ?Sized>(
+        backend: &B, ta: BlasTranspose, tb: BlasTranspose, m: i32, n: i32, k: i32,
+        alpha: Self, a: *const Self, lda: i32, b: *const Self, ldb: i32, beta: Self, c: *mut Self, ldc: i32,
+    ) {
+        backend.gemm_c64(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+}
+
+/// Which GEMM operands are conjugated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Conj {
+    pub a: bool,
+    pub b: bool,
+}
+
+/// `C = alpha * op(A) * op(B) + beta * C`, where `op` conjugates the
+/// operands selected by `conj`
+pub fn gemm_complex<T: ComplexGemm, B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, T>,
+    b: &TensorView<'_, T>,
+    c: &mut TensorViewMut<'_, T>,
+    alpha: T,
+    beta: T,
+    conj: Conj,
+) {
+    if let Err(e) = try_gemm_complex(backend, a, b, c, alpha, beta, conj) {
+        panic!("{e}");
+    }
+}
+
+/// `gemm_complex` returning shape and layout problems as errors. BLAS can
+/// only conjugate an operand it reads transposed: with a row-major C a
+/// conjugated operand must be column-major, and with a column-major C
+/// row-major. Other conjugated operands are copied first.
+pub fn try_gemm_complex<T: ComplexGemm, B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, T>,
+    b: &TensorView<'_, T>,
+    c: &mut TensorViewMut<'_, T>,
+    alpha: T,
+    beta: T,
+    conj: Conj,
+) -> Result<()> {
+    const OP: &str = "gemm_complex";
+    check_disjoint(OP, a, c)?;
+    check_disjoint(OP, b, c)?;
+
+    let (m, n, k) = check_gemm_shapes(OP, a.layout(), b.layout(), c.layout())?;
+    let lowered = try_lower_gemm(OP, a.layout(), b.layout(), c.layout())?;
+
+    // The kernel reads an operand transposed when its order differs from
+    // C's; a conjugated operand in C's order is conjugated into the other
+    let (reads_transposed, other_order): (BlasTranspose, fn([usize; 2]) -> Layout) = if lowered.swap {
+        (BlasTranspose::NoTrans, Layout::row_major)
+    } else {
+        (BlasTranspose::Trans, Layout::col_major)
+    };
+    let copy = |x: &TensorView<'_, T>, t: BlasTranspose, conj: bool, extents: [usize; 2]| {
+        (conj && t != reads_transposed).then(|| conjugated(x, other_order(extents)))
+    };
+    let a_copy = copy(a, lowered.lda.1, conj.a, [m, k]);
+    let b_copy = copy(b, lowered.ldb.1, conj.b, [k, n]);
+    let conj = Conj { a: conj.a && a_copy.is_none(), b: conj.b && b_copy.is_none() };
+    // SAFETY: a view with its own layout addresses the same elements
+    let a = a_copy.as_ref().map_or_else(|| unsafe { a.with_layout(a.layout().clone()) }, Tensor::as_view);
+    let b = b_copy.as_ref().map_or_else(|| unsafe { b.with_layout(b.layout().clone()) }, Tensor::as_view);
+    let lowered = try_lower_gemm(OP, a.layout(), b.layout(), c.layout())?;
+
+    let conjugate = |t: BlasTranspose, conj: bool| match (t, conj) {
+        (BlasTranspose::Trans, true) => BlasTranspose::ConjTrans,
+        (t, _) => t,
+    };
+
+    let ((lda, ta), (ldb, tb)) = (lowered.lda, lowered.ldb);
+    let (m, n, k) = (m as i32, n as i32, k as i32);
+    let (pa, pb, pc) = (a.as_ptr(), b.as_ptr(), c.ptr.as_ptr());
+    unsafe {
+        if lowered.swap {
+            // Row-major C^T = op(B)^T op(A)^T
+            let (tb, ta) = (conjugate(flip(tb), conj.b), conjugate(flip(ta), conj.a));
+            T::gemm(backend, tb, ta, n, m, k, alpha, pb, ldb, pa, lda, beta, pc, lowered.ldc);
+        } else {
+            let (ta, tb) = (conjugate(ta, conj.a), conjugate(tb, conj.b));
+            T::gemm(backend, ta, tb, m, n, k, alpha, pa, lda, pb, ldb, beta, pc, lowered.ldc);
+        }
+    }
+    Ok(())
+}
+
+/// Conjugated copy of `x` stored with `layout`
+fn conjugated<T: ComplexGemm>(x: &TensorView<'_, T>, layout: Layout) -> Tensor<T> {
+    let mut out = Tensor::new(vec![T::default(); layout.size()], layout);
+    tensor_copy(x, &mut out.as_view_mut());
+    out.data_mut().iter_mut().for_each(|v| *v = v.conj());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    fn reference(a: &Tensor<Complex64>, b: &Tensor<Complex64>, conj: Conj) -> Vec<Complex64> {
+        let pick = |t: &Tensor<Complex64>, c: bool| {
+            Tensor::new(t.data().iter().map(|x| if c { x.conj() } else { *x }).collect(), t.layout().clone())
+        };
+        let (a, b) = (pick(a, conj.a), pick(b, conj.b));
+        let (m, n) = (a.layout().shape().flat_at(0), b.layout().shape().flat_at(1));
+        let mut out = Tensor::new(vec![Complex64::ZERO; m * n], Layout::row_major([m, n]));
+        crate::reference::gemm(&a.as_view(), &b.as_view(), &mut out.as_view_mut(), Complex64::ONE, Complex64::ZERO);
+        out.into_vec()
+    }
+
+    fn operand(rows: usize, cols: usize, layout: fn([usize; 2]) -> Layout, seed: f64) -> Tensor<Complex64> {
+        let data = (0..rows * cols).map(|x| Complex64::new(x as f64 + seed, (x % 3) as f64 - seed)).collect();
+        Tensor::new(data, layout([rows, cols]))
+    }
+
+    #[test]
+    fn conjugation_follows_operand_orders() {
+        let (m, k, n) = (3, 4, 2);
+        let a = operand(m, k, Layout::col_major, 0.5);
+        let b = operand(k, n, Layout::row_major, -1.0);
+
+        let mut c = Tensor::new(vec![Complex64::ZERO; m * n], Layout::row_major([m, n]));
+        let conj = Conj { a: true, b: false };
+        gemm_complex(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Complex64::ONE, Complex64::ZERO, conj);
+        assert_eq!(c.data(), reference(&a, &b, conj).as_slice());
+
+        // Column-major C swaps the operands, so now the row-major B can be conjugated
+        let mut c = Tensor::new(vec![Complex64::ZERO; m * n], Layout::col_major([m, n]));
+        let conj = Conj { a: false, b: true };
+        gemm_complex(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Complex64::ONE, Complex64::ZERO, conj);
+        let expected = reference(&a, &b, conj);
+        for i in 0..m {
+            for j in 0..n {
+                assert_eq!(c.as_view()[[i, j]], expected[i * n + j]);
+            }
+        }
+
+    }
+
+    #[test]
+    fn operands_in_c_order_are_conjugated_through_a_copy() {
+        let (m, k, n) = (2, 3, 4);
+        let a = operand(m, k, Layout::row_major, 0.25);
+        let b = operand(k, n, Layout::col_major, 2.0);
+        for (c_order, conj) in [
+            (Layout::row_major as fn([usize; 2]) -> Layout, Conj { a: true, b: false }),
+            (Layout::row_major, Conj { a: true, b: true }),
+            (Layout::col_major, Conj { a: false, b: true }),
+            (Layout::col_major, Conj { a: true, b: true }),
+        ] {
+            let mut c = Tensor::new(vec![Complex64::ZERO; m * n], c_order([m, n]));
+            gemm_complex(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Complex64::ONE, Complex64::ZERO, conj);
+            let expected = reference(&a, &b, conj);
+            for i in 0..m {
+                for j in 0..n {
+                    assert_eq!(c.as_view()[[i, j]], expected[i * n + j], "{conj:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn c32_scales_by_complex_alpha_and_beta() {
+        let a = Tensor::new(vec![Complex32::new(1.0, 1.0), Complex32::new(0.0, 2.0)], Layout::row_major([1, 2]));
+        let b = Tensor::new(vec![Complex32::new(2.0, 0.0), Complex32::new(1.0, -1.0)], Layout::row_major([2, 1]));
+        let mut c = Tensor::new(vec![Complex32::new(1.0, 0.0)], Layout::row_major([1, 1]));
+        // a.b = (2+2i) + (2+2i) = 4+4i; i * (4+4i) + 2 * 1 = -2+4i
+        gemm_complex(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Complex32::I, Complex32::new(2.0, 0.0), Conj::default());
+        assert_eq!(c.data(), &[Complex32::new(-2.0, 4.0)]);
+    }
+}
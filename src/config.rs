@@ -0,0 +1,153 @@
+// src/config.rs
+//
+// Process-wide defaults for the drivers, read from the environment on first
+// use so deployments can be tuned without recompiling:
+//
+//   RUTILE_NUM_THREADS  threads per parallel driver (`parallel::num_threads`)
+//   RUTILE_TILE_M/N     C tile of the GEMM drivers (`hw::default_tile_for_gemm`)
+//   RUTILE_TILE_K       panel depth of `NativeGemm`
+//   RUTILE_BACKEND      `native`, `generic` or `auto`, for `config::backend`
+//
+// Unset, zero or unparsable values keep the built-in defaults. `set`
+// replaces the configuration programmatically; `set_num_threads` still
+// takes precedence over the thread count here.
+
+use std::sync::RwLock;
+
+use crate::blas::{BlasBackend, GenericBlas, NativeBlas};
+use crate::error::Result;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Config {
+    pub num_threads: Option<usize>,
+    pub tile_m: Option<usize>,
+    pub tile_n: Option<usize>,
+    pub tile_k: Option<usize>,
+    pub backend: BackendChoice,
+}
+
+/// BLAS backend returned by `config::backend`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendChoice {
+    /// The system CBLAS when one loads, `NativeBlas` otherwise
+    #[default]
+    Auto,
+    Native,
+    Generic,
+}
+
+impl BackendChoice {
+    fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "auto" => Some(BackendChoice::Auto),
+            "native" => Some(BackendChoice::Native),
+            "generic" | "cblas" => Some(BackendChoice::Generic),
+            _ => None,
+        }
+    }
+
+    /// Load the chosen backend; `Generic` fails with `Error::BackendLoad`
+    /// when no CBLAS library is installed
+    pub fn load(self) -> Result<Box<dyn BlasBackend + Send + Sync>> {
+        Ok(match self {
+            BackendChoice::Native => Box::new(NativeBlas),
+            BackendChoice::Generic => Box::new(GenericBlas::try_load()?),
+            BackendChoice::Auto => match GenericBlas::try_load() {
+                Ok(generic) => Box::new(generic),
+                Err(_) => Box::new(NativeBlas),
+            },
+        })
+    }
+}
+
+impl Config {
+    /// The configuration described by the `RUTILE_*` variables
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let count = |name| var(name).and_then(|v| v.trim().parse::<usize>().ok()).filter(|&n| n > 0);
+        Config {
+            num_threads: count("RUTILE_NUM_THREADS"),
+            tile_m: count("RUTILE_TILE_M"),
+            tile_n: count("RUTILE_TILE_N"),
+            tile_k: count("RUTILE_TILE_K"),
+            backend: var("RUTILE_BACKEND").and_then(|v| BackendChoice::parse(&v)).unwrap_or_default(),
+        }
+    }
+}
+
+static CONFIG: RwLock<Option<Config>> = RwLock::new(None);
+
+/// The current configuration, read from the environment the first time
+pub fn get() -> Config {
+    if let Some(config) = *CONFIG.read().unwrap() {
+        return config;
+    }
+    *CONFIG.write().unwrap().get_or_insert_with(|| {
+        let config = Config::from_env();
+        log_debug!("configuration from the environment: {config:?}");
+        config
+    })
+}
+
+/// Replace the configuration for every later driver call. Zero counts keep
+/// the built-in defaults, as they do in the environment.
+pub fn set(mut config: Config) {
+    for count in [&mut config.num_threads, &mut config.tile_m, &mut config.tile_n, &mut config.tile_k] {
+        *count = count.filter(|&n| n > 0);
+    }
+    *CONFIG.write().unwrap() = Some(config);
+}
+
+/// The backend named by the configuration
+pub fn backend() -> Result<Box<dyn BlasBackend + Send + Sync>> {
+    get().backend.load()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gemm::gemm_f32;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    #[test]
+    fn reads_variables_and_ignores_bad_values() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            Config::from_vars(|name| vars.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string()))
+        };
+        let config = env(&[("RUTILE_NUM_THREADS", "6"), ("RUTILE_TILE_M", " 64 "), ("RUTILE_TILE_K", "128"), ("RUTILE_BACKEND", "Native")]);
+        assert_eq!(
+            config,
+            Config { num_threads: Some(6), tile_m: Some(64), tile_n: None, tile_k: Some(128), backend: BackendChoice::Native }
+        );
+
+        let config = env(&[("RUTILE_NUM_THREADS", "0"), ("RUTILE_TILE_N", "many"), ("RUTILE_BACKEND", "gpu")]);
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn set_ignores_zero_counts() {
+        let previous = get();
+        set(Config { tile_k: Some(0), ..previous });
+        assert_eq!(get().tile_k, None);
+
+        // A zero panel depth would stall the packed kernel
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], Layout::row_major([2, 2]));
+        let mut c = Tensor::new(vec![0.0; 4], Layout::row_major([2, 2]));
+        gemm_f32(&crate::kernel::NativeGemm::default(), &a.as_view(), &a.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        assert_eq!(c.data(), &[7.0, 10.0, 15.0, 22.0]);
+        set(previous);
+    }
+
+    #[test]
+    fn loaded_backend_runs_gemm() {
+        let backend = BackendChoice::Native.load().unwrap();
+        let a = Tensor::new(vec![1.0, 2.0, 3.0, 4.0], Layout::row_major([2, 2]));
+        let mut c = Tensor::new(vec![0.0; 4], Layout::row_major([2, 2]));
+        gemm_f32(&backend, &a.as_view(), &a.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        assert_eq!(c.data(), &[7.0, 10.0, 15.0, 22.0]);
+    }
+}
@@ -0,0 +1,222 @@
+// src/contract.rs
+//
+// General tensor contraction. The modes of each operand are split into
+// free and contracted ones, and each group is merged into a single matrix
+// mode when its strides allow, so that the whole contraction is one GEMM on
+// views of the inputs. An operand whose groups do not merge (a transposed
+// slice, say) is first copied into a compact tensor in the grouped order.
+
+use crate::blas::BlasBackend;
+use crate::error::{Error, Result};
+use crate::gemm::{gemm_f32, try_lower_matrix};
+use crate::layout::{Layout, RowMajor};
+use crate::shape::Shape;
+use crate::tensor::{Tensor, TensorView};
+use crate::tuple::Tuple;
+
+/// Contract mode `axes_a[i]` of `a` with mode `axes_b[i]` of `b` for every
+/// `i`. The result has the free modes of `a` followed by the free modes of
+/// `b`, in their original order, and is row-major. Mode-k tensor-times-
+/// matrix is `contract(x, u, &[k], &[1])` followed by moving the last mode
+/// back to position `k`.
+pub fn contract<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    axes_a: &[usize],
+    axes_b: &[usize],
+) -> Tensor<f32> {
+    try_contract(backend, a, b, axes_a, axes_b).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `contract` returning invalid axes and mismatched extents as errors
+pub fn try_contract<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    axes_a: &[usize],
+    axes_b: &[usize],
+) -> Result<Tensor<f32>> {
+    const OP: &str = "contract";
+    if axes_a.len() != axes_b.len() {
+        return Err(Error::RankMismatch { op: OP, expected: axes_a.len(), found: axes_b.len() });
+    }
+    let free_a = free_modes(OP, a.layout(), axes_a)?;
+    let free_b = free_modes(OP, b.layout(), axes_b)?;
+    let (dims_a, dims_b) = (a.layout().flat_shape(), b.layout().flat_shape());
+    if axes_a.iter().zip(axes_b).any(|(&i, &j)| dims_a[i] != dims_b[j]) {
+        return Err(Error::ShapeMismatch { op: OP, lhs: a.layout().shape().clone(), rhs: b.layout().shape().clone() });
+    }
+
+    let lhs = Operand::new(a, &free_a, axes_a);
+    let rhs = Operand::new(b, axes_b, &free_b);
+    let (m, k) = lhs.extents;
+    let n = rhs.extents.1;
+
+    let mut c = Tensor::new(vec![0.0; m * n], Layout::row_major([m, n]));
+    gemm_f32(backend, &lhs.view(), &rhs.view(), &mut c.as_view_mut(), 1.0, 0.0);
+    debug_assert_eq!(k, rhs.extents.0);
+
+    let out: Vec<usize> = free_a.iter().map(|&i| dims_a[i]).chain(free_b.iter().map(|&j| dims_b[j])).collect();
+    Ok(Tensor::new(c.into_vec(), Layout::row_major(Shape::new(Tuple::int(out)))))
+}
+
+/// Flattened modes of `layout` not in `axes`, checking `axes` names
+/// distinct modes
+fn free_modes(op: &'static str, layout: &Layout, axes: &[usize]) -> Result<Vec<usize>> {
+    let rank = layout.flat_shape().len();
+    let mut used = vec![false; rank];
+    for &axis in axes {
+        if axis >= rank || used[axis] {
+            return Err(Error::InvalidAxis { op, axis, rank });
+        }
+        used[axis] = true;
+    }
+    Ok((0..rank).filter(|&i| !used[i]).collect())
+}
+
+/// One GEMM operand: `rows` modes merged into the first matrix mode and
+/// `cols` into the second, either as a view or through a compact copy
+struct Operand<'v> {
+    source: Source<'v>,
+    layout: Layout,
+    extents: (usize, usize),
+}
+
+enum Source<'v> {
+    View(TensorView<'v, f32>),
+    Copied(Tensor<f32>),
+}
+
+impl<'v> Operand<'v> {
+    fn new(view: &TensorView<'v, f32>, rows: &[usize], cols: &[usize]) -> Self {
+        let (dims, stride) = (view.layout().flat_shape(), view.layout().flat_stride());
+        let extent = |modes: &[usize]| modes.iter().map(|&i| dims[i]).product::<usize>();
+        let extents = (extent(rows), extent(cols));
+
+        if !view.layout().has_reversed_modes() {
+            if let (Some(sr), Some(sc)) = (merge(dims, stride, rows), merge(dims, stride, cols)) {
+                let layout = matrix(extents, (sr, sc));
+                if try_lower_matrix("contract", &layout).is_ok() {
+                    // `layout` reaches exactly the elements of `view`
+                    let view = unsafe { view.with_layout(layout.clone()) };
+                    return Self { source: Source::View(view), layout, extents };
+                }
+            }
+        }
+
+        // Permute the modes into `rows ++ cols` order and compact that
+        let order: Vec<usize> = rows.iter().chain(cols).copied().collect();
+        let mut permuted = Layout::with_shape_stride(
+            Shape::new(Tuple::int(order.iter().map(|&i| dims[i]).collect())),
+            Tuple::int(order.iter().map(|&i| stride[i]).collect()),
+        );
+        for (to, &from) in order.iter().enumerate() {
+            if view.layout().is_reversed(from) {
+                permuted = permuted.flip(to);
+            }
+        }
+        // The same elements as `view`, visited in another order
+        let copy = unsafe { view.with_layout(permuted) }.to_tensor::<RowMajor>();
+        let layout = matrix(extents, (extents.1, 1));
+        Self { source: Source::Copied(copy), layout, extents }
+    }
+
+    fn view(&self) -> TensorView<'_, f32> {
+        // `layout` addresses exactly the elements of either source
+        match &self.source {
+            Source::View(v) => unsafe { v.with_layout(self.layout.clone()) },
+            Source::Copied(t) => unsafe { t.as_view().with_layout(self.layout.clone()) },
+        }
+    }
+}
+
+/// Stride of `modes` merged into one mode, if consecutive modes nest
+/// (each stride is the next one times its extent). Extent-1 modes never
+/// constrain the merge.
+fn merge(dims: &[usize], stride: &[usize], modes: &[usize]) -> Option<usize> {
+    let kept: Vec<usize> = modes.iter().copied().filter(|&i| dims[i] != 1).collect();
+    for pair in kept.windows(2) {
+        if stride[pair[0]] != stride[pair[1]] * dims[pair[1]] {
+            return None;
+        }
+    }
+    Some(kept.last().map_or(1, |&i| stride[i]))
+}
+
+fn matrix((rows, cols): (usize, usize), (sr, sc): (usize, usize)) -> Layout {
+    Layout::with_shape_stride(Shape::new(Tuple::int(vec![rows, cols])), Tuple::int(vec![sr, sc]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::shape::for_each_flat_coord;
+
+    fn iota(dims: &[usize]) -> Tensor<f32> {
+        let n = dims.iter().product();
+        Tensor::new((0..n).map(|x| (x % 11) as f32 - 5.0).collect(), Layout::row_major(Shape::new(Tuple::int(dims.to_vec()))))
+    }
+
+    /// Sum over the contracted index by brute force
+    fn reference(a: &Tensor<f32>, b: &Tensor<f32>, axes_a: &[usize], axes_b: &[usize]) -> Vec<f32> {
+        let (da, db) = (a.layout().flat_shape().to_vec(), b.layout().flat_shape().to_vec());
+        let free_a = free_modes("test", a.layout(), axes_a).unwrap();
+        let free_b = free_modes("test", b.layout(), axes_b).unwrap();
+        let mut out = Vec::new();
+        let out_shape = Shape::new(Tuple::int(free_a.iter().map(|&i| da[i]).chain(free_b.iter().map(|&j| db[j])).collect()));
+        let k_shape = Shape::new(Tuple::int(axes_a.iter().map(|&i| da[i]).collect()));
+        for_each_flat_coord(&out_shape, |o| {
+            let mut sum = 0.0;
+            for_each_flat_coord(&k_shape, |kc| {
+                let (mut ca, mut cb) = (vec![0; da.len()], vec![0; db.len()]);
+                for (p, &i) in free_a.iter().enumerate() {
+                    ca[i] = o[p];
+                }
+                for (p, &j) in free_b.iter().enumerate() {
+                    cb[j] = o[free_a.len() + p];
+                }
+                for (p, (&i, &j)) in axes_a.iter().zip(axes_b).enumerate() {
+                    (ca[i], cb[j]) = (kc[p], kc[p]);
+                }
+                sum += unsafe { a.as_view().get_flat(&ca) * b.as_view().get_flat(&cb) };
+            });
+            out.push(sum);
+        });
+        out
+    }
+
+    fn check(da: &[usize], db: &[usize], axes_a: &[usize], axes_b: &[usize]) {
+        let (a, b) = (iota(da), iota(db));
+        let c = contract(&NativeBlas, &a.as_view(), &b.as_view(), axes_a, axes_b);
+        assert_eq!(c.data(), reference(&a, &b, axes_a, axes_b), "{da:?} x {db:?} over {axes_a:?} / {axes_b:?}");
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        check(&[2, 3, 4], &[4, 5], &[2], &[0]);
+        check(&[2, 3, 4], &[3, 5], &[1], &[0]);
+        // B's contracted modes do not merge in this order, so B is copied
+        check(&[2, 3, 4], &[4, 3, 2], &[1, 2], &[1, 0]);
+        check(&[3, 4], &[4, 3], &[0, 1], &[1, 0]);
+    }
+
+    #[test]
+    fn mode_k_product_keeps_free_extents() {
+        // X (2x3x4) times U (5x3) along mode 1: free modes of X, then U's rows
+        let (x, u) = (iota(&[2, 3, 4]), iota(&[5, 3]));
+        let y = contract(&NativeBlas, &x.as_view(), &u.as_view(), &[1], &[1]);
+        assert_eq!(y.layout().flat_shape(), &[2, 4, 5]);
+    }
+
+    #[test]
+    fn rejects_bad_axes() {
+        let (a, b) = (iota(&[2, 3]), iota(&[3, 2]));
+        let c = |aa: &[usize], bb: &[usize]| try_contract(&NativeBlas, &a.as_view(), &b.as_view(), aa, bb).err();
+        assert!(matches!(c(&[1], &[1]), Some(Error::ShapeMismatch { .. })));
+        assert!(matches!(c(&[1, 1], &[0, 1]), Some(Error::InvalidAxis { axis: 1, .. })));
+        assert!(matches!(c(&[2], &[0]), Some(Error::InvalidAxis { axis: 2, .. })));
+        assert!(matches!(c(&[1], &[]), Some(Error::RankMismatch { .. })));
+    }
+}
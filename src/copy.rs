@@ -1,106 +1,451 @@
-use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tensor::{check_disjoint, Tensor, TensorView, TensorViewMut};
+use crate::shape::{coords, for_each_flat_coord, Shape};
+use crate::shape_infer::{concat_shape, stack_shape};
+use crate::layout::{Layout, RowMajor};
 use crate::tuple::Tuple;
-use crate::shape::Shape;
-use crate::layout::*;
-use std::ptr::NonNull;
+use crate::error::{check_same_shape, Result};
+use crate::parallel::Parallelism;
+use crate::bench_utils::KernelStats;
+use std::ops::{AddAssign, Mul};
 
 /// Copy from `src` (Tensor / TensorView) to `dst` (Tensor / TensorViewMut)
 pub fn tensor_copy<T: Copy>(
     src: &TensorView<'_, T>,
     dst: &mut TensorViewMut<'_, T>,
 ) {
+    if let Err(e) = try_tensor_copy(src, dst) {
+        panic!("{e}");
+    }
+}
+
+/// `tensor_copy` returning a shape mismatch as an error. Debug builds also
+/// reject a `dst` that overlaps `src`.
+pub fn try_tensor_copy<T: Copy>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+) -> Result<()> {
+    check_disjoint("tensor_copy", src, dst)?;
+    copy_impl(src, dst)
+}
+
+/// `tensor_copy` without the aliasing check
+///
+/// # Safety
+/// `dst` must not overlap `src`.
+pub unsafe fn tensor_copy_unchecked<T: Copy>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+) {
+    if let Err(e) = copy_impl(src, dst) {
+        panic!("{e}");
+    }
+}
+
+fn copy_impl<T: Copy>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+) -> Result<()> {
     let shape = src.layout().shape();
-    assert_eq!(shape, dst.layout().shape(), "tensor_copy: shape mismatch");
+    check_same_shape("tensor_copy", shape, dst.layout().shape())?;
 
-    // fast path for contiguous 2D tensors
-    if src.layout().is_contiguous() && dst.layout().is_contiguous() {
+    // fast path: both contiguous in the same mode order, so memory matches element for element
+    if src.layout().is_contiguous()
+        && dst.layout().is_contiguous()
+        && src.layout().flat_stride() == dst.layout().flat_stride()
+    {
         let n = src.layout().size();
+        log_trace!("tensor_copy {shape}: contiguous, {n} elements");
         unsafe {
             std::ptr::copy_nonoverlapping(src.ptr.as_ptr(), dst.ptr.as_ptr(), n);
         }
-        return;
+        return Ok(());
     }
 
     // fallback: strided / N-D copy
-    copy_recursive(src, dst, 0, &mut Vec::new());
+    log_trace!("tensor_copy {shape}: strided, {} -> {}", src.layout().stride(), dst.layout().stride());
+    for_each_flat_coord(shape, |crd| unsafe {
+        *dst.get_flat_mut(crd) = *src.get_flat(crd);
+    });
+    Ok(())
+}
+
+/// `dst[c] = f(src[c])` for every coordinate `c`, walking memory linearly
+/// when both sides are contiguous in the same mode order (the `tensor_copy`
+/// fast path) and coordinate by coordinate otherwise
+pub(crate) fn map_impl<S: Copy, D>(
+    op: &'static str,
+    src: &TensorView<'_, S>,
+    dst: &mut TensorViewMut<'_, D>,
+    f: impl Fn(S) -> D,
+) -> Result<()> {
+    let shape = src.layout().shape();
+    check_same_shape(op, shape, dst.layout().shape())?;
+
+    if src.layout().is_contiguous()
+        && dst.layout().is_contiguous()
+        && src.layout().flat_stride() == dst.layout().flat_stride()
+    {
+        let n = src.layout().size();
+        let (from, to) = unsafe {
+            (std::slice::from_raw_parts(src.as_ptr(), n), std::slice::from_raw_parts_mut(dst.ptr.as_ptr(), n))
+        };
+        for (d, &s) in to.iter_mut().zip(from) {
+            *d = f(s);
+        }
+        return Ok(());
+    }
+
+    for_each_flat_coord(shape, |crd| unsafe {
+        *dst.get_flat_mut(crd) = f(*src.get_flat(crd));
+    });
+    Ok(())
+}
+
+/* ============================================================
+   Accumulation
+   ============================================================ */
+
+/// `dst += src` elementwise, e.g. folding the partial C of one K-tile into C
+pub fn tensor_add_assign<T: Copy + AddAssign>(dst: &mut TensorViewMut<'_, T>, src: &TensorView<'_, T>) {
+    if let Err(e) = try_tensor_add_assign(dst, src) {
+        panic!("{e}");
+    }
+}
+
+/// `tensor_add_assign` returning a shape mismatch (or, in debug builds, an
+/// overlap of `dst` and `src`) as an error
+pub fn try_tensor_add_assign<T: Copy + AddAssign>(dst: &mut TensorViewMut<'_, T>, src: &TensorView<'_, T>) -> Result<()> {
+    check_disjoint("tensor_add_assign", src, dst)?;
+    accumulate_impl("tensor_add_assign", dst, src, |d, s| *d += s)
+}
+
+/// `dst += alpha * src` elementwise (BLAS axpy over views)
+pub fn axpy_view<T: Copy + AddAssign + Mul<Output = T>>(dst: &mut TensorViewMut<'_, T>, alpha: T, src: &TensorView<'_, T>) {
+    if let Err(e) = try_axpy_view(dst, alpha, src) {
+        panic!("{e}");
+    }
 }
 
-/// Recursive N-D copy
-fn copy_recursive<T: Copy>(
+/// `axpy_view` returning a shape mismatch (or, in debug builds, an overlap
+/// of `dst` and `src`) as an error
+pub fn try_axpy_view<T: Copy + AddAssign + Mul<Output = T>>(
+    dst: &mut TensorViewMut<'_, T>,
+    alpha: T,
     src: &TensorView<'_, T>,
+) -> Result<()> {
+    check_disjoint("axpy_view", src, dst)?;
+    accumulate_impl("axpy_view", dst, src, |d, s| *d += alpha * s)
+}
+
+/// `f(&mut dst[c], src[c])` for every coordinate. The last flattened mode is
+/// the inner loop; when it has unit stride on both sides it runs over
+/// slices, which the compiler vectorizes. Outer modes are walked with signed
+/// strides, so reversed and hierarchical layouts need no coordinate tuples.
+fn accumulate_impl<T: Copy>(
+    op: &'static str,
     dst: &mut TensorViewMut<'_, T>,
-    dim: usize,
-    idx: &mut Vec<usize>,
-) {
-    let ndims = src.layout().shape().dims.len();
-    if dim == ndims {
-        let coord = Tuple::Int(idx.clone());
+    src: &TensorView<'_, T>,
+    f: impl Fn(&mut T, T),
+) -> Result<()> {
+    let shape = src.layout().shape();
+    check_same_shape(op, shape, dst.layout().shape())?;
+    let extents = src.layout().flat_shape();
+    if extents.contains(&0) {
+        return Ok(());
+    }
+
+    let (ss, ds) = (src.layout().signed_stride(), dst.layout().signed_stride());
+    let rank = extents.len();
+    let (inner, s_in, d_in) = match rank {
+        0 => (1, 0, 0),
+        _ => (extents[rank - 1], ss[rank - 1], ds[rank - 1]),
+    };
+    let outer = &extents[..rank.saturating_sub(1)];
+    log_trace!("{op} {shape}: {} inner mode of {inner}", if s_in == 1 && d_in == 1 { "unit-stride" } else { "strided" });
+
+    let mut crd = vec![0; outer.len()];
+    let (mut s_off, mut d_off) = (0isize, 0isize);
+    loop {
         unsafe {
-            *dst.get_mut(&coord) = *src.get(&coord);
+            let (s, d) = (src.as_ptr().offset(s_off), dst.ptr.as_ptr().offset(d_off));
+            if s_in == 1 && d_in == 1 {
+                let (s, d) = (std::slice::from_raw_parts(s, inner), std::slice::from_raw_parts_mut(d, inner));
+                for (d, &s) in d.iter_mut().zip(s) {
+                    f(d, s);
+                }
+            } else {
+                for i in 0..inner as isize {
+                    f(&mut *d.offset(i * d_in), *s.offset(i * s_in));
+                }
+            }
         }
-        return;
-    }
 
-    let dim_len = src.layout().shape().dims.flatten()[dim];
-    for i in 0..dim_len {
-        idx.push(i);
-        copy_recursive(src, dst, dim + 1, idx);
-        idx.pop();
+        // Odometer over the outer modes, last fastest
+        let mut m = outer.len();
+        loop {
+            if m == 0 {
+                return Ok(());
+            }
+            m -= 1;
+            crd[m] += 1;
+            s_off += ss[m];
+            d_off += ds[m];
+            if crd[m] < outer[m] {
+                break;
+            }
+            s_off -= ss[m] * outer[m] as isize;
+            d_off -= ds[m] * outer[m] as isize;
+            crd[m] = 0;
+        }
     }
 }
 
-fn assert_tensor_eq<T: PartialEq + std::fmt::Debug>(
-    src: &crate::tensor::Tensor<T>,
-    dst: &crate::tensor::Tensor<T>,
+/* ============================================================
+   Masked copy / fill
+   ============================================================ */
+
+/// Copy `src` into `dst` only where `mask` is true
+pub fn tensor_copy_masked<T: Copy>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+    mask: &TensorView<'_, bool>,
 ) {
-    assert_eq!(src.layout().shape(), dst.layout().shape());
+    let shape = src.layout().shape();
+    assert_eq!(shape, dst.layout().shape(), "tensor_copy_masked: shape mismatch");
+    assert_eq!(shape, mask.layout().shape(), "tensor_copy_masked: mask shape mismatch");
 
-    let mut coord = Vec::new();
-    enumerate_coords(&src.layout().shape().dims, &mut coord, &mut |c| {
-        let t = crate::tuple::Tuple::Int(c.to_vec());
-        unsafe {
-            assert_eq!(
-                src.as_view().get(&t),
-                dst.as_view().get(&t),
-                "Mismatch at coord {:?}",
-                c
-            );
+    for_each_flat_coord(shape, |crd| unsafe {
+        if *mask.get_flat(crd) {
+            *dst.get_flat_mut(crd) = *src.get_flat(crd);
         }
     });
 }
 
-fn enumerate_coords(
-    shape: &crate::tuple::Tuple,
-    prefix: &mut Vec<usize>,
-    f: &mut impl FnMut(&[usize]),
+/// Write `value` into `dst` where `mask` is true
+pub fn fill_masked<T: Copy>(
+    dst: &mut TensorViewMut<'_, T>,
+    mask: &TensorView<'_, bool>,
+    value: T,
 ) {
-    match shape {
-        crate::tuple::Tuple::Int(dims) => {
-            enumerate_int(dims, 0, prefix, f);
+    let shape = dst.layout().shape().clone();
+    assert_eq!(&shape, mask.layout().shape(), "fill_masked: mask shape mismatch");
+
+    for_each_flat_coord(&shape, |crd| unsafe {
+        if *mask.get_flat(crd) {
+            *dst.get_flat_mut(crd) = value;
         }
-        crate::tuple::Tuple::Tup(children) => {
-            for child in children {
-                enumerate_coords(child, prefix, f);
+    });
+}
+
+/* ============================================================
+   Parallel copy
+   ============================================================ */
+
+/// Element count below which `tensor_copy_par` stays single-threaded
+pub const PARALLEL_COPY_THRESHOLD: usize = 1 << 20;
+
+/// Multi-threaded `tensor_copy` with the default `Parallelism`
+pub fn tensor_copy_par<T: Copy + Send + Sync>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+) -> KernelStats {
+    tensor_copy_par_with(src, dst, &Parallelism::default())
+}
+
+/// Multi-threaded `tensor_copy`. The copy is split along the outermost
+/// flattened mode into one slab per thread of `par`; tensors smaller than
+/// `PARALLEL_COPY_THRESHOLD` elements are copied on the calling thread.
+pub fn tensor_copy_par_with<T: Copy + Send + Sync>(
+    src: &TensorView<'_, T>,
+    dst: &mut TensorViewMut<'_, T>,
+    par: &Parallelism,
+) -> KernelStats {
+    let shape = src.layout().shape();
+    assert_eq!(shape, dst.layout().shape(), "tensor_copy: shape mismatch");
+
+    let stats = KernelStats::copy(shape.size(), std::mem::size_of::<T>());
+    let extents = src.layout().flat_shape();
+    let threads = par.num_threads();
+    if shape.size() < PARALLEL_COPY_THRESHOLD || threads < 2 || extents.is_empty() {
+        tensor_copy(src, dst);
+        return stats;
+    }
+
+    let outer = extents[0];
+    let slabs = threads.min(outer);
+    log_debug!("tensor_copy_par {shape}: {slabs} slabs on {threads} threads");
+    let mut jobs = Vec::with_capacity(slabs);
+
+    for s in 0..slabs {
+        let r0 = outer * s / slabs;
+        let r1 = outer * (s + 1) / slabs;
+
+        let mut start = vec![0; extents.len()];
+        start[0] = r0;
+        let mut slab = extents.to_vec();
+        slab[0] = r1 - r0;
+
+        let start = Tuple::int(start);
+        let slab = Shape::new(Tuple::int(slab));
+        // Slabs cover disjoint outer-mode ranges of `dst`
+        unsafe {
+            jobs.push((src.subview(&start, &slab), dst.subview_mut(&start, &slab)));
+        }
+    }
+
+    par.run(jobs, |(src, mut dst)| tensor_copy(&src, &mut dst));
+    stats
+}
+
+/* ============================================================
+   Concatenate / stack
+   ============================================================ */
+
+/// Join `inputs` along flattened mode `axis` into a new row-major tensor
+pub fn concat<T: Copy>(inputs: &[TensorView<'_, T>], axis: usize) -> Tensor<T> {
+    try_concat(inputs, axis).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `concat` returning mismatched shapes or a bad axis as an error
+pub fn try_concat<T: Copy>(inputs: &[TensorView<'_, T>], axis: usize) -> Result<Tensor<T>> {
+    let shapes: Vec<&Shape> = inputs.iter().map(|v| v.layout().shape()).collect();
+    let out = concat_shape(&shapes, axis)?;
+    let stride = Layout::row_major(out.clone()).stride().flatten();
+
+    let mut start = 0;
+    let placed = inputs.iter().map(|v| {
+        let offset = (start * stride[axis]) as isize;
+        start += v.layout().flat_shape()[axis];
+        (offset, stride.clone())
+    });
+    Ok(assemble(inputs, axis, out, placed.collect()))
+}
+
+/// Join equally shaped `inputs` along a new mode inserted at `axis`
+pub fn stack<T: Copy>(inputs: &[TensorView<'_, T>], axis: usize) -> Tensor<T> {
+    try_stack(inputs, axis).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `stack` returning mismatched shapes or a bad axis as an error
+pub fn try_stack<T: Copy>(inputs: &[TensorView<'_, T>], axis: usize) -> Result<Tensor<T>> {
+    let shapes: Vec<&Shape> = inputs.iter().map(|v| v.layout().shape()).collect();
+    let out = stack_shape(&shapes, axis)?;
+    let mut stride = Layout::row_major(out.clone()).stride().flatten();
+    let step = stride.remove(axis);
+
+    let placed = (0..inputs.len()).map(|i| ((i * step) as isize, stride.clone())).collect();
+    Ok(assemble(inputs, axis, out, placed))
+}
+
+/* ============================================================
+   Repeat
+   ============================================================ */
+
+/// NumPy `tile`: `view` repeated `reps[i]` times along flattened mode `i`,
+/// as a new row-major tensor. Rank mismatches are resolved with leading 1s
+/// as in `TensorView::repeat_view`, which this uses directly when only
+/// extent-1 modes repeat.
+pub fn repeat<T: Copy>(view: &TensorView<'_, T>, reps: &[usize]) -> Tensor<T> {
+    if let Some(lazy) = view.repeat_view(reps) {
+        return lazy.to_tensor::<RowMajor>();
+    }
+
+    let rank = view.layout().shape().flat_len().max(reps.len());
+    let src = view.repeat_view(&vec![1; rank]).expect("unit repetitions always broadcast");
+    let dims = src.layout().shape().dims.flatten();
+    let reps: Vec<usize> = std::iter::repeat_n(1, rank - reps.len()).chain(reps.iter().copied()).collect();
+
+    let layout = Layout::row_major(Shape::new(Tuple::int(dims.iter().zip(&reps).map(|(d, r)| d * r).collect())));
+    if layout.size() == 0 {
+        return Tensor::new(Vec::new(), layout);
+    }
+    let fill = unsafe { *src.as_ptr() };
+    let mut t = Tensor::new(vec![fill; layout.size()], layout);
+    let mut dst = t.as_view_mut();
+    for copy in coords(&Shape::new(Tuple::int(reps))) {
+        let start: Vec<usize> = copy.iter_flat().zip(&dims).map(|(c, d)| c * d).collect();
+        // Copies land on disjoint blocks of the output
+        let mut block = unsafe { dst.subview_mut(Tuple::int(start), src.layout().shape().clone()) };
+        tensor_copy(&src, &mut block);
+    }
+    t
+}
+
+/// Row-major tensor of shape `out` with input `i` copied in at
+/// `placed[i]`: its origin offset and the output strides of its modes.
+/// Inputs whose modes from `axis` on are packed row-major are appended
+/// block by block; otherwise each input is copied element by element.
+fn assemble<T: Copy>(inputs: &[TensorView<'_, T>], axis: usize, out: Shape, placed: Vec<(isize, Vec<usize>)>) -> Tensor<T> {
+    let layout = Layout::row_major(out);
+    if inputs.iter().all(|v| packed_from(v.layout(), axis)) {
+        let outer: usize = layout.flat_shape()[..axis].iter().product();
+        let mut data = Vec::with_capacity(layout.size());
+        for o in 0..outer {
+            for v in inputs {
+                let dims = v.layout().flat_shape();
+                let block: usize = dims[axis..].iter().product();
+                // Row-major coordinate of `o` over the outer modes, then its offset in `v`
+                let (mut rest, mut offset) = (o, 0);
+                for (d, s) in dims[..axis].iter().zip(v.layout().flat_stride()).rev() {
+                    offset += (rest % d) * s;
+                    rest /= d;
+                }
+                data.extend_from_slice(unsafe { std::slice::from_raw_parts(v.as_ptr().add(offset), block) });
             }
         }
+        return Tensor::new(data, layout);
+    }
+
+    let first = inputs.iter().find(|v| v.layout().size() > 0);
+    let Some(fill) = first.map(|v| unsafe { *v.get(&Tuple::int(vec![0; v.layout().shape().flat_len()])) }) else {
+        return Tensor::new(Vec::new(), layout);
+    };
+    let mut t = Tensor::new(vec![fill; layout.size()], layout);
+    for (v, (offset, stride)) in inputs.iter().zip(placed) {
+        // Inputs are walked by flat coordinates, which every layout accepts
+        let flat = Shape::new(Tuple::int(v.layout().flat_shape().to_vec()));
+        let target = Layout::with_shape_stride(flat.clone(), Tuple::int(stride));
+        // Each input lands on its own slab of the fresh output
+        let mut dst = unsafe { t.as_view_mut().into_offset(offset, target) };
+        for_each_flat_coord(&flat, |crd| unsafe {
+            *dst.get_flat_mut(crd) = *v.get_flat(crd);
+        });
     }
+    t
 }
 
-fn enumerate_int(
-    dims: &[usize],
-    d: usize,
-    prefix: &mut Vec<usize>,
-    f: &mut impl FnMut(&[usize]),
-) {
-    if d == dims.len() {
-        f(prefix);
-        return;
+/// Whether flattened modes `axis..` of `layout` are packed row-major, so
+/// each outer coordinate owns one contiguous block
+fn packed_from(layout: &Layout, axis: usize) -> bool {
+    if layout.has_reversed_modes() {
+        return false;
+    }
+    let (dims, stride) = (layout.flat_shape(), layout.flat_stride());
+    let mut expected = 1;
+    for (d, s) in dims[axis.min(dims.len())..].iter().zip(&stride[axis.min(dims.len())..]).rev() {
+        if *d != 1 && *s != expected {
+            return false;
+        }
+        expected *= d;
     }
+    true
+}
 
-    for i in 0..dims[d] {
-        prefix.push(i);
-        enumerate_int(dims, d + 1, prefix, f);
-        prefix.pop();
+fn assert_tensor_eq<T: PartialEq + std::fmt::Debug>(
+    src: &crate::tensor::Tensor<T>,
+    dst: &crate::tensor::Tensor<T>,
+) {
+    assert_eq!(src.layout().shape(), dst.layout().shape());
+
+    for crd in coords(src.layout().shape()) {
+        unsafe {
+            assert_eq!(
+                src.as_view().get(&crd),
+                dst.as_view().get(&crd),
+                "Mismatch at coord {}",
+                crd
+            );
+        }
     }
 }
 
@@ -110,6 +455,8 @@ fn enumerate_int(
 mod tests {
     use super::*;
     use crate::tensor::Tensor;
+    use crate::tuple::Tuple;
+    use crate::shape::Shape;
 
     #[test]
     fn test_copy_tensor_to_tensor() {
@@ -133,6 +480,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_copy_reports_shape_mismatch() {
+        let src = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([2, 2]));
+        let mut dst = Tensor::new(vec![0; 4], Layout::row_major([4]));
+        let err = try_tensor_copy(&src.as_view(), &mut dst.as_view_mut());
+        assert!(matches!(err, Err(crate::Error::ShapeMismatch { op: "tensor_copy", .. })));
+        assert_eq!(dst.data(), &[0; 4]);
+    }
+
+    #[test]
+    fn copy_onto_itself_is_rejected_in_debug() {
+        let mut t = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([2, 2]));
+        let src = unsafe { crate::tensor::TensorView::from_raw(t.data().as_ptr(), t.layout().clone()) };
+        if cfg!(debug_assertions) {
+            let err = try_tensor_copy(&src.flip(1), &mut t.as_view_mut());
+            assert_eq!(err, Err(crate::Error::Aliasing { op: "tensor_copy" }));
+            assert_eq!(t.data(), &[1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn add_assign_and_axpy_follow_strides() {
+        let mut c = Tensor::new(vec![1.0f32; 6], Layout::row_major([2, 3]));
+        let p = Tensor::new((0..6).map(|x| x as f32).collect(), Layout::col_major([2, 3]));
+        tensor_add_assign(&mut c.as_view_mut(), &p.as_view());
+        assert_eq!(c.data(), &[1.0, 3.0, 5.0, 2.0, 4.0, 6.0]);
+
+        // Reversed source and a strided column of the destination
+        let mut wide = Tensor::new(vec![0i32; 8], Layout::row_major([4, 2]));
+        let src = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([4, 1]));
+        let mut col = unsafe { wide.as_view_mut().subview_mut([0, 1], [4, 1]) };
+        axpy_view(&mut col, 10, &src.as_view().flip(0));
+        assert_eq!(wide.data(), &[0, 40, 0, 30, 0, 20, 0, 10]);
+
+        let bad = Tensor::new(vec![0.0f32; 6], Layout::row_major([3, 2]));
+        let err = try_tensor_add_assign(&mut c.as_view_mut(), &bad.as_view());
+        assert!(matches!(err, Err(crate::Error::ShapeMismatch { op: "tensor_add_assign", .. })));
+    }
+
     #[test]
     fn copy_hierarchical_contiguous() {
 
@@ -165,5 +551,116 @@ mod tests {
         }
     }
 
+    #[test]
+    fn copy_between_memory_orders() {
+        let src = Tensor::new((0..6).map(|x| x as f32).collect(), Layout::col_major([2, 3]));
+        let mut dst = Tensor::new(vec![0.0; 6], Layout::row_major([2, 3]));
+        tensor_copy(&src.as_view(), &mut dst.as_view_mut());
+        // Column-major [[0,2,4],[1,3,5]] read back row by row
+        assert_eq!(dst.data(), &[0.0, 2.0, 4.0, 1.0, 3.0, 5.0]);
+    }
+
+    #[test]
+    fn copy_strided_subview() {
+        let shape = Shape::new(Tuple::int(vec![4, 4]));
+        let src = Tensor::new((0..16).collect::<Vec<i32>>(), Layout::row_major(shape));
+        let mut dst = Tensor::new(
+            vec![0; 4],
+            Layout::row_major(Shape::new(Tuple::tup(vec![Tuple::int1(2), Tuple::int1(2)]))),
+        );
+
+        let src_view = src.as_view();
+        let sub = unsafe { src_view.subview_2d(1, 2, 2, 2) };
+        tensor_copy(&sub, &mut dst.as_view_mut());
+
+        assert_eq!(dst.data(), &[6, 7, 10, 11]);
+    }
+
+    #[test]
+    fn masked_copy_and_fill() {
+        let shape = Shape::new(Tuple::int(vec![2, 3]));
+        let src = Tensor::new(vec![1, 2, 3, 4, 5, 6], Layout::row_major(shape.clone()));
+        let mask = Tensor::new(
+            vec![true, false, true, false, true, false],
+            Layout::row_major(shape.clone()),
+        );
+        let mut dst = Tensor::new(vec![0; 6], Layout::row_major(shape));
+
+        tensor_copy_masked(&src.as_view(), &mut dst.as_view_mut(), &mask.as_view());
+        assert_eq!(dst.data(), &[1, 0, 3, 0, 5, 0]);
+
+        fill_masked(&mut dst.as_view_mut(), &mask.as_view(), -1);
+        assert_eq!(dst.data(), &[-1, 0, -1, 0, -1, 0]);
+    }
+
+    #[test]
+    fn parallel_copy_strided_large() {
+        // 1024 x 1024 window out of a 1024 x 1025 buffer: strided, at the threshold
+        let (m, n) = (1024, 1024);
+        let src = Tensor::new(
+            (0..m * (n + 1)).map(|x| x as u32).collect(),
+            Layout::row_major(Shape::new(Tuple::int(vec![m, n + 1]))),
+        );
+        let mut dst = Tensor::new(vec![0u32; m * n], Layout::row_major(Shape::new(Tuple::int(vec![m, n]))));
+
+        let src_view = src.as_view();
+        let window = unsafe { src_view.subview(Tuple::int(vec![0, 1]), dst.layout().shape()) };
+        tensor_copy_par(&window, &mut dst.as_view_mut());
+
+        for (i, v) in dst.data().iter().enumerate() {
+            let (r, c) = (i / n, i % n);
+            assert_eq!(*v as usize, r * (n + 1) + c + 1);
+        }
+    }
+
+    #[test]
+    fn parallel_copy_small_falls_back() {
+        let shape = Shape::new(Tuple::int(vec![3, 3]));
+        let src = Tensor::new((0..9).collect::<Vec<i64>>(), Layout::row_major(shape.clone()));
+        let mut dst = Tensor::new(vec![0; 9], Layout::row_major(shape));
+
+        tensor_copy_par(&src.as_view(), &mut dst.as_view_mut());
+        assert_eq!(dst.data(), src.data());
+    }
+
+    #[test]
+    fn concat_and_stack_fast_and_strided() {
+        let a = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::row_major([2, 3]));
+        let b = Tensor::new((6..10).collect::<Vec<i32>>(), Layout::row_major([2, 2]));
+        let cat = concat(&[a.as_view(), b.as_view()], 1);
+        assert_eq!(cat.layout().shape().dims.flatten(), vec![2, 5]);
+        assert_eq!(cat.data(), &[0, 1, 2, 6, 7, 3, 4, 5, 8, 9]);
+
+        // Column-major and flipped inputs take the element-wise path
+        let c = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::col_major([2, 3]));
+        let cat = concat(&[a.as_view(), c.as_view().flip(0)], 0);
+        assert_eq!(cat.data(), &[0, 1, 2, 3, 4, 5, 1, 3, 5, 0, 2, 4]);
+
+        let s = stack(&[a.as_view(), c.as_view()], 2);
+        assert_eq!(s.layout().shape().dims.flatten(), vec![2, 3, 2]);
+        assert_eq!(s.data(), &[0, 0, 1, 2, 2, 4, 3, 1, 4, 3, 5, 5]);
+        assert_eq!(stack(&[a.as_view(), a.as_view()], 0).data(), &[0, 1, 2, 3, 4, 5, 0, 1, 2, 3, 4, 5]);
+
+        let err = try_concat(&[a.as_view(), b.as_view()], 0).err().unwrap();
+        assert!(matches!(err, crate::Error::ShapeMismatch { op: "concat_shape", .. }));
+        assert!(try_stack(&[a.as_view()], 3).is_err());
+    }
+
+    #[test]
+    fn repeat_follows_numpy_tile() {
+        let a = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([2, 2]));
+        let r = repeat(&a.as_view(), &[2, 3]);
+        assert_eq!(r.layout().shape().dims.flatten(), vec![4, 6]);
+        assert_eq!(&r.data()[..6], &[1, 2, 1, 2, 1, 2]);
+        assert_eq!(&r.data()[18..], &[3, 4, 3, 4, 3, 4]);
+
+        let r = repeat(&a.as_view().flip(1), &[2]);
+        assert_eq!(r.data(), &[2, 1, 2, 1, 4, 3, 4, 3]);
+
+        let col = Tensor::new(vec![5, 6], Layout::row_major([2, 1]));
+        assert_eq!(repeat(&col.as_view(), &[1, 3]).data(), &[5, 5, 5, 6, 6, 6]);
+        assert_eq!(repeat(&a.as_view(), &[0, 1]).layout().size(), 0);
+    }
+
 }
 
@@ -0,0 +1,418 @@
+// ============================================================
+// Device abstraction
+// ============================================================
+//
+// A Device owns memory that the host cannot address directly. Data moves
+// only through explicit host <-> device copies, and layouts travel with
+// the buffer so GEMM lowering works exactly as it does on the host.
+//
+// ============================================================
+
+use std::fmt;
+use std::marker::PhantomData;
+
+use crate::blas::{BlasBackend, GemmAlgo};
+use crate::error::Error;
+use crate::gemm::{check_gemm_shapes, try_lower_gemm, LoweredGemm};
+use crate::layout::Layout;
+use crate::tensor::Tensor;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeviceError {
+    /// Runtime library could not be loaded
+    Load(String),
+    /// Driver / runtime call returned a non-zero status
+    Call { what: &'static str, status: i32 },
+}
+
+impl fmt::Display for DeviceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeviceError::Load(msg) => write!(f, "failed to load device runtime: {}", msg),
+            DeviceError::Call { what, status } => write!(f, "{} failed with status {}", what, status),
+        }
+    }
+}
+
+impl std::error::Error for DeviceError {}
+
+/// Opaque device address. Host-backed devices hand out real pointers, so
+/// this keeps one (with its provenance) rather than a bare integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DevicePtr(pub *mut u8);
+
+// A device address is only dereferenced by the device that issued it
+unsafe impl Send for DevicePtr {}
+unsafe impl Sync for DevicePtr {}
+
+pub trait Device {
+    fn alloc(&self, bytes: usize) -> Result<DevicePtr, DeviceError>;
+
+    /// # Safety
+    /// `ptr` must come from `alloc` on this device and not be used afterwards.
+    unsafe fn free(&self, ptr: DevicePtr);
+
+    /// # Safety
+    /// `src` must be valid for `bytes` reads and `dst` an allocation of at least `bytes`.
+    unsafe fn copy_h2d(&self, dst: DevicePtr, src: *const u8, bytes: usize) -> Result<(), DeviceError>;
+
+    /// # Safety
+    /// `dst` must be valid for `bytes` writes and `src` an allocation of at least `bytes`.
+    unsafe fn copy_d2h(&self, dst: *mut u8, src: DevicePtr, bytes: usize) -> Result<(), DeviceError>;
+}
+
+/// Device that can run GEMM on its own memory
+pub trait GemmBackend: Device + Sized {
+    /// `C = alpha * A * B + beta * C` on device tensors. Operands BLAS
+    /// cannot address (no unit-stride mode, or reversed modes) are returned
+    /// as `Error::NotContiguous`.
+    fn gemm_f32(
+        &self,
+        a: &DeviceTensor<'_, f32, Self>,
+        b: &DeviceTensor<'_, f32, Self>,
+        c: &mut DeviceTensor<'_, f32, Self>,
+        alpha: f32,
+        beta: f32,
+    ) -> crate::error::Result<()>;
+}
+
+/* ========================= DeviceTensor ========================= */
+
+/// Tensor resident in device memory; freed on drop
+pub struct DeviceTensor<'d, T, D: Device> {
+    device: &'d D,
+    ptr: DevicePtr,
+    layout: Layout,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<'d, T: Copy, D: Device> DeviceTensor<'d, T, D> {
+    /// Allocate device memory for `layout` and upload `host` into it
+    pub fn from_host(device: &'d D, host: &Tensor<T>) -> Result<Self, DeviceError> {
+        let len = host.data().len();
+        let ptr = device.alloc(std::mem::size_of_val(host.data()))?;
+        let mut t = Self { device, ptr, layout: host.layout().clone(), len, _marker: PhantomData };
+        t.copy_from_host(host)?;
+        Ok(t)
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn device_ptr(&self) -> DevicePtr {
+        self.ptr
+    }
+
+    /// Overwrite the device buffer with `host`, which must have the same layout
+    pub fn copy_from_host(&mut self, host: &Tensor<T>) -> Result<(), DeviceError> {
+        assert_eq!(host.layout(), &self.layout, "DeviceTensor::copy_from_host: layout mismatch");
+        unsafe {
+            self.device.copy_h2d(
+                self.ptr,
+                host.data().as_ptr() as *const u8,
+                std::mem::size_of_val(host.data()),
+            )
+        }
+    }
+
+    /// Download into a new host tensor with the same layout
+    pub fn to_host(&self) -> Result<Tensor<T>, DeviceError> {
+        let mut data: Vec<T> = Vec::with_capacity(self.len);
+        unsafe {
+            self.device.copy_d2h(
+                data.as_mut_ptr() as *mut u8,
+                self.ptr,
+                self.len * std::mem::size_of::<T>(),
+            )?;
+            data.set_len(self.len);
+        }
+        Ok(Tensor::new(data, self.layout.clone()))
+    }
+}
+
+impl<T, D: Device> Drop for DeviceTensor<'_, T, D> {
+    fn drop(&mut self) {
+        unsafe { self.device.free(self.ptr) }
+    }
+}
+
+/// Shape and layout checks shared by every backend; returns (m, n, k) and
+/// the BLAS arguments
+fn lower_device_gemm(op: &'static str, a: &Layout, b: &Layout, c: &Layout) -> crate::error::Result<((usize, usize, usize), LoweredGemm)> {
+    let dims = check_gemm_shapes(op, a, b, c)?;
+    let lowered = try_lower_gemm(op, a, b, c).map_err(|_| Error::NotContiguous { op })?;
+    Ok((dims, lowered))
+}
+
+/* ========================= Host device ========================= */
+
+/// "Device" backed by host memory and any `BlasBackend`. Useful as a
+/// reference implementation and for testing device code paths on CPU.
+pub struct HostDevice<B: BlasBackend>(pub B);
+
+const HOST_ALIGN: usize = 64;
+
+impl<B: BlasBackend> Device for HostDevice<B> {
+    fn alloc(&self, bytes: usize) -> Result<DevicePtr, DeviceError> {
+        // Over-allocate a header that remembers the size for `free`
+        let layout = std::alloc::Layout::from_size_align(bytes + HOST_ALIGN, HOST_ALIGN)
+            .map_err(|e| DeviceError::Load(e.to_string()))?;
+        unsafe {
+            let base = std::alloc::alloc(layout);
+            if base.is_null() {
+                std::alloc::handle_alloc_error(layout);
+            }
+            base.cast::<usize>().write(bytes);
+            Ok(DevicePtr(base.add(HOST_ALIGN)))
+        }
+    }
+
+    unsafe fn free(&self, ptr: DevicePtr) {
+        let base = ptr.0.sub(HOST_ALIGN);
+        let bytes = base.cast::<usize>().read();
+        std::alloc::dealloc(base, std::alloc::Layout::from_size_align_unchecked(bytes + HOST_ALIGN, HOST_ALIGN));
+    }
+
+    unsafe fn copy_h2d(&self, dst: DevicePtr, src: *const u8, bytes: usize) -> Result<(), DeviceError> {
+        std::ptr::copy_nonoverlapping(src, dst.0, bytes);
+        Ok(())
+    }
+
+    unsafe fn copy_d2h(&self, dst: *mut u8, src: DevicePtr, bytes: usize) -> Result<(), DeviceError> {
+        std::ptr::copy_nonoverlapping(src.0.cast_const(), dst, bytes);
+        Ok(())
+    }
+}
+
+impl<B: BlasBackend> GemmBackend for HostDevice<B> {
+    fn gemm_f32(
+        &self,
+        a: &DeviceTensor<'_, f32, Self>,
+        b: &DeviceTensor<'_, f32, Self>,
+        c: &mut DeviceTensor<'_, f32, Self>,
+        alpha: f32,
+        beta: f32,
+    ) -> crate::error::Result<()> {
+        let ((m, n, k), lowered) = lower_device_gemm("HostDevice::gemm_f32", a.layout(), b.layout(), c.layout())?;
+        // SAFETY: the buffers hold operands of the layouts just lowered
+        unsafe {
+            lowered.run(&self.0, GemmAlgo::Default, m, n, k, alpha, a.ptr.0.cast_const().cast(), b.ptr.0.cast_const().cast(), beta, c.ptr.0.cast());
+        }
+        Ok(())
+    }
+}
+
+/* ========================= CUDA (cuBLAS) ========================= */
+
+#[cfg(all(feature = "cuda", target_arch = "wasm32"))]
+compile_error!("the `cuda` feature loads the CUDA runtime at run time and is not available on wasm32");
+
+#[cfg(feature = "cuda")]
+pub use cuda::CudaDevice;
+
+#[cfg(feature = "cuda")]
+mod cuda {
+    use std::ffi::c_void;
+
+    use libloading::Library;
+
+    use super::*;
+    use crate::blas::BlasTranspose;
+    use crate::gemm::flip;
+
+    type CudaMalloc = unsafe extern "C" fn(ptr: *mut *mut c_void, bytes: usize) -> i32;
+    type CudaFree = unsafe extern "C" fn(ptr: *mut c_void) -> i32;
+    type CudaMemcpy = unsafe extern "C" fn(dst: *mut c_void, src: *const c_void, bytes: usize, kind: i32) -> i32;
+    type CublasCreate = unsafe extern "C" fn(handle: *mut *mut c_void) -> i32;
+    type CublasDestroy = unsafe extern "C" fn(handle: *mut c_void) -> i32;
+    type CublasSgemm = unsafe extern "C" fn(
+        handle: *mut c_void,
+        transa: i32,
+        transb: i32,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: *const f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: *const f32,
+        c: *mut f32,
+        ldc: i32,
+    ) -> i32;
+
+    const MEMCPY_H2D: i32 = 1;
+    const MEMCPY_D2H: i32 = 2;
+
+    /// NVIDIA GPU driven through the CUDA runtime and cuBLAS, loaded at runtime
+    pub struct CudaDevice {
+        _cudart: Library,
+        _cublas: Library,
+        malloc: CudaMalloc,
+        free: CudaFree,
+        memcpy: CudaMemcpy,
+        destroy: CublasDestroy,
+        sgemm: CublasSgemm,
+        handle: *mut c_void,
+    }
+
+    fn open(names: &[&str]) -> Result<Library, DeviceError> {
+        let mut last = String::new();
+        for &name in names {
+            match unsafe { Library::new(name) } {
+                Ok(lib) => return Ok(lib),
+                Err(e) => last = e.to_string(),
+            }
+        }
+        Err(DeviceError::Load(last))
+    }
+
+    fn check(what: &'static str, status: i32) -> Result<(), DeviceError> {
+        if status == 0 { Ok(()) } else { Err(DeviceError::Call { what, status }) }
+    }
+
+    impl CudaDevice {
+        pub fn new() -> Result<Self, DeviceError> {
+            let cudart = open(&["libcudart.so", "libcudart.so.12", "libcudart.so.11.0"])?;
+            let cublas = open(&["libcublas.so", "libcublas.so.12", "libcublas.so.11"])?;
+
+            unsafe {
+                let sym = |lib: &Library, name: &[u8]| -> Result<*const c_void, DeviceError> {
+                    lib.get::<*const c_void>(name)
+                        .map(|s| *s)
+                        .map_err(|e| DeviceError::Load(e.to_string()))
+                };
+
+                let malloc: CudaMalloc = std::mem::transmute(sym(&cudart, b"cudaMalloc\0")?);
+                let free: CudaFree = std::mem::transmute(sym(&cudart, b"cudaFree\0")?);
+                let memcpy: CudaMemcpy = std::mem::transmute(sym(&cudart, b"cudaMemcpy\0")?);
+                let create: CublasCreate = std::mem::transmute(sym(&cublas, b"cublasCreate_v2\0")?);
+                let destroy: CublasDestroy = std::mem::transmute(sym(&cublas, b"cublasDestroy_v2\0")?);
+                let sgemm: CublasSgemm = std::mem::transmute(sym(&cublas, b"cublasSgemm_v2\0")?);
+
+                let mut handle = std::ptr::null_mut();
+                check("cublasCreate", create(&mut handle))?;
+
+                Ok(Self { _cudart: cudart, _cublas: cublas, malloc, free, memcpy, destroy, sgemm, handle })
+            }
+        }
+    }
+
+    impl Drop for CudaDevice {
+        fn drop(&mut self) {
+            unsafe {
+                (self.destroy)(self.handle);
+            }
+        }
+    }
+
+    impl Device for CudaDevice {
+        fn alloc(&self, bytes: usize) -> Result<DevicePtr, DeviceError> {
+            let mut p = std::ptr::null_mut();
+            check("cudaMalloc", unsafe { (self.malloc)(&mut p, bytes) })?;
+            Ok(DevicePtr(p.cast()))
+        }
+
+        unsafe fn free(&self, ptr: DevicePtr) {
+            (self.free)(ptr.0.cast());
+        }
+
+        unsafe fn copy_h2d(&self, dst: DevicePtr, src: *const u8, bytes: usize) -> Result<(), DeviceError> {
+            check("cudaMemcpy", (self.memcpy)(dst.0.cast(), src.cast(), bytes, MEMCPY_H2D))
+        }
+
+        unsafe fn copy_d2h(&self, dst: *mut u8, src: DevicePtr, bytes: usize) -> Result<(), DeviceError> {
+            check("cudaMemcpy", (self.memcpy)(dst.cast(), src.0.cast_const().cast(), bytes, MEMCPY_D2H))
+        }
+    }
+
+    impl GemmBackend for CudaDevice {
+        fn gemm_f32(
+            &self,
+            a: &DeviceTensor<'_, f32, Self>,
+            b: &DeviceTensor<'_, f32, Self>,
+            c: &mut DeviceTensor<'_, f32, Self>,
+            alpha: f32,
+            beta: f32,
+        ) -> crate::error::Result<()> {
+            let ((m, n, k), lowered) = lower_device_gemm("CudaDevice::gemm_f32", a.layout(), b.layout(), c.layout())?;
+            // A column-major C is computed as the row-major C^T = op(B)^T op(A)^T
+            let (m, n, (pa, ta, lda), (pb, tb, ldb)) = {
+                let ((lda, ta), (ldb, tb)) = (lowered.lda, lowered.ldb);
+                let (pa, pb): (*const f32, *const f32) = (a.ptr.0.cast_const().cast(), b.ptr.0.cast_const().cast());
+                if lowered.swap {
+                    (n, m, (pb, flip(tb), ldb), (pa, flip(ta), lda))
+                } else {
+                    (m, n, (pa, ta, lda), (pb, tb, ldb))
+                }
+            };
+
+            let op = |t: BlasTranspose| match t {
+                BlasTranspose::NoTrans => 0,
+                BlasTranspose::Trans => 1,
+                BlasTranspose::ConjTrans => 2,
+            };
+
+            // cuBLAS is column-major: row-major C = A B is column-major C^T = B^T A^T
+            let status = unsafe {
+                (self.sgemm)(
+                    self.handle,
+                    op(tb), op(ta),
+                    n as i32, m as i32, k as i32,
+                    &alpha,
+                    pb, ldb,
+                    pa, lda,
+                    &beta,
+                    c.ptr.0.cast(), lowered.ldc,
+                )
+            };
+            Ok(check("cublasSgemm", status)?)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::test_util::matrix;
+
+    #[test]
+    fn host_device_roundtrip() {
+        let dev = HostDevice(NativeBlas);
+        let host = matrix(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let d = DeviceTensor::from_host(&dev, &host).unwrap();
+        let back = d.to_host().unwrap();
+        assert_eq!(back.data(), host.data());
+        assert_eq!(back.layout(), host.layout());
+    }
+
+    #[test]
+    fn host_device_gemm() {
+        let dev = HostDevice(NativeBlas);
+        let a = DeviceTensor::from_host(&dev, &matrix(2, 2, vec![1.0, 2.0, 3.0, 4.0])).unwrap();
+        let b = DeviceTensor::from_host(&dev, &matrix(2, 2, vec![5.0, 6.0, 7.0, 8.0])).unwrap();
+        let mut c = DeviceTensor::from_host(&dev, &matrix(2, 2, vec![0.0; 4])).unwrap();
+
+        dev.gemm_f32(&a, &b, &mut c, 1.0, 0.0).unwrap();
+        assert_eq!(c.to_host().unwrap().data(), &[19.0, 22.0, 43.0, 50.0]);
+
+        let mut c = DeviceTensor::from_host(&dev, &Tensor::new(vec![0.0; 4], Layout::col_major([2, 2]))).unwrap();
+        dev.gemm_f32(&a, &b, &mut c, 1.0, 0.0).unwrap();
+        assert_eq!(c.to_host().unwrap().data(), &[19.0, 43.0, 22.0, 50.0]);
+    }
+
+    #[test]
+    fn host_device_gemm_rejects_strided_operands() {
+        let dev = HostDevice(NativeBlas);
+        let a = DeviceTensor::from_host(&dev, &matrix(2, 2, vec![1.0; 4])).unwrap();
+        let strided = Tensor::new(vec![0.0; 7], Layout::row_major([2, 2]).with_stride([4, 2]));
+        let mut c = DeviceTensor::from_host(&dev, &strided).unwrap();
+        let err = dev.gemm_f32(&a, &a, &mut c, 1.0, 0.0).unwrap_err();
+        assert!(matches!(err, Error::NotContiguous { op: "HostDevice::gemm_f32" }), "{err}");
+    }
+}
@@ -0,0 +1,175 @@
+// src/diag.rs
+//
+// Diagonal and banded views of rank-2 tensors. A diagonal is just a
+// rank-1 layout whose stride is row_stride + col_stride, so no data moves.
+
+use crate::layout::Layout;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tuple::Tuple;
+
+/// Origin offset and rank-1 layout of diagonal `k` (k > 0 above the main one)
+fn diagonal_layout(layout: &Layout, k: isize) -> (isize, Layout) {
+    assert_eq!(layout.shape().flat_len(), 2, "diagonal requires a rank-2 view");
+    let (m, n) = (layout.shape().flat_at(0) as isize, layout.shape().flat_at(1) as isize);
+    let s = layout.signed_stride();
+
+    let (r0, c0) = if k >= 0 { (0, k) } else { (-k, 0) };
+    let len = (m - r0).min(n - c0).max(0) as usize;
+    let offset = if len == 0 { 0 } else { r0 * s[0] + c0 * s[1] };
+
+    let step = s[0] + s[1];
+    let diag = Layout::row_major(len).with_stride(step.unsigned_abs());
+    (offset, if step < 0 { diag.flip(0) } else { diag })
+}
+
+impl<'a, T> TensorView<'a, T> {
+    /// Main diagonal as a rank-1 view of length `min(rows, cols)`
+    pub fn diagonal(&self) -> TensorView<'a, T> {
+        self.diagonal_at(0)
+    }
+
+    /// Diagonal `k`: above the main diagonal for `k > 0`, below for `k < 0`.
+    /// Diagonals that miss the matrix are empty.
+    pub fn diagonal_at(&self, k: isize) -> TensorView<'a, T> {
+        let (offset, layout) = diagonal_layout(self.layout(), k);
+        // Every element of the diagonal is an element of `self`
+        unsafe { self.at_offset(offset, layout) }.labelled("diagonal")
+    }
+
+    /// Band of `kl` sub- and `ku` super-diagonals
+    pub fn banded(&self, kl: usize, ku: usize) -> BandedView<'a, T> {
+        assert_eq!(self.layout().shape().flat_len(), 2, "banded requires a rank-2 view");
+        let view = unsafe { self.at_offset(0, self.layout().clone()) };
+        BandedView { view, kl, ku }
+    }
+}
+
+impl<'a, T> TensorViewMut<'a, T> {
+    /// Mutable diagonal `k`, consuming the matrix view
+    pub fn into_diagonal_at(self, k: isize) -> TensorViewMut<'a, T> {
+        let (offset, layout) = diagonal_layout(self.layout(), k);
+        unsafe { self.into_offset(offset, layout) }.labelled("diagonal")
+    }
+}
+
+/* ===== Banded view ===== */
+
+/// Read-only view of the band `-kl <= j - i <= ku` of a matrix
+pub struct BandedView<'a, T> {
+    view: TensorView<'a, T>,
+    kl: usize,
+    ku: usize,
+}
+
+impl<'a, T> BandedView<'a, T> {
+    pub fn rows(&self) -> usize {
+        self.view.layout().shape().flat_at(0)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.view.layout().shape().flat_at(1)
+    }
+
+    pub fn bandwidths(&self) -> (usize, usize) {
+        (self.kl, self.ku)
+    }
+
+    pub fn in_band(&self, i: usize, j: usize) -> bool {
+        j + self.kl >= i && i + self.ku >= j
+    }
+
+    /// Element `(i, j)`, or `None` outside the matrix or the band
+    pub fn get(&self, i: usize, j: usize) -> Option<&'a T> {
+        if i >= self.rows() || j >= self.cols() || !self.in_band(i, j) {
+            return None;
+        }
+        let crd = Tuple::int(vec![i, j]);
+        Some(unsafe { self.view.get(&crd) })
+    }
+
+    /// Diagonal `k` of the band (`-kl <= k <= ku`)
+    pub fn diagonal(&self, k: isize) -> TensorView<'a, T> {
+        assert!(
+            -(self.kl as isize) <= k && k <= self.ku as isize,
+            "diagonal {k} outside band ({}, {})",
+            self.kl,
+            self.ku
+        );
+        self.view.diagonal_at(k)
+    }
+
+    /// LAPACK-style band storage: a row-major `(kl + ku + 1) x cols` tensor
+    /// with `A[i][j]` at row `ku + i - j`, column `j`. Slots outside the
+    /// matrix hold `T::default()`.
+    pub fn to_band_storage(&self) -> Tensor<T>
+    where
+        T: Copy + Default,
+    {
+        let (kl, ku, n) = (self.kl, self.ku, self.cols());
+        let rows = kl + ku + 1;
+        let mut out = vec![T::default(); rows * n];
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..(j + kl + 1).min(self.rows()) {
+                out[(ku + i - j) * n + j] = *self.get(i, j).expect("index inside band");
+            }
+        }
+        Tensor::new(out, Layout::row_major([rows, n]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::iota;
+
+    #[test]
+    fn main_and_offset_diagonals() {
+        let t = iota::<i32>(3, 4, 0);
+        let v = t.as_view();
+
+        assert_eq!(v.diagonal().to_vec(), vec![0, 5, 10]);
+        assert_eq!(v.diagonal().layout().stride().to_string(), "5");
+        assert_eq!(v.diagonal_at(1).to_vec(), vec![1, 6, 11]);
+        assert_eq!(v.diagonal_at(-1).to_vec(), vec![4, 9]);
+        assert!(v.diagonal_at(4).to_vec().is_empty());
+        assert_eq!(v.diagonal().to_vec().iter().sum::<i32>(), 15);
+    }
+
+    #[test]
+    fn anti_diagonal_through_flip() {
+        let t = iota::<i32>(3, 3, 0);
+        let anti = t.as_view().flip(1).diagonal();
+        assert_eq!(anti.to_vec(), vec![2, 4, 6]);
+
+        // Flipping both modes walks the diagonal backwards
+        let back = t.as_view().flip(0).flip(1).diagonal();
+        assert_eq!(back.to_vec(), vec![8, 4, 0]);
+    }
+
+    #[test]
+    fn write_through_diagonal() {
+        let mut t = Tensor::new(vec![0; 9], Layout::col_major([3, 3]));
+        let mut d = t.as_view_mut().into_diagonal_at(0);
+        d[[1]] = 1;
+        d[[2]] = 2;
+        assert_eq!(t.data(), &[0, 0, 0, 0, 1, 0, 0, 0, 2]);
+    }
+
+    #[test]
+    fn band_access_and_storage() {
+        let t = iota::<i32>(4, 4, 0);
+        let band = t.as_view().banded(1, 2);
+
+        assert_eq!(band.get(2, 1), Some(&9));
+        assert_eq!(band.get(3, 1), None);
+        assert_eq!(band.get(0, 3), None);
+        assert_eq!(band.diagonal(2).to_vec(), vec![2, 7]);
+
+        let storage = band.to_band_storage();
+        assert_eq!(storage.layout().shape().to_string(), "(4,4)");
+        assert_eq!(
+            storage.data(),
+            &[0, 0, 2, 7, 0, 1, 6, 11, 0, 5, 10, 15, 4, 9, 14, 0]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+// src/error.rs
+//
+// Crate-wide error type returned by the `try_*` entry points. The
+// panicking variants format the same errors into their panic messages.
+
+use std::fmt;
+
+use crate::device::DeviceError;
+use crate::factor::FactorError;
+use crate::layout::{Contiguity, Layout};
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// Two operands of `op` need matching extents but do not
+    ShapeMismatch { op: &'static str, lhs: Shape, rhs: Shape },
+    /// An operand of `op` has the wrong number of flattened modes
+    RankMismatch { op: &'static str, expected: usize, found: usize },
+    /// `op` needs at least one operand and was given none
+    NoOperands { op: &'static str },
+    /// Two tuples of `op` differ in nesting
+    NotCongruent { op: &'static str, lhs: Tuple, rhs: Tuple },
+    /// A coordinate lies outside the shape it indexes
+    OutOfBounds { op: &'static str, coord: Tuple, shape: Shape },
+    /// The operands of a GEMM-like `op` cannot be multiplied; `problem`
+    /// names the violated constraint and `layouts` holds A, B and C
+    GemmOperands { op: &'static str, problem: String, layouts: Box<[Layout; 3]> },
+    /// An operand of `op` does not have the layout `op` was planned for;
+    /// `layouts` holds the planned and the given layout
+    LayoutMismatch { op: &'static str, layouts: Box<[Layout; 2]> },
+    /// `op` was given an axis past the last flattened mode
+    InvalidAxis { op: &'static str, axis: usize, rank: usize },
+    /// An operand of `op` has no unit-stride mode the kernel can use
+    NotContiguous { op: &'static str },
+    /// The output of `op` overlaps one of its inputs
+    Aliasing { op: &'static str },
+    /// The backend of `op` does not implement a requested feature
+    Unsupported { op: &'static str, what: String },
+    /// A compute backend (BLAS, device runtime) could not be loaded
+    BackendLoad(String),
+    /// A file could not be opened or mapped
+    Io(String),
+    /// A layout-algebra property checked by `invariants` does not hold
+    Invariant { op: &'static str, detail: String },
+    Factor(FactorError),
+    Device(DeviceError),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::ShapeMismatch { op, lhs, rhs } => write!(f, "{}: shape mismatch between {} and {}", op, lhs, rhs),
+            Error::RankMismatch { op, expected, found } => {
+                write!(f, "{}: expected rank {}, found rank {}", op, expected, found)
+            }
+            Error::NoOperands { op } => write!(f, "{}: needs at least one operand", op),
+            Error::NotCongruent { op, lhs, rhs } => write!(f, "{}: {} and {} are not congruent", op, lhs, rhs),
+            Error::OutOfBounds { op, coord, shape } => write!(f, "{}: coordinate {} out of bounds for shape {}", op, coord, shape),
+            Error::GemmOperands { op, problem, layouts } => {
+                let [a, b, c] = &**layouts;
+                write!(f, "{}: {} (A is {}, B is {}, C is {})", op, problem, describe(a), describe(b), describe(c))
+            }
+            Error::LayoutMismatch { op, layouts } => {
+                let [planned, given] = &**layouts;
+                write!(f, "{}: operand is {} but the plan expects {}", op, describe(given), describe(planned))
+            }
+            Error::InvalidAxis { op, axis, rank } => write!(f, "{}: axis {} out of range for rank {}", op, axis, rank),
+            Error::NotContiguous { op } => write!(f, "{}: operand has no unit-stride mode", op),
+            Error::Aliasing { op } => write!(f, "{}: output overlaps an input", op),
+            Error::Unsupported { op, what } => write!(f, "{}: {} is not supported by this backend", op, what),
+            Error::BackendLoad(msg) => write!(f, "failed to load backend: {}", msg),
+            Error::Io(msg) => write!(f, "I/O error: {}", msg),
+            Error::Invariant { op, detail } => write!(f, "{}: invariant violated: {}", op, detail),
+            Error::Factor(e) => write!(f, "{}", e),
+            Error::Device(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Factor(e) => Some(e),
+            Error::Device(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FactorError> for Error {
+    fn from(e: FactorError) -> Self {
+        Error::Factor(e)
+    }
+}
+
+impl From<DeviceError> for Error {
+    fn from(e: DeviceError) -> Self {
+        match e {
+            DeviceError::Load(msg) => Error::BackendLoad(msg),
+            e => Error::Device(e),
+        }
+    }
+}
+
+/// `shape:stride` and the memory order, e.g. `(64,32):(32,1) row-major`
+fn describe(layout: &Layout) -> String {
+    let order = match layout.contiguity() {
+        _ if layout.has_reversed_modes() => "reversed",
+        Some(Contiguity::RowMajor) => "row-major",
+        Some(Contiguity::ColMajor) => "column-major",
+        Some(Contiguity::Permuted) => "permuted",
+        None => "strided",
+    };
+    format!("{}:{} {}", layout.shape(), layout.stride(), order)
+}
+
+/// Shared shape check used by the `try_*` functions
+pub(crate) fn check_same_shape(op: &'static str, lhs: &Shape, rhs: &Shape) -> Result<()> {
+    if lhs == rhs {
+        Ok(())
+    } else {
+        Err(Error::ShapeMismatch { op, lhs: lhs.clone(), rhs: rhs.clone() })
+    }
+}
+
+pub(crate) fn check_rank(op: &'static str, expected: usize, found: usize) -> Result<()> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(Error::RankMismatch { op, expected, found })
+    }
+}
+
+pub(crate) fn check_axis(op: &'static str, axis: usize, rank: usize) -> Result<()> {
+    if axis < rank {
+        Ok(())
+    } else {
+        Err(Error::InvalidAxis { op, axis, rank })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_names_operation() {
+        let e = Error::ShapeMismatch {
+            op: "tensor_copy",
+            lhs: Shape::new(Tuple::int(vec![2, 3])),
+            rhs: Shape::new(Tuple::int(vec![3, 2])),
+        };
+        assert_eq!(e.to_string(), "tensor_copy: shape mismatch between (2,3) and (3,2)");
+    }
+
+    #[test]
+    fn wraps_module_errors() {
+        let e: Error = FactorError::Singular(3).into();
+        assert!(std::error::Error::source(&e).is_some());
+        assert_eq!(e.to_string(), "matrix is singular (pivot 3)");
+
+        let e: Error = DeviceError::Load("libcudart.so".into()).into();
+        assert_eq!(e, Error::BackendLoad("libcudart.so".into()));
+    }
+}
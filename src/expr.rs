@@ -0,0 +1,323 @@
+// src/expr.rs
+//
+// Lazy matrix expressions. Operators only build a small tree; `eval` walks
+// the elementwise ops down to the innermost matmul and runs them as an
+// epilogue on each C tile right after the tile's GEMM, while it is still in
+// cache, instead of sweeping the whole result once per op.
+
+use std::ops::Add;
+
+use crate::blas::{BlasBackend, NativeBlas};
+use crate::gemm::{gemm_f32_tiled_parallel, gemm_f32_tiled_parallel_with};
+use crate::layout::{Layout, RowMajor};
+use crate::parallel::Parallelism;
+use crate::tensor::{Tensor, TensorCow, TensorView, TensorViewMut};
+use crate::tiled_tensor::Tile;
+
+/// Elementwise function applied by `Expr::relu`, `scale` and `map`
+#[derive(Debug, Clone, Copy)]
+pub enum Unary {
+    Relu,
+    Scale(f32),
+    Map(fn(f32) -> f32),
+}
+
+impl Unary {
+    fn apply(self, x: f32) -> f32 {
+        match self {
+            Unary::Relu => x.max(0.0),
+            Unary::Scale(s) => s * x,
+            Unary::Map(f) => f(x),
+        }
+    }
+}
+
+enum Node<'a> {
+    Leaf(TensorView<'a, f32>),
+    MatMul(Box<Node<'a>>, Box<Node<'a>>),
+    Add(Box<Node<'a>>, Box<Node<'a>>),
+    Unary(Box<Node<'a>>, Unary),
+}
+
+/// A deferred rank-2 f32 expression, e.g.
+/// `(a.lazy().matmul(&b) + &bias).relu().eval()`.
+/// Sums broadcast a length-`n` vector over the rows of an `m x n` operand.
+pub struct Expr<'a>(Node<'a>);
+
+impl<'a> From<TensorView<'a, f32>> for Expr<'a> {
+    fn from(view: TensorView<'a, f32>) -> Self {
+        Expr(Node::Leaf(view))
+    }
+}
+
+impl<'a> From<&'a Tensor<f32>> for Expr<'a> {
+    fn from(t: &'a Tensor<f32>) -> Self {
+        Expr(Node::Leaf(t.as_view()))
+    }
+}
+
+impl Tensor<f32> {
+    /// Start a lazy expression over this tensor
+    pub fn lazy(&self) -> Expr<'_> {
+        self.into()
+    }
+}
+
+impl<'a> TensorView<'a, f32> {
+    pub fn lazy(self) -> Expr<'a> {
+        self.into()
+    }
+}
+
+impl<'a> Expr<'a> {
+    pub fn matmul(self, rhs: impl Into<Expr<'a>>) -> Expr<'a> {
+        Expr(Node::MatMul(Box::new(self.0), Box::new(rhs.into().0)))
+    }
+
+    pub fn relu(self) -> Expr<'a> {
+        self.unary(Unary::Relu)
+    }
+
+    pub fn scale(self, s: f32) -> Expr<'a> {
+        self.unary(Unary::Scale(s))
+    }
+
+    pub fn map(self, f: fn(f32) -> f32) -> Expr<'a> {
+        self.unary(Unary::Map(f))
+    }
+
+    fn unary(self, op: Unary) -> Expr<'a> {
+        Expr(Node::Unary(Box::new(self.0), op))
+    }
+
+    /// Evaluate into a row-major tensor with `NativeBlas`
+    pub fn eval(self) -> Tensor<f32> {
+        self.eval_with(&NativeBlas)
+    }
+
+    pub fn eval_with<B: BlasBackend + Sync>(self, backend: &B) -> Tensor<f32> {
+        eval_node(self.0, backend)
+    }
+}
+
+impl<'a, R: Into<Expr<'a>>> Add<R> for Expr<'a> {
+    type Output = Expr<'a>;
+
+    fn add(self, rhs: R) -> Expr<'a> {
+        Expr(Node::Add(Box::new(self.0), Box::new(rhs.into().0)))
+    }
+}
+
+/* ===== Evaluation ===== */
+
+/// Elementwise step run on every result element, innermost first
+enum Epilogue<'a> {
+    Add(TensorCow<'a, f32>),
+    Unary(Unary),
+}
+
+fn is_matmul(node: &Node<'_>) -> bool {
+    match node {
+        Node::MatMul(..) => true,
+        Node::Unary(x, _) => is_matmul(x),
+        Node::Add(x, y) => is_matmul(x) || is_matmul(y),
+        Node::Leaf(_) => false,
+    }
+}
+
+/// Leaves stay borrowed; anything else is evaluated
+fn operand<'a, B: BlasBackend + Sync>(node: Node<'a>, backend: &B) -> TensorCow<'a, f32> {
+    match node {
+        Node::Leaf(v) => TensorCow::Borrowed(v),
+        node => TensorCow::Owned(eval_node(node, backend)),
+    }
+}
+
+fn eval_node<B: BlasBackend + Sync>(node: Node<'_>, backend: &B) -> Tensor<f32> {
+    // Peel elementwise ops off the top; the sum operand on the matmul side
+    // stays in the chain, the other one becomes an addend.
+    let mut ops = Vec::new();
+    let mut root = node;
+    loop {
+        match root {
+            Node::Unary(x, op) => {
+                ops.push(Epilogue::Unary(op));
+                root = *x;
+            }
+            Node::Add(x, y) => {
+                let (main, other) = if !is_matmul(&x) && is_matmul(&y) { (y, x) } else { (x, y) };
+                ops.push(Epilogue::Add(operand(*other, backend)));
+                root = *main;
+            }
+            node => {
+                root = node;
+                break;
+            }
+        }
+    }
+    ops.reverse();
+
+    match root {
+        Node::MatMul(a, b) => fused_matmul(operand(*a, backend), operand(*b, backend), &ops, backend),
+        Node::Leaf(v) => {
+            let mut out = v.to_tensor::<RowMajor>();
+            let (m, n) = extents(out.layout(), "Expr");
+            let steps = resolve_epilogue(&ops, m, n);
+            apply_tile(&steps, &mut out.as_view_mut(), 0, 0);
+            out
+        }
+        _ => unreachable!("elementwise nodes were peeled above"),
+    }
+}
+
+fn extents(layout: &Layout, op: &str) -> (usize, usize) {
+    assert_eq!(layout.shape().flat_len(), 2, "{op}: expression operands must be rank-2");
+    (layout.shape().flat_at(0), layout.shape().flat_at(1))
+}
+
+/// An epilogue op with its addend resolved to signed (row, column) strides;
+/// a broadcast row vector has row stride 0
+enum Step<'a> {
+    Add(TensorView<'a, f32>, [isize; 2]),
+    Unary(Unary),
+}
+
+/// Check that every addend is `m x n` or a length-`n` row vector, and
+/// resolve its strides once for the whole result
+fn resolve_epilogue<'a>(ops: &'a [Epilogue<'_>], m: usize, n: usize) -> Vec<Step<'a>> {
+    ops.iter()
+        .map(|op| match op {
+            Epilogue::Unary(u) => Step::Unary(*u),
+            Epilogue::Add(t) => {
+                let v = t.view();
+                let stride = v.layout().signed_stride();
+                let strides = match v.layout().flat_shape() {
+                    [len] if *len == n => [0, stride[0]],
+                    [rows, cols] if [*rows, *cols] == [m, n] => [stride[0], stride[1]],
+                    _ => panic!("Expr: cannot add shape {} to a {m}x{n} result", v.layout().shape()),
+                };
+                Step::Add(v, strides)
+            }
+        })
+        .collect()
+}
+
+/// Run the epilogue on element `(i, j)` of the result
+#[inline]
+fn apply(steps: &[Step<'_>], mut x: f32, i: usize, j: usize) -> f32 {
+    for step in steps {
+        x = match step {
+            Step::Unary(u) => u.apply(x),
+            Step::Add(v, [rs, cs]) => x + unsafe { *v.as_ptr().offset(i as isize * rs + j as isize * cs) },
+        };
+    }
+    x
+}
+
+/// Run the epilogue over `tile`, whose element `(0, 0)` is `(m0, n0)` of the result
+fn apply_tile(steps: &[Step<'_>], tile: &mut TensorViewMut<'_, f32>, m0: usize, n0: usize) {
+    let (rows, cols) = extents(tile.layout(), "Expr");
+    for i in 0..rows {
+        for j in 0..cols {
+            let x = unsafe { tile.get_flat_mut(&[i, j]) };
+            *x = apply(steps, *x, m0 + i, n0 + j);
+        }
+    }
+}
+
+/// Tiled parallel GEMM, with the epilogue applied to each C tile as it completes
+fn fused_matmul<B: BlasBackend + Sync>(
+    a: TensorCow<'_, f32>,
+    b: TensorCow<'_, f32>,
+    ops: &[Epilogue<'_>],
+    backend: &B,
+) -> Tensor<f32> {
+    let (a, b) = (a.view(), b.view());
+    let (m, k) = extents(a.layout(), "matmul");
+    let (kb, n) = extents(b.layout(), "matmul");
+    assert_eq!(k, kb, "matmul: inner extents {k} and {kb} differ");
+
+    let steps = resolve_epilogue(ops, m, n);
+
+    let mut c = Tensor::new(vec![0.0; m * n], Layout::row_major([m, n]));
+    let epilogue = |tile: &Tile, c_tile: &mut TensorViewMut<'_, f32>| {
+        apply_tile(&steps, c_tile, tile.start(0), tile.start(1));
+    };
+    if ops.is_empty() {
+        gemm_f32_tiled_parallel(backend, &a, &b, &mut c.as_view_mut(), None, 1.0, 0.0);
+    } else {
+        gemm_f32_tiled_parallel_with(backend, &a, &b, &mut c.as_view_mut(), None, 1.0, 0.0, &Parallelism::default(), epilogue);
+    }
+    c
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::matrix;
+
+    #[test]
+    fn matmul_bias_relu() {
+        let a = matrix(2, 3, vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0]);
+        let b = matrix(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]);
+        let bias = Tensor::new(vec![0.5, -10.0], Layout::row_major([2]));
+
+        // A·B = [[4, 1], [-10, -1]]
+        let out = (a.lazy().matmul(&b) + &bias).relu().eval();
+        assert_eq!(out.data(), &[4.5, 0.0, 0.0, 0.0]);
+
+        let out = (a.lazy().matmul(&b).scale(2.0) + &bias).eval();
+        assert_eq!(out.data(), &[8.5, -8.0, -19.5, -12.0]);
+    }
+
+    #[test]
+    fn fused_result_matches_unfused() {
+        let (m, k, n) = (70, 5, 45);
+        let a = matrix(m, k, (0..m * k).map(|x| (x % 7) as f32 - 3.0).collect());
+        let b = matrix(k, n, (0..k * n).map(|x| (x % 4) as f32 - 1.5).collect());
+        let residual = matrix(m, n, (0..m * n).map(|x| (x % 3) as f32).collect());
+
+        let fused = (residual.lazy() + a.lazy().matmul(&b)).map(|x| x - 1.0).relu().eval();
+
+        let plain = a.lazy().matmul(&b).eval();
+        for (i, got) in fused.data().iter().enumerate() {
+            assert_eq!(*got, (plain.data()[i] + residual.data()[i] - 1.0).max(0.0));
+        }
+    }
+
+    #[test]
+    fn nested_matmul_and_elementwise_leaf() {
+        let a = matrix(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let id = matrix(2, 2, vec![1.0, 0.0, 0.0, 1.0]);
+        let out = a.lazy().matmul(id.lazy().matmul(&a)).eval();
+        assert_eq!(out.data(), &[7.0, 10.0, 15.0, 22.0]);
+
+        let out = (a.lazy() + &a).scale(0.5).eval();
+        assert_eq!(out.data(), a.data());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot add shape")]
+    fn rejects_unbroadcastable_addend() {
+        let a = matrix(2, 2, vec![0.0; 4]);
+        let v = Tensor::new(vec![0.0; 3], Layout::row_major([3]));
+        let _ = (a.lazy().matmul(&a) + &v).eval();
+    }
+
+    #[cfg(not(feature = "provenance"))]
+    #[test]
+    fn epilogue_does_not_allocate_per_element() {
+        use crate::tiled_tensor::tests::allocations;
+
+        let count = |n: usize| {
+            let a = matrix(n, n, vec![1.0; n * n]);
+            let bias = Tensor::new(vec![-0.5; n], Layout::row_major([n]));
+            let before = allocations();
+            let out = (a.lazy() + &bias + &a).relu().eval();
+            let after = allocations();
+            assert!(out.data().iter().all(|&x| x == 1.5));
+            after - before
+        };
+        assert_eq!(count(4), count(32));
+    }
+}
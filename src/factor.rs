@@ -0,0 +1,306 @@
+// ============================================================
+// Blocked Cholesky / LU factorization
+// ============================================================
+//
+// Right-looking blocked algorithms: factor a narrow panel with scalar
+// code, solve the off-diagonal block with TRSM, then apply the trailing
+// update tile by tile through TiledTensorViewMut with GEMM/SYRK.
+//
+// ============================================================
+
+use std::fmt;
+
+use crate::blas::{BlasBackend, BlasDiag, BlasSide, BlasUplo};
+use crate::gemm::{gemm_f32, syrk_f32, trsm_f32};
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::tensor::{TensorView, TensorViewMut};
+use crate::tiled_tensor::TiledTensorViewMut;
+use crate::tuple::Tuple;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorError {
+    /// Cholesky found a non-positive pivot at this diagonal index
+    NotPositiveDefinite(usize),
+    /// LU found an exactly zero pivot at this diagonal index
+    Singular(usize),
+}
+
+impl fmt::Display for FactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FactorError::NotPositiveDefinite(i) => write!(f, "matrix is not positive definite (pivot {})", i),
+            FactorError::Singular(i) => write!(f, "matrix is singular (pivot {})", i),
+        }
+    }
+}
+
+impl std::error::Error for FactorError {}
+
+/* ---------- helpers ---------- */
+
+/// Row/column strides of a rank-2 view
+fn strides_2d<T>(v: &TensorViewMut<'_, T>) -> (usize, usize) {
+    (v.layout().stride().flat_at(0), v.layout().stride().flat_at(1))
+}
+
+/// Square extent of a rank-2 view
+fn square_extent<T>(v: &TensorViewMut<'_, T>, op: &str) -> usize {
+    let shape = v.layout().shape();
+    assert_eq!(shape.flat_len(), 2, "{}: matrix must be rank 2", op);
+    assert_eq!(shape.flat_at(0), shape.flat_at(1), "{}: matrix must be square", op);
+    shape.flat_at(0)
+}
+
+/// `(r, c)` block of `a` starting at `(r0, c0)`, detached from `a`'s borrow
+unsafe fn block<'a>(a: &mut TensorViewMut<'a, f32>, r0: usize, c0: usize, r: usize, c: usize) -> TensorViewMut<'a, f32> {
+    a.subview_mut(Tuple::int(vec![r0, c0]), Shape::new(Tuple::int(vec![r, c])))
+}
+
+/// Transposed rank-2 view over the same memory
+fn transposed<'a>(v: &TensorView<'a, f32>) -> TensorView<'a, f32> {
+    unsafe { v.with_layout(v.layout().permute_modes(&[1, 0])) }
+}
+
+fn tiler(nb: usize) -> Layout {
+    Layout::row_major(Shape::new(Tuple::int(vec![nb, nb])))
+}
+
+/* ---------- Cholesky ---------- */
+
+/// In-place lower Cholesky factorization `A = L L^T` of a row-major
+/// symmetric positive definite matrix with block size `nb`.
+/// Only the lower triangle is read and overwritten with L.
+pub fn cholesky_f32<B: BlasBackend>(
+    backend: &B,
+    a: &mut TensorViewMut<'_, f32>,
+    nb: usize,
+) -> Result<(), FactorError> {
+    assert!(nb > 0, "cholesky_f32: block size must be > 0");
+    let n = square_extent(a, "cholesky_f32");
+
+    for k in (0..n).step_by(nb) {
+        let kb = nb.min(n - k);
+        let rest = n - k - kb;
+
+        // A11 = L11 L11^T (unblocked)
+        let a11 = unsafe { block(a, k, k, kb, kb) };
+        potrf_unblocked(&a11, kb).map_err(|i| FactorError::NotPositiveDefinite(k + i))?;
+        if rest == 0 {
+            break;
+        }
+        let l11 = a11.into_view();
+
+        // L21 = A21 L11^{-T}
+        let mut a21 = unsafe { block(a, k + kb, k, rest, kb) };
+        trsm_f32(backend, BlasSide::Right, BlasUplo::Upper, BlasDiag::NonUnit,
+                 &transposed(&l11), &mut a21, 1.0);
+        let l21 = a21.into_view();
+
+        // A22 -= L21 L21^T, lower tiles only
+        let a22 = unsafe { block(a, k + kb, k + kb, rest, rest) };
+        let mut tiled = TiledTensorViewMut::new(a22, tiler(nb));
+        for (tile, mut c) in tiled.tiles_mut() {
+            let (i0, j0) = (tile.start(0), tile.start(1));
+            let (tm, tn) = (tile.len(0), tile.len(1));
+            let li = unsafe { l21.subview_2d(i0, 0, tm, kb) };
+
+            if i0 == j0 {
+                syrk_f32(backend, BlasUplo::Lower, &li, &mut c, -1.0, 1.0);
+            } else if i0 > j0 {
+                let lj = unsafe { l21.subview_2d(j0, 0, tn, kb) };
+                gemm_f32(backend, &li, &transposed(&lj), &mut c, -1.0, 1.0);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Scalar Cholesky of the leading `n x n` block; returns the failing pivot
+fn potrf_unblocked(a: &TensorViewMut<'_, f32>, n: usize) -> Result<(), usize> {
+    let (s0, s1) = strides_2d(a);
+    let at = |i: usize, j: usize| unsafe { a.ptr.as_ptr().add(i * s0 + j * s1) };
+
+    unsafe {
+        for j in 0..n {
+            let d = *at(j, j) - (0..j).map(|p| *at(j, p) * *at(j, p)).sum::<f32>();
+            if d <= 0.0 || d.is_nan() {
+                return Err(j);
+            }
+            let d = d.sqrt();
+            *at(j, j) = d;
+
+            for i in j + 1..n {
+                let s = *at(i, j) - (0..j).map(|p| *at(i, p) * *at(j, p)).sum::<f32>();
+                *at(i, j) = s / d;
+            }
+        }
+    }
+    Ok(())
+}
+
+/* ---------- LU ---------- */
+
+/// In-place LU factorization with partial pivoting, `P A = L U`, of a
+/// row-major square matrix with block size `nb`. L (unit diagonal) and U
+/// overwrite A. Returns the pivots: row `i` was swapped with row `piv[i]`.
+pub fn lu_f32<B: BlasBackend>(
+    backend: &B,
+    a: &mut TensorViewMut<'_, f32>,
+    nb: usize,
+) -> Result<Vec<usize>, FactorError> {
+    assert!(nb > 0, "lu_f32: block size must be > 0");
+    let n = square_extent(a, "lu_f32");
+    let mut piv = vec![0; n];
+
+    for k in (0..n).step_by(nb) {
+        let kb = nb.min(n - k);
+        let rest = n - k - kb;
+
+        // Factor the panel A[k.., k..k+kb]; row swaps span the full matrix
+        getrf_panel(a, n, k, kb, &mut piv)?;
+        if rest == 0 {
+            break;
+        }
+
+        // U12 = L11^{-1} A12
+        let l11 = unsafe { block(a, k, k, kb, kb) }.into_view();
+        let mut a12 = unsafe { block(a, k, k + kb, kb, rest) };
+        trsm_f32(backend, BlasSide::Left, BlasUplo::Lower, BlasDiag::Unit, &l11, &mut a12, 1.0);
+        let u12 = a12.into_view();
+        let l21 = unsafe { block(a, k + kb, k, rest, kb) }.into_view();
+
+        // A22 -= L21 U12
+        let a22 = unsafe { block(a, k + kb, k + kb, rest, rest) };
+        let mut tiled = TiledTensorViewMut::new(a22, tiler(nb));
+        for (tile, mut c) in tiled.tiles_mut() {
+            let (i0, j0) = (tile.start(0), tile.start(1));
+            let li = unsafe { l21.subview_2d(i0, 0, tile.len(0), kb) };
+            let uj = unsafe { u12.subview_2d(0, j0, kb, tile.len(1)) };
+            gemm_f32(backend, &li, &uj, &mut c, -1.0, 1.0);
+        }
+    }
+
+    Ok(piv)
+}
+
+/// Unblocked partial-pivoting LU of columns `k..k+kb`, rows `k..n`
+fn getrf_panel(
+    a: &mut TensorViewMut<'_, f32>,
+    n: usize,
+    k: usize,
+    kb: usize,
+    piv: &mut [usize],
+) -> Result<(), FactorError> {
+    let (s0, s1) = strides_2d(a);
+    let base = a.ptr.as_ptr();
+    let at = |i: usize, j: usize| unsafe { base.add(i * s0 + j * s1) };
+
+    unsafe {
+        for (j, pj) in piv.iter_mut().enumerate().skip(k).take(kb) {
+            let p = (j..n)
+                .max_by(|&x, &y| (*at(x, j)).abs().total_cmp(&(*at(y, j)).abs()))
+                .unwrap_or(j);
+            *pj = p;
+            if *at(p, j) == 0.0 {
+                return Err(FactorError::Singular(j));
+            }
+            if p != j {
+                for c in 0..n {
+                    std::ptr::swap(at(j, c), at(p, c));
+                }
+            }
+
+            let d = *at(j, j);
+            for i in j + 1..n {
+                *at(i, j) /= d;
+                let l = *at(i, j);
+                for c in j + 1..k + kb {
+                    *at(i, c) -= l * *at(j, c);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::test_util::matrix;
+
+    /// Deterministic SPD matrix: M M^T + n I
+    fn spd(n: usize) -> Vec<f32> {
+        let m: Vec<f32> = (0..n * n).map(|x| ((x * 7 + 3) % 11) as f32 / 11.0).collect();
+        let mut out = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                out[i * n + j] = (0..n).map(|p| m[i * n + p] * m[j * n + p]).sum::<f32>()
+                    + if i == j { n as f32 } else { 0.0 };
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn cholesky_reconstructs_input() {
+        let n = 11;
+        let orig = spd(n);
+        let mut a = matrix(n, n, orig.clone());
+
+        cholesky_f32(&NativeBlas, &mut a.as_view_mut(), 4).unwrap();
+
+        let l = |i: usize, j: usize| if j <= i { a.data()[i * n + j] } else { 0.0 };
+        for i in 0..n {
+            for j in 0..=i {
+                let llt: f32 = (0..n).map(|p| l(i, p) * l(j, p)).sum();
+                assert!((llt - orig[i * n + j]).abs() < 1e-3, "mismatch at ({}, {})", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn cholesky_rejects_indefinite() {
+        let mut a = matrix(2, 2, vec![1.0, 2.0, 2.0, 1.0]);
+        let err = cholesky_f32(&NativeBlas, &mut a.as_view_mut(), 1).unwrap_err();
+        assert_eq!(err, FactorError::NotPositiveDefinite(1));
+    }
+
+    #[test]
+    fn lu_reconstructs_permuted_input() {
+        let n = 10;
+        let orig: Vec<f32> = (0..n * n).map(|x| ((x * 13 + 5) % 17) as f32 - 8.0).collect();
+        let mut a = matrix(n, n, orig.clone());
+
+        let piv = lu_f32(&NativeBlas, &mut a.as_view_mut(), 3).unwrap();
+
+        // Apply the recorded swaps to the original rows
+        let mut pa = orig.clone();
+        for (i, &p) in piv.iter().enumerate() {
+            for c in 0..n {
+                pa.swap(i * n + c, p * n + c);
+            }
+        }
+
+        let lu = a.data();
+        for i in 0..n {
+            for j in 0..n {
+                let s: f32 = (0..=i.min(j))
+                    .map(|p| {
+                        let l = if p == i { 1.0 } else { lu[i * n + p] };
+                        l * lu[p * n + j]
+                    })
+                    .sum();
+                assert!((s - pa[i * n + j]).abs() < 1e-3, "mismatch at ({}, {})", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn lu_detects_singular() {
+        let mut a = matrix(2, 2, vec![1.0, 2.0, 2.0, 4.0]);
+        assert_eq!(lu_f32(&NativeBlas, &mut a.as_view_mut(), 2), Err(FactorError::Singular(1)));
+    }
+}
@@ -0,0 +1,185 @@
+// src/ffi.rs
+//
+// Plain `(ptr, rows, cols, ld, order)` matrix descriptors, the way C and
+// C++ BLAS-style code passes matrices around, and conversions to and from
+// tensor views. `rutile_sgemm` exposes the GEMM driver over them.
+//
+// The crate builds as an rlib only. To link the C ABI from C, build a
+// shared or static library with
+// `cargo rustc --release --lib --crate-type cdylib` (or `staticlib`).
+
+use crate::error::{Error, Result};
+use crate::gemm::{try_gemm_f32, try_lower_matrix};
+use crate::blas::BlasTranspose;
+use crate::kernel::NativeGemm;
+use crate::layout::Layout;
+use crate::tensor::{check_disjoint_always, TensorView, TensorViewMut};
+
+/// Which mode of a descriptor has unit stride
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixOrder {
+    /// Element `(i, j)` is at `ptr[i * ld + j]`
+    RowMajor = 0,
+    /// Element `(i, j)` is at `ptr[j * ld + i]`
+    ColMajor = 1,
+}
+
+/// A `rows x cols` matrix at `ptr` with leading dimension `ld`, laid out
+/// as a C struct so it can cross an `extern "C"` boundary unchanged.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatrixDesc<T> {
+    pub ptr: *mut T,
+    pub rows: usize,
+    pub cols: usize,
+    pub ld: usize,
+    pub order: MatrixOrder,
+}
+
+pub type MatrixDescF32 = MatrixDesc<f32>;
+
+impl<T> MatrixDesc<T> {
+    /// Descriptor of a rank-2 view with one unit-stride mode, or
+    /// `Error::NotContiguous` when the view has none (or a reversed mode).
+    /// `ptr` is only valid while `view`'s borrow lasts and must not be
+    /// written through.
+    pub fn from_view(view: &TensorView<'_, T>) -> Result<Self> {
+        Self::describe("MatrixDesc::from_view", view.as_ptr().cast_mut(), view.layout())
+    }
+
+    /// Like `from_view`, for a view the descriptor may be written through
+    pub fn from_view_mut(view: &mut TensorViewMut<'_, T>) -> Result<Self> {
+        Self::describe("MatrixDesc::from_view_mut", view.ptr.as_ptr(), view.layout())
+    }
+
+    fn describe(op: &'static str, ptr: *mut T, layout: &Layout) -> Result<Self> {
+        let (ld, trans) = try_lower_matrix(op, layout)?;
+        let (rows, cols) = (layout.shape().flat_at(0), layout.shape().flat_at(1));
+        let order = match trans {
+            BlasTranspose::NoTrans => MatrixOrder::RowMajor,
+            _ => MatrixOrder::ColMajor,
+        };
+        Ok(MatrixDesc { ptr, rows, cols, ld: ld as usize, order })
+    }
+
+    /// Rank-2 layout this descriptor addresses, or `Error::NotContiguous`
+    /// if `ld` is smaller than the extent of the unit-stride mode
+    pub fn layout(&self) -> Result<Layout> {
+        let (minor, stride) = match self.order {
+            MatrixOrder::RowMajor => (self.cols, [self.ld, 1]),
+            MatrixOrder::ColMajor => (self.rows, [1, self.ld]),
+        };
+        if self.rows > 1 && self.cols > 1 && self.ld < minor {
+            return Err(Error::NotContiguous { op: "MatrixDesc::layout" });
+        }
+        Ok(Layout::row_major([self.rows, self.cols]).with_stride(stride))
+    }
+
+    /// View over the described memory
+    ///
+    /// # Safety
+    /// `ptr` must be non-null and every element of the matrix must be valid
+    /// for reads, and not written by anyone else, for `'a`.
+    pub unsafe fn as_view<'a>(&self) -> Result<TensorView<'a, T>> {
+        Ok(TensorView::from_raw(self.ptr, self.layout()?))
+    }
+
+    /// Mutable view over the described memory
+    ///
+    /// # Safety
+    /// `ptr` must be non-null, every element of the matrix must be valid for
+    /// reads and writes for `'a`, and nothing else may access them meanwhile.
+    pub unsafe fn as_view_mut<'a>(&self) -> Result<TensorViewMut<'a, T>> {
+        Ok(TensorViewMut::from_raw(self.ptr, self.layout()?))
+    }
+}
+
+/// `C = alpha * A * B + beta * C` over descriptors on the native packed
+/// kernel. Returns 0 on success, -1 for a null descriptor or pointer and
+/// -2 when the operands are rejected (shapes, leading dimensions that are
+/// too small or past `i32::MAX`, overlap).
+///
+/// # Safety
+/// Each descriptor must satisfy the contract of `MatrixDesc::as_view`
+/// (`as_view_mut` for `c`).
+#[no_mangle]
+pub unsafe extern "C" fn rutile_sgemm(
+    a: *const MatrixDescF32,
+    b: *const MatrixDescF32,
+    c: *const MatrixDescF32,
+    alpha: f32,
+    beta: f32,
+) -> i32 {
+    let (Some(a), Some(b), Some(c)) = (a.as_ref(), b.as_ref(), c.as_ref()) else {
+        return -1;
+    };
+    if a.ptr.is_null() || b.ptr.is_null() || c.ptr.is_null() {
+        return -1;
+    }
+
+    let run = || -> Result<()> {
+        let (a, b, mut c) = (a.as_view()?, b.as_view()?, c.as_view_mut()?);
+        // C callers get no borrow checking, so overlap is rejected in release builds too
+        check_disjoint_always("rutile_sgemm", &a, &c)?;
+        check_disjoint_always("rutile_sgemm", &b, &c)?;
+        try_gemm_f32(&NativeGemm::default(), &a, &b, &mut c, alpha, beta)
+    };
+    match run() {
+        Ok(()) => 0,
+        Err(_) => -2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::Tensor;
+    use crate::tuple::Tuple;
+
+    #[test]
+    fn views_round_trip_through_descriptors() {
+        let t = Tensor::new((0..12).map(|x| x as f32).collect(), Layout::row_major([3, 4]));
+        let desc = MatrixDesc::from_view(&t.as_view()).unwrap();
+        assert_eq!((desc.rows, desc.cols, desc.ld, desc.order), (3, 4, 4, MatrixOrder::RowMajor));
+
+        // Columns 1..3 keep the parent's leading dimension
+        let sub = unsafe { t.as_view().subview([0, 1], [3, 2]) };
+        let desc = MatrixDesc::from_view(&sub).unwrap();
+        assert_eq!((desc.rows, desc.cols, desc.ld), (3, 2, 4));
+        let back = unsafe { desc.as_view() }.unwrap();
+        assert_eq!(back.to_vec(), vec![1.0, 2.0, 5.0, 6.0, 9.0, 10.0]);
+
+        let cm = Tensor::new((0..6).map(|x| x as f32).collect(), Layout::col_major([2, 3]));
+        let desc = MatrixDesc::from_view(&cm.as_view()).unwrap();
+        assert_eq!((desc.ld, desc.order), (2, MatrixOrder::ColMajor));
+        assert_eq!(unsafe { *desc.as_view().unwrap().get(&Tuple::int(vec![1, 2])) }, 5.0);
+    }
+
+    #[test]
+    fn rejects_unusable_layouts() {
+        let t = Tensor::new(vec![0.0f32; 23], Layout::row_major([3, 4]).with_stride([8, 2]));
+        assert_eq!(MatrixDesc::from_view(&t.as_view()), Err(Error::NotContiguous { op: "MatrixDesc::from_view" }));
+
+        let mut data = vec![0.0f32; 12];
+        let desc = MatrixDesc { ptr: data.as_mut_ptr(), rows: 3, cols: 4, ld: 2, order: MatrixOrder::RowMajor };
+        assert!(unsafe { desc.as_view_mut() }.is_err());
+    }
+
+    #[test]
+    fn sgemm_entry_point_matches_gemm() {
+        // A row-major 2x3, B column-major 3x2
+        let mut a = vec![1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let mut b = vec![1.0f32, 0.0, 1.0, 0.0, 1.0, 0.0];
+        let mut c = vec![0.0f32; 4];
+        let a = MatrixDesc { ptr: a.as_mut_ptr(), rows: 2, cols: 3, ld: 3, order: MatrixOrder::RowMajor };
+        let b = MatrixDesc { ptr: b.as_mut_ptr(), rows: 3, cols: 2, ld: 3, order: MatrixOrder::ColMajor };
+        let cd = MatrixDesc { ptr: c.as_mut_ptr(), rows: 2, cols: 2, ld: 2, order: MatrixOrder::RowMajor };
+
+        assert_eq!(unsafe { rutile_sgemm(&a, &b, &cd, 1.0, 0.0) }, 0);
+        assert_eq!(c, vec![4.0, 2.0, 10.0, 5.0]);
+
+        assert_eq!(unsafe { rutile_sgemm(&a, &a, &cd, 1.0, 0.0) }, -2);
+        assert_eq!(unsafe { rutile_sgemm(std::ptr::null(), &b, &cd, 1.0, 0.0) }, -1);
+    }
+}
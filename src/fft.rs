@@ -0,0 +1,101 @@
+// src/fft.rs
+//
+// Batched 1D FFTs over tensor modes, computed by rustfft. Every lane along
+// the chosen mode is transformed in place: lanes with unit stride are handed
+// to rustfft directly, strided lanes are gathered into a scratch buffer.
+
+use rustfft::num_complex::Complex;
+use rustfft::num_traits::Zero;
+use rustfft::{FftNum, FftPlanner};
+
+pub use rustfft::FftDirection;
+
+use crate::error::check_axis;
+use crate::shape::{coords, Shape};
+use crate::tensor::TensorViewMut;
+use crate::tuple::Tuple;
+
+/// Transform every lane of `view` along flattened mode `axis` in place.
+/// Like rustfft, neither direction is normalized: a forward and an inverse
+/// transform scale the data by the lane length.
+pub fn fft_along<T: FftNum>(view: &mut TensorViewMut<'_, Complex<T>>, axis: usize, direction: FftDirection) {
+    let dims = view.layout().shape().dims.flatten();
+    if let Err(e) = check_axis("fft_along", axis, dims.len()) {
+        panic!("{e}");
+    }
+    let len = dims[axis];
+    if len <= 1 || view.layout().size() == 0 {
+        return;
+    }
+
+    let fft = FftPlanner::new().plan_fft(len, direction);
+    let mut scratch = vec![Complex::zero(); fft.get_inplace_scratch_len()];
+    let stride = view.layout().signed_stride()[axis];
+    let mut lane = if stride == 1 { Vec::new() } else { vec![Complex::zero(); len] };
+
+    let mut starts = dims.clone();
+    starts[axis] = 1;
+    for crd in coords(&Shape::new(Tuple::int(starts))) {
+        // Lanes differ in some other mode, so no two of them share an element
+        let first = unsafe { view.ptr.as_ptr().offset(view.layout().crd2offset(&crd)) };
+        unsafe {
+            if stride == 1 {
+                fft.process_with_scratch(std::slice::from_raw_parts_mut(first, len), &mut scratch);
+            } else {
+                for (i, x) in lane.iter_mut().enumerate() {
+                    *x = *first.offset(i as isize * stride);
+                }
+                fft.process_with_scratch(&mut lane, &mut scratch);
+                for (i, x) in lane.iter().enumerate() {
+                    *first.offset(i as isize * stride) = *x;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    fn dft(x: &[Complex<f64>]) -> Vec<Complex<f64>> {
+        let n = x.len() as f64;
+        (0..x.len())
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .map(|(j, v)| v * Complex::from_polar(1.0, -2.0 * std::f64::consts::PI * (j * k) as f64 / n))
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn close(a: Complex<f64>, b: Complex<f64>) -> bool {
+        (a - b).norm() < 1e-9
+    }
+
+    #[test]
+    fn batched_lanes_match_dft_along_either_mode() {
+        let data: Vec<Complex<f64>> = (0..12).map(|x| Complex::new(x as f64, (x % 5) as f64)).collect();
+        for axis in [0, 1] {
+            let mut t = Tensor::new(data.clone(), Layout::row_major([3, 4]));
+            fft_along(&mut t.as_view_mut(), axis, FftDirection::Forward);
+
+            let (lanes, len) = if axis == 1 { (3, 4) } else { (4, 3) };
+            for l in 0..lanes {
+                let at = |i: usize| if axis == 1 { [l, i] } else { [i, l] };
+                let input: Vec<_> = (0..len).map(|i| data[at(i)[0] * 4 + at(i)[1]]).collect();
+                let expected = dft(&input);
+                for (i, e) in expected.iter().enumerate() {
+                    assert!(close(t.as_view()[at(i)], *e), "axis {axis} lane {l}");
+                }
+            }
+
+            fft_along(&mut t.as_view_mut(), axis, FftDirection::Inverse);
+            let n = if axis == 1 { 4.0 } else { 3.0 };
+            assert!(t.data().iter().zip(&data).all(|(x, y)| close(x / n, *y)));
+        }
+    }
+}
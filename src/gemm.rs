@@ -1,9 +1,17 @@
 
-use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tensor::{check_disjoint, Tensor, TensorView, TensorViewMut};
 use crate::layout::Layout;
+#[cfg(test)]
 use crate::shape::Shape;
 use crate::tuple::Tuple;
 use crate::blas::*;
+use crate::tiled_tensor::{Tile, TiledTensorViewMut};
+use crate::hw::default_tile_for_gemm;
+use crate::error::{check_rank, Error, Result};
+use crate::copy::tensor_copy;
+use crate::parallel::Parallelism;
+use crate::bench_utils::KernelStats;
+use std::ops::Mul;
 
 /// Compare two contiguous buffers with a tolerance `eps`.
 /// Panics if any element differs more than `eps`.
@@ -36,22 +44,147 @@ pub unsafe fn assert_close_f32(
    Layout → BLAS lowering
    ============================================================ */
 
-fn lower_matrix(layout: &Layout) -> (i32, BlasTranspose) {
-    assert_eq!(layout.shape().flat_len(), 2);
+pub(crate) fn try_lower_matrix(op: &'static str, layout: &Layout) -> Result<(i32, BlasTranspose)> {
+    check_rank(op, 2, layout.shape().flat_len())?;
+    if layout.has_reversed_modes() {
+        return Err(Error::NotContiguous { op });
+    }
 
-    let s0 = layout.stride().flat_at(0);
-    let s1 = layout.stride().flat_at(1);
+    let (rows, cols) = (layout.shape().flat_at(0), layout.shape().flat_at(1));
+    let (s0, s1) = (layout.stride().flat_at(0), layout.stride().flat_at(1));
+    let to_i32 = |ld: usize| i32::try_from(ld).map_err(|_| Error::Unsupported {
+        op,
+        what: "leading dimensions past i32::MAX".into(),
+    });
 
+    // The stride of an extent-1 mode is never used, so it is free to pick;
+    // BLAS only needs ld >= the extent of the contiguous mode.
     // Row-major: [i][j] → j is contiguous
-    if s1 == 1 {
-        (s0 as i32, BlasTranspose::NoTrans)
+    if s1 == 1 || cols == 1 {
+        let ld = if rows == 1 { cols } else { s0 };
+        if ld >= cols.max(1) {
+            return Ok((to_i32(ld)?, BlasTranspose::NoTrans));
+        }
     }
     // Column-major: transpose trick
-    else if s0 == 1 {
-        (s1 as i32, BlasTranspose::Trans)
+    if s0 == 1 || rows == 1 {
+        let ld = if cols == 1 { rows } else { s1 };
+        if ld >= rows.max(1) {
+            return Ok((to_i32(ld)?, BlasTranspose::Trans));
+        }
     }
-    else {
-        panic!("GEMM requires dense 2D layout");
+    Err(Error::NotContiguous { op })
+}
+
+/// `(len, inc)` of a rank-1 operand. Extent-1 and empty vectors get
+/// `inc = 1`, since BLAS never steps past their first element.
+pub(crate) fn try_lower_vector(op: &'static str, layout: &Layout) -> Result<(usize, i32)> {
+    check_rank(op, 1, layout.shape().flat_len())?;
+    let (len, stride) = (layout.shape().flat_at(0), layout.stride().flat_at(0));
+    if len <= 1 {
+        return Ok((len, 1));
+    }
+    if layout.has_reversed_modes() || stride == 0 {
+        return Err(Error::NotContiguous { op });
+    }
+    let inc = i32::try_from(stride).map_err(|_| Error::Unsupported { op, what: "increments past i32::MAX".into() })?;
+    Ok((len, inc))
+}
+
+pub(crate) fn flip(t: BlasTranspose) -> BlasTranspose {
+    match t {
+        BlasTranspose::NoTrans => BlasTranspose::Trans,
+        BlasTranspose::Trans => BlasTranspose::NoTrans,
+        // Lowering only yields NoTrans / Trans; conjugation is applied after any flip
+        BlasTranspose::ConjTrans => unreachable!("flip of a conjugated operand"),
+    }
+}
+
+/// BLAS arguments for `C = op(A) op(B)` with any mix of operand orders.
+/// Kernels write a row-major C, so a column-major C is computed as the
+/// row-major `C^T = op(B)^T op(A)^T`: the operands swap and both flip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct LoweredGemm {
+    pub lda: (i32, BlasTranspose),
+    pub ldb: (i32, BlasTranspose),
+    pub ldc: i32,
+    pub swap: bool,
+}
+
+pub(crate) fn try_lower_gemm(op: &'static str, la: &Layout, lb: &Layout, lc: &Layout) -> Result<LoweredGemm> {
+    let lower = |name: &str, layout: &Layout| {
+        try_lower_matrix(op, layout).map_err(|_| gemm_error(
+            op,
+            format!("{name} needs a unit-stride mode and a leading dimension at least the other extent"),
+            la,
+            lb,
+            lc,
+        ))
+    };
+    let (lda, ldb, (ldc, tc)) = (lower("A", la)?, lower("B", lb)?, lower("C", lc)?);
+    Ok(LoweredGemm { lda, ldb, ldc, swap: tc == BlasTranspose::Trans })
+}
+
+fn gemm_error(op: &'static str, problem: String, la: &Layout, lb: &Layout, lc: &Layout) -> Error {
+    Error::GemmOperands { op, problem, layouts: Box::new([la.clone(), lb.clone(), lc.clone()]) }
+}
+
+impl LoweredGemm {
+    /// Run `C = alpha op(A) op(B) + beta C` for an `m x n` C
+    ///
+    /// # Safety
+    /// The pointers must address operands with the lowered layouts.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) unsafe fn run<B: BlasBackend + ?Sized>(
+        &self,
+        backend: &B,
+        algo: GemmAlgo,
+        m: usize,
+        n: usize,
+        k: usize,
+        alpha: f32,
+        a: *const f32,
+        b: *const f32,
+        beta: f32,
+        c: *mut f32,
+    ) {
+        let ((lda, ta), (ldb, tb)) = (self.lda, self.ldb);
+        let (m, n, k) = (m as i32, n as i32, k as i32);
+        if self.swap {
+            backend.gemm_f32_algo(algo, flip(tb), flip(ta), n, m, k, alpha, b, ldb, a, lda, beta, c, self.ldc);
+        } else {
+            backend.gemm_f32_algo(algo, ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, self.ldc);
+        }
+    }
+
+    /// `run` over `batch` operand triples, each `strides` = (A, B, C)
+    /// elements after the previous one
+    ///
+    /// # Safety
+    /// The pointers must address `batch` operands of the lowered layouts.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) unsafe fn run_strided_batched<B: BlasBackend + ?Sized>(
+        &self,
+        backend: &B,
+        (m, n, k): (usize, usize, usize),
+        alpha: f32,
+        a: *const f32,
+        b: *const f32,
+        beta: f32,
+        c: *mut f32,
+        strides: (i32, i32, i32),
+        batch: usize,
+    ) {
+        let ((lda, ta), (ldb, tb)) = (self.lda, self.ldb);
+        let (m, n, k, batch) = (m as i32, n as i32, k as i32, batch as i32);
+        let (sa, sb, sc) = strides;
+        if self.swap {
+            backend.gemm_f32_strided_batched(
+                flip(tb), flip(ta), n, m, k, alpha, b, ldb, sb, a, lda, sa, beta, c, self.ldc, sc, batch,
+            );
+        } else {
+            backend.gemm_f32_strided_batched(ta, tb, m, n, k, alpha, a, lda, sa, b, ldb, sb, beta, c, self.ldc, sc, batch);
+        }
     }
 }
 
@@ -59,6 +192,9 @@ fn lower_matrix(layout: &Layout) -> (i32, BlasTranspose) {
    Public GEMM API
    ============================================================ */
 
+/// `C = alpha * A * B + beta * C`. Each operand may be row- or
+/// column-major with any leading dimension, so sub-matrices of larger
+/// allocations (`subview_2d`, tiles) go to the backend without a copy.
 pub fn gemm_f32<B: BlasBackend>(
     backend: &B,
     a: &TensorView<'_, f32>,
@@ -67,53 +203,594 @@ pub fn gemm_f32<B: BlasBackend>(
     alpha: f32,
     beta: f32
 ) {
-    let la = a.layout();
-    let lb = b.layout();
-    let lc = c.layout();
+    if let Err(e) = try_gemm_f32(backend, a, b, c, alpha, beta) {
+        panic!("{e}");
+    }
+}
+
+/// `gemm_f32` returning shape and layout problems as errors. Debug builds
+/// also reject a `c` that overlaps `a` or `b`.
+pub fn try_gemm_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32
+) -> Result<()> {
+    check_disjoint("gemm_f32", a, c)?;
+    check_disjoint("gemm_f32", b, c)?;
+    gemm_f32_impl(backend, a, b, c, alpha, beta)
+}
+
+/// `gemm_f32` without the aliasing check
+///
+/// # Safety
+/// `c` must not overlap `a` or `b`. Use this only where the check is known
+/// to be a false positive or its cost matters in a hot loop.
+pub unsafe fn gemm_f32_unchecked<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32
+) {
+    if let Err(e) = gemm_f32_impl(backend, a, b, c, alpha, beta) {
+        panic!("{e}");
+    }
+}
+
+fn gemm_f32_impl<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32
+) -> Result<()> {
+    const OP: &str = "gemm_f32";
+
+    let (la, lb, lc) = (a.layout(), b.layout(), c.layout());
+    let (m, n, k) = check_gemm_shapes(OP, la, lb, lc)?;
+    // One backend call; lowering is a few comparisons, so it is redone
+    // per call rather than looked up in the plan cache
+    let lowered = try_lower_gemm(OP, la, lb, lc)?;
+    log_trace!("gemm_f32 {m}x{n}x{k} on {}", std::any::type_name::<B>());
+    unsafe {
+        lowered.run(backend, GemmAlgo::Default, m, n, k, alpha, a.ptr.as_ptr(), b.ptr.as_ptr(), beta, c.ptr.as_ptr());
+    }
+    Ok(())
+}
+
+/// `(m, n, k)` of `C = A B`, checking every operand is rank-2 and the
+/// extents agree. Failures are `Error::GemmOperands` naming the constraint.
+pub(crate) fn check_gemm_shapes(op: &'static str, la: &Layout, lb: &Layout, lc: &Layout) -> Result<(usize, usize, usize)> {
+    let fail = |problem: String| Err(gemm_error(op, problem, la, lb, lc));
+
+    for (name, layout) in [("A", la), ("B", lb), ("C", lc)] {
+        let rank = layout.shape().flat_len();
+        if rank != 2 {
+            return fail(format!("{name} has rank {rank}, expected 2"));
+        }
+    }
+
+    let (m, k) = (la.shape().flat_at(0), la.shape().flat_at(1));
+    let (kb, n) = (lb.shape().flat_at(0), lb.shape().flat_at(1));
+    let (mc, nc) = (lc.shape().flat_at(0), lc.shape().flat_at(1));
+
+    if kb != k {
+        return fail(format!("A has {k} columns but B has {kb} rows"));
+    }
+    if (mc, nc) != (m, n) {
+        return fail(format!("C is {mc}x{nc} but A * B is {m}x{n}"));
+    }
+    Ok((m, n, k))
+}
+
+/* ============================================================
+   Strided batched GEMM
+   ============================================================ */
+
+/// `C[i] = alpha * A[i] * B[i] + beta * C[i]` for rank-3 operands whose
+/// first mode is the batch. Each batch mode may have any stride, including
+/// 0 on A or B to reuse one matrix for the whole batch; the matrix modes
+/// follow the same rules as `gemm_f32`. Lowers to one
+/// `gemm_f32_strided_batched` backend call.
+pub fn gemm_strided_batched_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) {
+    if let Err(e) = try_gemm_strided_batched_f32(backend, a, b, c, alpha, beta) {
+        panic!("{e}");
+    }
+}
+
+/// `gemm_strided_batched_f32` returning shape and layout problems as errors
+pub fn try_gemm_strided_batched_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) -> Result<()> {
+    const OP: &str = "gemm_strided_batched_f32";
+    check_disjoint(OP, a, c)?;
+    check_disjoint(OP, b, c)?;
+
+    let (la, lb, lc) = (a.layout(), b.layout(), c.layout());
+    for layout in [la, lb, lc] {
+        check_rank(OP, 3, layout.shape().flat_len())?;
+        if layout.has_reversed_modes() {
+            return Err(Error::NotContiguous { op: OP });
+        }
+    }
+    let batch = lc.flat_shape()[0];
+    for layout in [la, lb] {
+        if layout.flat_shape()[0] != batch {
+            return Err(Error::ShapeMismatch { op: OP, lhs: layout.shape().clone(), rhs: lc.shape().clone() });
+        }
+    }
+    let (ma, mb, mc) = (matrix_modes(la), matrix_modes(lb), matrix_modes(lc));
+    // Consecutive C matrices must not overlap, or the batch races on them
+    if batch > 1 && lc.flat_stride()[0] < mc.cosize() {
+        return Err(Error::Aliasing { op: OP });
+    }
+    let (m, n, k) = check_gemm_shapes(OP, &ma, &mb, &mc)?;
+    let lowered = try_lower_gemm(OP, &ma, &mb, &mc)?;
+    if batch == 0 {
+        return Ok(());
+    }
 
-    /* ---------- shape checks ---------- */
+    let stride = |l: &Layout| i32::try_from(l.flat_stride()[0]).map_err(|_| Error::Unsupported {
+        op: OP,
+        what: "batch strides past i32::MAX".into(),
+    });
+    let strides = (stride(la)?, stride(lb)?, stride(lc)?);
+    unsafe {
+        lowered.run_strided_batched(
+            backend, (m, n, k), alpha, a.ptr.as_ptr(), b.ptr.as_ptr(), beta, c.ptr.as_ptr(), strides, batch,
+        );
+    }
+    Ok(())
+}
 
-    assert_eq!(la.shape().flat_len(), 2);
-    assert_eq!(lb.shape().flat_len(), 2);
-    assert_eq!(lc.shape().flat_len(), 2);
+/// Modes 1 and 2 of a rank-3 layout, i.e. one matrix of the batch
+fn matrix_modes(layout: &Layout) -> Layout {
+    Layout::from_flat(&layout.flat_shape()[1..], &layout.flat_stride()[1..])
+}
 
-    let m = la.shape().flat_at(0) as i32;
-    let k = la.shape().flat_at(1) as i32;
-    let n = lb.shape().flat_at(1) as i32;
+/* ============================================================
+   Triangular solve / symmetric rank-k update
+   ============================================================ */
 
-    assert_eq!(lb.shape().flat_at(0) as i32, k);
-    assert_eq!(lc.shape().flat_at(0) as i32, m);
-    assert_eq!(lc.shape().flat_at(1) as i32, n);
+/// Solve `op(A) X = alpha B` (`side = Left`) or `X op(A) = alpha B`
+/// (`side = Right`) for the triangular matrix A; X overwrites B.
+/// `uplo` describes A in logical coordinates, whatever its memory order.
+pub fn trsm_f32<B: BlasBackend>(
+    backend: &B,
+    side: BlasSide,
+    uplo: BlasUplo,
+    diag: BlasDiag,
+    a: &TensorView<'_, f32>,
+    b: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+) {
+    if let Err(e) = try_trsm_f32(backend, side, uplo, diag, a, b, alpha) {
+        panic!("{e}");
+    }
+}
 
-    /* ---------- layout checks ---------- */
+/// `trsm_f32` returning shape and layout problems as errors
+pub fn try_trsm_f32<B: BlasBackend>(
+    backend: &B,
+    side: BlasSide,
+    uplo: BlasUplo,
+    diag: BlasDiag,
+    a: &TensorView<'_, f32>,
+    b: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+) -> Result<()> {
+    const OP: &str = "trsm_f32";
+    check_disjoint(OP, a, b)?;
 
-    //assert!(la.is_contiguous());
-    //assert!(lb.is_contiguous());
-    //assert!(lc.is_contiguous());
+    let (lda, ta) = try_lower_matrix(OP, a.layout())?;
+    let (ldb, tb) = try_lower_matrix(OP, b.layout())?;
+    if tb != BlasTranspose::NoTrans {
+        return Err(Error::Unsupported { op: OP, what: "a column-major B".into() });
+    }
 
-    /* ---------- BLAS lowering ---------- */
+    let (sa, sb) = (a.layout().shape(), b.layout().shape());
+    let (m, n) = (sb.flat_at(0), sb.flat_at(1));
+    let order = match side {
+        BlasSide::Left => m,
+        BlasSide::Right => n,
+    };
+    if (sa.flat_at(0), sa.flat_at(1)) != (order, order) {
+        return Err(Error::ShapeMismatch { op: OP, lhs: sa.clone(), rhs: sb.clone() });
+    }
 
-    let (lda, ta) = lower_matrix(la);
-    let (ldb, tb) = lower_matrix(lb);
-    let ldc = lc.stride().flat_at(0) as i32;
+    // A column-major A is stored as the row-major A^T, whose populated triangle is the other one
+    let uplo = if ta == BlasTranspose::Trans { uplo.flip() } else { uplo };
 
+    // SAFETY: A and B address the extents of their lowered layouts, and B
+    // was checked not to overlap A
     unsafe {
-        backend.gemm_f32(
+        backend.trsm_f32(
+            side,
+            uplo,
             ta,
-            tb,
-            m,
-            n,
-            k,
+            diag,
+            m as i32,
+            n as i32,
             alpha,
             a.ptr.as_ptr(),
             lda,
             b.ptr.as_ptr(),
             ldb,
+        );
+    }
+    Ok(())
+}
+
+/// `C = alpha A A^T + beta C`, updating only the `uplo` triangle of C
+pub fn syrk_f32<B: BlasBackend>(
+    backend: &B,
+    uplo: BlasUplo,
+    a: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) {
+    if let Err(e) = try_syrk_f32(backend, uplo, a, c, alpha, beta) {
+        panic!("{e}");
+    }
+}
+
+/// `syrk_f32` returning shape and layout problems as errors
+pub fn try_syrk_f32<B: BlasBackend>(
+    backend: &B,
+    uplo: BlasUplo,
+    a: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) -> Result<()> {
+    const OP: &str = "syrk_f32";
+    check_disjoint(OP, a, c)?;
+
+    let (lda, ta) = try_lower_matrix(OP, a.layout())?;
+    let (ldc, tc) = try_lower_matrix(OP, c.layout())?;
+    if tc != BlasTranspose::NoTrans {
+        return Err(Error::Unsupported { op: OP, what: "a column-major C".into() });
+    }
+
+    let (sa, sc) = (a.layout().shape(), c.layout().shape());
+    let (n, k) = (sa.flat_at(0), sa.flat_at(1));
+    if (sc.flat_at(0), sc.flat_at(1)) != (n, n) {
+        return Err(Error::ShapeMismatch { op: OP, lhs: sa.clone(), rhs: sc.clone() });
+    }
+
+    // SAFETY: A and C address the extents of their lowered layouts, and C
+    // was checked not to overlap A
+    unsafe {
+        backend.syrk_f32(
+            uplo,
+            ta,
+            n as i32,
+            k as i32,
+            alpha,
+            a.ptr.as_ptr(),
+            lda,
             beta,
             c.ptr.as_ptr(),
             ldc,
         );
     }
+    Ok(())
+}
+
+/* ============================================================
+   Matrix-vector product / rank-1 update
+   ============================================================ */
+
+/// `y = alpha * A * x + beta * y`. A may be row- or column-major with any
+/// leading dimension and the vectors may be strided, like a column of a
+/// row-major matrix.
+pub fn gemv_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    x: &TensorView<'_, f32>,
+    y: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) {
+    if let Err(e) = try_gemv_f32(backend, a, x, y, alpha, beta) {
+        panic!("{e}");
+    }
+}
+
+/// `gemv_f32` returning shape and layout problems as errors
+pub fn try_gemv_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    x: &TensorView<'_, f32>,
+    y: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) -> Result<()> {
+    const OP: &str = "gemv_f32";
+    check_disjoint(OP, a, y)?;
+    check_disjoint(OP, x, y)?;
+
+    let (lda, ta) = try_lower_matrix(OP, a.layout())?;
+    let (nx, incx) = try_lower_vector(OP, x.layout())?;
+    let (my, incy) = try_lower_vector(OP, y.layout())?;
+    let (m, n) = (a.layout().shape().flat_at(0), a.layout().shape().flat_at(1));
+    if nx != n {
+        return Err(Error::ShapeMismatch { op: OP, lhs: a.layout().shape().clone(), rhs: x.layout().shape().clone() });
+    }
+    if my != m {
+        return Err(Error::ShapeMismatch { op: OP, lhs: a.layout().shape().clone(), rhs: y.layout().shape().clone() });
+    }
+
+    // A column-major A is the row-major n x m matrix A^T
+    let (rows, cols) = if ta == BlasTranspose::Trans { (n, m) } else { (m, n) };
+    // SAFETY: the operands address the extents of their lowered layouts, and y
+    // was checked not to overlap A or x
+    unsafe {
+        backend.gemv_f32(
+            ta,
+            rows as i32,
+            cols as i32,
+            alpha,
+            a.ptr.as_ptr(),
+            lda,
+            x.ptr.as_ptr(),
+            incx,
+            beta,
+            y.ptr.as_ptr(),
+            incy,
+        );
+    }
+    Ok(())
+}
+
+/// `A += alpha * x * y^T`, with the same operand freedom as `gemv_f32`
+pub fn ger_f32<B: BlasBackend>(
+    backend: &B,
+    x: &TensorView<'_, f32>,
+    y: &TensorView<'_, f32>,
+    a: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+) {
+    if let Err(e) = try_ger_f32(backend, x, y, a, alpha) {
+        panic!("{e}");
+    }
+}
+
+/// `ger_f32` returning shape and layout problems as errors
+pub fn try_ger_f32<B: BlasBackend>(
+    backend: &B,
+    x: &TensorView<'_, f32>,
+    y: &TensorView<'_, f32>,
+    a: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+) -> Result<()> {
+    const OP: &str = "ger_f32";
+    check_disjoint(OP, x, a)?;
+    check_disjoint(OP, y, a)?;
+
+    let (lda, ta) = try_lower_matrix(OP, a.layout())?;
+    let (m, incx) = try_lower_vector(OP, x.layout())?;
+    let (n, incy) = try_lower_vector(OP, y.layout())?;
+    let shape = a.layout().shape();
+    if (shape.flat_at(0), shape.flat_at(1)) != (m, n) {
+        let outer = Layout::row_major([m, n]).shape().clone();
+        return Err(Error::ShapeMismatch { op: OP, lhs: shape.clone(), rhs: outer });
+    }
+
+    let (x, y) = (x.ptr.as_ptr(), y.ptr.as_ptr());
+    // A column-major A is the row-major A^T, updated by y x^T
+    // SAFETY: the operands address the extents of their lowered layouts, and
+    // A was checked not to overlap x or y
+    unsafe {
+        if ta == BlasTranspose::Trans {
+            backend.ger_f32(n as i32, m as i32, alpha, y, incy, x, incx, a.ptr.as_ptr(), lda);
+        } else {
+            backend.ger_f32(m as i32, n as i32, alpha, x, incx, y, incy, a.ptr.as_ptr(), lda);
+        }
+    }
+    Ok(())
+}
+
+/// Row-major `x.len() x y.len()` tensor of the products `x[i] * y[j]`
+pub fn outer<T: Copy + Mul<Output = T>>(x: &TensorView<'_, T>, y: &TensorView<'_, T>) -> Tensor<T> {
+    assert_eq!(x.layout().shape().flat_len(), 1, "outer: x must be rank-1");
+    assert_eq!(y.layout().shape().flat_len(), 1, "outer: y must be rank-1");
+    let (m, n) = (x.layout().size(), y.layout().size());
+    let ys: Vec<T> = (0..n).map(|j| y[[j]]).collect();
+    let data = (0..m).flat_map(|i| {
+        let xi = x[[i]];
+        ys.iter().map(move |&yj| xi * yj)
+    });
+    Tensor::new(data.collect(), Layout::row_major([m, n]))
+}
+
+/* ============================================================
+   Tiled parallel GEMM
+   ============================================================ */
+
+/// Per-tile hook for `gemm_f32_tiled_parallel_with`: runs on each C tile
+/// right after its GEMM, on the thread that computed it.
+pub trait EpilogueFn: Fn(&Tile, &mut TensorViewMut<'_, f32>) + Sync {}
+
+impl<F: Fn(&Tile, &mut TensorViewMut<'_, f32>) + Sync> EpilogueFn for F {}
+
+/// `C = alpha * A * B + beta * C`, split into C tiles of shape `tiler`
+/// that are distributed over the available hardware threads.
+/// With `tiler = None` the tile is chosen by `hw::default_tile_for_gemm`.
+pub fn gemm_f32_tiled_parallel<B: BlasBackend + Sync>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    tiler: Option<&Layout>,
+    alpha: f32,
+    beta: f32,
+) -> KernelStats {
+    let no_epilogue = |_: &Tile, _: &mut TensorViewMut<'_, f32>| {};
+    gemm_f32_tiled_parallel_with(backend, a, b, c, tiler, alpha, beta, &Parallelism::default(), no_epilogue)
+}
+
+/// `gemm_f32_tiled_parallel` on the threads of `par`, handing every
+/// finished C tile to `epilogue` (bias, activation, quantization, ...)
+/// while it is in cache.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm_f32_tiled_parallel_with<B: BlasBackend + Sync, E: EpilogueFn>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    tiler: Option<&Layout>,
+    alpha: f32,
+    beta: f32,
+    par: &Parallelism,
+    epilogue: E,
+) -> KernelStats {
+    let k = match check_gemm_shapes("gemm_f32_tiled_parallel", a.layout(), b.layout(), c.layout()) {
+        Ok((_, _, k)) => k,
+        Err(e) => panic!("{e}"),
+    };
+
+    let tiler = match tiler {
+        Some(t) => t.clone(),
+        None => {
+            let (m, n) = (c.layout().shape().flat_at(0), c.layout().shape().flat_at(1));
+            default_tile_for_gemm(m, n, k, std::mem::size_of::<f32>()).tiler()
+        }
+    };
+
+    log_debug!(
+        "gemm_f32_tiled_parallel {}x{k}: tile {}, {} threads on {}",
+        c.layout().shape(),
+        tiler.shape(),
+        par.num_threads(),
+        std::any::type_name::<B>()
+    );
+
+    let origin = Tuple::int(vec![0; c.layout().shape().flat_len()]);
+    let shape = c.layout().shape().clone();
+    let c_full = unsafe { c.subview_mut(&origin, &shape) };
+
+    // Tiles from `TiledTensorViewMut` are disjoint, so each can go to its own thread
+    let mut tiled_c = TiledTensorViewMut::new(c_full, tiler);
+    let jobs: Vec<(Tile, TensorViewMut<'_, f32>)> = tiled_c.tiles_mut().collect();
+
+    par.run(jobs, |(tile, mut view)| {
+        let (m0, n0) = (tile.start(0), tile.start(1));
+        let (tm, tn) = (tile.len(0), tile.len(1));
+
+        let a_sub = unsafe { a.subview_2d(m0, 0, tm, k) };
+        let b_sub = unsafe { b.subview_2d(0, n0, k, tn) };
+
+        #[cfg(feature = "trace")]
+        let _span = crate::trace::TileSpan::new(
+            "gemm_f32_tiled_parallel",
+            &tile,
+            4 * (tm * k + k * tn + 2 * tm * tn),
+            2 * tm * tn * k,
+        );
+        gemm_f32(backend, &a_sub, &b_sub, &mut view, alpha, beta);
+        epilogue(&tile, &mut view);
+    });
+
+    KernelStats::gemm(shape.flat_at(0), shape.flat_at(1), k, std::mem::size_of::<f32>(), beta)
+}
+
+/* ============================================================
+   Out-of-core GEMM
+   ============================================================ */
+
+/// `C = alpha * A * B + beta * C` for operands too large to hold in memory,
+/// e.g. tensors from `Tensor::from_mmap`. A and B are streamed through in
+/// K-panels sized so both panels fit in `workspace_bytes`.
+pub fn gemm_f32_streaming<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+    workspace_bytes: usize,
+) -> KernelStats {
+    let k = match check_gemm_shapes("gemm_f32_streaming", a.layout(), b.layout(), c.layout()) {
+        Ok((_, _, k)) => k,
+        Err(e) => panic!("{e}"),
+    };
+
+    gemm_f32_streaming_from(
+        backend,
+        k,
+        |k0, panel| tensor_copy(&unsafe { a.subview([0, k0], panel.layout().shape()) }, panel),
+        |k0, panel| tensor_copy(&unsafe { b.subview([k0, 0], panel.layout().shape()) }, panel),
+        c,
+        alpha,
+        beta,
+        workspace_bytes,
+    )
+}
+
+/// Streaming GEMM over operands produced by readers. For the K-panel
+/// starting at `k0`, `read_a` fills the row-major `m x kb` panel
+/// `A[:, k0..k0 + kb]` and `read_b` the `kb x n` panel `B[k0..k0 + kb, :]`.
+/// The first panel applies `beta`; later panels accumulate with beta = 1.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm_f32_streaming_from<B, RA, RB>(
+    backend: &B,
+    k: usize,
+    mut read_a: RA,
+    mut read_b: RB,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+    workspace_bytes: usize,
+) -> KernelStats
+where
+    B: BlasBackend,
+    RA: FnMut(usize, &mut TensorViewMut<'_, f32>),
+    RB: FnMut(usize, &mut TensorViewMut<'_, f32>),
+{
+    let (m, n) = (c.layout().shape().flat_at(0), c.layout().shape().flat_at(1));
+    let stats = KernelStats::gemm(m, n, k, std::mem::size_of::<f32>(), beta);
+    if k == 0 {
+        for (_, x) in c.indexed_iter_mut() {
+            *x = if beta == 0.0 { 0.0 } else { beta * *x };
+        }
+        return stats;
+    }
+
+    let kc = (workspace_bytes / std::mem::size_of::<f32>() / (m + n).max(1)).clamp(1, k);
+    let mut a_ws = Tensor::new(vec![0.0f32; m * kc], Layout::row_major([m, kc]));
+    let mut b_ws = Tensor::new(vec![0.0f32; kc * n], Layout::row_major([kc, n]));
+
+    for k0 in (0..k).step_by(kc) {
+        let kb = kc.min(k - k0);
+        // The last panel may be shallower; it uses the leading part of the workspace
+        let mut a_panel = unsafe { a_ws.as_view_mut().subview_mut([0, 0], [m, kb]) };
+        let mut b_panel = unsafe { b_ws.as_view_mut().subview_mut([0, 0], [kb, n]) };
+        read_a(k0, &mut a_panel);
+        read_b(k0, &mut b_panel);
+
+        let beta = if k0 == 0 { beta } else { 1.0 };
+        gemm_f32(backend, &a_panel.into_view(), &b_panel.into_view(), c, alpha, beta);
+    }
+    stats
 }
 
 /* ============================================================
@@ -127,11 +804,12 @@ mod tests {
     use crate::layout::Layout;
     use crate::shape::Shape;
     use crate::tuple::Tuple;
+    use crate::test_util::matrix;
 
     struct MockBlas;
 
     impl BlasBackend for MockBlas {
-        fn gemm_f32(
+        unsafe fn gemm_f32(
             &self,
             _ta: BlasTranspose,
             _tb: BlasTranspose,
@@ -180,6 +858,391 @@ mod tests {
 
         gemm_f32(&backend, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
     }
+
+    #[test]
+    fn native_matches_reference_with_transposes() {
+        // A = [[1,2],[3,4]], B = [[5,6],[7,8]]
+        let a_rm = [1.0f32, 2.0, 3.0, 4.0];
+        let a_cm = [1.0f32, 3.0, 2.0, 4.0];
+        let b_rm = [5.0f32, 6.0, 7.0, 8.0];
+        let mut c = [1.0f32; 4];
+
+        unsafe {
+            NativeBlas.gemm_f32(
+                BlasTranspose::Trans, BlasTranspose::NoTrans,
+                2, 2, 2, 1.0,
+                a_cm.as_ptr(), 2, b_rm.as_ptr(), 2,
+                1.0, c.as_mut_ptr(), 2,
+            );
+        }
+        assert_eq!(c, [20.0, 23.0, 44.0, 51.0]);
+
+        unsafe {
+            NativeBlas.gemm_f32(
+                BlasTranspose::NoTrans, BlasTranspose::NoTrans,
+                2, 2, 2, 2.0,
+                a_rm.as_ptr(), 2, b_rm.as_ptr(), 2,
+                0.0, c.as_mut_ptr(), 2,
+            );
+        }
+        assert_eq!(c, [38.0, 44.0, 86.0, 100.0]);
+    }
+
+    #[test]
+    fn trsm_left_lower_and_col_major_upper() {
+        // L = [[2,0],[1,4]], L X = B with X = [[1,2],[3,4]] -> B = [[2,4],[13,18]]
+        let l = matrix(2, 2, vec![2.0, 0.0, 1.0, 4.0]);
+        let mut b = matrix(2, 2, vec![2.0, 4.0, 13.0, 18.0]);
+        trsm_f32(&NativeBlas, BlasSide::Left, BlasUplo::Lower, BlasDiag::NonUnit,
+                 &l.as_view(), &mut b.as_view_mut(), 1.0);
+        assert_eq!(b.data(), &[1.0, 2.0, 3.0, 4.0]);
+
+        // X U = B with U = [[2,1],[0,4]] stored column-major
+        let u = Tensor::new(
+            vec![2.0, 0.0, 1.0, 4.0],
+            Layout::with_shape_stride(Shape::new(Tuple::int(vec![2, 2])), Tuple::int(vec![1, 2])),
+        );
+        // [[1,2],[3,4]] * U = [[2,9],[6,19]]
+        let mut b = matrix(2, 2, vec![2.0, 9.0, 6.0, 19.0]);
+        trsm_f32(&NativeBlas, BlasSide::Right, BlasUplo::Upper, BlasDiag::NonUnit,
+                 &u.as_view(), &mut b.as_view_mut(), 1.0);
+        assert_eq!(b.data(), &[1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn syrk_updates_one_triangle() {
+        let a = matrix(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let mut c = matrix(2, 2, vec![-1.0; 4]);
+        syrk_f32(&NativeBlas, BlasUplo::Lower, &a.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        // A A^T = [[14,32],[32,77]]; upper off-diagonal untouched
+        assert_eq!(c.data(), &[14.0, -1.0, 32.0, 77.0]);
+    }
+
+    #[test]
+    fn try_trsm_and_syrk_report_errors() {
+        let l = matrix(2, 2, vec![2.0, 0.0, 1.0, 4.0]);
+        let mut b = matrix(3, 2, vec![1.0; 6]);
+        let err = try_trsm_f32(&NativeBlas, BlasSide::Left, BlasUplo::Lower, BlasDiag::NonUnit,
+                               &l.as_view(), &mut b.as_view_mut(), 1.0);
+        assert!(matches!(err, Err(Error::ShapeMismatch { op: "trsm_f32", .. })));
+
+        let mut b = Tensor::new(vec![1.0; 4], Layout::col_major([2, 2]));
+        let err = try_trsm_f32(&NativeBlas, BlasSide::Left, BlasUplo::Lower, BlasDiag::NonUnit,
+                               &l.as_view(), &mut b.as_view_mut(), 1.0);
+        assert!(matches!(err, Err(Error::Unsupported { op: "trsm_f32", .. })));
+
+        let a = matrix(2, 3, vec![1.0; 6]);
+        let mut c = matrix(3, 3, vec![0.0; 9]);
+        let err = try_syrk_f32(&NativeBlas, BlasUplo::Lower, &a.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        assert!(matches!(err, Err(Error::ShapeMismatch { op: "syrk_f32", .. })));
+    }
+
+    #[test]
+    #[should_panic(expected = "syrk_f32: shape mismatch")]
+    fn syrk_panics_on_shape_mismatch() {
+        let a = matrix(2, 3, vec![1.0; 6]);
+        let mut c = matrix(3, 3, vec![0.0; 9]);
+        syrk_f32(&NativeBlas, BlasUplo::Lower, &a.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+    }
+
+    #[test]
+    fn strided_batch_matches_per_matrix_gemm() {
+        let (batch, m, k, n) = (3, 2, 4, 3);
+        let a = Tensor::new((0..batch * m * k).map(|x| (x % 7) as f32 - 3.0).collect(), Layout::row_major([batch, m, k]));
+        // One column-major B shared by every batch through a stride-0 mode
+        let b = Tensor::new((0..k * n).map(|x| (x % 5) as f32).collect(), Layout::col_major([k, n]));
+        let b_batch = b.as_view().repeat_view(&[batch, 1, 1]).unwrap();
+        // C matrices one padding row apart
+        let mut c = Tensor::new(vec![1.0; batch * (m + 1) * n], Layout::row_major([batch, m + 1, n]));
+
+        let mut cv = c.as_view_mut();
+        let mut inner = cv.try_subview_mut([0, 0, 0], [batch, m, n]).unwrap();
+        gemm_strided_batched_f32(&NativeBlas, &a.as_view(), &b_batch, &mut inner, 2.0, 1.0);
+
+        for i in 0..batch {
+            let ai = Tensor::new(a.data()[i * m * k..(i + 1) * m * k].to_vec(), Layout::row_major([m, k]));
+            let mut expected = matrix(m, n, vec![1.0; m * n]);
+            gemm_f32(&NativeBlas, &ai.as_view(), &b.as_view(), &mut expected.as_view_mut(), 2.0, 1.0);
+            let start = i * (m + 1) * n;
+            assert_eq!(&c.data()[start..start + m * n], expected.data(), "batch {i}");
+            assert_eq!(&c.data()[start + m * n..start + (m + 1) * n], &[1.0; 3]);
+        }
+
+        let mut short = Tensor::new(vec![0.0; 2 * m * n], Layout::row_major([2, m, n]));
+        assert!(matches!(
+            try_gemm_strided_batched_f32(&NativeBlas, &a.as_view(), &b_batch, &mut short.as_view_mut(), 1.0, 0.0),
+            Err(Error::ShapeMismatch { .. })
+        ));
+
+        // Each C matrix spans m * n elements, so a batch stride one short overlaps
+        let overlapping = Layout::row_major([batch, m, n]).with_stride([m * n - 1, n, 1]);
+        let mut data = vec![0.0; overlapping.cosize()];
+        let mut c = unsafe { TensorViewMut::from_raw(data.as_mut_ptr(), overlapping) };
+        assert!(matches!(
+            try_gemm_strided_batched_f32(&NativeBlas, &a.as_view(), &b_batch, &mut c, 1.0, 0.0),
+            Err(Error::Aliasing { .. })
+        ));
+    }
+
+    #[test]
+    fn gemv_and_ger_take_strided_vectors() {
+        use crate::matrix::Matrix;
+
+        // A = [[1,2,3],[4,5,6]], row-major and column-major
+        let a_rm = matrix(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let a_cm = Tensor::new(vec![1.0, 4.0, 2.0, 5.0, 3.0, 6.0], Layout::col_major([2, 3]));
+        // x = [1,0,-1] is column 1 of a 3x2 matrix, stride 2
+        let xs = Matrix::new(3, 2, vec![9.0, 1.0, 9.0, 0.0, 9.0, -1.0]);
+        for a in [&a_rm, &a_cm] {
+            let mut y = Matrix::new(2, 2, vec![1.0, 0.0, 1.0, 0.0]);
+            gemv_f32(&NativeBlas, &a.as_view(), &xs.col(1), &mut y.col_mut(0), 2.0, 1.0);
+            assert_eq!(y.data(), &[-3.0, 0.0, -3.0, 0.0]);
+        }
+
+        // A += x y^T with x = [1,2], y = [1,0,-1] strided
+        let mut a = Tensor::new(vec![0.0; 6], Layout::col_major([2, 3]));
+        let x = matrix(1, 2, vec![1.0, 2.0]);
+        let x = Matrix::from_tensor(x);
+        ger_f32(&NativeBlas, &x.row(0), &xs.col(1), &mut a.as_view_mut(), 1.0);
+        assert_eq!(a.data(), &[1.0, 2.0, 0.0, 0.0, -1.0, -2.0]);
+
+        let mut y = Tensor::new(vec![0.0; 3], Layout::row_major(3));
+        assert!(matches!(try_gemv_f32(&NativeBlas, &a_rm.as_view(), &xs.col(1), &mut y.as_view_mut(), 1.0, 0.0),
+                         Err(Error::ShapeMismatch { .. })));
+    }
+
+    #[test]
+    fn outer_of_strided_vectors() {
+        let m = crate::matrix::Matrix::new(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let o = outer(&m.col(0), &m.row(1));
+        assert_eq!(o.layout().shape().dims.flatten(), vec![2, 3]);
+        assert_eq!(o.data(), &[4, 5, 6, 16, 20, 24]);
+    }
+
+    #[test]
+    fn try_gemm_reports_shape_and_layout_errors() {
+        let a = matrix(2, 3, vec![0.0; 6]);
+        let b = matrix(2, 2, vec![0.0; 4]);
+        let mut c = matrix(2, 2, vec![0.0; 4]);
+        let err = try_gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "gemm_f32: A has 3 columns but B has 2 rows \
+             (A is (2,3):(3,1) row-major, B is (2,2):(2,1) row-major, C is (2,2):(2,1) row-major)"
+        );
+
+        let v = Tensor::new(vec![0.0; 3], Layout::row_major(Shape::new(Tuple::int(vec![3]))));
+        let err = try_gemm_f32(&NativeBlas, &v.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        assert!(matches!(err, Err(Error::GemmOperands { op: "gemm_f32", problem, .. }) if problem == "A has rank 1, expected 2"));
+
+        let b_wide = matrix(3, 4, vec![0.0; 12]);
+        let err = try_gemm_f32(&NativeBlas, &a.as_view(), &b_wide.as_view(), &mut c.as_view_mut(), 1.0, 0.0).unwrap_err();
+        assert!(err.to_string().starts_with("gemm_f32: C is 2x2 but A * B is 2x4 ("));
+
+        // C with no unit-stride mode cannot be handed to a kernel
+        let b = matrix(3, 2, vec![0.0; 6]);
+        let mut buf = matrix(2, 4, vec![0.0; 8]);
+        let mut c_strided = unsafe { buf.as_view_mut().into_offset(0, Layout::row_major([2, 2]).with_stride([4, 2])) };
+        let err = try_gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c_strided, 1.0, 0.0).unwrap_err();
+        assert!(err.to_string().contains("C needs a unit-stride mode"), "{err}");
+        assert!(err.to_string().ends_with("C is (2,2):(4,2) strided)"), "{err}");
+
+        assert!(try_gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0).is_ok());
+    }
+
+    #[test]
+    fn every_combination_of_operand_orders() {
+        let (m, k, n) = (3, 4, 2);
+        let a_data: Vec<f32> = (0..m * k).map(|x| x as f32).collect();
+        let b_data: Vec<f32> = (0..k * n).map(|x| (x as f32) - 3.0).collect();
+        let expected: Vec<f32> = (0..m * n)
+            .map(|x| (0..k).map(|p| a_data[x / n * k + p] * b_data[p * n + x % n]).sum())
+            .collect();
+
+        let order = |col: bool, rows: usize, cols: usize, data: &[f32]| {
+            if col {
+                let t: Vec<f32> = (0..rows * cols).map(|x| data[x % rows * cols + x / rows]).collect();
+                Tensor::new(t, Layout::col_major([rows, cols]))
+            } else {
+                Tensor::new(data.to_vec(), Layout::row_major([rows, cols]))
+            }
+        };
+        for bits in 0..8 {
+            let (a_col, b_col, c_col) = (bits & 1 != 0, bits & 2 != 0, bits & 4 != 0);
+            let a = order(a_col, m, k, &a_data);
+            let b = order(b_col, k, n, &b_data);
+            let mut c = order(c_col, m, n, &[0.0; 6]);
+            gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+            let got: Vec<f32> = (0..m * n).map(|x| c.as_view()[[x / n, x % n]]).collect();
+            assert_eq!(got, expected, "a_col={a_col} b_col={b_col} c_col={c_col}");
+        }
+
+        // Extent-1 modes lower whatever their stride
+        assert_eq!(try_lower_matrix("gemm", &Layout::row_major([1, 2]).with_stride([7, 1])), Ok((2, BlasTranspose::NoTrans)));
+        assert_eq!(try_lower_matrix("gemm", &Layout::row_major([2, 1]).with_stride([1, 1])), Ok((1, BlasTranspose::NoTrans)));
+
+        // BLAS takes 32-bit leading dimensions
+        let wide = Layout::row_major([2, 2]).with_stride([1 << 31, 1]);
+        assert!(matches!(try_lower_matrix("gemm", &wide), Err(Error::Unsupported { .. })));
+    }
+
+    #[test]
+    fn padded_submatrices_use_leading_dimension() {
+        // 3x2 and 2x3 blocks inside 5x6 allocations, C a 3x3 block of a 4x8 one
+        let big_a = matrix(5, 6, (0..30).map(|x| x as f32).collect());
+        let big_b = Tensor::new((0..30).map(|x| (x % 4) as f32).collect(), Layout::col_major([5, 6]));
+        let mut big_c = matrix(4, 8, vec![-1.0; 32]);
+
+        let a = unsafe { big_a.as_view().subview_2d(1, 2, 3, 2) };
+        let b = unsafe { big_b.as_view().subview_2d(2, 1, 2, 3) };
+        assert_eq!(try_lower_matrix("gemm", a.layout()), Ok((6, BlasTranspose::NoTrans)));
+        assert_eq!(try_lower_matrix("gemm", b.layout()), Ok((5, BlasTranspose::Trans)));
+
+        let mut c = unsafe { big_c.as_view_mut().subview_2d_mut(1, 4, 3, 3) };
+        gemm_f32(&NativeBlas, &a, &b, &mut c, 1.0, 0.0);
+
+        let (a, b, c) = (a.to_tensor::<crate::layout::RowMajor>(), b.to_tensor::<crate::layout::RowMajor>(), big_c.as_view());
+        for i in 0..3 {
+            for j in 0..3 {
+                let dot: f32 = (0..2).map(|p| a.as_view()[[i, p]] * b.as_view()[[p, j]]).sum();
+                assert_eq!(c[[1 + i, 4 + j]], dot);
+            }
+        }
+        // Padding around the block is untouched
+        assert_eq!(c[[0, 4]], -1.0);
+        assert_eq!(c[[1, 3]], -1.0);
+        assert_eq!(c[[1, 7]], -1.0);
+    }
+
+    #[test]
+    fn gemm_rejects_aliased_output() {
+        let a = matrix(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let mut c = matrix(2, 2, vec![1.0, 0.0, 0.0, 1.0]);
+        // A second, read-only handle on C's memory
+        let c_in = unsafe { TensorView::from_raw(c.data().as_ptr(), c.layout().clone()) };
+
+        if cfg!(debug_assertions) {
+            let err = try_gemm_f32(&NativeBlas, &a.as_view(), &c_in, &mut c.as_view_mut(), 1.0, 0.0);
+            assert_eq!(err, Err(Error::Aliasing { op: "gemm_f32" }));
+        }
+
+        // Disjoint halves of one buffer are fine
+        let mut buf = matrix(2, 4, vec![1.0, 0.0, 5.0, 5.0, 0.0, 1.0, 5.0, 5.0]);
+        let mut whole = buf.as_view_mut();
+        let (left, mut right) = unsafe { (whole.subview_mut([0, 0], [2, 2]).into_view(), whole.subview_mut([0, 2], [2, 2])) };
+        gemm_f32(&NativeBlas, &left, &a.as_view(), &mut right, 1.0, 0.0);
+        assert_eq!(buf.data(), &[1.0, 0.0, 1.0, 2.0, 0.0, 1.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn streaming_matches_in_memory_gemm() {
+        let (m, k, n) = (5, 23, 4);
+        let a = matrix(m, k, (0..m * k).map(|x| (x % 7) as f32).collect());
+        let b = matrix(k, n, (0..k * n).map(|x| (x % 5) as f32 - 2.0).collect());
+        let mut expected = matrix(m, n, vec![1.0; m * n]);
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 0.5, 2.0);
+
+        // 9 * (5 + 4) floats: panels of depth 9, 9 and 5
+        let mut c = matrix(m, n, vec![1.0; m * n]);
+        gemm_f32_streaming(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 0.5, 2.0, 9 * 9 * 4);
+        assert_eq!(c.data(), expected.data());
+
+        // Reader-backed operands, one K column at a time
+        let mut c = matrix(m, n, vec![1.0; m * n]);
+        let mut reads = 0;
+        gemm_f32_streaming_from(
+            &NativeBlas,
+            k,
+            |k0, panel| {
+                reads += 1;
+                for (crd, x) in panel.indexed_iter_mut() {
+                    *x = a.data()[crd[0] * k + k0 + crd[1]];
+                }
+            },
+            |k0, panel| {
+                for (crd, x) in panel.indexed_iter_mut() {
+                    *x = b.data()[(k0 + crd[0]) * n + crd[1]];
+                }
+            },
+            &mut c.as_view_mut(),
+            0.5,
+            2.0,
+            0,
+        );
+        assert_eq!(reads, k);
+        assert_eq!(c.data(), expected.data());
+    }
+
+    #[test]
+    #[should_panic(expected = "gemm_f32_streaming: C")]
+    fn streaming_rejects_c_larger_than_the_product() {
+        let a = matrix(2, 3, vec![0.0; 6]);
+        let b = matrix(3, 2, vec![0.0; 6]);
+        let mut c = matrix(4, 2, vec![0.0; 8]);
+        gemm_f32_streaming(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0, 64);
+    }
+
+    #[test]
+    fn epilogue_sees_every_tile_once() {
+        let (m, k, n) = (9, 4, 7);
+        let a = matrix(m, k, (0..m * k).map(|x| x as f32 - 10.0).collect());
+        let b = matrix(k, n, (0..k * n).map(|x| (x % 3) as f32).collect());
+        let mut expected = matrix(m, n, vec![0.0; m * n]);
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 1.0, 0.0);
+
+        let mut c = matrix(m, n, vec![0.0; m * n]);
+        let seen = std::sync::Mutex::new(Vec::new());
+        let tiler = Layout::row_major([4, 3]);
+        gemm_f32_tiled_parallel_with(
+            &NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Some(&tiler), 1.0, 0.0,
+            &Parallelism::default().with_threads(3),
+            |tile: &Tile, view: &mut TensorViewMut<'_, f32>| {
+                seen.lock().unwrap().push(tile.index());
+                for (_, x) in view.indexed_iter_mut() {
+                    *x = x.max(0.0);
+                }
+            },
+        );
+
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, (0..9).collect::<Vec<_>>());
+        for (got, want) in c.data().iter().zip(expected.data()) {
+            assert_eq!(*got, want.max(0.0));
+        }
+    }
+
+    #[test]
+    fn tiled_parallel_matches_untiled() {
+        let (m, k, n) = (13, 7, 10);
+        let a = Tensor::new(
+            (0..m * k).map(|x| (x % 5) as f32 - 2.0).collect(),
+            Layout::row_major(Shape::new(Tuple::int(vec![m, k]))),
+        );
+        let b = Tensor::new(
+            (0..k * n).map(|x| (x % 3) as f32).collect(),
+            Layout::row_major(Shape::new(Tuple::int(vec![k, n]))),
+        );
+        let mut c_ref = Tensor::new(vec![0.0; m * n], Layout::row_major(Shape::new(Tuple::int(vec![m, n]))));
+        let mut c_tiled = Tensor::new(vec![0.0; m * n], Layout::row_major(Shape::new(Tuple::int(vec![m, n]))));
+
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c_ref.as_view_mut(), 1.0, 0.0);
+
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![4, 3])));
+        gemm_f32_tiled_parallel(
+            &NativeBlas, &a.as_view(), &b.as_view(), &mut c_tiled.as_view_mut(), Some(&tiler), 1.0, 0.0,
+        );
+
+        let mut c_auto = Tensor::new(vec![0.0; m * n], Layout::row_major(Shape::new(Tuple::int(vec![m, n]))));
+        gemm_f32_tiled_parallel(
+            &NativeBlas, &a.as_view(), &b.as_view(), &mut c_auto.as_view_mut(), None, 1.0, 0.0,
+        );
+
+        unsafe {
+            assert_close_f32(m * n, c_tiled.data().as_ptr(), c_ref.data().as_ptr(), 1e-5);
+            assert_close_f32(m * n, c_auto.data().as_ptr(), c_ref.data().as_ptr(), 1e-5);
+        }
+    }
 }
 
 
@@ -227,23 +1290,24 @@ mod randomized_gemm {
     use crate::layout::Layout;
     use crate::shape::Shape;
     use crate::tuple::Tuple;
+    use crate::random::seeded;
     use rand::Rng;
 
     #[test]
     fn gemm_randomized() {
-        let mut rng = rand::thread_rng();
+        let mut rng = seeded(0x5eed);
 
         // Random sizes
-        let m = rng.gen_range(2..10);
-        let k = rng.gen_range(2..10);
-        let n = rng.gen_range(2..10);
+        let m = rng.random_range(2..10);
+        let k = rng.random_range(2..10);
+        let n = rng.random_range(2..10);
 
         let a_shape = Shape::new(Tuple::int(vec![m, k]));
         let b_shape = Shape::new(Tuple::int(vec![k, n]));
         let c_shape = Shape::new(Tuple::int(vec![m, n]));
 
-        let a_data: Vec<f32> = (0..(m*k)).map(|_| rng.gen_range(-10.0..10.0)).collect();
-        let b_data: Vec<f32> = (0..(k*n)).map(|_| rng.gen_range(-10.0..10.0)).collect();
+        let a_data: Vec<f32> = (0..(m*k)).map(|_| rng.random_range(-10.0..10.0)).collect();
+        let b_data: Vec<f32> = (0..(k*n)).map(|_| rng.random_range(-10.0..10.0)).collect();
         let mut c_data: Vec<f32> = vec![0.0; m*n];
 
         let a = Tensor::new(a_data.clone(), Layout::row_major(a_shape));
@@ -253,22 +1317,11 @@ mod randomized_gemm {
         let backend = GenericBlas;
         gemm_f32(&backend, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
 
-        // Reference computation in Rust
-        let mut expected = vec![0.0; (m*n) as usize];
-        for i in 0..m {
-            for j in 0..n {
-                let mut sum = 0.0;
-                for p in 0..k {
-                    sum += a_data[i*k + p] * b_data[p*n + j];
-                }
-                expected[i*n + j] = sum;
-            }
-        }
+        let mut expected = Tensor::new(vec![0.0; m*n], Layout::row_major([m, n]));
+        crate::reference::gemm(&a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 1.0, 0.0);
 
         unsafe {
-            for i in 0..m*n {
-                assert_close_f32(m*n, c.as_view().ptr.as_ptr(), expected.as_ptr(), 1e-3);
-            } 
+            assert_close_f32(m*n, c.data().as_ptr(), expected.data().as_ptr(), 1e-3);
         }
     }
 }
@@ -0,0 +1,292 @@
+// src/graph.rs
+//
+// A small task graph of tensor ops. Ops name their operands by `TensorId`
+// and dependencies are inferred from those operands as nodes are added: a
+// node waits for the last writer of everything it touches, and a writer
+// also waits for the readers since. `execute` runs the graph level by
+// level, so independent ops (the Q, K and V projections of an attention
+// layer, say) share the thread pool instead of running one after another.
+
+use crate::bench_utils::KernelStats;
+use crate::blas::BlasBackend;
+use crate::copy::tensor_copy;
+use crate::error::{Error, Result};
+use crate::gemm::{check_gemm_shapes, gemm_f32};
+use crate::parallel::Parallelism;
+use crate::shape::for_each_flat_coord;
+use crate::shape_infer::reduce_shape;
+use crate::tensor::Tensor;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TensorId(usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId(usize);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op {
+    /// `c = alpha * a * b + beta * c`
+    Gemm { a: TensorId, b: TensorId, c: TensorId, alpha: f32, beta: f32 },
+    /// `dst = src`
+    Copy { src: TensorId, dst: TensorId },
+    /// `dst` = `src` summed over `axes`, which `dst` either drops or keeps
+    /// with extent 1
+    Sum { src: TensorId, dst: TensorId, axes: Vec<usize> },
+}
+
+impl Op {
+    fn inputs(&self) -> Vec<TensorId> {
+        match *self {
+            Op::Gemm { a, b, .. } => vec![a, b],
+            Op::Copy { src, .. } | Op::Sum { src, .. } => vec![src],
+        }
+    }
+
+    fn output(&self) -> TensorId {
+        match *self {
+            Op::Gemm { c, .. } => c,
+            Op::Copy { dst, .. } | Op::Sum { dst, .. } => dst,
+        }
+    }
+}
+
+struct Node {
+    op: Op,
+    deps: Vec<NodeId>,
+    stats: KernelStats,
+}
+
+/// Who touched a tensor last, for dependency inference
+#[derive(Default)]
+struct Access {
+    writer: Option<NodeId>,
+    readers: Vec<NodeId>,
+}
+
+/// Tensors plus the ops between them; see the module comment
+#[derive(Default)]
+pub struct TaskGraph {
+    tensors: Vec<Tensor<f32>>,
+    access: Vec<Access>,
+    nodes: Vec<Node>,
+}
+
+impl TaskGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_tensor(&mut self, tensor: Tensor<f32>) -> TensorId {
+        self.tensors.push(tensor);
+        self.access.push(Access::default());
+        TensorId(self.tensors.len() - 1)
+    }
+
+    pub fn tensor(&self, id: TensorId) -> &Tensor<f32> {
+        &self.tensors[id.0]
+    }
+
+    /// For refilling inputs between runs; a tensor replaced by one of
+    /// another shape makes `execute` panic
+    pub fn tensor_mut(&mut self, id: TensorId) -> &mut Tensor<f32> {
+        &mut self.tensors[id.0]
+    }
+
+    pub fn into_tensors(self) -> Vec<Tensor<f32>> {
+        self.tensors
+    }
+
+    pub fn gemm(&mut self, a: TensorId, b: TensorId, c: TensorId, alpha: f32, beta: f32) -> Result<NodeId> {
+        self.add(Op::Gemm { a, b, c, alpha, beta })
+    }
+
+    pub fn copy(&mut self, src: TensorId, dst: TensorId) -> Result<NodeId> {
+        self.add(Op::Copy { src, dst })
+    }
+
+    pub fn sum(&mut self, src: TensorId, dst: TensorId, axes: &[usize]) -> Result<NodeId> {
+        self.add(Op::Sum { src, dst, axes: axes.to_vec() })
+    }
+
+    /// Add `op` after every node it conflicts with. Shapes are checked here,
+    /// so `execute` cannot fail on them.
+    pub fn add(&mut self, op: Op) -> Result<NodeId> {
+        let stats = self.check(&op)?;
+        let id = NodeId(self.nodes.len());
+        let out = op.output().0;
+
+        let mut deps: Vec<NodeId> = op.inputs().iter().filter_map(|t| self.access[t.0].writer).collect();
+        deps.extend(self.access[out].writer);
+        deps.extend_from_slice(&self.access[out].readers);
+        deps.sort_unstable();
+        deps.dedup();
+
+        for t in op.inputs() {
+            self.access[t.0].readers.push(id);
+        }
+        self.access[out] = Access { writer: Some(id), readers: Vec::new() };
+        self.nodes.push(Node { op, deps, stats });
+        Ok(id)
+    }
+
+    pub fn op(&self, node: NodeId) -> &Op {
+        &self.nodes[node.0].op
+    }
+
+    /// Nodes that must finish before `node` starts
+    pub fn dependencies(&self, node: NodeId) -> &[NodeId] {
+        &self.nodes[node.0].deps
+    }
+
+    /// Nodes grouped by the longest dependency chain leading to them; the
+    /// nodes of one level are independent of each other
+    pub fn levels(&self) -> Vec<Vec<NodeId>> {
+        let mut depth = vec![0; self.nodes.len()];
+        let mut levels: Vec<Vec<NodeId>> = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            // Dependencies always point at earlier nodes
+            depth[i] = node.deps.iter().map(|d| depth[d.0] + 1).max().unwrap_or(0);
+            if depth[i] == levels.len() {
+                levels.push(Vec::new());
+            }
+            levels[depth[i]].push(NodeId(i));
+        }
+        levels
+    }
+
+    /// Run every node once, the nodes of each level spread over
+    /// `parallelism`. Work counts are summed over all nodes.
+    pub fn execute<B: BlasBackend + Sync>(&mut self, backend: &B, parallelism: &Parallelism) -> KernelStats {
+        let mut stats = KernelStats::default();
+        for level in self.levels() {
+            let mut jobs = Vec::with_capacity(level.len());
+            let mut outputs: Vec<Option<&mut Tensor<f32>>> = self.tensors.iter_mut().map(Some).collect();
+            let written: Vec<usize> = level.iter().map(|n| self.nodes[n.0].op.output().0).collect();
+            for (&node, &out) in level.iter().zip(&written) {
+                jobs.push((node, outputs[out].take().expect("TaskGraph: two nodes of one level write one tensor")));
+            }
+            // Tensors nobody in this level writes are free to share
+            let inputs: Vec<Option<&Tensor<f32>>> = outputs.into_iter().map(|t| t.map(|t| &*t)).collect();
+
+            let nodes = &self.nodes;
+            parallelism.run(jobs, |(node, out)| {
+                let tensor = |id: TensorId| inputs[id.0].expect("TaskGraph: a node reads a tensor written in its own level");
+                run(backend, &nodes[node.0].op, tensor, out);
+            });
+            stats += level.iter().map(|n| self.nodes[n.0].stats).fold(KernelStats::default(), |a, b| a + b);
+        }
+        stats
+    }
+
+    fn check(&self, op: &Op) -> Result<KernelStats> {
+        const OP: &str = "TaskGraph::add";
+        if op.inputs().contains(&op.output()) {
+            return Err(Error::Aliasing { op: OP });
+        }
+        let layout = |id: TensorId| self.tensors[id.0].layout();
+        let elem = std::mem::size_of::<f32>();
+        match op {
+            Op::Gemm { a, b, c, beta, .. } => {
+                let (m, n, k) = check_gemm_shapes(OP, layout(*a), layout(*b), layout(*c))?;
+                Ok(KernelStats::gemm(m, n, k, elem, *beta))
+            }
+            Op::Copy { src, dst } => {
+                let (src, dst) = (layout(*src), layout(*dst));
+                if src.flat_shape() != dst.flat_shape() {
+                    return Err(Error::ShapeMismatch { op: OP, lhs: src.shape().clone(), rhs: dst.shape().clone() });
+                }
+                Ok(KernelStats::copy(src.size(), elem))
+            }
+            Op::Sum { src, dst, axes } => {
+                let (src, dst) = (layout(*src), layout(*dst));
+                let keep_dims = dst.flat_shape().len() == src.flat_shape().len();
+                let expected = reduce_shape(src.shape(), axes, keep_dims)?;
+                if expected.dims.flatten() != dst.flat_shape() {
+                    return Err(Error::ShapeMismatch { op: OP, lhs: expected, rhs: dst.shape().clone() });
+                }
+                let (n, r) = (src.size(), dst.size());
+                Ok(KernelStats { elements: n + r, flops: n, bytes: (n + r) * elem })
+            }
+        }
+    }
+}
+
+fn run<'t, B: BlasBackend>(backend: &B, op: &Op, tensor: impl Fn(TensorId) -> &'t Tensor<f32>, out: &mut Tensor<f32>) {
+    match op {
+        Op::Gemm { a, b, alpha, beta, .. } => {
+            gemm_f32(backend, &tensor(*a).as_view(), &tensor(*b).as_view(), &mut out.as_view_mut(), *alpha, *beta)
+        }
+        Op::Copy { src, .. } => tensor_copy(&tensor(*src).as_view(), &mut out.as_view_mut()),
+        Op::Sum { src, axes, .. } => {
+            let src = tensor(*src).as_view();
+            let keep_dims = out.layout().flat_shape().len() == src.layout().flat_shape().len();
+            let expected = reduce_shape(src.layout().shape(), axes, keep_dims).map(|s| s.dims.flatten());
+            assert!(expected.is_ok_and(|s| s == out.layout().flat_shape()), "TaskGraph: sum operands changed shape");
+            out.data_mut().fill(0.0);
+            let mut dst = out.as_view_mut();
+
+            let mut crd = Vec::with_capacity(src.layout().flat_shape().len());
+            for_each_flat_coord(src.layout().shape(), |s| {
+                crd.clear();
+                for (i, &c) in s.iter().enumerate() {
+                    match (axes.contains(&i), keep_dims) {
+                        (false, _) => crd.push(c),
+                        (true, true) => crd.push(0),
+                        (true, false) => {}
+                    }
+                }
+                // Both coordinates lie inside the shapes checked above
+                unsafe { *dst.get_flat_mut(&crd) += *src.get_flat(s) };
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::layout::Layout;
+    use crate::test_util::seeded_matrix;
+
+    #[test]
+    fn independent_gemms_share_a_level() {
+        let mut g = TaskGraph::new();
+        let x = g.add_tensor(seeded_matrix(4, 6, 0));
+        let (wq, wk) = (g.add_tensor(seeded_matrix(6, 3, 1)), g.add_tensor(seeded_matrix(6, 3, 2)));
+        let (q, k) = (g.add_tensor(seeded_matrix(4, 3, 0)), g.add_tensor(seeded_matrix(4, 3, 0)));
+        let q_copy = g.add_tensor(seeded_matrix(4, 3, 0));
+        let rows = g.add_tensor(Tensor::new(vec![0.0; 4], Layout::row_major([4])));
+
+        let gq = g.gemm(x, wq, q, 1.0, 0.0).unwrap();
+        let gk = g.gemm(x, wk, k, 1.0, 0.0).unwrap();
+        let cq = g.copy(q, q_copy).unwrap();
+        let sk = g.sum(k, rows, &[1]).unwrap();
+        // Overwrites Q, so it must wait for the copy reading it
+        let gq2 = g.gemm(x, wk, q, 2.0, 0.0).unwrap();
+
+        assert_eq!(g.dependencies(gq2), &[gq, cq]);
+        assert_eq!(g.levels(), vec![vec![gq, gk], vec![cq, sk], vec![gq2]]);
+
+        let stats = g.execute(&NativeBlas, &Parallelism::default().with_threads(2));
+        assert_eq!(stats.flops, 3 * 2 * 4 * 3 * 6 + 12);
+
+        let mut expected = seeded_matrix(4, 3, 0);
+        gemm_f32(&NativeBlas, &g.tensor(x).as_view(), &g.tensor(wq).as_view(), &mut expected.as_view_mut(), 1.0, 0.0);
+        assert_eq!(g.tensor(q_copy).data(), expected.data());
+        gemm_f32(&NativeBlas, &g.tensor(x).as_view(), &g.tensor(wk).as_view(), &mut expected.as_view_mut(), 1.0, 0.0);
+        let sums: Vec<f32> = expected.data().chunks(3).map(|r| r.iter().sum()).collect();
+        assert_eq!(g.tensor(rows).data(), &sums[..]);
+        assert_eq!(g.tensor(q).data(), expected.data().iter().map(|v| 2.0 * v).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn rejects_bad_operands() {
+        let mut g = TaskGraph::new();
+        let (a, b) = (g.add_tensor(seeded_matrix(2, 3, 0)), g.add_tensor(seeded_matrix(2, 2, 0)));
+        assert!(matches!(g.copy(a, b), Err(Error::ShapeMismatch { .. })));
+        assert!(matches!(g.gemm(a, a, a, 1.0, 0.0), Err(Error::Aliasing { .. })));
+        assert!(matches!(g.sum(a, b, &[1]), Err(Error::ShapeMismatch { .. })));
+        assert!(g.levels().is_empty());
+    }
+}
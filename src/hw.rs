@@ -0,0 +1,261 @@
+use std::sync::OnceLock;
+
+use crate::tune::TileConfig;
+
+/// Data / unified cache capacities in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheInfo {
+    pub l1d: usize,
+    pub l2: usize,
+    pub l3: usize,
+}
+
+/// Conservative values used when nothing can be queried
+const FALLBACK_CACHES: CacheInfo = CacheInfo {
+    l1d: 32 * 1024,
+    l2: 256 * 1024,
+    l3: 8 * 1024 * 1024,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuTopology {
+    pub caches: CacheInfo,
+    /// Logical cores available to this process
+    pub cores: usize,
+}
+
+static TOPOLOGY: OnceLock<CpuTopology> = OnceLock::new();
+
+/// Detected CPU topology (queried once, then cached)
+pub fn topology() -> &'static CpuTopology {
+    TOPOLOGY.get_or_init(|| CpuTopology {
+        caches: detect_caches(),
+        cores: std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1),
+    })
+}
+
+fn detect_caches() -> CacheInfo {
+    let sysfs = caches_from_sysfs();
+    let cpuid = caches_from_cpuid();
+
+    let pick = |f: fn(&CacheInfo) -> usize, fallback: usize| {
+        sysfs
+            .as_ref()
+            .map(f)
+            .filter(|&v| v > 0)
+            .or_else(|| cpuid.as_ref().map(f).filter(|&v| v > 0))
+            .unwrap_or(fallback)
+    };
+
+    CacheInfo {
+        l1d: pick(|c| c.l1d, FALLBACK_CACHES.l1d),
+        l2: pick(|c| c.l2, FALLBACK_CACHES.l2),
+        l3: pick(|c| c.l3, FALLBACK_CACHES.l3),
+    }
+}
+
+/* ---------- sysfs (Linux) ---------- */
+
+/// Parse sysfs cache sizes such as "48K", "2048K" or "32M"
+fn parse_cache_size(s: &str) -> Option<usize> {
+    let s = s.trim();
+    let (num, mult) = match s.chars().last()? {
+        'K' | 'k' => (&s[..s.len() - 1], 1024),
+        'M' | 'm' => (&s[..s.len() - 1], 1024 * 1024),
+        'G' | 'g' => (&s[..s.len() - 1], 1024 * 1024 * 1024),
+        _ => (s, 1),
+    };
+    num.parse::<usize>().ok().map(|v| v * mult)
+}
+
+fn caches_from_sysfs() -> Option<CacheInfo> {
+    let dir = std::fs::read_dir("/sys/devices/system/cpu/cpu0/cache").ok()?;
+    let mut info = CacheInfo { l1d: 0, l2: 0, l3: 0 };
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+
+        let (Some(level), Some(kind), Some(size)) = (read("level"), read("type"), read("size")) else {
+            continue;
+        };
+        if kind.trim() == "Instruction" {
+            continue;
+        }
+        let Some(size) = parse_cache_size(&size) else {
+            continue;
+        };
+
+        match level.trim() {
+            "1" => info.l1d = size,
+            "2" => info.l2 = size,
+            "3" => info.l3 = size,
+            _ => {}
+        }
+    }
+
+    Some(info)
+}
+
+/* ---------- cpuid (x86_64) ---------- */
+
+#[cfg(target_arch = "x86_64")]
+fn caches_from_cpuid() -> Option<CacheInfo> {
+    use std::arch::x86_64::{__cpuid, __cpuid_count};
+
+    // Leaf 4 (deterministic cache parameters) is Intel's; AMD mirrors it at 0x8000_001D.
+    let leaf = if __cpuid(0).eax >= 4 {
+        4
+    } else if __cpuid(0x8000_0000).eax >= 0x8000_001D {
+        0x8000_001D
+    } else {
+        return None;
+    };
+
+    let mut info = CacheInfo { l1d: 0, l2: 0, l3: 0 };
+    for sub in 0..16 {
+        let r = __cpuid_count(leaf, sub);
+        let kind = r.eax & 0x1f;
+        if kind == 0 {
+            break;
+        }
+        if kind == 2 {
+            continue; // instruction cache
+        }
+
+        let ways = ((r.ebx >> 22) & 0x3ff) as usize + 1;
+        let partitions = ((r.ebx >> 12) & 0x3ff) as usize + 1;
+        let line = (r.ebx & 0xfff) as usize + 1;
+        let sets = r.ecx as usize + 1;
+        let size = ways * partitions * line * sets;
+
+        match (r.eax >> 5) & 0x7 {
+            1 => info.l1d = size,
+            2 => info.l2 = size,
+            3 => info.l3 = size,
+            _ => {}
+        }
+    }
+
+    Some(info)
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn caches_from_cpuid() -> Option<CacheInfo> {
+    None
+}
+
+/* ---------- NUMA nodes (Linux) ---------- */
+
+/// A NUMA node and the logical CPUs attached to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumaNode {
+    pub id: usize,
+    pub cpus: Vec<usize>,
+}
+
+static NUMA_NODES: OnceLock<Vec<NumaNode>> = OnceLock::new();
+
+/// NUMA nodes that have CPUs (queried once, then cached). Without sysfs
+/// node information the whole machine is reported as a single node.
+pub fn numa_nodes() -> &'static [NumaNode] {
+    NUMA_NODES.get_or_init(|| {
+        nodes_from_sysfs()
+            .filter(|nodes| !nodes.is_empty())
+            .unwrap_or_else(|| vec![NumaNode { id: 0, cpus: (0..topology().cores).collect() }])
+    })
+}
+
+/// Parse sysfs CPU lists such as "0-3,8-11"
+fn parse_cpu_list(s: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in s.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((lo, hi)) => cpus.extend(lo.parse::<usize>().ok()?..=hi.parse::<usize>().ok()?),
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+fn nodes_from_sysfs() -> Option<Vec<NumaNode>> {
+    let dir = std::fs::read_dir("/sys/devices/system/node").ok()?;
+    let mut nodes = Vec::new();
+
+    for entry in dir.flatten() {
+        let name = entry.file_name();
+        let Some(id) = name.to_str().and_then(|n| n.strip_prefix("node")).and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        let Some(cpus) = std::fs::read_to_string(entry.path().join("cpulist")).ok().and_then(|s| parse_cpu_list(&s))
+        else {
+            continue;
+        };
+        if !cpus.is_empty() {
+            nodes.push(NumaNode { id, cpus });
+        }
+    }
+
+    nodes.sort_by_key(|n| n.id);
+    Some(nodes)
+}
+
+/* ---------- tile heuristics ---------- */
+
+/// Default C tile for an `m x k` by `k x n` GEMM: the largest power-of-two
+/// square tile whose A/B panels and C tile fit in half of L2, clamped to the
+/// matrix. `RUTILE_TILE_M` / `RUTILE_TILE_N` (see `config`) override either extent.
+pub fn default_tile_for_gemm(m: usize, n: usize, k: usize, dtype_size: usize) -> TileConfig {
+    let budget = topology().caches.l2 / 2;
+
+    let mut t = 16;
+    while t < 1024 && TileConfig::new(2 * t, 2 * t).working_set_bytes(k, dtype_size) <= budget {
+        t *= 2;
+    }
+
+    let config = crate::config::get();
+    let (tm, tn) = (config.tile_m.unwrap_or(t), config.tile_n.unwrap_or(t));
+    TileConfig::new(tm.min(m.max(1)), tn.min(n.max(1)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sizes() {
+        assert_eq!(parse_cache_size("48K\n"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size("abc"), None);
+    }
+
+    #[test]
+    fn parse_cpu_lists() {
+        assert_eq!(parse_cpu_list("0-3,8-9\n"), Some(vec![0, 1, 2, 3, 8, 9]));
+        assert_eq!(parse_cpu_list("5"), Some(vec![5]));
+        assert_eq!(parse_cpu_list(""), Some(vec![]));
+        assert_eq!(parse_cpu_list("1-x"), None);
+
+        let nodes = numa_nodes();
+        assert!(!nodes.is_empty() && nodes.iter().all(|n| !n.cpus.is_empty()));
+    }
+
+    #[test]
+    fn topology_is_plausible() {
+        let t = topology();
+        assert!(t.cores >= 1);
+        assert!(t.caches.l1d > 0 && t.caches.l2 > 0 && t.caches.l3 > 0);
+    }
+
+    #[test]
+    fn default_tile_fits_budget_and_matrix() {
+        let cfg = default_tile_for_gemm(1000, 20, 256, 4);
+        assert!(cfg.tile_m <= 1000 && cfg.tile_n <= 20);
+        assert!(cfg.tile_m >= 16 || cfg.tile_m == 1000);
+
+        let square = default_tile_for_gemm(4096, 4096, 64, 4);
+        assert_eq!(square.tile_m, square.tile_n);
+        assert!(square.working_set_bytes(64, 4) <= topology().caches.l2 / 2 || square.tile_m == 16);
+    }
+}
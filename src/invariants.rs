@@ -0,0 +1,168 @@
+// src/invariants.rs
+//
+// Properties the layout algebra must satisfy for every input, written as
+// checks that return `Error::Invariant` instead of asserting so a fuzzer or
+// a property test can tell a broken invariant from a rejected input (any
+// other error). Each check visits every coordinate, so keep layouts small.
+// The cargo-fuzz target in `fuzz/` drives them with decoded random layouts.
+
+use crate::error::{Error, Result};
+use crate::layout::Layout;
+use crate::layout_algebra::try_flat_divide;
+use crate::shape::{for_each_flat_coord, Shape};
+use crate::tuple::Tuple;
+
+fn violated(op: &'static str, layout: &Layout, detail: String) -> Error {
+    Error::Invariant { op, detail: format!("{} for {}:{}", detail, layout.shape(), layout.stride()) }
+}
+
+/// Every offset lies within `cosize`, and the offsets span exactly `cosize`
+/// elements (none when the layout is empty)
+pub fn check_cosize_bounds(layout: &Layout) -> Result<()> {
+    const OP: &str = "check_cosize_bounds";
+    let (mut lo, mut hi) = (isize::MAX, isize::MIN);
+    for_each_flat_coord(layout.shape(), |crd| {
+        let off = layout.crd2offset_flat(crd);
+        (lo, hi) = (lo.min(off), hi.max(off));
+    });
+    let span = if layout.size() == 0 { 0 } else { (hi - lo) as usize + 1 };
+    if span != layout.cosize() {
+        return Err(violated(OP, layout, format!("offsets span {span} elements but cosize is {}", layout.cosize())));
+    }
+    if !layout.has_reversed_modes() && layout.size() > 0 && lo != 0 {
+        return Err(violated(OP, layout, format!("smallest offset is {lo}, not 0")));
+    }
+    Ok(())
+}
+
+/// For a compact layout, `idx2crd` inverts `crd2idx` in both directions and
+/// agrees with `idx2crd_flat`. Other layouts are rejected with
+/// `Error::NotContiguous`, since an index there can have several
+/// coordinates or none.
+pub fn check_index_roundtrip(layout: &Layout) -> Result<()> {
+    const OP: &str = "check_index_roundtrip";
+    if !layout.is_contiguous() || layout.has_reversed_modes() {
+        return Err(Error::NotContiguous { op: OP });
+    }
+    let mut flat = vec![0; layout.flat_shape().len()];
+    for idx in 0..layout.size() {
+        let crd = layout.idx2crd(idx);
+        layout.idx2crd_flat(idx, &mut flat);
+        if crd.flatten() != flat {
+            return Err(violated(OP, layout, format!("idx2crd({idx}) is {crd} but idx2crd_flat gives {flat:?}")));
+        }
+        let back = layout.crd2idx(&crd);
+        if back != idx {
+            return Err(violated(OP, layout, format!("crd2idx(idx2crd({idx})) is {back}")));
+        }
+    }
+    let mut result = Ok(());
+    for_each_flat_coord(layout.shape(), |crd| {
+        let idx = layout.crd2idx_flat(crd);
+        layout.idx2crd_flat(idx, &mut flat);
+        if result.is_ok() && flat != crd {
+            result = Err(violated(OP, layout, format!("idx2crd(crd2idx({crd:?})) is {flat:?}")));
+        }
+    });
+    result
+}
+
+/// `flat_divide(layout, tiler)` followed by recomposing each tile and rest
+/// coordinate into `rest * tile_extent + tile` indexes the same element as
+/// `layout`, and covers all of `layout` when every tile extent divides the
+/// matching layout extent. Divides `try_flat_divide` refuses are returned as
+/// its error.
+pub fn check_divide_recompose(layout: &Layout, tiler: &Layout) -> Result<()> {
+    const OP: &str = "check_divide_recompose";
+    let divided = try_flat_divide(layout, tiler)?;
+    let (extents, tile) = (layout.flat_shape(), tiler.flat_shape());
+    let rank = extents.len();
+    if divided.flat_shape().len() != 2 * rank {
+        return Err(violated(OP, layout, format!("flat_divide has rank {}, expected {}", divided.flat_shape().len(), 2 * rank)));
+    }
+
+    let mut result = Ok(());
+    let mut orig = vec![0; rank];
+    for_each_flat_coord(divided.shape(), |crd| {
+        let (t, r) = crd.split_at(rank);
+        for j in 0..rank {
+            orig[j] = r[j] * tile[j] + t[j];
+        }
+        if result.is_err() {
+            return;
+        }
+        if orig.iter().zip(extents).any(|(c, e)| c >= e) {
+            result = Err(violated(OP, layout, format!("divided coordinate {crd:?} recomposes to {orig:?}, outside the layout")));
+        } else if divided.crd2offset_flat(crd) != layout.crd2offset_flat(&orig) {
+            result = Err(violated(OP, layout, format!("divided coordinate {crd:?} and {orig:?} index different elements")));
+        }
+    });
+    result?;
+
+    if extents.iter().zip(tile).all(|(e, t)| e % t == 0) && divided.size() != layout.size() {
+        let tiler_shape = Shape::new(Tuple::int(tile.to_vec()));
+        return Err(violated(OP, layout, format!("tiles of {tiler_shape} cover {} of {} elements", divided.size(), layout.size())));
+    }
+    Ok(())
+}
+
+/// Every check above that applies to `layout` and `tiler`; inputs a check
+/// rejects are skipped, so only `Error::Invariant` is returned
+pub fn check_layout_invariants(layout: &Layout, tiler: &Layout) -> Result<()> {
+    let skip = |r: Result<()>| match r {
+        Err(e @ Error::Invariant { .. }) => Err(e),
+        _ => Ok(()),
+    };
+    check_cosize_bounds(layout)?;
+    skip(check_index_roundtrip(layout))?;
+    skip(check_divide_recompose(layout, tiler))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(shape: Tuple, stride: Tuple) -> Layout {
+        Layout::with_shape_stride(Shape::new(shape), stride)
+    }
+
+    #[test]
+    fn hold_for_compact_strided_and_nested_layouts() {
+        let layouts = [
+            Layout::row_major([4, 6]),
+            Layout::col_major([2, 3, 4]),
+            Layout::row_major([3, 5]).with_stride([1, 3]),
+            Layout::row_major([4, 4]).with_stride([9, 2]),
+            Layout::row_major([0, 3]),
+            Layout::row_major([4, 3]).flip(0),
+            nested(Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 2])]), Tuple::tup(vec![Tuple::int1(1), Tuple::int(vec![2, 6])])),
+        ];
+        for layout in &layouts {
+            let rank = layout.flat_shape().len();
+            for t in 1..=4 {
+                let tiler = Layout::row_major(vec![t; rank]);
+                check_layout_invariants(layout, &tiler).unwrap_or_else(|e| panic!("{e}"));
+            }
+        }
+    }
+
+    #[test]
+    fn col_major_and_nested_indices_roundtrip() {
+        // These used to trip idx2crd's descending-stride assumption and
+        // cosize's last-mode shortcut
+        check_index_roundtrip(&Layout::col_major([3, 4])).unwrap();
+        let l = nested(Tuple::tup(vec![Tuple::int1(2), Tuple::int1(3)]), Tuple::tup(vec![Tuple::int1(1), Tuple::int1(2)]));
+        assert_eq!(l.cosize(), 6);
+        check_cosize_bounds(&l).unwrap();
+    }
+
+    #[test]
+    fn rejected_inputs_are_not_violations() {
+        let strided = Layout::row_major([2, 2]).with_stride([1, 1]);
+        assert!(matches!(check_index_roundtrip(&strided), Err(Error::NotContiguous { .. })));
+        let flipped = Layout::row_major([4, 4]).flip(1);
+        assert!(matches!(check_divide_recompose(&flipped, &Layout::row_major([2, 2])), Err(Error::Unsupported { .. })));
+        assert!(matches!(check_divide_recompose(&strided, &Layout::row_major([2])), Err(Error::RankMismatch { .. })));
+        check_layout_invariants(&strided, &Layout::row_major([0, 1])).unwrap();
+    }
+}
@@ -0,0 +1,412 @@
+// src/kernel.rs
+//
+// Register-blocked micro-kernels and the packed GEMM driver that runs them.
+// The driver packs op(A) and op(B) with `pack`, then hands MR x KC and
+// KC x NR panels to the kernel; edge tiles go through a scratch accumulator,
+// so kernels only ever see full panels.
+
+use num_complex::{Complex32, Complex64};
+use crate::blas::{BlasBackend, BlasDiag, BlasSide, BlasTranspose, BlasUplo, GemmAlgo, NativeBlas};
+use crate::layout::Layout;
+use crate::pack::{pack_panel_a, pack_panel_b, packed_a_len, packed_b_len};
+use crate::tensor::TensorView;
+
+/// An `MR x NR` f32 kernel over packed panels.
+///
+/// `run` performs `kc` rank-1 updates
+/// `c[i * NR + j] += a[p * MR + i] * b[p * NR + j]`, where `a` is laid out
+/// like `pack_panel_a` output and `b` like `pack_panel_b` output.
+pub trait MicroKernel: Send + Sync {
+    const MR: usize;
+    const NR: usize;
+
+    fn run(&self, kc: usize, a: &[f32], b: &[f32], c: &mut [f32]);
+}
+
+/// Portable kernel; the default for `NativeGemm`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ScalarKernel;
+
+impl MicroKernel for ScalarKernel {
+    const MR: usize = 4;
+    const NR: usize = 4;
+
+    fn run(&self, kc: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+        let (a, b) = (&a[..kc * Self::MR], &b[..kc * Self::NR]);
+        for (ap, bp) in a.chunks_exact(Self::MR).zip(b.chunks_exact(Self::NR)) {
+            for (i, &ai) in ap.iter().enumerate() {
+                for (cij, &bj) in c[i * Self::NR..][..Self::NR].iter_mut().zip(bp) {
+                    *cij += ai * bj;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+pub use self::avx2::Avx2Kernel;
+
+#[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+mod avx2 {
+    use super::MicroKernel;
+    use std::arch::x86_64::*;
+
+    /// 4 x 8 AVX2/FMA kernel: one ymm accumulator per row.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Avx2Kernel(());
+
+    impl Avx2Kernel {
+        /// `None` when the CPU lacks AVX2 or FMA
+        pub fn new() -> Option<Self> {
+            (is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma")).then_some(Avx2Kernel(()))
+        }
+    }
+
+    impl MicroKernel for Avx2Kernel {
+        const MR: usize = 4;
+        const NR: usize = 8;
+
+        fn run(&self, kc: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+            assert!(a.len() >= kc * 4 && b.len() >= kc * 8 && c.len() >= 32);
+            // Construction checked the CPU features; lengths are checked above
+            unsafe { kernel_4x8(kc, a.as_ptr(), b.as_ptr(), c.as_mut_ptr()) }
+        }
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn kernel_4x8(kc: usize, a: *const f32, b: *const f32, c: *mut f32) {
+        let mut acc = [
+            _mm256_loadu_ps(c),
+            _mm256_loadu_ps(c.add(8)),
+            _mm256_loadu_ps(c.add(16)),
+            _mm256_loadu_ps(c.add(24)),
+        ];
+        for p in 0..kc {
+            let bv = _mm256_loadu_ps(b.add(p * 8));
+            for (i, row) in acc.iter_mut().enumerate() {
+                *row = _mm256_fmadd_ps(_mm256_set1_ps(*a.add(p * 4 + i)), bv, *row);
+            }
+        }
+        for (i, row) in acc.iter().enumerate() {
+            _mm256_storeu_ps(c.add(i * 8), *row);
+        }
+    }
+}
+
+#[cfg(all(feature = "neon", target_arch = "aarch64"))]
+pub use self::neon::NeonKernel;
+
+#[cfg(all(feature = "neon", target_arch = "aarch64"))]
+mod neon {
+    use super::MicroKernel;
+    use std::arch::aarch64::*;
+
+    /// 4 x 4 NEON kernel: one q-register accumulator per row.
+    #[derive(Debug, Clone, Copy, Default)]
+    pub struct NeonKernel;
+
+    impl MicroKernel for NeonKernel {
+        const MR: usize = 4;
+        const NR: usize = 4;
+
+        fn run(&self, kc: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+            assert!(a.len() >= kc * 4 && b.len() >= kc * 4 && c.len() >= 16);
+            // NEON is baseline on aarch64; lengths are checked above
+            unsafe {
+                let c = c.as_mut_ptr();
+                let mut acc = [vld1q_f32(c), vld1q_f32(c.add(4)), vld1q_f32(c.add(8)), vld1q_f32(c.add(12))];
+                for p in 0..kc {
+                    let bv = vld1q_f32(b.as_ptr().add(p * 4));
+                    for (i, row) in acc.iter_mut().enumerate() {
+                        *row = vfmaq_f32(*row, bv, vdupq_n_f32(a[p * 4 + i]));
+                    }
+                }
+                for (i, row) in acc.iter().enumerate() {
+                    vst1q_f32(c.add(i * 4), *row);
+                }
+            }
+        }
+    }
+}
+
+/* ============================================================
+   Packed GEMM driver
+   ============================================================ */
+
+/// Default depth of a packed panel, unless `RUTILE_TILE_K` (see `config`) is set
+pub const DEFAULT_KC: usize = 256;
+
+/// Native SGEMM that packs operands and runs `K` on every `MR x NR` tile
+/// of C. TRSM and SYRK fall back to `NativeBlas`.
+#[derive(Debug, Clone, Copy)]
+pub struct NativeGemm<K: MicroKernel = ScalarKernel> {
+    kernel: K,
+    kc: usize,
+}
+
+impl<K: MicroKernel> NativeGemm<K> {
+    pub fn new(kernel: K) -> Self {
+        Self { kernel, kc: crate::config::get().tile_k.unwrap_or(DEFAULT_KC) }
+    }
+
+    /// Use panels of depth `kc` along the reduction mode
+    pub fn with_kc(mut self, kc: usize) -> Self {
+        assert!(kc > 0, "NativeGemm: kc must be > 0");
+        self.kc = kc;
+        self
+    }
+}
+
+impl Default for NativeGemm<ScalarKernel> {
+    fn default() -> Self {
+        Self::new(ScalarKernel)
+    }
+}
+
+/// Row-major strides of op(X) for a BLAS operand with leading dimension `ld`
+pub(crate) fn op_stride(t: BlasTranspose, ld: usize) -> [usize; 2] {
+    match t {
+        BlasTranspose::NoTrans => [ld, 1],
+        BlasTranspose::Trans | BlasTranspose::ConjTrans => [1, ld],
+    }
+}
+
+// `BlasBackend` methods take raw pointers
+impl<K: MicroKernel> BlasBackend for NativeGemm<K> {
+    unsafe fn gemm_f32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        let (m, n, k) = (m as usize, n as usize, k as usize);
+        let ldc = ldc as usize;
+        let (sa, sb) = (op_stride(ta, lda as usize), op_stride(tb, ldb as usize));
+        let (mr, nr) = (K::MR, K::NR);
+        log_trace!("NativeGemm {m}x{n}x{k}: {} {mr}x{nr}, kc {}", std::any::type_name::<K>(), self.kc);
+
+        unsafe {
+            for i in 0..m {
+                for j in 0..n {
+                    let cij = c.add(i * ldc + j);
+                    *cij = if beta == 0.0 { 0.0 } else { beta * *cij };
+                }
+            }
+        }
+
+        let mut a_buf = vec![0.0f32; packed_a_len(m, mr, self.kc)];
+        let mut b_buf = vec![0.0f32; packed_b_len(n, self.kc, nr)];
+        let mut acc = vec![0.0f32; mr * nr];
+
+        for pc in (0..k).step_by(self.kc) {
+            let kb = self.kc.min(k - pc);
+            // op(A)[:, pc..pc + kb] and op(B)[pc..pc + kb, :]
+            let a_blk = unsafe {
+                TensorView::from_raw(a.add(pc * sa[1]), Layout::row_major([m, kb]).with_stride(sa))
+            };
+            let b_blk = unsafe {
+                TensorView::from_raw(b.add(pc * sb[0]), Layout::row_major([kb, n]).with_stride(sb))
+            };
+            pack_panel_a(&a_blk, mr, kb, &mut a_buf);
+            pack_panel_b(&b_blk, kb, nr, &mut b_buf);
+
+            for (ir, a_panel) in a_buf[..packed_a_len(m, mr, kb)].chunks_exact(mr * kb).enumerate() {
+                for (jr, b_panel) in b_buf[..packed_b_len(n, kb, nr)].chunks_exact(kb * nr).enumerate() {
+                    acc.fill(0.0);
+                    self.kernel.run(kb, a_panel, b_panel, &mut acc);
+
+                    let (i0, j0) = (ir * mr, jr * nr);
+                    for i in 0..mr.min(m - i0) {
+                        for j in 0..nr.min(n - j0) {
+                            unsafe { *c.add((i0 + i) * ldc + j0 + j) += alpha * acc[i * nr + j] };
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    unsafe fn gemm_c32(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex32,
+        a: *const Complex32,
+        lda: i32,
+        b: *const Complex32,
+        ldb: i32,
+        beta: Complex32,
+        c: *mut Complex32,
+        ldc: i32,
+    ) {
+        NativeBlas.gemm_c32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+
+    unsafe fn gemm_c64(
+        &self,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: Complex64,
+        a: *const Complex64,
+        lda: i32,
+        b: *const Complex64,
+        ldb: i32,
+        beta: Complex64,
+        c: *mut Complex64,
+        ldc: i32,
+    ) {
+        NativeBlas.gemm_c64(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc)
+    }
+
+    unsafe fn trsm_f32(
+        &self,
+        side: BlasSide,
+        uplo: BlasUplo,
+        ta: BlasTranspose,
+        diag: BlasDiag,
+        m: i32,
+        n: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *mut f32,
+        ldb: i32,
+    ) {
+        NativeBlas.trsm_f32(side, uplo, ta, diag, m, n, alpha, a, lda, b, ldb)
+    }
+
+    unsafe fn syrk_f32(
+        &self,
+        uplo: BlasUplo,
+        trans: BlasTranspose,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        NativeBlas.syrk_f32(uplo, trans, n, k, alpha, a, lda, beta, c, ldc)
+    }
+
+    /// `Default` and `Packed` are the packed driver; `Naive` is `NativeBlas`
+    fn gemm_algos(&self) -> &'static [GemmAlgo] {
+        &[GemmAlgo::Default, GemmAlgo::Packed, GemmAlgo::Naive]
+    }
+
+    unsafe fn gemm_f32_algo(
+        &self,
+        algo: GemmAlgo,
+        ta: BlasTranspose,
+        tb: BlasTranspose,
+        m: i32,
+        n: i32,
+        k: i32,
+        alpha: f32,
+        a: *const f32,
+        lda: i32,
+        b: *const f32,
+        ldb: i32,
+        beta: f32,
+        c: *mut f32,
+        ldc: i32,
+    ) {
+        match algo {
+            GemmAlgo::Naive => NativeBlas.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+            _ => self.gemm_f32(ta, tb, m, n, k, alpha, a, lda, b, ldb, beta, c, ldc),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench_utils::random_matrix_f32;
+
+    fn check<B: BlasBackend>(backend: &B, ta: BlasTranspose, tb: BlasTranspose, m: usize, n: usize, k: usize) {
+        // Stored shapes of A and B; op() transposes them back to m x k and k x n
+        let (ar, ac) = if ta == BlasTranspose::NoTrans { (m, k) } else { (k, m) };
+        let (br, bc) = if tb == BlasTranspose::NoTrans { (k, n) } else { (n, k) };
+        let a = random_matrix_f32(ar, ac, 1).into_vec();
+        let b = random_matrix_f32(br, bc, 2).into_vec();
+        let (sa, sb) = (op_stride(ta, ac), op_stride(tb, bc));
+
+        let mut c = vec![1.0f32; m * n];
+        unsafe {
+            backend.gemm_f32(
+                ta, tb, m as i32, n as i32, k as i32, 2.0,
+                a.as_ptr(), ac as i32, b.as_ptr(), bc as i32,
+                0.5, c.as_mut_ptr(), n as i32,
+            );
+        }
+        for i in 0..m {
+            for j in 0..n {
+                let dot: f32 = (0..k).map(|p| a[i * sa[0] + p * sa[1]] * b[p * sb[0] + j * sb[1]]).sum();
+                let want = 2.0 * dot + 0.5;
+                assert!((c[i * n + j] - want).abs() < 1e-3, "C[{i}][{j}] = {} vs {want}", c[i * n + j]);
+            }
+        }
+    }
+
+    #[test]
+    fn scalar_driver_handles_edges_and_transposes() {
+        let gemm = NativeGemm::default().with_kc(5);
+        for &(ta, tb) in &[
+            (BlasTranspose::NoTrans, BlasTranspose::NoTrans),
+            (BlasTranspose::Trans, BlasTranspose::NoTrans),
+            (BlasTranspose::NoTrans, BlasTranspose::Trans),
+        ] {
+            check(&gemm, ta, tb, 7, 9, 13);
+        }
+        check(&gemm, BlasTranspose::NoTrans, BlasTranspose::NoTrans, 3, 2, 0);
+    }
+
+    /// Odd-shaped user kernel, to exercise the plugin path
+    struct Naive3x5;
+
+    impl MicroKernel for Naive3x5 {
+        const MR: usize = 3;
+        const NR: usize = 5;
+
+        fn run(&self, kc: usize, a: &[f32], b: &[f32], c: &mut [f32]) {
+            for p in 0..kc {
+                for i in 0..3 {
+                    for j in 0..5 {
+                        c[i * 5 + j] += a[p * 3 + i] * b[p * 5 + j];
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn custom_kernel_plugs_in() {
+        check(&NativeGemm::new(Naive3x5), BlasTranspose::NoTrans, BlasTranspose::Trans, 8, 11, 6);
+    }
+
+    #[cfg(all(feature = "avx2", target_arch = "x86_64"))]
+    #[test]
+    fn avx2_kernel_matches_reference() {
+        if let Some(kernel) = Avx2Kernel::new() {
+            check(&NativeGemm::new(kernel).with_kc(16), BlasTranspose::NoTrans, BlasTranspose::NoTrans, 13, 17, 40);
+        }
+    }
+}
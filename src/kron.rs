@@ -0,0 +1,82 @@
+// src/kron.rs
+//
+// Kronecker and Khatri-Rao products. Mode `i` of `kron(a, b)` is the pair
+// `(a_i, b_i)` of input extents, so the result layout is the nested tuple
+// `((m, p), (n, q))` for matrices: its flat coordinate `(i, k, j, l)` holds
+// `a[i, j] * b[k, l]`, and because the layout is row-major the data is the
+// textbook `(m p) x (n q)` Kronecker matrix.
+
+use std::ops::Mul;
+
+use crate::layout::Layout;
+use crate::shape::{for_each_flat_coord, Shape};
+use crate::tensor::{Tensor, TensorView};
+use crate::tuple::Tuple;
+
+/// Kronecker product of two views of equal rank. Mode `i` of the result is
+/// `(a_i, b_i)`; `Layout::row_major([m * p, n * q])` over the same data
+/// gives the flat matrix.
+pub fn kron<T: Copy + Mul<Output = T>>(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Tensor<T> {
+    let (da, db) = (a.layout().flat_shape(), b.layout().flat_shape());
+    assert_eq!(da.len(), db.len(), "kron: operands must have the same rank");
+    let modes = da.iter().zip(db).map(|(&x, &y)| Tuple::int(vec![x, y])).collect();
+    let shape = Shape::new(Tuple::tup(modes));
+
+    let rank = da.len();
+    let (mut ca, mut cb) = (vec![0; rank], vec![0; rank]);
+    let mut data = Vec::with_capacity(da.iter().chain(db).product());
+    for_each_flat_coord(&shape, |crd| {
+        for i in 0..rank {
+            (ca[i], cb[i]) = (crd[2 * i], crd[2 * i + 1]);
+        }
+        // `crd` ranges over `(a_i, b_i)` pairs, so both coordinates are in bounds
+        data.push(unsafe { *a.get_flat(&ca) * *b.get_flat(&cb) });
+    });
+    Tensor::new(data, Layout::row_major(shape))
+}
+
+/// Column-wise Kronecker product of an `m x r` and a `p x r` matrix: the
+/// result has shape `((m, p), r)` and column `j` is `kron(a[:, j], b[:, j])`.
+pub fn khatri_rao<T: Copy + Mul<Output = T>>(a: &TensorView<'_, T>, b: &TensorView<'_, T>) -> Tensor<T> {
+    let (da, db) = (a.layout().flat_shape(), b.layout().flat_shape());
+    assert!(da.len() == 2 && db.len() == 2, "khatri_rao: operands must be rank-2");
+    assert_eq!(da[1], db[1], "khatri_rao: operands must have the same number of columns");
+    let shape = Shape::new(Tuple::int(vec![da[0], db[0], da[1]])).group_modes(0..2);
+
+    let mut data = Vec::with_capacity(da[0] * db[0] * da[1]);
+    for_each_flat_coord(&shape, |crd| {
+        let (i, k, j) = (crd[0], crd[1], crd[2]);
+        data.push(unsafe { *a.get_flat(&[i, j]) * *b.get_flat(&[k, j]) });
+    });
+    Tensor::new(data, Layout::row_major(shape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kron_of_matrices_is_the_block_matrix() {
+        let a = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([2, 2]));
+        let b = Tensor::new(vec![0, 5, 6, 7, 0, 1], Layout::col_major([2, 3]));
+        let k = kron(&a.as_view(), &b.as_view());
+
+        assert_eq!(k.layout().shape().dims, Tuple::tup(vec![Tuple::int(vec![2, 2]), Tuple::int(vec![2, 3])]));
+        // b = [[0,6,0],[5,7,1]]; row 1 of the 4x6 result is [5,7,1,10,14,2]
+        assert_eq!(&k.data()[6..12], &[5, 7, 1, 10, 14, 2]);
+        assert_eq!(k.as_view().layout().crd2idx_flat(&[1, 1, 1, 2]), 3 * 6 + 5);
+        assert_eq!(k.data()[3 * 6 + 5], 4);
+    }
+
+    #[test]
+    fn khatri_rao_matches_kron_of_columns() {
+        let a = Tensor::new(vec![1, 2, 3, 4, 5, 6], Layout::row_major([3, 2]));
+        let b = Tensor::new(vec![1, -1, 2, 0], Layout::row_major([2, 2]));
+        let kr = khatri_rao(&a.as_view(), &b.as_view());
+        assert_eq!(kr.layout().flat_shape(), &[3, 2, 2]);
+        // Column 0 is kron([1,3,5], [1,2]), column 1 is kron([2,4,6], [-1,0])
+        let col = |j: usize| kr.data().iter().skip(j).step_by(2).copied().collect::<Vec<_>>();
+        assert_eq!(col(0), vec![1, 2, 3, 6, 5, 10]);
+        assert_eq!(col(1), vec![-2, 0, -4, 0, -6, 0]);
+    }
+}
@@ -1,14 +1,128 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::OnceLock;
+
 use crate::shape::Shape;
 use crate::tuple::Tuple;
 use crate::tuple::Stride;
 use crate::layout_algebra::{flat_divide};
+use crate::error::{check_rank, Error, Result};
 
 /// Layout = mapping from coordinates → linear index
-#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Layout {
-    shape: Shape,
-    stride: Tuple,
-    contig: bool,
+    /// Shape and stride trees. A flat layout of rank up to `INLINE_RANK`
+    /// rebuilds them from `flat` on first use, and its clones start without
+    /// them, so copying such a layout never allocates.
+    tree: OnceLock<(Shape, Tuple)>,
+    /// Whether the trees have nesting `flat` cannot describe
+    nested: bool,
+    contig: Option<Contiguity>,
+    /// Bit `i` set: flattened mode `i` runs backwards through memory
+    reversed: u64,
+    /// Position of coordinate 0 relative to the root allocation a view was cut from
+    offset: isize,
+    /// Leaves of `shape` and `stride`, computed once so hot paths read slices
+    flat: FlatModes,
+}
+
+/// Largest flattened rank whose modes `FlatModes` keeps inline
+pub(crate) const INLINE_RANK: usize = 4;
+
+/// Flattened extents and strides. Ranks 1-4 are stored in fixed-size arrays,
+/// so the common vector / matrix / batched-matrix layouts keep them inline
+/// and index arithmetic on them is unrolled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FlatModes {
+    Rank1 { shape: [usize; 1], stride: [usize; 1] },
+    Rank2 { shape: [usize; 2], stride: [usize; 2] },
+    Rank3 { shape: [usize; 3], stride: [usize; 3] },
+    Rank4 { shape: [usize; 4], stride: [usize; 4] },
+    Dyn { shape: Box<[usize]>, stride: Box<[usize]> },
+}
+
+impl FlatModes {
+    fn new(shape: &[usize], stride: &[usize]) -> Self {
+        match (shape, stride) {
+            (&[n], &[s]) => FlatModes::Rank1 { shape: [n], stride: [s] },
+            (&[n0, n1], &[s0, s1]) => FlatModes::Rank2 { shape: [n0, n1], stride: [s0, s1] },
+            (&[n0, n1, n2], &[s0, s1, s2]) => FlatModes::Rank3 { shape: [n0, n1, n2], stride: [s0, s1, s2] },
+            (&[n0, n1, n2, n3], &[s0, s1, s2, s3]) => {
+                FlatModes::Rank4 { shape: [n0, n1, n2, n3], stride: [s0, s1, s2, s3] }
+            }
+            _ => FlatModes::Dyn { shape: shape.into(), stride: stride.into() },
+        }
+    }
+
+    fn is_inline(&self) -> bool {
+        !matches!(self, FlatModes::Dyn { .. })
+    }
+
+    #[inline]
+    fn shape(&self) -> &[usize] {
+        match self {
+            FlatModes::Rank1 { shape, .. } => shape,
+            FlatModes::Rank2 { shape, .. } => shape,
+            FlatModes::Rank3 { shape, .. } => shape,
+            FlatModes::Rank4 { shape, .. } => shape,
+            FlatModes::Dyn { shape, .. } => shape,
+        }
+    }
+
+    #[inline]
+    fn stride(&self) -> &[usize] {
+        match self {
+            FlatModes::Rank1 { stride, .. } => stride,
+            FlatModes::Rank2 { stride, .. } => stride,
+            FlatModes::Rank3 { stride, .. } => stride,
+            FlatModes::Rank4 { stride, .. } => stride,
+            FlatModes::Dyn { stride, .. } => stride,
+        }
+    }
+
+    /// `crd · stride`
+    #[inline]
+    fn dot(&self, crd: &[usize]) -> usize {
+        match self {
+            FlatModes::Rank1 { stride: [s], .. } => crd[0] * s,
+            FlatModes::Rank2 { stride: [s0, s1], .. } => crd[0] * s0 + crd[1] * s1,
+            FlatModes::Rank3 { stride: [s0, s1, s2], .. } => crd[0] * s0 + crd[1] * s1 + crd[2] * s2,
+            FlatModes::Rank4 { stride: [s0, s1, s2, s3], .. } => {
+                crd[0] * s0 + crd[1] * s1 + crd[2] * s2 + crd[3] * s3
+            }
+            FlatModes::Dyn { stride, .. } => crd.iter().zip(stride.iter()).map(|(c, s)| c * s).sum(),
+        }
+    }
+}
+
+impl Clone for Layout {
+    fn clone(&self) -> Self {
+        // Inline flat layouts can rebuild their trees, so leave them behind
+        let tree = if !self.nested && self.flat.is_inline() { OnceLock::new() } else { self.tree.clone() };
+        Layout { tree, nested: self.nested, contig: self.contig, reversed: self.reversed, offset: self.offset, flat: self.flat.clone() }
+    }
+}
+
+/// Layouts are equal when shape, stride, reversed modes and the base
+/// `offset` all match, so two views of one buffer that differ only in where
+/// they start compare unequal; `same_modes` ignores the offset.
+impl PartialEq for Layout {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_modes(other) && self.offset == other.offset
+    }
+}
+
+impl Eq for Layout {}
+
+impl fmt::Debug for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layout")
+            .field("shape", self.shape())
+            .field("stride", self.stride())
+            .field("contig", &self.contig)
+            .field("reversed", &self.reversed)
+            .field("offset", &self.offset)
+            .finish()
+    }
 }
 
 /// Layout policy trait
@@ -22,90 +136,223 @@ pub struct RowMajor;
 /// Column-major policy
 pub struct ColMajor;
 
-fn is_layout_contig(shape: Shape, stride: Stride) -> bool {
-    let mut expected_stride = 1;
-    let flat_shape_vec = shape.dims.flatten();
-    for (dim, s) in stride.flatten().into_iter().rev().enumerate() {
-        if s != expected_stride {
-            return false;
+/// Mode order of a layout that covers `0..size` exactly once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Contiguity {
+    /// Strides grow from the last flattened mode to the first
+    RowMajor,
+    /// Strides grow from the first flattened mode to the last
+    ColMajor,
+    /// Some other order; see `Layout::memory_order`
+    Permuted,
+}
+
+/// Flattened modes from fastest to slowest varying, ignoring extent-1
+/// modes (their stride is never used). `None` unless the modes tile
+/// memory with no gaps or overlaps.
+fn compact_order(extents: &[usize], strides: &[usize]) -> Option<Vec<usize>> {
+    let mut modes = vec![0; extents.len()];
+    let len = compact_order_into(extents, strides, &mut modes)?;
+    modes.truncate(len);
+    Some(modes)
+}
+
+/// `compact_order` written to the front of `modes`; returns how many
+/// modes it wrote
+fn compact_order_into(extents: &[usize], strides: &[usize], modes: &mut [usize]) -> Option<usize> {
+    if extents.contains(&0) {
+        return Some(0);
+    }
+    let mut len = 0;
+    for i in (0..extents.len()).filter(|&i| extents[i] > 1) {
+        modes[len] = i;
+        len += 1;
+    }
+    // Equal strides of modes longer than 1 fail the check below, so the
+    // order among them does not matter
+    modes[..len].sort_unstable_by_key(|&i| strides[i]);
+
+    let mut expected = 1;
+    for &i in &modes[..len] {
+        if strides[i] != expected {
+            return None;
         }
-        expected_stride *= flat_shape_vec[flat_shape_vec.len()-1 - dim];
+        expected *= extents[i];
+    }
+    Some(len)
+}
+
+fn contiguity(extents: &[usize], strides: &[usize]) -> Option<Contiguity> {
+    let mut inline = [0; INLINE_RANK];
+    let heap;
+    let order = if extents.len() <= INLINE_RANK {
+        let len = compact_order_into(extents, strides, &mut inline)?;
+        &inline[..len]
+    } else {
+        heap = compact_order(extents, strides)?;
+        &heap[..]
+    };
+    Some(if order.windows(2).all(|w| w[0] > w[1]) {
+        Contiguity::RowMajor
+    } else if order.windows(2).all(|w| w[0] < w[1]) {
+        Contiguity::ColMajor
+    } else {
+        Contiguity::Permuted
+    })
+}
+
+/// Coordinate of linear index `idx` in one mode; stride-0 modes stay at 0
+fn mode_coord(idx: usize, extent: usize, stride: usize) -> usize {
+    if stride == 0 || extent == 0 {
+        0
+    } else {
+        idx / stride % extent
     }
-    true
 }
 
 impl Layout {
-    pub fn new<P: LayoutPolicy>(shape: Shape) -> Self {
+    pub fn new<P: LayoutPolicy>(shape: impl Into<Shape>) -> Self {
+        let shape = shape.into();
         let stride = P::make_stride(&shape);
-        Self { shape, stride, contig : true }
+        Layout::with_shape_stride(shape, stride)
     }
 
-    pub fn row_major(shape: Shape) -> Self {
+    pub fn row_major(shape: impl Into<Shape>) -> Self {
         Layout::new::<RowMajor>(shape)
     }
 
-    pub fn col_major(shape: Shape) -> Self {
+    pub fn col_major(shape: impl Into<Shape>) -> Self {
         Layout::new::<ColMajor>(shape)
     }
 
     pub fn shape(&self) -> &Shape {
-        &self.shape
+        &self.tree().0
     }
 
     pub fn stride(&self) -> &Tuple {
-        &self.stride
+        &self.tree().1
+    }
+
+    fn tree(&self) -> &(Shape, Tuple) {
+        self.tree.get_or_init(|| {
+            (Shape::new(Tuple::Int(self.flat_shape().to_vec())), Tuple::Int(self.flat_stride().to_vec()))
+        })
+    }
+
+    /// Extents of the flattened modes
+    #[inline]
+    pub fn flat_shape(&self) -> &[usize] {
+        self.flat.shape()
+    }
+
+    /// Strides of the flattened modes (magnitudes; see `signed_stride`)
+    #[inline]
+    pub fn flat_stride(&self) -> &[usize] {
+        self.flat.stride()
     }
 
     pub fn size(&self) -> usize {
-        self.shape.size()
+        self.flat_shape().iter().product()
     }
 
+    /// Number of top-level modes; `flat_shape().len()` counts leaves
     pub fn rank(&self) -> usize {
-        self.shape.rank()
+        self.shape().rank()
+    }
+
+    /// Extent of top-level mode `mode` (see `Shape::extent`)
+    pub fn extent(&self, mode: usize) -> usize {
+        self.shape().extent(mode)
     }
 
-    /// Maximum linear index + 1 = codomain size
+    /// Maximum linear index + 1 = codomain size (0 for an empty layout)
     pub fn cosize(&self) -> usize {
-        fn recur(shape: &Tuple, stride: &Tuple) -> usize {
-            match (shape, stride) {
-                (Tuple::Int(sizes), Tuple::Int(strides)) => {
-                    let last_idx = sizes.iter().zip(strides.iter())
-                                        .map(|(s, st)| (s - 1) * st)
-                                        .sum::<usize>();
-                    last_idx + 1
-                }
-                (Tuple::Tup(ss), Tuple::Tup(sts)) => {
-                    let last_s = ss.last().expect("Empty Tup in cosize");
-                    let last_st = sts.last().expect("Stride mismatch in cosize");
-                    recur(last_s, last_st)
-                }
-                _ => panic!("Shape/Stride mismatch in cosize"),
-            }
+        if self.size() == 0 {
+            return 0;
         }
-
-        recur(&self.shape.dims, &self.stride)
+        self.flat_shape().iter().zip(self.flat_stride()).map(|(s, st)| (s - 1) * st).sum::<usize>() + 1
     }
 
+    /// Whether the layout covers `0..size` exactly once, in any mode order
     pub fn is_contiguous(&self) -> bool {
+        self.contig.is_some()
+    }
+
+    /// Whether no two coordinates share an offset. Modes sorted by stride
+    /// that each step past everything the smaller ones reach settle it
+    /// cheaply; other layouts are checked by marking every offset.
+    pub fn is_injective(&self) -> bool {
+        if self.contig.is_some() || self.size() == 0 {
+            return true;
+        }
+        let mut modes: Vec<(usize, usize)> =
+            self.flat_shape().iter().zip(self.flat_stride()).filter(|(&n, _)| n > 1).map(|(&n, &s)| (s, n)).collect();
+        modes.sort_unstable();
+        let mut reach = 0;
+        if modes.iter().all(|&(s, n)| {
+            let ok = s > reach;
+            reach += (n - 1) * s;
+            ok
+        }) {
+            return true;
+        }
+        if modes.iter().any(|&(s, _)| s == 0) {
+            return false;
+        }
+        let mut seen = vec![false; self.cosize()];
+        let mut injective = true;
+        crate::shape::for_each_flat_coord(self.shape(), |crd| {
+            let off = self.flat.dot(crd);
+            injective &= !std::mem::replace(&mut seen[off], true);
+        });
+        injective
+    }
+
+    /// Mode order of a contiguous layout, for picking a kernel
+    pub fn contiguity(&self) -> Option<Contiguity> {
         self.contig
     }
 
-    pub fn crd2idx(&self, crd: &Tuple) -> usize {
-        crd.dot(&self.stride)
+    /// Flattened modes of a contiguous layout from fastest to slowest
+    /// varying; extent-1 modes are left out
+    pub fn memory_order(&self) -> Option<Vec<usize>> {
+        self.contig?;
+        compact_order(self.flat_shape(), self.flat_stride())
+    }
+
+    /// Linear index of `crd`. Layouts with reversed modes need signed
+    /// offsets; use `crd2offset` for those.
+    pub fn crd2idx(&self, crd: impl Into<Tuple>) -> usize {
+        assert!(!self.has_reversed_modes(), "crd2idx on a layout with reversed modes; use crd2offset");
+        let crd = crd.into().flatten();
+        assert_eq!(crd.len(), self.flat_stride().len(), "crd2idx: coordinate rank mismatch");
+        self.crd2idx_flat(&crd)
     }
 
-    pub fn idx2crd(&self, mut idx: usize) -> Tuple {
-        fn recur(idx: &mut usize, shape: &Tuple, stride: &Tuple) -> Tuple {
+    /// `crd2idx` that checks the rank and every coordinate against the
+    /// shape, and rejects layouts with reversed modes
+    pub fn try_crd2idx(&self, crd: impl Into<Tuple>) -> Result<usize> {
+        if self.has_reversed_modes() {
+            return Err(Error::Unsupported { op: "crd2idx", what: "a layout with reversed modes".into() });
+        }
+        let crd = crd.into();
+        check_rank("crd2idx", self.flat_shape().len(), crd.flat_len())?;
+
+        if crd.iter_flat().zip(self.flat_shape()).any(|(c, e)| c >= e) {
+            return Err(Error::OutOfBounds { op: "crd2idx", coord: crd, shape: self.shape().clone() });
+        }
+        Ok(crd.dot(self.stride()))
+    }
+
+    /// Coordinate whose linear index is `idx`, taking `(idx / stride) % extent`
+    /// in every mode, so compact layouts invert in any mode order. Panics
+    /// when `idx` is not an index of the layout.
+    pub fn idx2crd(&self, idx: usize) -> Tuple {
+        assert!(!self.has_reversed_modes(), "idx2crd on a layout with reversed modes");
+        fn recur(idx: usize, shape: &Tuple, stride: &Tuple) -> Tuple {
             match (shape, stride) {
                 (Tuple::Int(sizes), Tuple::Int(strides)) => {
-                    let mut out = Vec::with_capacity(sizes.len());
-                    for (sz, st) in sizes.iter().zip(strides.iter()) {
-                        let v = *idx / *st;
-                        *idx -= v * st;
-                        assert!(v < *sz);
-                        out.push(v);
-                    }
-                    Tuple::Int(out)
+                    Tuple::Int(sizes.iter().zip(strides.iter()).map(|(&sz, &st)| mode_coord(idx, sz, st)).collect())
                 }
                 (Tuple::Tup(ss), Tuple::Tup(st)) => {
                     Tuple::Tup(
@@ -119,15 +366,350 @@ impl Layout {
             }
         }
 
-        recur(&mut idx, &self.shape.dims, &self.stride)
+        let crd = recur(idx, &self.shape().dims, self.stride());
+        assert_eq!(crd.dot(self.stride()), idx, "idx2crd: {idx} is not an index of {}:{}", self.shape(), self.stride());
+        crd
+    }
+
+    /// `crd2idx` for a flattened coordinate, without building a `Tuple`
+    #[inline]
+    pub fn crd2idx_flat(&self, crd: &[usize]) -> usize {
+        assert!(!self.has_reversed_modes(), "crd2idx_flat on a layout with reversed modes; use crd2offset_flat");
+        debug_assert_eq!(self.flat_stride().len(), crd.len(), "crd2idx_flat: coordinate rank mismatch");
+        self.flat.dot(crd)
+    }
+
+    /// `idx2crd` written into `crd`, one entry per flattened mode
+    pub fn idx2crd_flat(&self, idx: usize, crd: &mut [usize]) {
+        assert!(!self.has_reversed_modes(), "idx2crd_flat on a layout with reversed modes");
+        debug_assert_eq!(self.flat_stride().len(), crd.len(), "idx2crd_flat: coordinate rank mismatch");
+        for (c, (&sz, &st)) in crd.iter_mut().zip(self.flat_shape().iter().zip(self.flat_stride())) {
+            *c = mode_coord(idx, sz, st);
+        }
+        assert_eq!(self.flat.dot(crd), idx, "idx2crd_flat: {idx} is not an index of {}:{}", self.shape(), self.stride());
+    }
+}
+
+impl Layout {
+    /// Replace the shape, keeping the current stride.
+    pub fn with_shape(self, shape: impl Into<Shape>) -> Self {
+        Layout::congruent(shape.into(), self.into_tree().1)
+    }
+
+    /// Replace the stride, keeping the current shape.
+    pub fn with_stride(self, stride: impl Into<Stride>) -> Self {
+        Layout::congruent(self.into_tree().0, stride.into())
+    }
+
+    fn into_tree(mut self) -> (Shape, Tuple) {
+        self.tree();
+        self.tree.take().unwrap()
+    }
+
+    /// Build from a user-supplied shape/stride pair, normalizing
+    /// representation differences so later recursion sees matching trees.
+    fn congruent(shape: Shape, stride: Stride) -> Self {
+        assert!(
+            shape.dims.is_congruent(&stride),
+            "Shape {shape} and stride {stride} are not congruent"
+        );
+        Layout::with_shape_stride(Shape::new(shape.dims.canonicalize()), stride.canonicalize())
+    }
+}
+
+/* ---------- base offset ---------- */
+
+impl Layout {
+    /// Element offset of coordinate 0 from the start of the root tensor.
+    /// Zero for freshly built layouts; subviews, flips and diagonals
+    /// accumulate it so a view knows where it sits in its parent.
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    /// `==` without comparing `offset`: same shape, stride and reversed modes
+    pub fn same_modes(&self, other: &Layout) -> bool {
+        let trees_equal = match (self.nested, other.nested) {
+            (false, false) => self.flat == other.flat,
+            (true, true) => self.tree() == other.tree(),
+            _ => false,
+        };
+        trees_equal && self.reversed == other.reversed
+    }
+
+    pub(crate) fn with_offset(mut self, offset: isize) -> Layout {
+        self.offset = offset;
+        self
+    }
+
+    /// Flat coordinate whose `crd2offset` equals `offset`, if there is one
+    pub fn offset2crd(&self, offset: isize) -> Option<Tuple> {
+        let extents = self.flat_shape();
+        let strides = self.signed_stride();
+        if extents.contains(&0) {
+            return None;
+        }
+
+        // Solve on physical positions (reversed modes counted from their far end),
+        // taking the largest strides first.
+        let shift: isize = strides
+            .iter()
+            .zip(extents.iter())
+            .filter(|(s, _)| **s < 0)
+            .map(|(s, &e)| -s * (e as isize - 1))
+            .sum();
+        let mut rem = offset + shift;
+        if rem < 0 {
+            return None;
+        }
+
+        let mut order: Vec<usize> = (0..extents.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(strides[i].unsigned_abs()));
+
+        let mut crd = vec![0; extents.len()];
+        for i in order {
+            let step = strides[i].unsigned_abs() as isize;
+            let p = if step == 0 { 0 } else { (rem / step).min(extents[i] as isize - 1) };
+            rem -= p * step;
+            crd[i] = if strides[i] < 0 { extents[i] - 1 - p as usize } else { p as usize };
+        }
+
+        let crd = Tuple::Int(crd);
+        (rem == 0 && self.crd2offset(&crd) == offset).then_some(crd)
+    }
+
+    /// Coordinate in `parent` of this layout's coordinate 0, when both were
+    /// derived from the same root (e.g. by `subview`).
+    pub fn position_in(&self, parent: &Layout) -> Option<Tuple> {
+        parent.offset2crd(self.offset - parent.offset)
+    }
+}
+
+/* ---------- reversed modes ---------- */
+
+impl Layout {
+    /// Reverse flattened mode `mode`. Coordinate 0 of that mode becomes the
+    /// element that was last, so views must move their base pointer to it
+    /// (see `TensorView::flip`) and index with `crd2offset`.
+    pub fn flip(&self, mode: usize) -> Layout {
+        let rank = self.flat_shape().len();
+        assert!(mode < rank, "flip: mode {mode} out of range for rank {rank}");
+        assert!(mode < 64, "flip: only the first 64 modes can be reversed");
+
+        let mut out = self.clone();
+        out.reversed ^= 1 << mode;
+        out.contig = if out.reversed == 0 { contiguity(out.flat_shape(), out.flat_stride()) } else { None };
+        out
+    }
+
+    pub fn is_reversed(&self, mode: usize) -> bool {
+        mode < 64 && self.reversed & (1 << mode) != 0
+    }
+
+    pub fn has_reversed_modes(&self) -> bool {
+        self.reversed != 0
+    }
+
+    /// Flattened strides, negated for reversed modes
+    pub fn signed_stride(&self) -> Vec<isize> {
+        self.flat_stride()
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| if self.is_reversed(i) { -(s as isize) } else { s as isize })
+            .collect()
+    }
+
+    /// Signed offset of `crd` from the element at coordinate 0
+    #[inline]
+    pub fn crd2offset(&self, crd: &Tuple) -> isize {
+        let crd = crd.flatten();
+        assert_eq!(crd.len(), self.flat_stride().len(), "crd2offset: coordinate rank mismatch");
+        self.crd2offset_flat(&crd)
+    }
+
+    /// `crd2offset` for a flattened coordinate, without building a `Tuple`
+    #[inline]
+    pub fn crd2offset_flat(&self, crd: &[usize]) -> isize {
+        debug_assert_eq!(self.flat_stride().len(), crd.len(), "crd2offset_flat: coordinate rank mismatch");
+        if self.reversed == 0 {
+            return self.flat.dot(crd) as isize;
+        }
+        let mut offset = 0isize;
+        for (i, (&c, &s)) in crd.iter().zip(self.flat_stride()).enumerate() {
+            let term = (c * s) as isize;
+            offset += if self.is_reversed(i) { -term } else { term };
+        }
+        offset
+    }
+
+    /// Same shape/stride as `layout`, carrying over this layout's reversed modes
+    pub(crate) fn reversed_like(&self, layout: Layout) -> Layout {
+        if self.reversed == 0 {
+            return layout;
+        }
+        assert_eq!(
+            layout.flat_shape().len(),
+            self.flat_shape().len(),
+            "views of layouts with reversed modes must keep the flattened rank"
+        );
+        Layout { reversed: self.reversed, contig: None, ..layout }
     }
 }
 
 impl Layout {
     /// Create a new layout from shape + stride (used for subviews)
     pub(crate) fn with_shape_stride(shape: Shape, stride: Stride) -> Self {
-        let is_contiguous = is_layout_contig(shape.clone(), stride.clone());
-        Layout { shape, stride, contig : is_contiguous }
+        let nested = !matches!((&shape.dims, &stride), (Tuple::Int(_), Tuple::Int(_)));
+        let (flat_shape, flat_stride) = (shape.dims.flatten(), stride.flatten());
+        Layout {
+            tree: OnceLock::from((shape, stride)),
+            nested,
+            contig: contiguity(&flat_shape, &flat_stride),
+            reversed: 0,
+            offset: 0,
+            flat: FlatModes::new(&flat_shape, &flat_stride),
+        }
+    }
+
+    /// Flat layout with the given extents and strides. Up to `INLINE_RANK`
+    /// modes this does not allocate; the shape and stride tuples are built
+    /// if something asks for them.
+    pub(crate) fn from_flat(shape: &[usize], stride: &[usize]) -> Self {
+        assert_eq!(shape.len(), stride.len(), "Layout::from_flat: shape and stride ranks differ");
+        Layout {
+            tree: OnceLock::new(),
+            nested: false,
+            contig: contiguity(shape, stride),
+            reversed: 0,
+            offset: 0,
+            flat: FlatModes::new(shape, stride),
+        }
+    }
+}
+
+/* ---------- mode editing ---------- */
+
+// The `Tuple` mode edits applied to shape and stride together. A mode
+// inserted from another layout brings its shape and stride but not its
+// base offset. Reversed modes follow their leaves to the new positions.
+
+impl Layout {
+    pub fn append_mode(&self, mode: &Layout) -> Layout {
+        self.insert_mode(self.rank(), mode)
+    }
+
+    pub fn prepend_mode(&self, mode: &Layout) -> Layout {
+        self.insert_mode(0, mode)
+    }
+
+    /// `mode` as mode `i`; a layout of rank > 1 becomes one nested mode
+    pub fn insert_mode(&self, i: usize, mode: &Layout) -> Layout {
+        self.edit_modes(Some(mode), |t, m| t.insert_mode(i, m.unwrap()))
+    }
+
+    pub fn remove_mode(&self, i: usize) -> Layout {
+        self.edit_modes(None, |t, _| t.remove_mode(i))
+    }
+
+    pub fn group_modes(&self, range: Range<usize>) -> Layout {
+        self.edit_modes(None, |t, _| t.group_modes(range.clone()))
+    }
+
+    pub fn ungroup(&self, i: usize) -> Layout {
+        self.edit_modes(None, |t, _| t.ungroup(i))
+    }
+
+    /// Top-level modes `modes` with their strides, in that order; modes may
+    /// be repeated or left out
+    pub fn select_modes(&self, modes: &[usize]) -> Layout {
+        self.edit_modes(None, |t, _| t.select(modes))
+    }
+
+    /// All top-level modes reordered so that mode `i` of the result is mode
+    /// `perm[i]` of `self`, e.g. `&[1, 0]` transposes a matrix
+    pub fn permute_modes(&self, perm: &[usize]) -> Layout {
+        let rank = self.rank();
+        let mut sorted = perm.to_vec();
+        sorted.sort_unstable();
+        assert!(sorted.into_iter().eq(0..rank), "permute_modes: {perm:?} is not a permutation of {rank} modes");
+        self.select_modes(perm)
+    }
+
+    /// Run `edit` on the shape, the stride and a tuple numbering the leaves,
+    /// passing it the matching tuple of `other` if there is one
+    fn edit_modes(&self, other: Option<&Layout>, edit: impl Fn(&Tuple, Option<Tuple>) -> Tuple) -> Layout {
+        fn number_leaves(t: &Tuple, next: &mut usize) -> Tuple {
+            match t {
+                Tuple::Int(v) => Tuple::Int(v.iter().map(|_| { *next += 1; *next - 1 }).collect()),
+                Tuple::Tup(vs) => Tuple::Tup(vs.iter().map(|m| number_leaves(m, next)).collect()),
+            }
+        }
+
+        let shape = edit(&self.shape().dims, other.map(|o| o.shape().dims.clone()));
+        let stride = edit(self.stride(), other.map(|o| o.stride().clone()));
+        let layout = Layout::with_shape_stride(Shape::new(shape), stride).with_offset(self.offset);
+        if !self.has_reversed_modes() && !other.is_some_and(|o| o.has_reversed_modes()) {
+            return layout;
+        }
+
+        let mut next = 0;
+        let ids = number_leaves(&self.shape().dims, &mut next);
+        let n = next;
+        let sources = edit(&ids, other.map(|o| number_leaves(&o.shape().dims, &mut next))).flatten();
+        let mut reversed = 0;
+        for (mode, src) in sources.into_iter().enumerate() {
+            let rev = if src < n { self.is_reversed(src) } else { other.unwrap().is_reversed(src - n) };
+            if rev {
+                assert!(mode < 64, "only the first 64 modes can be reversed");
+                reversed |= 1 << mode;
+            }
+        }
+        Layout { reversed, contig: None, ..layout }
+    }
+}
+
+/* ---------- canonical form ---------- */
+
+/// The index mapping of a layout with its tuple structure forgotten:
+/// flattened `(extent, signed stride)` modes, outermost first, with
+/// extent-1 modes dropped and nesting neighbours merged. Layouts with equal
+/// canonical forms send every row-major coordinate index to the same
+/// offset, so this is the key to use for caches.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalLayout {
+    modes: Vec<(usize, isize)>,
+}
+
+impl CanonicalLayout {
+    pub fn modes(&self) -> &[(usize, isize)] {
+        &self.modes
+    }
+}
+
+impl Layout {
+    /// Coalesced form of the index mapping; see `CanonicalLayout`.
+    /// The base offset is not part of it.
+    pub fn canonical(&self) -> CanonicalLayout {
+        if self.size() == 0 {
+            return CanonicalLayout { modes: vec![(0, 0)] };
+        }
+        let mut modes: Vec<(usize, isize)> = Vec::new();
+        for (&n, s) in self.flat_shape().iter().zip(self.signed_stride()).rev() {
+            match modes.last_mut() {
+                _ if n == 1 => {}
+                // Merge an outer mode that steps exactly over the inner one
+                Some((inner_n, inner_s)) if s == *inner_s * *inner_n as isize => *inner_n *= n,
+                _ => modes.push((n, s)),
+            }
+        }
+        modes.reverse();
+        CanonicalLayout { modes }
+    }
+
+    /// Whether `self` and `other` map coordinate indices to the same
+    /// offsets, however their shapes are nested or split
+    pub fn is_equivalent(&self, other: &Layout) -> bool {
+        self.canonical() == other.canonical()
     }
 }
 
@@ -208,6 +790,32 @@ impl LayoutPolicy for ColMajor {
 mod tests {
     use super::*;
     use crate::tuple::Tuple;
+    use std::collections::HashSet;
+
+    #[test]
+    fn equality_includes_the_offset() {
+        let l = Layout::row_major([2, 3]);
+        let shifted = l.clone().with_offset(3);
+        assert_ne!(l, shifted);
+        assert!(l.same_modes(&shifted));
+        assert!(!l.same_modes(&Layout::col_major([2, 3])));
+    }
+
+    #[test]
+    fn flat_layouts_rebuild_their_tuples() {
+        let l = Layout::row_major([2, 3, 4, 5]).with_offset(7);
+        let copy = l.clone();
+        assert!(copy.tree.get().is_none());
+        assert_eq!(copy, l);
+        assert_eq!(copy.shape(), l.shape());
+        assert_eq!(copy.stride(), &Tuple::int(vec![60, 20, 5, 1]));
+        assert_eq!(Layout::from_flat(&[2, 3, 4, 5], &[60, 20, 5, 1]).with_offset(7), l);
+
+        // Nested trees cannot be rebuilt from the leaves and travel with clones
+        let nested = l.group_modes(0..2);
+        assert_ne!(nested, l);
+        assert_eq!(nested.clone().shape(), nested.shape());
+    }
 
     #[test]
     fn row_major_roundtrip() {
@@ -238,6 +846,194 @@ mod tests {
         let layout = Layout::new::<RowMajor>(shape);
         assert_eq!(layout.stride().to_string(), "(12,(4,1))");
     }
+
+    #[test]
+    fn builders_replace_shape_and_stride() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![4, 3])))
+            .with_stride(Tuple::int(vec![1, 4]));
+        assert_eq!(layout.stride().to_string(), "(1,4)");
+        assert_eq!(layout.crd2idx(Tuple::int(vec![1, 2])), 9);
+
+        let layout = layout.with_shape(Shape::new(Tuple::int(vec![2, 2])));
+        assert_eq!(layout.shape().to_string(), "(2,2)");
+        assert_eq!(layout.stride().to_string(), "(1,4)");
+        assert!(!layout.is_contiguous());
+    }
+
+    #[test]
+    fn contiguity_in_any_mode_order() {
+        assert_eq!(Layout::row_major([4, 3]).contiguity(), Some(Contiguity::RowMajor));
+        assert_eq!(Layout::col_major([4, 3]).contiguity(), Some(Contiguity::ColMajor));
+        assert_eq!(Layout::col_major([4, 3]).memory_order(), Some(vec![0, 1]));
+
+        let permuted = Layout::row_major([2, 3, 4]).with_stride([4, 8, 1]);
+        assert_eq!(permuted.contiguity(), Some(Contiguity::Permuted));
+        assert_eq!(permuted.memory_order(), Some(vec![2, 0, 1]));
+
+        // Extent-1 modes do not break contiguity whatever their stride
+        assert!(Layout::row_major([3, 1]).with_stride([1, 7]).is_contiguous());
+        // Gaps and overlaps do
+        assert!(!Layout::row_major([2, 3]).with_stride([4, 1]).is_contiguous());
+        assert!(!Layout::row_major([2, 3]).with_stride([1, 1]).is_contiguous());
+        assert_eq!(Layout::col_major([4, 3]).flip(0).contiguity(), None);
+    }
+
+    #[test]
+    fn constructors_accept_conversions() {
+        let layout = Layout::col_major([4, 3]).with_stride((1, 4));
+        let manual = Layout::col_major(Shape::new(Tuple::int(vec![4, 3])));
+        assert_eq!(layout.shape(), manual.shape());
+        assert_eq!(layout.stride(), manual.stride());
+        assert_eq!(layout.crd2idx([1, 2]), 9);
+        assert_eq!(Layout::row_major((2, (3, 4))).stride().to_string(), "(12,(4,1))");
+    }
+
+    #[test]
+    fn try_crd2idx_reports_errors() {
+        let layout = Layout::row_major([2, 3]);
+        assert_eq!(layout.try_crd2idx([1, 2]), Ok(5));
+        assert!(matches!(layout.try_crd2idx([2, 0]), Err(Error::OutOfBounds { .. })));
+        assert_eq!(
+            layout.try_crd2idx([1]),
+            Err(Error::RankMismatch { op: "crd2idx", expected: 2, found: 1 })
+        );
+        assert!(matches!(layout.flip(1).try_crd2idx([1, 2]), Err(Error::Unsupported { op: "crd2idx", .. })));
+    }
+
+    #[test]
+    fn flip_negates_mode() {
+        let layout = Layout::row_major([2, 3]).flip(1);
+        assert!(layout.is_reversed(1) && !layout.is_reversed(0));
+        assert!(!layout.is_contiguous());
+        assert_eq!(layout.signed_stride(), vec![3, -1]);
+        assert_eq!(layout.crd2offset(&Tuple::int(vec![1, 2])), 1);
+        assert_eq!(layout.flip(1), Layout::row_major([2, 3]));
+    }
+
+    #[test]
+    fn offset2crd_inverts_crd2offset() {
+        for layout in [
+            Layout::row_major([3, 4]),
+            Layout::col_major([3, 4]),
+            Layout::row_major([3, 4]).flip(0),
+            Layout::row_major((2, (3, 2))).with_stride((1, (4, 2))),
+        ] {
+            for crd in crate::shape::coords(layout.shape()) {
+                let flat = Tuple::Int(crd.flatten());
+                assert_eq!(layout.offset2crd(layout.crd2offset(&crd)), Some(flat), "{layout:?}");
+            }
+        }
+        // Gap between rows of a padded layout
+        assert_eq!(Layout::row_major([2, 3]).with_stride((8, 1)).offset2crd(5), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "crd2offset")]
+    fn crd2idx_rejects_reversed_layout() {
+        Layout::row_major([2, 3]).flip(0).crd2idx([0, 0]);
+    }
+
+    #[test]
+    fn flat_shape_and_stride_follow_updates() {
+        let layout = crate::layout!((8, (2, 4)) : (8, (4, 1)));
+        assert_eq!((layout.flat_shape(), layout.flat_stride()), (&[8, 2, 4][..], &[8, 4, 1][..]));
+
+        let layout = layout.with_stride(Tuple::tup(vec![Tuple::int1(1), Tuple::int(vec![32, 8])]));
+        assert_eq!(layout.flat_stride(), &[1, 32, 8]);
+        assert_eq!(layout.flip(2).flat_stride(), &[1, 32, 8]);
+
+        // Past `INLINE_RANK` modes the flat modes live on the heap; indexing
+        // and updates are unchanged
+        let layout = Layout::col_major([2, 3, 4, 5, 6]);
+        assert!(layout.flat_shape().len() > INLINE_RANK);
+        assert_eq!(layout.flat_stride(), &[1, 2, 6, 24, 120]);
+        assert_eq!(layout.crd2idx_flat(&[1, 2, 3, 4, 5]), 1 + 4 + 18 + 96 + 600);
+        let layout = layout.with_stride([720, 120, 30, 6, 1]);
+        assert_eq!((layout.flat_shape(), layout.flat_stride()), (&[2, 3, 4, 5, 6][..], &[720, 120, 30, 6, 1][..]));
+    }
+
+    #[test]
+    fn mode_edits_move_shape_stride_and_reversal_together() {
+        let batched = Layout::row_major([4, 2, 3]);
+        let matrix = batched.remove_mode(0);
+        assert_eq!((matrix.flat_shape(), matrix.flat_stride()), (&[2, 3][..], &[3, 1][..]));
+
+        let grouped = batched.group_modes(1..3);
+        assert_eq!(format!("{}:{}", grouped.shape(), grouped.stride()), "(4,(2,3)):(6,(3,1))");
+        assert_eq!(grouped.ungroup(1), batched);
+
+        let broadcast = matrix.prepend_mode(&Layout::row_major([4]).with_stride([0]));
+        assert_eq!(format!("{}:{}", broadcast.shape(), broadcast.stride()), "(4,2,3):(0,3,1)");
+
+        let flipped = matrix.flip(1).prepend_mode(&Layout::row_major([5]));
+        assert!(!flipped.is_reversed(1) && flipped.is_reversed(2));
+        assert_eq!(flipped.crd2offset_flat(&[1, 1, 2]), 1 + 3 - 2);
+        assert_eq!(flipped.remove_mode(1).signed_stride(), vec![1, -1]);
+    }
+
+    #[test]
+    fn select_and_permute_carry_strides() {
+        let layout = Layout::row_major([2, 3, 4]).group_modes(1..3);
+        let t = layout.permute_modes(&[1, 0]);
+        assert_eq!(format!("{}:{}", t.shape(), t.stride()), "((3,4),2):((4,1),12)");
+        assert_eq!(t.crd2idx(Tuple::int(vec![2, 3, 1])), layout.crd2idx(Tuple::int(vec![1, 2, 3])));
+
+        let picked = Layout::col_major([2, 3, 4]).flip(2).select_modes(&[2, 0]);
+        assert_eq!((picked.flat_shape(), picked.signed_stride()), (&[4, 2][..], vec![-6, 1]));
+        assert_eq!(layout.shape().select(&[1, 1]).to_string(), "((3,4),(3,4))");
+    }
+
+    #[test]
+    fn extent_counts_modes_not_leaves() {
+        let layout = Layout::row_major([4, 2, 3]).group_modes(1..3);
+        assert_eq!((layout.rank(), layout.extent(0), layout.extent(1)), (2, 4, 6));
+        assert_eq!(layout.flat_shape().len(), 3);
+        assert_eq!(Layout::col_major([5, 7]).rank(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a permutation")]
+    fn permute_rejects_repeated_modes() {
+        Layout::row_major([2, 3]).permute_modes(&[0, 0]);
+    }
+
+    #[test]
+    fn flat_coordinates_match_tuples() {
+        let layout = crate::layout!((8, (2, 4)) : (8, (4, 1)));
+        let mut crd = [0; 3];
+        for idx in [0, 13, 63] {
+            layout.idx2crd_flat(idx, &mut crd);
+            assert_eq!(crd.to_vec(), layout.idx2crd(idx).flatten());
+            assert_eq!(layout.crd2idx_flat(&crd), idx);
+        }
+
+        let flipped = Layout::row_major([2, 3]).flip(1);
+        assert_eq!(flipped.crd2offset_flat(&[1, 2]), flipped.crd2offset(&Tuple::int(vec![1, 2])));
+    }
+
+    #[test]
+    fn equivalence_ignores_nesting_and_unit_modes() {
+        let flat = Layout::row_major([8, 8]);
+        let nested = crate::layout!((8, (2, 4)) : (8, (4, 1)));
+        assert!(nested.is_equivalent(&flat));
+        assert!(Layout::row_major([4, 1, 16]).is_equivalent(&Layout::row_major([64])));
+        assert_eq!(flat.canonical().modes(), &[(64, 1)]);
+
+        // Same codomain, different mapping
+        assert!(!Layout::col_major([8, 8]).is_equivalent(&flat));
+        assert!(!flat.flip(1).is_equivalent(&flat));
+        assert!(Layout::row_major([0, 3]).is_equivalent(&Layout::col_major([5, 0])));
+
+        let keys: HashSet<_> = [flat.clone(), nested, Layout::col_major([8, 8])].iter().map(Layout::canonical).collect();
+        assert_eq!(keys.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "not congruent")]
+    fn with_stride_rejects_incongruent_stride() {
+        let shape = Shape::new(Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 4])]));
+        Layout::row_major(shape).with_stride(Tuple::int(vec![12, 4, 1]));
+    }
 }
 
 
@@ -1,23 +1,10 @@
 // src/layout_algebra.rs
-use crate::layout::{Layout, LayoutPolicy, RowMajor};
+use crate::layout::Layout;
 use crate::shape::Shape;
 use crate::tuple::Tuple;
+use crate::error::{check_rank, Error, Result};
 
 /// ---------- Helper functions for tuple arithmetic ----------
-fn subtract_tuples(a: &Tuple, b: &Tuple) -> Tuple {
-    match (a, b) {
-        (Tuple::Int(av), Tuple::Int(bv)) => {
-            assert_eq!(av.len(), bv.len(), "Tuple length mismatch in subtract_tuples");
-            Tuple::Int(av.iter().zip(bv.iter()).map(|(x, y)| x - y).collect())
-        }
-        (Tuple::Tup(av), Tuple::Tup(bv)) => {
-            assert_eq!(av.len(), bv.len(), "Tuple length mismatch in subtract_tuples");
-            Tuple::Tup(av.iter().zip(bv.iter()).map(|(x, y)| subtract_tuples(x, y)).collect())
-        }
-        _ => panic!("Tuple shape mismatch in subtract_tuples"),
-    }
-}
-
 /// Take the first N elements from Tuple recursively
 fn take_tuple(a: &Tuple, n: usize) -> Tuple {
     match a {
@@ -34,84 +21,148 @@ fn skip_tuple(a: &Tuple, n: usize) -> Tuple {
     }
 }
 
-/// ---------- Layout Algebra Operations ----------
+/// Split every mode of `(shape, stride)` by the matching `tiler` extent.
+/// Returns `(tile_shape, tile_stride, rest_shape, rest_stride)`, all congruent to `tiler`:
+/// the tile keeps the original stride, the rest steps over whole tiles.
+/// Sub-trees whose nesting differs from the tiler are matched on their flattened leaves.
+fn divide_modes(shape: &Tuple, stride: &Tuple, tiler: &Tuple) -> (Tuple, Tuple, Tuple, Tuple) {
+    match (shape, stride, tiler) {
+        (Tuple::Tup(ls), Tuple::Tup(ss), Tuple::Tup(ts)) if ls.len() == ts.len() && ss.len() == ts.len() => {
+            let mut out = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+            for ((l, s), t) in ls.iter().zip(ss.iter()).zip(ts.iter()) {
+                let (ts_, tst, rs, rst) = divide_modes(l, s, t);
+                out.0.push(ts_);
+                out.1.push(tst);
+                out.2.push(rs);
+                out.3.push(rst);
+            }
+            (Tuple::Tup(out.0), Tuple::Tup(out.1), Tuple::Tup(out.2), Tuple::Tup(out.3))
+        }
+        _ => {
+            let l = Tuple::Int(shape.flatten());
+            let s = Tuple::Int(stride.flatten());
+            let t = Tuple::Int(tiler.flatten());
+            assert_eq!(l.len(), s.len(), "Shape/Stride mismatch in layout divide");
 
-/// logical_divide: ((TileM,RestM),(TileN,RestN),...)
-pub fn logical_divide(layout: &Layout, tiler: &Layout) -> Layout {
-    fn recur(l: &Tuple, t: &Tuple) -> Tuple {
-        match (l, t) {
-            (Tuple::Int(ldims), Tuple::Int(tdims)) => {
-                let mut out = Vec::new();
-                for (l_i, t_i) in ldims.iter().zip(tdims.iter()) {
-                    out.push(Tuple::Int(vec![*t_i, l_i - t_i]));
-                }
-                Tuple::Tup(out)
+            let rest = l.div(&t);
+            let rest_stride = s.mul(&t);
+            (t, s, rest, rest_stride)
+        }
+    }
+}
+
+/// Check the preconditions `divide_modes` asserts, without panicking.
+fn check_divide(op: &'static str, shape: &Tuple, stride: &Tuple, tiler: &Tuple) -> Result<()> {
+    match (shape, stride, tiler) {
+        (Tuple::Tup(ls), Tuple::Tup(ss), Tuple::Tup(ts)) if ls.len() == ts.len() && ss.len() == ts.len() => {
+            for ((l, s), t) in ls.iter().zip(ss.iter()).zip(ts.iter()) {
+                check_divide(op, l, s, t)?;
             }
-            (Tuple::Tup(lv), Tuple::Tup(tv)) => {
-                Tuple::Tup(lv.iter().zip(tv.iter()).map(|(l_, t_)| recur(l_, t_)).collect())
+            Ok(())
+        }
+        _ => {
+            check_rank(op, shape.flat_len(), tiler.flat_len())?;
+            if stride.flat_len() != shape.flat_len() {
+                return Err(Error::NotCongruent { op, lhs: shape.clone(), rhs: stride.clone() });
+            }
+            if tiler.iter_flat().any(|&t| t == 0) {
+                return Err(Error::ShapeMismatch { op, lhs: Shape::new(shape.clone()), rhs: Shape::new(tiler.clone()) });
             }
-            _ => panic!("Tuple shape mismatch in logical_divide"),
+            Ok(())
         }
     }
+}
 
-    let shape = Shape::new(recur(&layout.shape().dims, &tiler.shape().dims));
-    Layout::new::<RowMajor>(shape)
+fn check_layout_divide(op: &'static str, layout: &Layout, tiler: &Layout) -> Result<()> {
+    if layout.has_reversed_modes() {
+        return Err(Error::Unsupported { op, what: "a layout with reversed modes".into() });
+    }
+    check_divide(op, &layout.shape().dims, layout.stride(), &tiler.shape().dims)
 }
 
-/// zipped_divide: ((TileM,TileN),(RestM,RestN,...))
-pub fn zipped_divide(layout: &Layout, tiler: &Layout) -> Layout {
-    fn recur(l: &Tuple, t: &Tuple) -> (Tuple, Tuple) {
-        match (l, t) {
-            (Tuple::Int(ldims), Tuple::Int(tdims)) => {
-                let tile = Tuple::Int(tdims.clone());
-                let rest = Tuple::Int(ldims.iter().zip(tdims.iter()).map(|(l_i, t_i)| l_i / t_i).collect());
-                (tile, rest)
-            }
-            (Tuple::Tup(lv), Tuple::Tup(tv)) => {
-                let tiles: Vec<Tuple> = lv.iter().zip(tv.iter()).map(|(l_, t_)| recur(l_, t_).0).collect();
-                let rests: Vec<Tuple> = lv.iter().zip(tv.iter()).map(|(l_, t_)| recur(l_, t_).1).collect();
-                (Tuple::Tup(tiles), Tuple::Tup(rests))
-            }
-            _ => panic!("Tuple shape mismatch in zipped_divide"),
+/// Pair tile and rest mode-by-mode: ((TileM,RestM),(TileN,RestN),...)
+fn zip_modes(tile: &Tuple, rest: &Tuple) -> Tuple {
+    match (tile, rest) {
+        (Tuple::Int(t), Tuple::Int(r)) => {
+            Tuple::Tup(t.iter().zip(r.iter()).map(|(t_i, r_i)| Tuple::Int(vec![*t_i, *r_i])).collect())
+        }
+        (Tuple::Tup(t), Tuple::Tup(r)) => {
+            Tuple::Tup(t.iter().zip(r.iter()).map(|(t_, r_)| zip_modes(t_, r_)).collect())
         }
+        _ => panic!("Tuple shape mismatch in logical_divide"),
     }
+}
+
+/// ((TileM,TileN), RestM, RestN, ...) from (Tile, Rest)
+fn unpack_rest(tile: Tuple, rest: &Tuple) -> Tuple {
+    let mut out = vec![tile];
+    match rest {
+        Tuple::Tup(r) => out.extend(r.iter().cloned()),
+        Tuple::Int(r) => out.push(Tuple::Int(r.clone())),
+    }
+    Tuple::Tup(out)
+}
 
-    let (tile, rest) = recur(&layout.shape().dims, &tiler.shape().dims);
-    let shape = Shape::new(Tuple::Tup(vec![tile, rest]));
-    Layout::new::<RowMajor>(shape)
+// ---------- Layout Algebra Operations ----------
+
+/// Tile/rest split of `layout` by `tiler`, arranged by `arrange` into the
+/// divided layout. Every divide keeps indexing the same memory as `layout`:
+/// tile modes keep the original stride, rest modes are scaled by the tile
+/// extent, and the offset carries over. A tile extent that does not divide
+/// its mode rounds the rest extent down, so the rest covers only the full
+/// tiles; `Layout::rest_iter` and the tiled tensor views cover the edge.
+/// Reversed modes would need a shifted origin for partial tiles, so they
+/// are rejected here.
+fn divide_layout(layout: &Layout, tiler: &Layout, arrange: impl Fn(Tuple, Tuple, Tuple, Tuple) -> (Tuple, Tuple)) -> Layout {
+    assert!(!layout.has_reversed_modes(), "layout divides do not support reversed modes");
+    let (ts, tst, rs, rst) = divide_modes(&layout.shape().dims, layout.stride(), &tiler.shape().dims);
+    let (shape, stride) = arrange(ts, tst, rs, rst);
+    Layout::with_shape_stride(Shape::new(shape), stride).with_offset(layout.offset())
+}
+
+/// logical_divide: ((TileM,RestM),(TileN,RestN),...)
+pub fn logical_divide(layout: &Layout, tiler: &Layout) -> Layout {
+    divide_layout(layout, tiler, |ts, tst, rs, rst| (zip_modes(&ts, &rs), zip_modes(&tst, &rst)))
+}
+
+/// zipped_divide: ((TileM,TileN),(RestM,RestN,...))
+pub fn zipped_divide(layout: &Layout, tiler: &Layout) -> Layout {
+    divide_layout(layout, tiler, |ts, tst, rs, rst| (Tuple::Tup(vec![ts, rs]), Tuple::Tup(vec![tst, rst])))
 }
 
 /// tiled_divide: ((TileM,TileN), RestM, RestN, ...)
 pub fn tiled_divide(layout: &Layout, tiler: &Layout) -> Layout {
-    let zipped = zipped_divide(layout, tiler);
-    match &zipped.shape().dims {
-        Tuple::Tup(v) => {
-            let mut flattened = Vec::new();
-            // First element is tile tuple
-            flattened.push(v[0].clone());
-            // Rest elements: flatten rest tuple if Tup
-            match &v[1] {
-                Tuple::Tup(rest) => flattened.extend(rest.clone()),
-                Tuple::Int(rest) => flattened.push(Tuple::Int(rest.clone())),
-            }
-            Layout::new::<RowMajor>(Shape::new(Tuple::Tup(flattened)))
-        }
-        _ => panic!("Unexpected shape in tiled_divide"),
-    }
+    divide_layout(layout, tiler, |ts, tst, rs, rst| (unpack_rest(ts, &rs), unpack_rest(tst, &rst)))
 }
 
 /// flat_divide: (TileM,TileN, RestM, RestN, ...)
 pub fn flat_divide(layout: &Layout, tiler: &Layout) -> Layout {
-    let tiled = tiled_divide(layout, tiler);
-    fn flatten(t: &Tuple) -> Vec<usize> {
-        match t {
-            Tuple::Int(v) => v.clone(),
-            Tuple::Tup(vs) => vs.iter().flat_map(|x| flatten(x)).collect(),
-        }
-    }
+    divide_layout(layout, tiler, |ts, tst, rs, rst| {
+        (Tuple::Int(unpack_rest(ts, &rs).flatten()), Tuple::Int(unpack_rest(tst, &rst).flatten()))
+    })
+}
+
+/// Non-panicking versions of the divides: a rank mismatch or zero tile
+/// extent between `layout` and `tiler` is returned as an error. A tile
+/// extent that does not divide its mode is not one; the rest rounds down.
+pub fn try_logical_divide(layout: &Layout, tiler: &Layout) -> Result<Layout> {
+    check_layout_divide("logical_divide", layout, tiler)?;
+    Ok(logical_divide(layout, tiler))
+}
+
+pub fn try_zipped_divide(layout: &Layout, tiler: &Layout) -> Result<Layout> {
+    check_layout_divide("zipped_divide", layout, tiler)?;
+    Ok(zipped_divide(layout, tiler))
+}
+
+pub fn try_tiled_divide(layout: &Layout, tiler: &Layout) -> Result<Layout> {
+    check_layout_divide("tiled_divide", layout, tiler)?;
+    Ok(tiled_divide(layout, tiler))
+}
 
-    let flat_dims = flatten(&tiled.shape().dims);
-    Layout::new::<RowMajor>(Shape::new(Tuple::Int(flat_dims)))
+pub fn try_flat_divide(layout: &Layout, tiler: &Layout) -> Result<Layout> {
+    check_layout_divide("flat_divide", layout, tiler)?;
+    Ok(flat_divide(layout, tiler))
 }
 
 /// ---------- Unit Tests ----------
@@ -119,7 +170,7 @@ pub fn flat_divide(layout: &Layout, tiler: &Layout) -> Layout {
 mod tests {
     use super::*;
     use crate::tuple::Tuple;
-    use crate::layout::Layout;
+    use crate::layout::{Layout, RowMajor};
     use crate::shape::Shape;
 
     #[test]
@@ -134,7 +185,8 @@ mod tests {
         ])));
 
         let result = logical_divide(&layout, &tiler);
-        assert_eq!(result.shape().to_string(), "((2,6),(3,3))");
+        assert_eq!(result.shape().to_string(), "((2,4),(3,2))");
+        assert_eq!(result.stride().to_string(), "((6,12),(1,3))");
     }
 
     #[test]
@@ -150,6 +202,7 @@ mod tests {
 
         let result = zipped_divide(&layout, &tiler);
         assert_eq!(result.shape().to_string(), "((2,3),(4,2))");
+        assert_eq!(result.stride().to_string(), "((6,1),(12,3))");
     }
 
     #[test]
@@ -165,6 +218,7 @@ mod tests {
 
         let result = tiled_divide(&layout, &tiler);
         assert_eq!(result.shape().to_string(), "((2,3),4,2)");
+        assert_eq!(result.stride().to_string(), "((6,1),12,3)");
     }
 
     #[test]
@@ -180,6 +234,83 @@ mod tests {
 
         let result = flat_divide(&layout, &tiler);
         assert_eq!(result.shape().to_string(), "(2,3,4,2)");
+        assert_eq!(result.stride().to_string(), "(6,1,12,3)");
+    }
+
+    #[test]
+    fn divided_layout_indexes_same_memory() {
+        let layout = Layout::col_major(Shape::new(Tuple::int(vec![8, 6])));
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let flat = flat_divide(&layout, &tiler);
+
+        for ti in 0..2 {
+            for tj in 0..3 {
+                for ri in 0..4 {
+                    for rj in 0..2 {
+                        let divided = flat.crd2idx(Tuple::int(vec![ti, tj, ri, rj]));
+                        let original = layout.crd2idx(Tuple::int(vec![ri * 2 + ti, rj * 3 + tj]));
+                        assert_eq!(divided, original);
+                    }
+                }
+            }
+        }
+        assert_eq!(flat.cosize(), layout.cosize());
+    }
+
+    #[test]
+    fn try_divide_reports_rank_mismatch() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![8, 6])));
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3, 1])));
+        assert_eq!(
+            try_flat_divide(&layout, &tiler),
+            Err(Error::RankMismatch { op: "flat_divide", expected: 2, found: 3 })
+        );
+
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 0])));
+        assert!(try_zipped_divide(&layout, &tiler).is_err());
+
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        assert_eq!(try_tiled_divide(&layout, &tiler), Ok(tiled_divide(&layout, &tiler)));
+    }
+
+    #[test]
+    fn divide_accepts_mismatched_nesting() {
+        // Tup-shaped layout (as produced by subview_2d) divided by an Int tiler
+        let layout = Layout::with_shape_stride(
+            Shape::new(Tuple::tup(vec![Tuple::int1(4), Tuple::int1(6)])),
+            Tuple::tup(vec![Tuple::int1(10), Tuple::int1(1)]),
+        );
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+
+        let flat = flat_divide(&layout, &tiler);
+        assert_eq!(flat.shape().to_string(), "(2,3,2,2)");
+        assert_eq!(flat.stride().to_string(), "(10,1,20,3)");
+    }
+
+    #[test]
+    fn non_dividing_tiler_keeps_only_full_tiles() {
+        // 7 x 5 by 2 x 3: three full tiles down, one across
+        let layout = Layout::row_major([7, 5]);
+        let tiler = Layout::row_major([2, 3]);
+        let zipped = try_zipped_divide(&layout, &tiler).unwrap();
+        assert_eq!(format!("{}:{}", zipped.shape(), zipped.stride()), "((2,3),(3,1)):((5,1),(10,3))");
+        let logical = try_logical_divide(&layout, &tiler).unwrap();
+        assert_eq!(logical.shape().to_string(), "((2,3),(3,1))");
+        assert_eq!(logical.size(), 6 * 3);
+    }
+
+    #[test]
+    fn divides_keep_the_layout_offset() {
+        let tiler = Layout::row_major([2, 3]);
+        let plain = Layout::row_major([8, 6]);
+        let layout = plain.clone().with_offset(13);
+
+        for divide in [logical_divide, zipped_divide, tiled_divide, flat_divide] {
+            let divided = divide(&layout, &tiler);
+            assert_eq!(divided.offset(), 13);
+            assert!(divided.same_modes(&divide(&plain, &tiler)));
+        }
+        assert_eq!(try_flat_divide(&layout, &tiler).map(|l| l.offset()), Ok(13));
     }
 }
 
@@ -1,22 +1,30 @@
-use crate::shape::Shape;
-use crate::tuple::Tuple;
 use crate::layout::Layout;
 use crate::layout_algebra::{flat_divide};
+#[cfg(test)]
+use crate::shape::Shape;
+#[cfg(test)]
+use crate::tuple::Tuple;
 
+/// Lexicographic counter over a box of extents (last dimension fastest)
 pub struct LayoutIterator {
-    shape: Vec<usize>,   // owns tile dimensions
-    current: Vec<usize>,
-    done: bool,
+    counter: Layout,     // row-major over the iterated extents
+    rank: usize,
+    front: usize,        // next linear position from the front
+    back: usize,         // one past the next linear position from the back
 }
 
 impl LayoutIterator {
     pub fn new(shape: Vec<usize>) -> Self {
-        let ndim = shape.len();
-        Self {
-            shape,
-            current: vec![0; ndim],
-            done: false,
-        }
+        let back = shape.iter().product();
+        let rank = shape.len();
+        Self { counter: Layout::row_major(shape), rank, front: 0, back }
+    }
+
+    /// Coordinate of linear position `i`
+    fn coord(&self, i: usize) -> Vec<usize> {
+        let mut crd = vec![0; self.rank];
+        self.counter.idx2crd_flat(i, &mut crd);
+        crd
     }
 }
 
@@ -24,29 +32,114 @@ impl Iterator for LayoutIterator {
     type Item = Vec<usize>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
+        if self.front == self.back {
             return None;
         }
+        self.front += 1;
+        Some(self.coord(self.front - 1))
+    }
 
-        let result = self.current.clone();
-
-        // Increment index lexicographically
-        for i in (0..self.current.len()).rev() {
-            self.current[i] += 1;
-            if self.current[i] < self.shape[i] {
-                break;
-            } else {
-                self.current[i] = 0;
-                if i == 0 {
-                    self.done = true;
-                }
-            }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl DoubleEndedIterator for LayoutIterator {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
         }
+        self.back -= 1;
+        Some(self.coord(self.back))
+    }
+}
 
-        Some(result)
+impl ExactSizeIterator for LayoutIterator {}
+
+/* ---------- tile-space iterators ---------- */
+
+/// Start coordinates (in element space) of every full tile
+pub struct TileStartIter {
+    counter: LayoutIterator,
+    tile: Vec<usize>,
+}
+
+impl Iterator for TileStartIter {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.counter.next()?;
+        Some(idx.iter().zip(self.tile.iter()).map(|(i, t)| i * t).collect())
     }
 }
 
+/// Linear base offset (start coordinate · stride) of every full tile
+pub struct TileOffsetIter {
+    starts: TileStartIter,
+    layout: Layout,
+}
+
+impl Iterator for TileOffsetIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.starts.next()?;
+        Some(self.layout.crd2idx_flat(&start))
+    }
+}
+
+impl Layout {
+    /// Split `flat_divide(self, tiler)` into (tile extents, tile counts)
+    fn tile_counts(&self, tiler: &Layout) -> (Vec<usize>, Vec<usize>) {
+        let flat = flat_divide(self, tiler).shape().dims.flatten();
+        let t = tiler.shape().flat_len();
+        (flat[..t].to_vec(), flat[t..].to_vec())
+    }
+
+    /// Iterate over the element-space start coordinate of every full tile.
+    /// Partial tiles at the edges are covered by `rest_iter`.
+    pub fn tile_iter(&self, tiler: &Layout) -> TileStartIter {
+        let (tile, counts) = self.tile_counts(tiler);
+        TileStartIter {
+            counter: LayoutIterator::new(counts),
+            tile,
+        }
+    }
+
+    /// Iterate over the linear base offset of every full tile, i.e. the
+    /// value `crd2idx` returns for each coordinate yielded by `tile_iter`.
+    pub fn tile_offsets(&self, tiler: &Layout) -> TileOffsetIter {
+        TileOffsetIter {
+            starts: self.tile_iter(tiler),
+            layout: self.clone(),
+        }
+    }
+
+    /// Start coordinate of the remainder block left uncovered by full tiles.
+    /// Dimensions that divide evenly start at 0; yields nothing if every
+    /// dimension divides evenly.
+    pub fn rest_iter(&self, tiler: &Layout) -> std::option::IntoIter<Vec<usize>> {
+        let full = self.shape().dims.flatten();
+        let (tile, counts) = self.tile_counts(tiler);
+
+        let mut has_rest = false;
+        let start = full
+            .iter()
+            .zip(tile.iter().zip(counts.iter()))
+            .map(|(f, (t, c))| {
+                if f % t == 0 {
+                    0
+                } else {
+                    has_rest = true;
+                    c * t
+                }
+            })
+            .collect();
+
+        if has_rest { Some(start) } else { None }.into_iter()
+    }
+}
 
 #[test]
 fn test_tile_iter_2d() {
@@ -136,3 +229,40 @@ fn test_rest_iter_3d() {
     assert_eq!(rests[0], vec![3,4,5]); // starting index of remaining block
 }
 
+
+#[test]
+fn test_tile_offsets_2d() {
+    // 4x6 row-major, tile 2x3 -> starts (0,0),(0,3),(2,0),(2,3)
+    let layout = Layout::row_major(Shape::new(Tuple::int(vec![4, 6])));
+    let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+
+    let offsets: Vec<usize> = layout.tile_offsets(&tiler).collect();
+    assert_eq!(offsets, vec![0, 3, 12, 15]);
+
+    for (start, off) in layout.tile_iter(&tiler).zip(layout.tile_offsets(&tiler)) {
+        assert_eq!(layout.crd2idx(Tuple::int(start)), off);
+    }
+}
+
+#[test]
+fn test_tile_larger_than_layout() {
+    let layout = Layout::row_major(Shape::new(Tuple::int(vec![2, 2])));
+    let tiler = Layout::row_major(Shape::new(Tuple::int(vec![4, 4])));
+
+    assert_eq!(layout.tile_iter(&tiler).count(), 0);
+    assert_eq!(layout.rest_iter(&tiler).collect::<Vec<_>>(), vec![vec![0, 0]]);
+}
+
+#[test]
+fn test_layout_iterator_both_ends() {
+    let mut it = LayoutIterator::new(vec![2, 3]);
+    assert_eq!(it.len(), 6);
+    assert_eq!(it.next_back(), Some(vec![1, 2]));
+    assert_eq!(it.next(), Some(vec![0, 0]));
+    assert_eq!(it.len(), 4);
+
+    let rest: Vec<_> = it.rev().collect();
+    assert_eq!(rest, vec![vec![1, 1], vec![1, 0], vec![0, 2], vec![0, 1]]);
+
+    assert_eq!(LayoutIterator::new(vec![3, 0]).len(), 0);
+}
@@ -1,11 +1,68 @@
+#[macro_use]
+mod macros;
+
+pub mod error;
+pub mod config;
 pub mod dim;
 pub mod tuple;
 pub mod shape;
+pub mod shape_infer;
 pub mod layout;
 pub mod layout_algebra;
+pub mod invariants;
+pub mod layout_iter;
+pub mod static_layout;
+pub mod swizzle;
+pub mod allocator;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+mod provenance;
 pub mod tensor;
 pub mod tiled_tensor;
+pub mod matrix;
+pub mod diag;
+pub mod pack;
 
 pub mod copy;
+pub mod cast;
+pub mod compare;
+pub mod quant;
 pub mod gemm;
+pub mod contract;
+pub mod kron;
+pub mod complex;
+#[cfg(feature = "fft")]
+pub mod fft;
 pub mod blas;
+pub mod kernel;
+pub mod plan;
+pub mod expr;
+pub mod reduction;
+pub mod strassen;
+pub mod parallel;
+pub mod graph;
+#[cfg(feature = "async")]
+pub mod stream;
+#[cfg(feature = "trace")]
+pub mod trace;
+
+pub mod bench_utils;
+pub mod random;
+pub mod reference;
+pub mod tune;
+pub mod hw;
+pub mod scatter;
+pub mod factor;
+pub mod device;
+pub mod ffi;
+#[cfg(feature = "ndarray")]
+pub mod ndarray;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(test)]
+mod test_util;
+
+pub use error::{Error, Result};
@@ -0,0 +1,141 @@
+/* ===== Layout DSL ===== */
+
+/// Build a hierarchical `Tuple` from CuTe-style nested parentheses.
+/// Leaves are single tokens (literals, identifiers, or `{ expr }` blocks).
+///
+/// ```
+/// use rutilelib::tuple;
+/// let t = tuple!(2, (3, 4));
+/// assert_eq!(t.to_string(), "(2,(3,4))");
+/// assert_eq!(t.size(), 24);
+/// ```
+#[macro_export]
+macro_rules! tuple {
+    (( $($mode:tt),* $(,)? )) => {
+        $crate::tuple::Tuple::from_modes(vec![$($crate::tuple!($mode)),*])
+    };
+    ($leaf:expr) => {
+        $crate::tuple::Tuple::int1($leaf)
+    };
+    ($($mode:tt),+ $(,)?) => {
+        $crate::tuple!(($($mode),+))
+    };
+}
+
+/// Build a `Shape` with the same notation as [`tuple!`].
+///
+/// ```
+/// use rutilelib::shape;
+/// assert_eq!(shape!(2, (3, 4)).flat_len(), 3);
+/// ```
+#[macro_export]
+macro_rules! shape {
+    ($($mode:tt),+ $(,)?) => {
+        $crate::shape::Shape::new($crate::tuple!($($mode),+))
+    };
+}
+
+/// Build a `Layout` from CuTe notation: `layout!(shape : stride)`.
+///
+/// ```
+/// use rutilelib::layout;
+/// let l = layout!((8, (2, 4)) : (8, (4, 1)));
+/// assert_eq!(l.stride().to_string(), "(8,(4,1))");
+/// ```
+///
+/// Without a stride the layout is row-major.
+#[macro_export]
+macro_rules! layout {
+    ($shape:tt : $stride:tt) => {
+        $crate::layout::Layout::row_major($crate::shape!($shape))
+            .with_stride($crate::tuple!($stride))
+    };
+    ($shape:tt) => {
+        $crate::layout::Layout::row_major($crate::shape!($shape))
+    };
+}
+
+/* ===== Diagnostics ===== */
+
+// `log::debug!` / `log::trace!` under the `log` feature. Without it the
+// arguments are still type-checked, so call sites need no `cfg`.
+macro_rules! log_debug {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+macro_rules! log_trace {
+    ($($arg:tt)+) => {{
+        #[cfg(feature = "log")]
+        log::trace!($($arg)+);
+        #[cfg(not(feature = "log"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::Layout;
+    use crate::shape::Shape;
+    use crate::tuple::Tuple;
+
+    #[test]
+    fn tuple_macro_nesting() {
+        assert_eq!(tuple!(2, 3), Tuple::int(vec![2, 3]));
+        assert_eq!(tuple!((2, 3)), Tuple::int(vec![2, 3]));
+        assert_eq!(tuple!(7), Tuple::int1(7));
+        assert_eq!(
+            tuple!(2, (3, 4)),
+            Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 4])])
+        );
+        assert_eq!(
+            tuple!((2, 3), (4, (5, 6))),
+            Tuple::tup(vec![
+                Tuple::int(vec![2, 3]),
+                Tuple::tup(vec![Tuple::int1(4), Tuple::int(vec![5, 6])]),
+            ])
+        );
+    }
+
+    #[test]
+    fn shape_macro_wraps_tuple() {
+        let n = 4;
+        assert_eq!(shape!(2, (3, n)), Shape::new(tuple!(2, (3, 4))));
+        assert_eq!(shape!(8).size(), 8);
+    }
+
+    #[test]
+    fn layout_macro_matches_manual_construction() {
+        let l = layout!((8, (2, 4)) : (8, (4, 1)));
+        let shape = Tuple::tup(vec![Tuple::int1(8), Tuple::int(vec![2, 4])]);
+        let stride = Tuple::tup(vec![Tuple::int1(8), Tuple::int(vec![4, 1])]);
+
+        assert_eq!(l.shape().dims, shape);
+        assert_eq!(*l.stride(), stride);
+        assert_eq!(l.shape().to_string(), "(8,(2,4))");
+    }
+
+    #[test]
+    fn layout_macro_defaults_to_row_major() {
+        let n = 3;
+        let l = layout!((4, n));
+        assert_eq!(l, Layout::row_major(Shape::new(Tuple::int(vec![4, 3]))));
+        assert_eq!(layout!(5 : 2).stride().to_string(), "2");
+    }
+
+    #[test]
+    fn layout_macro_accepts_block_leaves() {
+        let m = 4;
+        let l = layout!(({ m * 2 }, m) : (1, { m * 2 }));
+        assert_eq!(l.shape().to_string(), "(8,4)");
+        assert_eq!(l.crd2idx(Tuple::int(vec![1, 1])), 9);
+    }
+}
@@ -0,0 +1,234 @@
+// src/matrix.rs
+//
+// Rank-2 / rank-1 wrappers over `Tensor` for the common GEMM-style case,
+// where the hierarchical `Tuple` API is more than callers need.
+
+use std::ops::{Deref, DerefMut, Index, IndexMut, Mul};
+
+use crate::blas::NativeBlas;
+use crate::gemm::gemm_f32;
+use crate::layout::Layout;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+
+/* ===== Matrix ===== */
+
+/// Owned 2-D tensor with row/column accessors.
+pub struct Matrix<T>(Tensor<T>);
+
+impl<T> Matrix<T> {
+    /// Row-major `rows x cols` matrix over `data`.
+    pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
+        Matrix(Tensor::new(data, Layout::row_major([rows, cols])))
+    }
+
+    /// Wrap an existing tensor; its layout must flatten to two modes.
+    pub fn from_tensor(tensor: Tensor<T>) -> Self {
+        assert_eq!(tensor.layout().shape().flat_len(), 2, "Matrix requires a rank-2 layout");
+        Matrix(tensor)
+    }
+
+    pub fn into_tensor(self) -> Tensor<T> {
+        self.0
+    }
+
+    pub fn rows(&self) -> usize {
+        self.0.layout().shape().flat_at(0)
+    }
+
+    pub fn cols(&self) -> usize {
+        self.0.layout().shape().flat_at(1)
+    }
+
+    /// (row stride, column stride)
+    fn strides(&self) -> (usize, usize) {
+        let s = self.0.layout().stride();
+        (s.flat_at(0), s.flat_at(1))
+    }
+
+    /// Row `i` as a rank-1 view of length `cols()`.
+    pub fn row(&self, i: usize) -> TensorView<'_, T> {
+        assert!(i < self.rows(), "Matrix::row {i} out of bounds for {} rows", self.rows());
+        let (rs, cs) = self.strides();
+        line(self.0.as_view(), i * rs, self.cols(), cs).labelled("row")
+    }
+
+    /// Column `j` as a rank-1 view of length `rows()`.
+    pub fn col(&self, j: usize) -> TensorView<'_, T> {
+        assert!(j < self.cols(), "Matrix::col {j} out of bounds for {} cols", self.cols());
+        let (rs, cs) = self.strides();
+        line(self.0.as_view(), j * cs, self.rows(), rs).labelled("col")
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> TensorViewMut<'_, T> {
+        assert!(i < self.rows(), "Matrix::row_mut {i} out of bounds for {} rows", self.rows());
+        let (rs, cs) = self.strides();
+        let len = self.cols();
+        line_mut(self.0.as_view_mut(), i * rs, len, cs).labelled("row")
+    }
+
+    pub fn col_mut(&mut self, j: usize) -> TensorViewMut<'_, T> {
+        assert!(j < self.cols(), "Matrix::col_mut {j} out of bounds for {} cols", self.cols());
+        let (rs, cs) = self.strides();
+        let len = self.rows();
+        line_mut(self.0.as_view_mut(), j * cs, len, rs).labelled("col")
+    }
+}
+
+impl<T: Clone + Default> Matrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix::new(rows, cols, vec![T::default(); rows * cols])
+    }
+}
+
+/// Rank-1 view of `len` elements, `stride` apart, starting `offset` elements into `base`.
+fn line<T>(base: TensorView<'_, T>, offset: usize, len: usize, stride: usize) -> TensorView<'_, T> {
+    unsafe { base.at_offset(offset as isize, Layout::row_major(len).with_stride(stride)) }
+}
+
+fn line_mut<T>(base: TensorViewMut<'_, T>, offset: usize, len: usize, stride: usize) -> TensorViewMut<'_, T> {
+    unsafe { base.into_offset(offset as isize, Layout::row_major(len).with_stride(stride)) }
+}
+
+impl<T> Deref for Matrix<T> {
+    type Target = Tensor<T>;
+
+    fn deref(&self) -> &Tensor<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Matrix<T> {
+    fn deref_mut(&mut self) -> &mut Tensor<T> {
+        &mut self.0
+    }
+}
+
+/// `&a * &b` runs a single-precision GEMM through `NativeBlas`.
+impl Mul for &Matrix<f32> {
+    type Output = Matrix<f32>;
+
+    fn mul(self, rhs: &Matrix<f32>) -> Matrix<f32> {
+        assert_eq!(self.cols(), rhs.rows(), "Matrix product dimension mismatch");
+        let mut out = Matrix::zeros(self.rows(), rhs.cols());
+        gemm_f32(&NativeBlas, &self.as_view(), &rhs.as_view(), &mut out.as_view_mut(), 1.0, 0.0);
+        out
+    }
+}
+
+/* ===== Vector ===== */
+
+/// Owned 1-D tensor.
+pub struct Vector<T>(Tensor<T>);
+
+impl<T> Vector<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        let len = data.len();
+        Vector(Tensor::new(data, Layout::row_major(len)))
+    }
+
+    pub fn into_tensor(self) -> Tensor<T> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.layout().size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Deref for Vector<T> {
+    type Target = Tensor<T>;
+
+    fn deref(&self) -> &Tensor<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Vector<T> {
+    fn deref_mut(&mut self) -> &mut Tensor<T> {
+        &mut self.0
+    }
+}
+
+impl<T> Index<usize> for Vector<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.0.data()[i]
+    }
+}
+
+impl<T> IndexMut<usize> for Vector<T> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0.data_mut()[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iota(rows: usize, cols: usize) -> Matrix<f32> {
+        Matrix::new(rows, cols, (0..rows * cols).map(|x| x as f32).collect())
+    }
+
+    #[test]
+    fn rows_and_cols() {
+        let m = iota(3, 4);
+        assert_eq!((m.rows(), m.cols()), (3, 4));
+        assert_eq!(m[(2, 1)], 9.0);
+
+        let row: Vec<f32> = m.row(1).indexed_iter().map(|(_, v)| *v).collect();
+        assert_eq!(row, vec![4.0, 5.0, 6.0, 7.0]);
+
+        let col: Vec<f32> = m.col(2).indexed_iter().map(|(_, v)| *v).collect();
+        assert_eq!(col, vec![2.0, 6.0, 10.0]);
+    }
+
+    #[test]
+    fn col_major_tensor() {
+        let t = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::col_major([2, 3]));
+        let m = Matrix::from_tensor(t);
+        assert_eq!(m[(1, 0)], 1);
+        assert_eq!(m[(0, 1)], 2);
+
+        let row: Vec<i32> = m.row(1).indexed_iter().map(|(_, v)| *v).collect();
+        assert_eq!(row, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn mutate_through_views() {
+        let mut m = Matrix::<i32>::zeros(2, 3);
+        m[(0, 2)] = 7;
+        for (_, v) in m.col_mut(1).indexed_iter_mut() {
+            *v = 1;
+        }
+        assert_eq!(m.data(), &[0, 1, 7, 0, 1, 0]);
+    }
+
+    #[test]
+    fn matrix_product() {
+        let a = iota(2, 3);
+        let b = iota(3, 2);
+        let c = &a * &b;
+        assert_eq!(c.data(), &[10.0, 13.0, 28.0, 40.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn index_out_of_bounds() {
+        let m = iota(2, 2);
+        let _ = m[(0, 2)];
+    }
+
+    #[test]
+    fn vector_index() {
+        let mut v = Vector::new(vec![1, 2, 3]);
+        v[1] = 5;
+        assert_eq!(v.len(), 3);
+        assert_eq!(v.data(), &[1, 5, 3]);
+    }
+}
@@ -0,0 +1,144 @@
+// src/mmap.rs
+//
+// File mappings backing `Tensor::from_mmap`. Mappings are read-only: the
+// tensor reads pages straight from the file, and the first mutable access
+// copies the elements onto the heap, so writes never reach the file.
+
+use std::fs::File;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+
+/// Element types that may be read straight out of file bytes: every bit
+/// pattern is a valid value.
+///
+/// # Safety
+/// Implementors must be `Copy` with no padding and no invalid bit patterns.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => { $(unsafe impl Pod for $t {})* };
+}
+
+impl_pod!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+/// A read-only, private mapping of `len` bytes of a file
+pub(crate) struct Mapping {
+    base: *mut u8,
+    map_len: usize,
+    data: *const u8,
+}
+
+unsafe impl Send for Mapping {}
+unsafe impl Sync for Mapping {}
+
+impl Mapping {
+    /// Map bytes `offset..offset + len` of `path`
+    #[cfg(unix)]
+    pub(crate) fn new(path: &Path, offset: usize, len: usize) -> Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let io = |e: std::io::Error| Error::Io(format!("{}: {}", path.display(), e));
+        let file = File::open(path).map_err(io)?;
+        let file_len = file.metadata().map_err(io)?.len() as usize;
+        let end = offset
+            .checked_add(len)
+            .ok_or_else(|| Error::Io(format!("{}: mapping {} bytes at offset {} overflows", path.display(), len, offset)))?;
+        if end > file_len {
+            return Err(Error::Io(format!(
+                "{}: file holds {} bytes, mapping needs {}",
+                path.display(),
+                file_len,
+                end
+            )));
+        }
+        if len == 0 {
+            return Ok(Mapping { base: std::ptr::null_mut(), map_len: 0, data: std::ptr::NonNull::dangling().as_ptr() });
+        }
+
+        // mmap offsets must be page-aligned; map from the enclosing page
+        let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+        let start = offset - offset % page;
+        let map_len = len + (offset - start);
+        let base = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                map_len,
+                libc::PROT_READ,
+                libc::MAP_PRIVATE,
+                file.as_raw_fd(),
+                start as libc::off_t,
+            )
+        };
+        if base == libc::MAP_FAILED {
+            return Err(io(std::io::Error::last_os_error()));
+        }
+        let base = base as *mut u8;
+        Ok(Mapping { base, map_len, data: unsafe { base.add(offset - start) } })
+    }
+
+    #[cfg(not(unix))]
+    pub(crate) fn new(path: &Path, _offset: usize, _len: usize) -> Result<Self> {
+        Err(Error::Io(format!("{}: memory mapping is only supported on unix", path.display())))
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const u8 {
+        self.data
+    }
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if self.map_len != 0 {
+            unsafe {
+                libc::munmap(self.base.cast(), self.map_len);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    fn scratch_file(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("rutilelib-{}-{}", std::process::id(), name));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn mapped_tensor_reads_file_and_keeps_writes_private() {
+        let values: Vec<f32> = (0..6).map(|x| x as f32).collect();
+        let bytes: Vec<u8> = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        let path = scratch_file("rw", &bytes);
+
+        let mut t = Tensor::<f32>::from_mmap(&path, Layout::row_major([2, 3])).unwrap();
+        assert_eq!(t[(1, 2)], 5.0);
+        t[(0, 0)] = 9.0;
+        assert_eq!(t.data()[0], 9.0);
+        assert_eq!(std::fs::read(&path).unwrap(), bytes);
+
+        assert_eq!(t.into_vec(), vec![9.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn mapping_past_a_header() {
+        let mut bytes = vec![0xffu8; 5000];
+        bytes.extend((0..4u32).flat_map(|v| v.to_ne_bytes()));
+        let path = scratch_file("header", &bytes);
+
+        let t = Tensor::<u32>::from_mmap_at(&path, 5000, Layout::row_major([4])).unwrap();
+        assert_eq!(t.data(), &[0, 1, 2, 3]);
+
+        let err = Tensor::<u32>::from_mmap_at(&path, 5000, Layout::row_major([5])).err().unwrap();
+        assert!(err.to_string().contains("mapping needs 5020"));
+        assert!(Tensor::<u32>::from_mmap_at(&path, 5001, Layout::row_major([1])).is_err());
+        let err = Tensor::<u32>::from_mmap_at(&path, usize::MAX - 3, Layout::row_major([2])).err().unwrap();
+        assert!(err.to_string().contains("overflows"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,188 @@
+// src/ndarray.rs
+//
+// Conversions between ndarray arrays and tensors. Views convert without
+// copying in both directions: ndarray's negative strides become reversed
+// modes and back. Hierarchical shapes flatten to one ndarray axis per mode.
+
+use ::ndarray::{Array, ArrayD, ArrayView, ArrayViewD, ArrayViewMut, ArrayViewMutD, Axis, Dimension, IxDyn, ShapeBuilder};
+
+use crate::layout::Layout;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+
+/// Layout with ndarray's `shape` and `strides`; negative strides become
+/// reversed modes, so the view keeps ndarray's pointer to index 0
+fn layout_from_ndarray(shape: &[usize], strides: &[isize]) -> Layout {
+    let magnitudes: Vec<usize> = strides.iter().map(|s| s.unsigned_abs()).collect();
+    let mut layout = Layout::row_major(shape.to_vec()).with_stride(magnitudes);
+    for (mode, &s) in strides.iter().enumerate() {
+        if s < 0 {
+            layout = layout.flip(mode);
+        }
+    }
+    layout
+}
+
+/// ndarray's positive strides for `layout` and the offset from coordinate 0
+/// to the far end of every reversed mode, where an ndarray view must start
+/// before `invert_axis` brings it back
+fn ndarray_parts(layout: &Layout) -> (IxDyn, IxDyn, isize, Vec<usize>) {
+    let shape = layout.shape().dims.flatten();
+    let signed = layout.signed_stride();
+    let mut start = 0;
+    let mut reversed = Vec::new();
+    for (mode, (&n, &s)) in shape.iter().zip(&signed).enumerate() {
+        if s < 0 {
+            start += (n.max(1) as isize - 1) * s;
+            reversed.push(mode);
+        }
+    }
+    let strides: Vec<usize> = signed.iter().map(|s| s.unsigned_abs()).collect();
+    (IxDyn(&shape), IxDyn(&strides), start, reversed)
+}
+
+impl<'a, T, D: Dimension> From<ArrayView<'a, T, D>> for TensorView<'a, T> {
+    fn from(view: ArrayView<'a, T, D>) -> Self {
+        let layout = layout_from_ndarray(view.shape(), view.strides());
+        unsafe { TensorView::from_raw(view.as_ptr(), layout) }
+    }
+}
+
+impl<'a, T, D: Dimension> From<ArrayViewMut<'a, T, D>> for TensorViewMut<'a, T> {
+    fn from(mut view: ArrayViewMut<'a, T, D>) -> Self {
+        let layout = layout_from_ndarray(view.shape(), view.strides());
+        unsafe { TensorViewMut::from_raw(view.as_mut_ptr(), layout) }
+    }
+}
+
+/// Reuses the buffer when `array` is in standard (row-major) or Fortran
+/// order and copies in logical order otherwise
+impl<T: Clone, D: Dimension> From<Array<T, D>> for Tensor<T> {
+    fn from(array: Array<T, D>) -> Self {
+        let shape = array.shape().to_vec();
+        let layout = if array.is_standard_layout() {
+            Layout::row_major(shape)
+        } else if array.t().is_standard_layout() {
+            Layout::col_major(shape)
+        } else {
+            return Tensor::new(array.iter().cloned().collect(), Layout::row_major(shape));
+        };
+        // A sliced array keeps its whole allocation; drop the elements around it
+        let (mut data, offset) = array.into_raw_vec_and_offset();
+        let len = layout.size();
+        if offset.unwrap_or(0) != 0 || data.len() != len {
+            data = data.into_iter().skip(offset.unwrap_or(0)).take(len).collect();
+        }
+        Tensor::new(data, layout)
+    }
+}
+
+impl<'a, T> TensorView<'a, T> {
+    /// The same elements as an ndarray view with one axis per flattened mode
+    pub fn to_ndarray(&self) -> ArrayViewD<'a, T> {
+        let (shape, strides, start, reversed) = ndarray_parts(self.layout());
+        let mut view = unsafe { ArrayViewD::from_shape_ptr(shape.strides(strides), self.as_ptr().offset(start)) };
+        for mode in reversed {
+            view.invert_axis(Axis(mode));
+        }
+        view
+    }
+}
+
+impl<'a, T> TensorViewMut<'a, T> {
+    /// Mutable counterpart of `TensorView::to_ndarray`. Panics if two
+    /// coordinates share an element (a zero stride), which ndarray forbids
+    /// for mutable views.
+    pub fn into_ndarray(self) -> ArrayViewMutD<'a, T> {
+        let (shape, strides, start, reversed) = ndarray_parts(self.layout());
+        assert!(
+            shape.slice().iter().zip(strides.slice()).all(|(&n, &s)| n <= 1 || s != 0),
+            "TensorViewMut::into_ndarray: layout {} has a broadcast mode",
+            self.layout().shape()
+        );
+        let mut view = unsafe { ArrayViewMutD::from_shape_ptr(shape.strides(strides), self.ptr.as_ptr().offset(start)) };
+        for mode in reversed {
+            view.invert_axis(Axis(mode));
+        }
+        view
+    }
+}
+
+impl<T: Clone> Tensor<T> {
+    /// Owned ndarray array of the same shape. Compact layouts keep their
+    /// storage and strides; anything else is gathered in row-major order.
+    pub fn into_ndarray(self) -> ArrayD<T> {
+        let (shape, strides, _, _) = ndarray_parts(self.layout());
+        if self.layout().contiguity().is_some() {
+            let data = self.into_vec();
+            return ArrayD::from_shape_vec(shape.strides(strides), data).expect("compact layouts index their storage");
+        }
+        let data = self.as_view().to_vec();
+        ArrayD::from_shape_vec(shape, data).expect("row-major data matches its shape")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::ndarray::{s, Array2, Array3, ShapeBuilder};
+    use crate::tuple::Tuple;
+
+    #[test]
+    fn views_keep_strides_both_ways() {
+        let a = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 100 + j * 10 + k) as f32);
+        let sliced = a.slice(s![.., 1.., ..;2]);
+        let view = TensorView::from(sliced.view());
+        assert_eq!(view.layout().shape().dims.flatten(), vec![2, 2, 2]);
+        assert_eq!(view.layout().stride().flatten(), vec![12, 4, 2]);
+        assert_eq!(unsafe { *view.get(&Tuple::int(vec![1, 1, 1])) }, 122.0);
+
+        let back = view.to_ndarray();
+        assert_eq!(back, sliced.into_dyn());
+        assert_eq!(back.as_ptr(), sliced.as_ptr());
+    }
+
+    #[test]
+    fn negative_strides_become_reversed_modes() {
+        let a = Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as i32);
+        let flipped = a.slice(s![..;-1, 1..]);
+        let view = TensorView::from(flipped.view());
+        assert!(view.layout().is_reversed(0) && !view.layout().is_reversed(1));
+        assert_eq!(view.to_vec(), flipped.iter().copied().collect::<Vec<_>>());
+        assert_eq!(view.to_ndarray(), flipped.into_dyn());
+
+        let t = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::row_major([2, 3]));
+        let rev = t.as_view().flip(1);
+        assert_eq!(rev.to_ndarray(), Array2::from_shape_vec((2, 3), vec![2, 1, 0, 5, 4, 3]).unwrap().into_dyn());
+    }
+
+    #[test]
+    fn mutable_views_write_through() {
+        let mut a = Array2::<f32>::zeros((3, 3));
+        {
+            let mut view = TensorViewMut::from(a.slice_mut(s![.., 1]));
+            unsafe { *view.get_mut(&Tuple::int(vec![2])) = 7.0 };
+        }
+        assert_eq!(a[[2, 1]], 7.0);
+
+        let mut t = Tensor::new(vec![0.0f32; 4], Layout::col_major([2, 2]));
+        t.as_view_mut().into_ndarray()[[0, 1]] = 3.0;
+        assert_eq!(t.data(), &[0.0, 0.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn owned_arrays_move_compact_storage() {
+        let f = Array2::from_shape_vec((2, 3).f(), (0..6).collect::<Vec<i32>>()).unwrap();
+        let t = Tensor::from(f.clone());
+        assert_eq!(t.layout().stride().flatten(), vec![1, 2]);
+        assert_eq!(t.data(), &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(t.into_ndarray(), f.into_dyn());
+
+        let rows = Array2::from_shape_fn((4, 2), |(i, j)| i * 2 + j).slice_move(s![1..3, ..]);
+        assert_eq!(Tensor::from(rows).data(), &[2, 3, 4, 5]);
+
+        let strided = Array2::from_shape_fn((4, 4), |(i, j)| i * 4 + j).slice_move(s![1..3, ..;3]);
+        let t = Tensor::from(strided.clone());
+        assert_eq!(t.data(), &[4, 7, 8, 11]);
+        assert_eq!(t.into_ndarray(), strided.into_dyn());
+    }
+}
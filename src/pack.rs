@@ -0,0 +1,142 @@
+// src/pack.rs
+//
+// BLIS-style operand packing. A row-panel of A (MR x KC) or a column-panel
+// of B (KC x NR) is copied into a contiguous buffer in the order a
+// micro-kernel consumes it, one rank-1 update at a time.
+
+use crate::tensor::TensorView;
+use crate::tiled_tensor::{Tile, TileIter};
+
+/// Buffer length `pack_panel_a` needs for an `m`-row block
+pub fn packed_a_len(m: usize, mr: usize, kc: usize) -> usize {
+    m.div_ceil(mr) * mr * kc
+}
+
+/// Buffer length `pack_panel_b` needs for an `n`-column block
+pub fn packed_b_len(n: usize, kc: usize, nr: usize) -> usize {
+    n.div_ceil(nr) * kc * nr
+}
+
+/// (rows, cols) of a rank-2 view
+fn extents<T>(view: &TensorView<'_, T>, op: &str) -> (usize, usize) {
+    let shape = view.layout().shape();
+    assert_eq!(shape.flat_len(), 2, "{op} requires a rank-2 view");
+    (shape.flat_at(0), shape.flat_at(1))
+}
+
+/// `view` at coordinate `(r, c)` of `tile`, indexed through a stack
+/// coordinate so the packing loops never allocate
+#[inline]
+fn at<T: Copy>(view: &TensorView<'_, T>, tile: &Tile, r: usize, c: usize) -> T {
+    unsafe { *view.get_flat(&[tile.start(0) + r, tile.start(1) + c]) }
+}
+
+/// Pack an `m x k` block of A (`k <= kc`) into row-panels of `mr` rows.
+///
+/// Panel `i` occupies `dst[i * mr * kc..][..mr * kc]` and stores column `p`
+/// of the panel at `p * mr`. Rows past `m` and columns past `k` are filled
+/// with `T::default()`, so the micro-kernel can always run a full `mr x kc`
+/// panel. Returns the number of elements written.
+pub fn pack_panel_a<T: Copy + Default>(a: &TensorView<'_, T>, mr: usize, kc: usize, dst: &mut [T]) -> usize {
+    let (m, k) = extents(a, "pack_panel_a");
+    assert!(k <= kc, "pack_panel_a: block depth {k} exceeds kc {kc}");
+    let len = packed_a_len(m, mr, kc);
+    assert!(dst.len() >= len, "pack_panel_a: buffer holds {} of {len} elements", dst.len());
+
+    let dst = &mut dst[..len];
+    dst.fill(T::default());
+    for tile in TileIter::new(vec![mr, kc], vec![m, k]) {
+        let panel = &mut dst[tile.index() * mr * kc..][..mr * kc];
+        for p in 0..tile.len(1) {
+            for i in 0..tile.len(0) {
+                panel[p * mr + i] = at(a, &tile, i, p);
+            }
+        }
+    }
+    len
+}
+
+/// Pack a `k x n` block of B (`k <= kc`) into column-panels of `nr` columns.
+///
+/// Panel `j` occupies `dst[j * kc * nr..][..kc * nr]` and stores row `p`
+/// of the panel at `p * nr`, zero-padded like `pack_panel_a`. Returns the
+/// number of elements written.
+pub fn pack_panel_b<T: Copy + Default>(b: &TensorView<'_, T>, kc: usize, nr: usize, dst: &mut [T]) -> usize {
+    let (k, n) = extents(b, "pack_panel_b");
+    assert!(k <= kc, "pack_panel_b: block depth {k} exceeds kc {kc}");
+    let len = packed_b_len(n, kc, nr);
+    assert!(dst.len() >= len, "pack_panel_b: buffer holds {} of {len} elements", dst.len());
+
+    let dst = &mut dst[..len];
+    dst.fill(T::default());
+    for tile in TileIter::new(vec![kc, nr], vec![k, n]) {
+        let panel = &mut dst[tile.index() * kc * nr..][..kc * nr];
+        for p in 0..tile.len(0) {
+            for j in 0..tile.len(1) {
+                panel[p * nr + j] = at(b, &tile, p, j);
+            }
+        }
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+    use crate::test_util::iota;
+
+    #[test]
+    fn pack_a_pads_partial_panel() {
+        // 3 x 2 row-major block, MR = 2, KC = 3
+        let a = iota::<i32>(3, 2, 1);
+        let mut buf = vec![-1; packed_a_len(3, 2, 3)];
+        assert_eq!(pack_panel_a(&a.as_view(), 2, 3, &mut buf), 12);
+        assert_eq!(buf, vec![1, 3, 2, 4, 0, 0, /* panel 1 */ 5, 0, 6, 0, 0, 0]);
+    }
+
+    #[test]
+    fn pack_b_from_col_major_view() {
+        // 2 x 3 col-major block, KC = 2, NR = 2
+        let b = Tensor::new((1..=6).collect(), Layout::col_major([2, 3]));
+        let mut buf = vec![0; packed_b_len(3, 2, 2)];
+        pack_panel_b(&b.as_view(), 2, 2, &mut buf);
+        // B = [[1, 3, 5], [2, 4, 6]]
+        assert_eq!(buf, vec![1, 3, 2, 4, /* panel 1 */ 5, 0, 6, 0]);
+    }
+
+    #[test]
+    fn packing_strided_subview() {
+        let t = iota::<i32>(4, 4, 1);
+        let view = t.as_view();
+        let sub = unsafe { view.subview([1, 1], [2, 2]) };
+        let mut buf = vec![0; 4];
+        pack_panel_a(&sub, 2, 2, &mut buf);
+        assert_eq!(buf, vec![6, 10, 7, 11]);
+    }
+
+    // Only the tile iterator allocates, however large the block
+    #[cfg(not(feature = "provenance"))]
+    #[test]
+    fn packing_does_not_allocate_per_element() {
+        use crate::tiled_tensor::tests::allocations;
+
+        let count = |n: usize| {
+            let a = iota::<i32>(n, n, 1);
+            let mut buf = vec![0; packed_a_len(n, 4, n)];
+            let before = allocations();
+            pack_panel_a(&a.as_view(), 4, n, &mut buf);
+            pack_panel_b(&a.as_view(), n, 4, &mut buf);
+            allocations() - before
+        };
+        assert_eq!(count(4), count(32));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds kc")]
+    fn pack_rejects_deep_block() {
+        let a = iota::<i32>(2, 4, 1);
+        pack_panel_a(&a.as_view(), 2, 3, &mut [0; 16]);
+    }
+}
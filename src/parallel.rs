@@ -0,0 +1,296 @@
+// src/parallel.rs
+//
+// Thread configuration shared by the parallel drivers (tiled GEMM, parallel
+// copy, GEMM plans, K-blocked reductions). Drivers build a list of disjoint
+// jobs and hand it to `Parallelism::run`.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::hw::numa_nodes;
+
+/// Thread count set by `set_num_threads`; 0 means one per hardware thread
+static NUM_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Set while a thread is running jobs for an internal-pool driver
+    static IN_PARALLEL: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Default thread count for every driver, like `openblas_set_num_threads`.
+/// `0` restores the default: `RUTILE_NUM_THREADS` (see `config`), else one
+/// thread per hardware thread.
+pub fn set_num_threads(n: usize) {
+    NUM_THREADS.store(n, Ordering::Relaxed);
+}
+
+pub fn num_threads() -> usize {
+    match NUM_THREADS.load(Ordering::Relaxed) {
+        0 => crate::config::get()
+            .num_threads
+            .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)),
+        n => n,
+    }
+}
+
+/// Where jobs run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pool {
+    /// Scoped OS threads spawned per call
+    Internal,
+    /// The rayon thread pool (global pool when the thread count matches it)
+    #[cfg(feature = "rayon")]
+    Rayon,
+}
+
+/// What a driver does when called from inside another driver's job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NestedPolicy {
+    /// Run on the calling thread; avoids oversubscribing the machine
+    Serial,
+    /// Spawn threads again
+    Parallel,
+}
+
+/// How `Pool::Internal` places jobs and threads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumaPolicy {
+    /// Jobs are dealt round-robin and threads float between CPUs
+    Off,
+    /// Threads are spread evenly over the NUMA nodes and pinned to one CPU
+    /// each, and thread `t` runs the `t`-th contiguous block of jobs. Memory
+    /// first touched through `first_touch` with the same settings, and
+    /// buffers a job allocates itself (such as `NativeGemm` packing
+    /// panels), then stay on the node that computes with them.
+    Bind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parallelism {
+    /// 0 follows `set_num_threads`
+    pub threads: usize,
+    pub pool: Pool,
+    pub nested: NestedPolicy,
+    pub numa: NumaPolicy,
+}
+
+impl Default for Parallelism {
+    fn default() -> Self {
+        Self { threads: 0, pool: Pool::Internal, nested: NestedPolicy::Serial, numa: NumaPolicy::Off }
+    }
+}
+
+impl Parallelism {
+    /// Single-threaded execution
+    pub fn serial() -> Self {
+        Self { threads: 1, ..Self::default() }
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    pub fn with_pool(mut self, pool: Pool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    pub fn with_nested(mut self, nested: NestedPolicy) -> Self {
+        self.nested = nested;
+        self
+    }
+
+    pub fn with_numa(mut self, numa: NumaPolicy) -> Self {
+        self.numa = numa;
+        self
+    }
+
+    fn is_nested(&self) -> bool {
+        match self.pool {
+            Pool::Internal => IN_PARALLEL.with(|f| f.get()),
+            #[cfg(feature = "rayon")]
+            Pool::Rayon => rayon::current_thread_index().is_some(),
+        }
+    }
+
+    /// Threads a driver called here will use. Always 1 on wasm32 without
+    /// the `atomics` target feature, which cannot spawn threads.
+    pub fn num_threads(&self) -> usize {
+        if cfg!(all(target_arch = "wasm32", not(target_feature = "atomics"))) {
+            return 1;
+        }
+        if self.nested == NestedPolicy::Serial && self.is_nested() {
+            return 1;
+        }
+        match self.threads {
+            0 => num_threads(),
+            n => n,
+        }
+    }
+
+    /// Run `f` on every job, spread over `num_threads()` threads. Jobs are
+    /// dealt round-robin, so job `i` and `i + threads` share a thread, or in
+    /// contiguous blocks under `NumaPolicy::Bind`.
+    pub fn run<J: Send, F: Fn(J) + Sync>(&self, jobs: Vec<J>, f: F) {
+        let threads = self.num_threads().min(jobs.len());
+        if threads <= 1 {
+            jobs.into_iter().for_each(f);
+            return;
+        }
+
+        match self.pool {
+            Pool::Internal => {
+                let bind = self.numa == NumaPolicy::Bind;
+                let len = jobs.len();
+                let mut buckets: Vec<Vec<J>> = (0..threads).map(|_| Vec::new()).collect();
+                for (i, job) in jobs.into_iter().enumerate() {
+                    buckets[if bind { i * threads / len } else { i % threads }].push(job);
+                }
+                let f = &f;
+                std::thread::scope(|scope| {
+                    for (t, bucket) in buckets.into_iter().enumerate() {
+                        scope.spawn(move || {
+                            if bind {
+                                pin_to_cpu(cpu_for_thread(t, threads));
+                            }
+                            IN_PARALLEL.with(|flag| flag.set(true));
+                            bucket.into_iter().for_each(f);
+                        });
+                    }
+                });
+            }
+            #[cfg(feature = "rayon")]
+            Pool::Rayon => {
+                use rayon::prelude::*;
+                if threads == rayon::current_num_threads() {
+                    jobs.into_par_iter().for_each(&f);
+                } else {
+                    let pool = rayon::ThreadPoolBuilder::new()
+                        .num_threads(threads)
+                        .build()
+                        .expect("failed to build rayon pool");
+                    pool.install(|| jobs.into_par_iter().for_each(&f));
+                }
+            }
+        }
+    }
+
+    /// `len` default values, written by the threads `run` would hand the
+    /// matching contiguous block of a `len`-job list to. Under
+    /// `NumaPolicy::Bind` the OS places each page on the node of the thread
+    /// that first wrote it, so a row-major C allocated this way sits next to
+    /// the threads that compute its tiles with the same `Parallelism`.
+    pub fn first_touch<T: Copy + Default + Send + Sync>(&self, len: usize) -> Vec<T> {
+        let threads = self.num_threads().min(len).max(1);
+        let mut out: Vec<T> = Vec::with_capacity(len);
+        let base = SendPtr(out.as_mut_ptr());
+        let blocks: Vec<(usize, usize)> = (0..threads).map(|t| (len * t / threads, len * (t + 1) / threads)).collect();
+
+        self.run(blocks, |(lo, hi)| {
+            for i in lo..hi {
+                // Blocks are disjoint and within capacity
+                unsafe { base.get().add(i).write(T::default()) };
+            }
+        });
+        unsafe { out.set_len(len) };
+        out
+    }
+}
+
+struct SendPtr<T>(*mut T);
+
+unsafe impl<T: Send> Send for SendPtr<T> {}
+unsafe impl<T: Send> Sync for SendPtr<T> {}
+
+impl<T> SendPtr<T> {
+    fn get(&self) -> *mut T {
+        self.0
+    }
+}
+
+/// CPU for thread `t` of `threads`: threads are split evenly over the
+/// nodes, then cycle through each node's CPUs
+fn cpu_for_thread(t: usize, threads: usize) -> usize {
+    let nodes = numa_nodes();
+    let node = t * nodes.len() / threads;
+    let first = (node * threads).div_ceil(nodes.len());
+    let cpus = &nodes[node].cpus;
+    cpus[(t - first) % cpus.len()]
+}
+
+/// Best effort: a failed pin (CPU outside this process's cpuset, ...) only
+/// costs locality
+#[cfg(target_os = "linux")]
+fn pin_to_cpu(cpu: usize) {
+    extern "C" {
+        fn sched_setaffinity(pid: i32, size: usize, mask: *const u64) -> i32;
+    }
+
+    let mut mask = [0u64; 16];
+    if cpu < 64 * mask.len() {
+        mask[cpu / 64] |= 1 << (cpu % 64);
+        unsafe {
+            sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr());
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn pin_to_cpu(_cpu: usize) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn runs_every_job_once() {
+        let seen = Mutex::new(Vec::new());
+        Parallelism::default().with_threads(3).run((0..10).collect(), |i| seen.lock().unwrap().push(i));
+        let mut seen = seen.into_inner().unwrap();
+        seen.sort();
+        assert_eq!(seen, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn nested_calls_follow_policy() {
+        let inner = Mutex::new(Vec::new());
+        Parallelism::default().with_threads(2).run(vec![0, 1], |_| {
+            let serial = Parallelism::default().with_threads(4);
+            let parallel = serial.with_nested(NestedPolicy::Parallel);
+            inner.lock().unwrap().push((serial.num_threads(), parallel.num_threads()));
+        });
+        assert_eq!(inner.into_inner().unwrap(), vec![(1, 4), (1, 4)]);
+        assert_eq!(Parallelism::default().with_threads(4).num_threads(), 4);
+    }
+
+    #[test]
+    fn numa_bind_runs_contiguous_blocks() {
+        let par = Parallelism::default().with_threads(2).with_numa(NumaPolicy::Bind);
+        let ran = Mutex::new(Vec::new());
+        par.run((0..5).collect(), |i| ran.lock().unwrap().push((i, std::thread::current().id())));
+
+        let mut ran = ran.into_inner().unwrap();
+        ran.sort_by_key(|&(i, _)| i);
+        let thread = |i: usize| ran[i].1;
+        assert!(thread(0) == thread(1) && thread(1) == thread(2));
+        assert!(thread(3) == thread(4) && thread(2) != thread(3));
+
+        let buf: Vec<f32> = par.first_touch(1001);
+        assert_eq!(buf, vec![0.0; 1001]);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn rayon_pool_runs_jobs() {
+        let sum = AtomicUsize::new(0);
+        Parallelism::default()
+            .with_pool(Pool::Rayon)
+            .with_threads(2)
+            .run((1..=100).collect(), |i| {
+                sum.fetch_add(i, Ordering::Relaxed);
+            });
+        assert_eq!(sum.into_inner(), 5050);
+    }
+}
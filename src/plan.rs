@@ -0,0 +1,408 @@
+// src/plan.rs
+//
+// Reusable GEMM plans. Everything that depends only on shapes and layouts
+// (BLAS lowering, the C tiling, per-tile operand offsets and the split of
+// tiles over threads) is computed once in `GemmPlan::new`; `execute` only
+// issues the backend calls. That schedule is also kept in a small LRU cache
+// keyed by the operands' canonical layouts, so plans for shapes seen before
+// skip the planning. Plain `gemm_f32` calls lower directly and never touch
+// the cache.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::bench_utils::KernelStats;
+use crate::blas::{BlasBackend, GemmAlgo};
+use crate::error::{check_rank, Error, Result};
+use crate::gemm::{try_lower_gemm, LoweredGemm};
+use crate::hw::default_tile_for_gemm;
+use crate::layout::{CanonicalLayout, Layout};
+use crate::parallel::Parallelism;
+use crate::shape::Shape;
+use crate::tensor::{check_disjoint, TensorView, TensorViewMut};
+use crate::tiled_tensor::TileIter;
+use crate::tune::TileConfig;
+use crate::tuple::Tuple;
+
+/// Knobs for `GemmPlan::new`
+#[derive(Debug, Clone, Copy)]
+pub struct GemmConfig {
+    pub alpha: f32,
+    pub beta: f32,
+    /// C tile; `None` picks `hw::default_tile_for_gemm`
+    pub tile: Option<TileConfig>,
+    /// Threads `execute` splits C over
+    pub parallelism: Parallelism,
+    /// Backend implementation run on each tile; must be in `gemm_algos`
+    pub algo: GemmAlgo,
+}
+
+impl Default for GemmConfig {
+    fn default() -> Self {
+        Self { alpha: 1.0, beta: 0.0, tile: None, parallelism: Parallelism::default(), algo: GemmAlgo::Default }
+    }
+}
+
+/// Operand layouts of an `m x k` by `k x n` product
+#[derive(Debug, Clone)]
+pub struct GemmLayouts {
+    pub a: Layout,
+    pub b: Layout,
+    pub c: Layout,
+}
+
+/// One backend call: a C tile and the operand offsets it reads
+#[derive(Debug, Clone, Copy)]
+struct PlannedTile {
+    a_off: isize,
+    b_off: isize,
+    /// Relative to the origin of the tile's row band
+    c_off: isize,
+    m: usize,
+    n: usize,
+}
+
+/// Rows `start..start + rows` of C and the tiles inside them, run by one thread
+#[derive(Debug, Clone)]
+struct Band {
+    start: usize,
+    rows: usize,
+    tiles: Vec<PlannedTile>,
+}
+
+/// The part of a plan that depends only on shapes and layouts
+#[derive(Debug)]
+pub(crate) struct GemmSchedule {
+    k: usize,
+    lowered: LoweredGemm,
+    bands: Vec<Band>,
+}
+
+impl GemmSchedule {
+    fn new(op: &'static str, (m, n, k): (usize, usize, usize), [la, lb, lc]: [&Layout; 3], tile: TileConfig, threads: usize) -> Result<Self> {
+        let lowered = try_lower_gemm(op, la, lb, lc)?;
+
+        let tile_rows = m.div_ceil(tile.tile_m);
+        let threads = threads.min(tile_rows).max(1);
+
+        // Whole tile rows go to each thread, so every band is a rectangle of C
+        let mut bands: Vec<Band> = (0..threads)
+            .map(|t| {
+                let (r0, r1) = (tile_rows * t / threads, tile_rows * (t + 1) / threads);
+                let start = (r0 * tile.tile_m).min(m);
+                Band { start, rows: (r1 * tile.tile_m).min(m) - start, tiles: Vec::new() }
+            })
+            .collect();
+
+        for t in TileIter::new(vec![tile.tile_m, tile.tile_n], vec![m, n]) {
+            let (m0, n0) = (t.start(0), t.start(1));
+            let band = bands.iter_mut().find(|b| m0 < b.start + b.rows).expect("tile outside every band");
+            band.tiles.push(PlannedTile {
+                a_off: la.crd2offset(&Tuple::int(vec![m0, 0])),
+                b_off: lb.crd2offset(&Tuple::int(vec![0, n0])),
+                c_off: lc.crd2offset(&Tuple::int(vec![m0 - band.start, n0])),
+                m: t.len(0),
+                n: t.len(1),
+            });
+        }
+
+        Ok(Self { k, lowered, bands })
+    }
+
+    /// Schedule for `(m, n, k)` on `B`, from the plan cache when an
+    /// equivalent one was built before
+    fn cached<B: BlasBackend + ?Sized>(
+        op: &'static str,
+        (m, n, k): (usize, usize, usize),
+        layouts: [&Layout; 3],
+        tile: TileConfig,
+        threads: usize,
+    ) -> Result<Arc<Self>> {
+        let key = PlanKey {
+            backend: std::any::type_name::<B>(),
+            extents: (m, n, k),
+            layouts: layouts.map(Layout::canonical),
+            tile: (tile.tile_m, tile.tile_n),
+            threads,
+        };
+        let cache = PLAN_CACHE.get_or_init(|| Mutex::new(PlanCache::default()));
+        if let Some(schedule) = cache.lock().unwrap().get(&key) {
+            log_trace!("{op}: reusing cached schedule for {m}x{n}x{k}");
+            return Ok(schedule);
+        }
+        // Build outside the lock; a racing thread may build the same one
+        let schedule = Arc::new(Self::new(op, (m, n, k), layouts, tile, threads)?);
+        cache.lock().unwrap().insert(key, schedule.clone());
+        Ok(schedule)
+    }
+
+    /// Run every planned tile of `band`
+    ///
+    /// # Safety
+    /// `a`, `b` and `c` must address operands with the layouts the schedule
+    /// was built for, `c` starting at the band's first row.
+    pub(crate) unsafe fn run_band<B: BlasBackend + ?Sized>(
+        &self,
+        band: usize,
+        backend: &B,
+        config: &GemmConfig,
+        a: *const f32,
+        b: *const f32,
+        c: *mut f32,
+    ) {
+        for t in &self.bands[band].tiles {
+            self.lowered.run(
+                backend,
+                config.algo,
+                t.m,
+                t.n,
+                self.k,
+                config.alpha,
+                a.offset(t.a_off),
+                b.offset(t.b_off),
+                config.beta,
+                c.offset(t.c_off),
+            );
+        }
+    }
+}
+
+/* ---------- plan cache ---------- */
+
+const PLAN_CACHE_CAPACITY: usize = 64;
+
+static PLAN_CACHE: OnceLock<Mutex<PlanCache>> = OnceLock::new();
+
+/// Everything a `GemmSchedule` depends on. Canonical layouts make operands
+/// that differ only in tuple nesting share an entry: with the extents
+/// fixed, equal canonical forms mean every coordinate has the same offset.
+/// Schedules are f32-only, so the element type is not part of the key.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct PlanKey {
+    backend: &'static str,
+    extents: (usize, usize, usize),
+    layouts: [CanonicalLayout; 3],
+    tile: (usize, usize),
+    threads: usize,
+}
+
+/// Least-recently-used map of schedules; eviction scans all entries,
+/// which is cheap at this capacity
+#[derive(Default)]
+struct PlanCache {
+    entries: HashMap<PlanKey, (Arc<GemmSchedule>, u64)>,
+    tick: u64,
+}
+
+impl PlanCache {
+    fn get(&mut self, key: &PlanKey) -> Option<Arc<GemmSchedule>> {
+        self.tick += 1;
+        let (schedule, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(schedule.clone())
+    }
+
+    fn insert(&mut self, key: PlanKey, schedule: Arc<GemmSchedule>) {
+        if self.entries.len() >= PLAN_CACHE_CAPACITY && !self.entries.contains_key(&key) {
+            let oldest = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.tick += 1;
+        self.entries.insert(key, (schedule, self.tick));
+    }
+}
+
+pub struct GemmPlan<B: BlasBackend + Sync> {
+    backend: B,
+    config: GemmConfig,
+    layouts: GemmLayouts,
+    schedule: Arc<GemmSchedule>,
+}
+
+impl<B: BlasBackend + Sync> GemmPlan<B> {
+    pub fn new(m: usize, n: usize, k: usize, layouts: GemmLayouts, backend: B, config: GemmConfig) -> Result<Self> {
+        const OP: &str = "GemmPlan";
+        for (layout, rows, cols) in [(&layouts.a, m, k), (&layouts.b, k, n), (&layouts.c, m, n)] {
+            check_rank(OP, 2, layout.shape().flat_len())?;
+            if layout.shape().dims.flatten() != [rows, cols] {
+                let expected = Shape::new(Tuple::int(vec![rows, cols]));
+                return Err(Error::ShapeMismatch { op: OP, lhs: layout.shape().clone(), rhs: expected });
+            }
+        }
+
+        if !backend.gemm_algos().contains(&config.algo) {
+            return Err(Error::Unsupported { op: OP, what: format!("{:?} GEMM", config.algo) });
+        }
+
+        let tile = config.tile.unwrap_or_else(|| default_tile_for_gemm(m, n, k, std::mem::size_of::<f32>()));
+        let schedule = GemmSchedule::cached::<B>(OP, (m, n, k), [&layouts.a, &layouts.b, &layouts.c], tile, config.parallelism.num_threads())?;
+        log_debug!(
+            "GemmPlan {m}x{n}x{k}: tile {}x{}, {} bands, {:?} on {}",
+            tile.tile_m,
+            tile.tile_n,
+            schedule.bands.len(),
+            config.algo,
+            std::any::type_name::<B>()
+        );
+        Ok(Self { backend, config, layouts, schedule })
+    }
+
+    pub fn layouts(&self) -> &GemmLayouts {
+        &self.layouts
+    }
+
+    /// Number of backend calls per `execute`
+    pub fn num_tiles(&self) -> usize {
+        self.schedule.bands.iter().map(|b| b.tiles.len()).sum()
+    }
+
+    /// `C = alpha * A * B + beta * C` with the planned layouts
+    pub fn execute(
+        &self,
+        a: &TensorView<'_, f32>,
+        b: &TensorView<'_, f32>,
+        c: &mut TensorViewMut<'_, f32>,
+    ) -> KernelStats {
+        self.try_execute(a, b, c).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// `execute` returning operands whose index mapping differs from the
+    /// plan (including flipped modes) as `Error::LayoutMismatch`
+    pub fn try_execute(
+        &self,
+        a: &TensorView<'_, f32>,
+        b: &TensorView<'_, f32>,
+        c: &mut TensorViewMut<'_, f32>,
+    ) -> Result<KernelStats> {
+        const OP: &str = "GemmPlan::execute";
+        for (view, planned) in [(a.layout(), &self.layouts.a), (b.layout(), &self.layouts.b), (c.layout(), &self.layouts.c)] {
+            // The schedule's offsets depend on the extents and the signed
+            // index mapping, not on how the shape is nested or where the
+            // view sits in its parent
+            if view.flat_shape() != planned.flat_shape() || view.canonical() != planned.canonical() {
+                return Err(Error::LayoutMismatch { op: OP, layouts: Box::new([planned.clone(), view.clone()]) });
+            }
+        }
+        check_disjoint(OP, a, c)?;
+        check_disjoint(OP, b, c)?;
+
+        let n = self.layouts.c.shape().flat_at(1);
+        // Bands cover disjoint row ranges of C
+        let jobs: Vec<(usize, TensorViewMut<'_, f32>)> = self
+            .schedule
+            .bands
+            .iter()
+            .enumerate()
+            .map(|(i, band)| (i, unsafe { c.subview_mut([band.start, 0], [band.rows, n]) }))
+            .collect();
+
+        // `execute` checked the operands against the planned layouts
+        self.config.parallelism.run(jobs, |(band, c_band)| unsafe {
+            self.schedule.run_band(band, &self.backend, &self.config, a.as_ptr(), b.as_ptr(), c_band.ptr.as_ptr())
+        });
+
+        let (m, k) = (self.layouts.a.shape().flat_at(0), self.schedule.k);
+        Ok(KernelStats::gemm(m, n, k, std::mem::size_of::<f32>(), self.config.beta))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::gemm::gemm_f32;
+    use crate::kernel::NativeGemm;
+    use crate::tensor::Tensor;
+
+    fn operands(m: usize, n: usize, k: usize) -> (Tensor<f32>, Tensor<f32>) {
+        let a = Tensor::new((0..m * k).map(|x| (x % 5) as f32).collect(), Layout::row_major([m, k]));
+        let b = Tensor::new((0..k * n).map(|x| (x % 3) as f32 - 1.0).collect(), Layout::col_major([k, n]));
+        (a, b)
+    }
+
+    #[test]
+    fn plan_matches_gemm_and_is_reusable() {
+        let (m, n, k) = (11, 9, 6);
+        let (a, b) = operands(m, n, k);
+        let layouts = GemmLayouts { a: a.layout().clone(), b: b.layout().clone(), c: Layout::row_major([m, n]) };
+        let config = GemmConfig { tile: Some(TileConfig::new(4, 4)), parallelism: Parallelism::default().with_threads(2), ..Default::default() };
+        let plan = GemmPlan::new(m, n, k, layouts, NativeBlas, config).unwrap();
+        assert_eq!(plan.num_tiles(), 9);
+
+        let mut expected = Tensor::new(vec![0.0; m * n], Layout::row_major([m, n]));
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 1.0, 0.0);
+
+        let mut c = Tensor::new(vec![7.0; m * n], Layout::row_major([m, n]));
+        for _ in 0..3 {
+            plan.execute(&a.as_view(), &b.as_view(), &mut c.as_view_mut());
+            assert_eq!(c.data(), expected.data());
+        }
+
+        for &algo in NativeBlas.gemm_algos() {
+            let config = GemmConfig { algo, ..config };
+            let plan = GemmPlan::new(m, n, k, plan.layouts().clone(), NativeBlas, config).unwrap();
+            let mut c = Tensor::new(vec![7.0; m * n], Layout::row_major([m, n]));
+            plan.execute(&a.as_view(), &b.as_view(), &mut c.as_view_mut());
+            assert_eq!(c.data(), expected.data(), "{algo:?}");
+        }
+    }
+
+    #[test]
+    fn equivalent_layouts_share_a_cached_schedule() {
+        let (m, n, k) = (6, 10, 4);
+        let config = GemmConfig { tile: Some(TileConfig::new(3, 5)), ..Default::default() };
+        let layouts = GemmLayouts { a: Layout::row_major([m, k]), b: Layout::col_major([k, n]), c: Layout::row_major([m, n]) };
+        let first = GemmPlan::new(m, n, k, layouts.clone(), NativeBlas, config).unwrap();
+
+        // C as the nested ((6), (10)) maps every coordinate to the same offset
+        let nested = Shape::new(Tuple::tup(vec![Tuple::int1(m), Tuple::int1(n)]));
+        let layouts = GemmLayouts { c: Layout::row_major(nested), ..layouts };
+        let second = GemmPlan::new(m, n, k, layouts.clone(), NativeBlas, config).unwrap();
+        assert!(Arc::ptr_eq(&first.schedule, &second.schedule));
+
+        let c = Layout::col_major(Shape::new(Tuple::int(vec![m, n])));
+        let third = GemmPlan::new(m, n, k, GemmLayouts { c, ..layouts }, NativeBlas, config).unwrap();
+        assert!(!Arc::ptr_eq(&first.schedule, &third.schedule));
+    }
+
+    #[test]
+    fn plan_rejects_bad_layouts() {
+        let layouts = GemmLayouts {
+            a: Layout::row_major([4, 3]),
+            b: Layout::row_major([2, 5]),
+            c: Layout::row_major([4, 5]),
+        };
+        let err = GemmPlan::new(4, 5, 3, layouts, NativeBlas, GemmConfig::default()).err();
+        assert!(matches!(err, Some(Error::ShapeMismatch { op: "GemmPlan", .. })));
+
+        let layouts = GemmLayouts { a: Layout::row_major([2, 2]), b: Layout::row_major([2, 2]), c: Layout::row_major([2, 2]) };
+        let config = GemmConfig { algo: GemmAlgo::Strassen, ..Default::default() };
+        let err = GemmPlan::new(2, 2, 2, layouts, NativeGemm::default(), config).err().unwrap();
+        assert_eq!(err.to_string(), "GemmPlan: Strassen GEMM is not supported by this backend");
+    }
+
+    #[test]
+    #[should_panic(expected = "but the plan expects")]
+    fn execute_checks_layouts() {
+        let (a, b) = operands(3, 3, 3);
+        let layouts = GemmLayouts { a: a.layout().clone(), b: b.layout().clone(), c: Layout::row_major([3, 3]) };
+        let plan = GemmPlan::new(3, 3, 3, layouts, NativeBlas, GemmConfig::default()).unwrap();
+        let mut c = Tensor::new(vec![0.0; 9], Layout::row_major([3, 3]));
+        plan.execute(&b.as_view(), &a.as_view(), &mut c.as_view_mut());
+    }
+
+    #[test]
+    fn execute_rejects_flipped_operands() {
+        let (a, b) = operands(3, 3, 3);
+        let layouts = GemmLayouts { a: a.layout().clone(), b: b.layout().clone(), c: Layout::row_major([3, 3]) };
+        let plan = GemmPlan::new(3, 3, 3, layouts, NativeBlas, GemmConfig::default()).unwrap();
+        let mut c = Tensor::new(vec![0.0; 9], Layout::row_major([3, 3]));
+        let err = plan.try_execute(&a.as_view(), &b.as_view(), &mut c.as_view_mut().flip(0));
+        assert!(matches!(err, Err(Error::LayoutMismatch { op: "GemmPlan::execute", .. })));
+        let err = plan.try_execute(&a.as_view().flip(1), &b.as_view(), &mut c.as_view_mut());
+        assert!(matches!(err, Err(Error::LayoutMismatch { .. })));
+        plan.try_execute(&a.as_view(), &b.as_view(), &mut c.as_view_mut()).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+// src/provenance.rs
+//
+// How a view was derived. With the `provenance` feature every derived view
+// links to a chain of steps, each naming the operation, the parent's layout
+// and the origin offset from the parent, and `debug_path` prints the chain.
+// Without the feature `Provenance` is zero-sized and recording is a no-op.
+
+#[cfg(feature = "provenance")]
+use std::sync::Arc;
+
+use crate::layout::Layout;
+
+#[derive(Clone, Default)]
+pub(crate) struct Provenance {
+    #[cfg(feature = "provenance")]
+    last: Option<Arc<Step>>,
+}
+
+#[cfg(feature = "provenance")]
+struct Step {
+    op: &'static str,
+    parent: Layout,
+    offset: isize,
+    prev: Option<Arc<Step>>,
+}
+
+impl Provenance {
+    /// This chain extended by `op`, applied to a view of layout `parent`
+    /// with the new origin `offset` elements from the old one
+    #[inline]
+    pub(crate) fn then(&self, op: &'static str, parent: &Layout, offset: isize) -> Provenance {
+        #[cfg(feature = "provenance")]
+        {
+            let step = Step { op, parent: parent.clone(), offset, prev: self.last.clone() };
+            Provenance { last: Some(Arc::new(step)) }
+        }
+        #[cfg(not(feature = "provenance"))]
+        {
+            let _ = (op, parent, offset);
+            Provenance {}
+        }
+    }
+
+    /// Rename the latest step, for public operations (tiles, diagonals)
+    /// built on a generic internal one
+    #[inline]
+    pub(crate) fn renamed(self, op: &'static str) -> Provenance {
+        #[cfg(feature = "provenance")]
+        if let Some(last) = &self.last {
+            let step = Step { op, parent: last.parent.clone(), offset: last.offset, prev: last.prev.clone() };
+            return Provenance { last: Some(Arc::new(step)) };
+        }
+        let _ = op;
+        self
+    }
+
+    /// `root -> op[+offset] layout -> ...`, ending with `layout`, the
+    /// layout of the view this chain belongs to
+    #[cfg(feature = "provenance")]
+    pub(crate) fn path(&self, layout: &Layout) -> String {
+        let mut steps = Vec::new();
+        let mut cur = self.last.as_deref();
+        while let Some(step) = cur {
+            steps.push(step);
+            cur = step.prev.as_deref();
+        }
+
+        let describe = |l: &Layout| format!("{}:{}", l.shape(), l.stride());
+        let Some(first) = steps.last() else {
+            return describe(layout);
+        };
+        let mut out = describe(&first.parent);
+        for (i, step) in steps.iter().enumerate().rev() {
+            let child = if i == 0 { layout } else { &steps[i - 1].parent };
+            out += &format!(" -> {}[{:+}] {}", step.op, step.offset, describe(child));
+        }
+        out
+    }
+}
@@ -0,0 +1,244 @@
+// src/python.rs
+//
+// Python bindings (pyo3). `Layout` wraps the layout algebra with CuTe-style
+// nested tuples; `Tensor` owns f32 data and hands it to NumPy without a
+// copy; `gemm` and `matmul` run directly on NumPy arrays through views.
+// Build the extension with `maturin develop --features python`.
+
+use numpy::{IntoPyArray, PyArrayDyn, PyReadonlyArrayDyn, PyReadwriteArrayDyn};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyTuple;
+
+use crate::blas::NativeBlas;
+use crate::error::Error;
+use crate::gemm::try_gemm_f32;
+use crate::layout::Layout;
+use crate::layout_algebra::{try_logical_divide, try_zipped_divide};
+use crate::shape::Shape;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tuple::Tuple;
+
+impl From<Error> for PyErr {
+    fn from(e: Error) -> Self {
+        PyValueError::new_err(e.to_string())
+    }
+}
+
+/// `int` or a (nested) tuple / list of ints; a sequence of plain ints is one
+/// leaf group, like `Tuple::int`
+fn tuple_from_py(obj: &Bound<'_, PyAny>) -> PyResult<Tuple> {
+    if let Ok(x) = obj.extract::<usize>() {
+        return Ok(Tuple::int1(x));
+    }
+    if let Ok(xs) = obj.extract::<Vec<usize>>() {
+        return Ok(Tuple::int(xs));
+    }
+    let items: Vec<Bound<'_, PyAny>> = obj.extract()?;
+    Ok(Tuple::tup(items.iter().map(tuple_from_py).collect::<PyResult<_>>()?))
+}
+
+fn tuple_to_py<'py>(py: Python<'py>, t: &Tuple) -> PyResult<Bound<'py, PyTuple>> {
+    match t {
+        Tuple::Int(xs) => PyTuple::new(py, xs),
+        Tuple::Tup(ts) => {
+            let items = ts
+                .iter()
+                .map(|t| match t {
+                    Tuple::Int(xs) if xs.len() == 1 => Ok(xs[0].into_pyobject(py)?.into_any()),
+                    t => Ok(tuple_to_py(py, t)?.into_any()),
+                })
+                .collect::<PyResult<Vec<_>>>()?;
+            PyTuple::new(py, items)
+        }
+    }
+}
+
+#[pyclass(name = "Layout", module = "rutilelib", frozen)]
+#[derive(Debug, Clone)]
+pub struct PyLayout(pub Layout);
+
+#[pymethods]
+impl PyLayout {
+    /// `Layout(shape, stride=None)`; row-major when no stride is given
+    #[new]
+    #[pyo3(signature = (shape, stride = None))]
+    fn new(shape: &Bound<'_, PyAny>, stride: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let layout = Layout::row_major(Shape::new(tuple_from_py(shape)?));
+        match stride {
+            None => Ok(PyLayout(layout)),
+            Some(stride) => {
+                let stride = tuple_from_py(stride)?;
+                if !layout.shape().dims.is_congruent(&stride) {
+                    return Err(Error::NotCongruent { op: "Layout", lhs: layout.shape().dims.clone(), rhs: stride }.into());
+                }
+                Ok(PyLayout(layout.with_stride(stride)))
+            }
+        }
+    }
+
+    #[staticmethod]
+    fn row_major(shape: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyLayout(Layout::row_major(Shape::new(tuple_from_py(shape)?))))
+    }
+
+    #[staticmethod]
+    fn col_major(shape: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyLayout(Layout::col_major(Shape::new(tuple_from_py(shape)?))))
+    }
+
+    #[getter]
+    fn shape<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        tuple_to_py(py, &self.0.shape().dims)
+    }
+
+    #[getter]
+    fn stride<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyTuple>> {
+        tuple_to_py(py, self.0.stride())
+    }
+
+    fn size(&self) -> usize {
+        self.0.size()
+    }
+
+    fn cosize(&self) -> usize {
+        self.0.cosize()
+    }
+
+    /// Index of a coordinate, `layout(crd)` as in CuTe
+    fn __call__(&self, crd: &Bound<'_, PyAny>) -> PyResult<usize> {
+        Ok(self.0.try_crd2idx(tuple_from_py(crd)?)?)
+    }
+
+    fn idx2crd<'py>(&self, py: Python<'py>, idx: usize) -> PyResult<Bound<'py, PyTuple>> {
+        tuple_to_py(py, &self.0.idx2crd(idx))
+    }
+
+    fn logical_divide(&self, tiler: &PyLayout) -> PyResult<Self> {
+        Ok(PyLayout(try_logical_divide(&self.0, &tiler.0)?))
+    }
+
+    fn zipped_divide(&self, tiler: &PyLayout) -> PyResult<Self> {
+        Ok(PyLayout(try_zipped_divide(&self.0, &tiler.0)?))
+    }
+
+    fn __eq__(&self, other: &PyLayout) -> bool {
+        self.0 == other.0
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Layout({}:{})", self.0.shape(), self.0.stride())
+    }
+}
+
+#[pyclass(name = "Tensor", module = "rutilelib")]
+pub struct PyTensor(pub Tensor<f32>);
+
+#[pymethods]
+impl PyTensor {
+    /// Copy `array` into a tensor with `layout` (row-major by default). A
+    /// layout with gaps gets `cosize` elements of storage; reversed or
+    /// overlapping layouts raise `ValueError`.
+    #[new]
+    #[pyo3(signature = (array, layout = None))]
+    fn new(array: PyReadonlyArrayDyn<'_, f32>, layout: Option<PyLayout>) -> PyResult<Self> {
+        const OP: &str = "Tensor";
+        let src = TensorView::from(array.as_array());
+        let layout = layout.map_or_else(|| Layout::row_major(src.layout().shape().clone()), |l| l.0);
+        if layout.has_reversed_modes() {
+            return Err(Error::Unsupported { op: OP, what: "a layout with reversed modes".into() }.into());
+        }
+        if !layout.is_injective() {
+            return Err(Error::Aliasing { op: OP }.into());
+        }
+        let mut out = Tensor::new(vec![0.0; layout.cosize()], layout);
+        crate::copy::try_tensor_copy(&src, &mut out.as_view_mut())?;
+        Ok(PyTensor(out))
+    }
+
+    #[getter]
+    fn layout(&self) -> PyLayout {
+        PyLayout(self.0.layout().clone())
+    }
+
+    /// NumPy view of the tensor's memory; the array keeps the tensor alive
+    fn numpy<'py>(slf: Bound<'py, Self>) -> Bound<'py, PyArrayDyn<f32>> {
+        let this = slf.borrow();
+        let array = this.0.as_view().to_ndarray();
+        // The storage is never reallocated while the tensor object lives
+        unsafe { PyArrayDyn::borrow_from_array(&array, slf.clone().into_any()) }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Tensor({}:{})", self.0.layout().shape(), self.0.layout().stride())
+    }
+}
+
+/// `c = alpha * a @ b + beta * c` in place on 2-D float32 arrays
+#[pyfunction]
+#[pyo3(signature = (a, b, c, alpha = 1.0, beta = 0.0))]
+fn gemm(
+    a: PyReadonlyArrayDyn<'_, f32>,
+    b: PyReadonlyArrayDyn<'_, f32>,
+    mut c: PyReadwriteArrayDyn<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) -> PyResult<()> {
+    let (a, b) = (TensorView::from(a.as_array()), TensorView::from(b.as_array()));
+    let mut c = TensorViewMut::from(c.as_array_mut());
+    Ok(try_gemm_f32(&NativeBlas, &a, &b, &mut c, alpha, beta)?)
+}
+
+/// `a @ b` into a new array
+#[pyfunction]
+fn matmul<'py>(
+    py: Python<'py>,
+    a: PyReadonlyArrayDyn<'py, f32>,
+    b: PyReadonlyArrayDyn<'py, f32>,
+) -> PyResult<Bound<'py, PyArrayDyn<f32>>> {
+    let (a, b) = (TensorView::from(a.as_array()), TensorView::from(b.as_array()));
+    // Wrong ranks are reported by `try_gemm_f32`; only the output extents are needed here
+    let (m, n) = (a.layout().shape().dims.flatten().first().copied(), b.layout().shape().dims.flatten().last().copied());
+    let (m, n) = (m.unwrap_or(0), n.unwrap_or(0));
+    let mut c = Tensor::new(vec![0.0; m * n], Layout::row_major([m, n]));
+    try_gemm_f32(&NativeBlas, &a, &b, &mut c.as_view_mut(), 1.0, 0.0)?;
+    Ok(c.into_ndarray().into_pyarray(py))
+}
+
+#[pymodule]
+fn rutilelib(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyLayout>()?;
+    m.add_class::<PyTensor>()?;
+    m.add_function(wrap_pyfunction!(gemm, m)?)?;
+    m.add_function(wrap_pyfunction!(matmul, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `(a, (b, c))`
+    fn nested(py: Python<'_>, a: usize, bc: [usize; 2]) -> Bound<'_, PyTuple> {
+        let inner = PyTuple::new(py, bc).unwrap();
+        PyTuple::new(py, [a.into_pyobject(py).unwrap().into_any(), inner.into_any()]).unwrap()
+    }
+
+    #[test]
+    fn layouts_take_nested_python_tuples() {
+        Python::initialize();
+        Python::attach(|py| {
+            let (shape, stride) = (nested(py, 8, [2, 4]), nested(py, 8, [4, 1]));
+            let layout = PyLayout::new(shape.as_any(), Some(stride.as_any())).unwrap();
+            assert_eq!(layout.0, crate::layout!((8, (2, 4)) : (8, (4, 1))));
+            assert_eq!(layout.__repr__(), "Layout((8,(2,4)):(8,(4,1)))");
+            assert!(layout.shape(py).unwrap().eq(&shape).unwrap());
+
+            assert_eq!(layout.__call__(nested(py, 1, [1, 3]).as_any()).unwrap(), 8 + 4 + 3);
+
+            let bad = PyTuple::new(py, [1usize, 2]).unwrap();
+            let err = PyLayout::new(shape.as_any(), Some(bad.as_any())).unwrap_err();
+            assert!(err.to_string().contains("not congruent"), "{err}");
+        });
+    }
+}
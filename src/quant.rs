@@ -0,0 +1,163 @@
+// src/quant.rs
+//
+// Symmetric int8 quantization: x ~= scale * q with q in [-127, 127] and one
+// scale per tensor or per row. Products of quantized matrices accumulate
+// in i32 and are rescaled once per output element.
+
+use crate::cast::CastFrom;
+use crate::error::check_rank;
+use crate::layout::Layout;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+
+fn extents<T>(op: &'static str, v: &TensorView<'_, T>) -> (usize, usize) {
+    let shape = v.layout().shape();
+    if let Err(e) = check_rank(op, 2, shape.flat_len()) {
+        panic!("{e}");
+    }
+    (shape.flat_at(0), shape.flat_at(1))
+}
+
+/// Scale of row `i` when there are `len` scales for `rows` rows
+fn scale_index(op: &'static str, len: usize, rows: usize) -> impl Fn(usize) -> usize {
+    assert!(len == 1 || len == rows, "{op}: expected 1 (per-tensor) or {rows} (per-row) scales, got {len}");
+    move |i| if len == 1 { 0 } else { i }
+}
+
+/// Quantize a rank-2 `src` into a row-major i8 tensor, writing the scales
+/// to `scales_out`: one for the whole tensor if it has length 1, one per
+/// row if it has one entry per row. Each scale maps the largest magnitude
+/// it covers to 127.
+pub fn quantize_f32_to_i8(src: &TensorView<'_, f32>, scales_out: &mut [f32]) -> Tensor<i8> {
+    const OP: &str = "quantize_f32_to_i8";
+    let (rows, cols) = extents(OP, src);
+    let slot = scale_index(OP, scales_out.len(), rows);
+
+    scales_out.fill(0.0);
+    if let [s] = scales_out {
+        // One scale for everything: element order does not matter
+        *s = src.iter_memory_order().fold(0.0, |m, x| m.max(x.abs()));
+    } else {
+        for i in 0..rows {
+            for j in 0..cols {
+                let s = &mut scales_out[slot(i)];
+                *s = s.max(src[[i, j]].abs());
+            }
+        }
+    }
+    for s in scales_out.iter_mut() {
+        *s = if *s > 0.0 { *s / 127.0 } else { 1.0 };
+    }
+
+    let mut data = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        let scale = scales_out[slot(i)];
+        data.extend((0..cols).map(|j| i8::cast_from(src[[i, j]] / scale)));
+    }
+    Tensor::new(data, Layout::row_major([rows, cols]))
+}
+
+/// Inverse of `quantize_f32_to_i8`, with the same scale layout
+pub fn dequantize_i8_to_f32(q: &TensorView<'_, i8>, scales: &[f32]) -> Tensor<f32> {
+    const OP: &str = "dequantize_i8_to_f32";
+    let (rows, cols) = extents(OP, q);
+    let slot = scale_index(OP, scales.len(), rows);
+
+    let mut data = Vec::with_capacity(rows * cols);
+    for i in 0..rows {
+        data.extend((0..cols).map(|j| scales[slot(i)] * q[[i, j]] as f32));
+    }
+    Tensor::new(data, Layout::row_major([rows, cols]))
+}
+
+/// `C = A * B` over int8 operands, accumulated exactly in i32
+pub fn gemm_i8(a: &TensorView<'_, i8>, b: &TensorView<'_, i8>, c: &mut TensorViewMut<'_, i32>) {
+    const OP: &str = "gemm_i8";
+    let ((m, k), (kb, n)) = (extents(OP, a), extents(OP, b));
+    assert_eq!(k, kb, "{OP}: inner extents {k} and {kb} differ");
+    let cs = c.layout().shape();
+    assert!(cs.flat_len() == 2 && (cs.flat_at(0), cs.flat_at(1)) == (m, n), "{OP}: C must be {m}x{n}");
+
+    for i in 0..m {
+        for j in 0..n {
+            c[[i, j]] = (0..k).map(|p| a[[i, p]] as i32 * b[[p, j]] as i32).sum();
+        }
+    }
+}
+
+/// `C = alpha * dequant(A) * dequant(B) + beta * C` from quantized operands:
+/// `a_scales` may be per-tensor or per-row; `b` must be quantized per
+/// tensor, since per-row scales of B vary along the summed mode.
+pub fn gemm_quantized_f32(
+    a: &TensorView<'_, i8>,
+    a_scales: &[f32],
+    b: &TensorView<'_, i8>,
+    b_scale: f32,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+) {
+    let ((m, _), n) = (extents("gemm_quantized_f32", a), extents("gemm_quantized_f32", b).1);
+    let slot = scale_index("gemm_quantized_f32", a_scales.len(), m);
+
+    let mut acc = Tensor::new(vec![0i32; m * n], Layout::row_major([m, n]));
+    gemm_i8(a, b, &mut acc.as_view_mut());
+    for i in 0..m {
+        let scale = alpha * a_scales[slot(i)] * b_scale;
+        for j in 0..n {
+            let old = if beta == 0.0 { 0.0 } else { beta * c[[i, j]] };
+            c[[i, j]] = scale * acc.as_view()[[i, j]] as f32 + old;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::gemm::gemm_f32;
+    use crate::test_util::matrix;
+
+    fn graded_rows(rows: usize, cols: usize, seed: u32) -> Tensor<f32> {
+        // Rows of very different magnitude, where per-row scales pay off
+        let data = (0..rows * cols)
+            .map(|x| {
+                let r = (x / cols) as f32;
+                (((x as u32).wrapping_mul(2654435761).wrapping_add(seed) >> 16) as f32 / 65536.0 - 0.5) * (1.0 + 10.0 * r)
+            })
+            .collect();
+        matrix(rows, cols, data)
+    }
+
+    #[test]
+    fn round_trip_is_within_half_a_step() {
+        let x = graded_rows(4, 16, 7);
+        for per_row in [false, true] {
+            let mut scales = vec![0.0; if per_row { 4 } else { 1 }];
+            let q = quantize_f32_to_i8(&x.as_view(), &mut scales);
+            assert!(q.data().iter().all(|&v| v >= -127));
+            let back = dequantize_i8_to_f32(&q.as_view(), &scales);
+            for (i, (a, b)) in x.data().iter().zip(back.data()).enumerate() {
+                let scale = scales[if per_row { i / 16 } else { 0 }];
+                assert!((a - b).abs() <= 0.5 * scale + 1e-6, "{a} vs {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn quantized_gemm_tracks_f32_reference() {
+        let (m, k, n) = (6, 64, 5);
+        let (a, b) = (graded_rows(m, k, 1), graded_rows(k, n, 2));
+        let mut reference = matrix(m, n, vec![0.0; m * n]);
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut reference.as_view_mut(), 1.0, 0.0);
+
+        let mut a_scales = vec![0.0; m];
+        let mut b_scale = [0.0];
+        let (qa, qb) = (quantize_f32_to_i8(&a.as_view(), &mut a_scales), quantize_f32_to_i8(&b.as_view(), &mut b_scale));
+        let mut c = matrix(m, n, vec![0.0; m * n]);
+        gemm_quantized_f32(&qa.as_view(), &a_scales, &qb.as_view(), b_scale[0], &mut c.as_view_mut(), 1.0, 0.0);
+
+        let norm = reference.data().iter().map(|x| x * x).sum::<f32>().sqrt();
+        let err = reference.data().iter().zip(c.data()).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt();
+        assert!(err / norm < 0.02, "relative error {}", err / norm);
+    }
+}
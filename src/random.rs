@@ -0,0 +1,87 @@
+// src/random.rs
+//
+// Random fills for tensors and views. Every function takes the generator
+// explicitly, so a seeded `StdRng` (see `seeded`) makes tests, benchmarks
+// and auto-tuning runs reproducible.
+
+use rand::distr::uniform::SampleUniform;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::cast::CastFrom;
+use crate::tensor::TensorViewMut;
+
+/// Deterministic generator for `seed`
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+/// Fill `dst` with values uniform in `[low, high)`
+pub fn fill_uniform<T: SampleUniform + Copy + PartialOrd, R: Rng + ?Sized>(
+    dst: &mut TensorViewMut<'_, T>,
+    low: T,
+    high: T,
+    rng: &mut R,
+) {
+    assert!(low < high, "fill_uniform: empty range");
+    for (_, x) in dst.indexed_iter_mut() {
+        *x = rng.random_range(low..high);
+    }
+}
+
+/// Fill `dst` with normally distributed values (Box-Muller, computed in f64)
+pub fn fill_normal<T: CastFrom<f64>, R: Rng + ?Sized>(dst: &mut TensorViewMut<'_, T>, mean: f64, std_dev: f64, rng: &mut R) {
+    assert!(std_dev >= 0.0, "fill_normal: std_dev must be >= 0");
+    // Each pair of uniforms gives two independent normals
+    let mut spare = None;
+    for (_, x) in dst.indexed_iter_mut() {
+        let z = spare.take().unwrap_or_else(|| {
+            let u1: f64 = 1.0 - rng.random::<f64>();
+            let u2: f64 = rng.random();
+            let (r, theta) = ((-2.0 * u1.ln()).sqrt(), 2.0 * std::f64::consts::PI * u2);
+            spare = Some(r * theta.sin());
+            r * theta.cos()
+        });
+        *x = T::cast_from(mean + std_dev * z);
+    }
+}
+
+/// Fill `dst` with `true` at probability `p`
+pub fn fill_bernoulli<R: Rng + ?Sized>(dst: &mut TensorViewMut<'_, bool>, p: f64, rng: &mut R) {
+    assert!((0.0..=1.0).contains(&p), "fill_bernoulli: p = {p} is not a probability");
+    for (_, x) in dst.indexed_iter_mut() {
+        *x = rng.random_bool(p);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    #[test]
+    fn fills_are_reproducible_and_roughly_distributed() {
+        let draw = |seed| {
+            let mut t = Tensor::new(vec![0.0f32; 4000], Layout::row_major([40, 100]));
+            fill_normal(&mut t.as_view_mut(), 2.0, 0.5, &mut seeded(seed));
+            t
+        };
+        let (a, b) = (draw(7), draw(7));
+        assert_eq!(a.data(), b.data());
+        assert_ne!(a.data(), draw(8).data());
+        let mean = a.data().iter().sum::<f32>() / 4000.0;
+        let var = a.data().iter().map(|x| (x - mean) * (x - mean)).sum::<f32>() / 4000.0;
+        assert!((mean - 2.0).abs() < 0.05 && (var.sqrt() - 0.5).abs() < 0.05, "{mean} {var}");
+
+        let mut rng = seeded(1);
+        let mut u = Tensor::new(vec![0i32; 1000], Layout::row_major([1000]));
+        fill_uniform(&mut u.as_view_mut(), -3, 3, &mut rng);
+        assert!(u.data().iter().all(|x| (-3..3).contains(x)));
+
+        let mut m = Tensor::new(vec![false; 1000], Layout::col_major([10, 100]));
+        fill_bernoulli(&mut m.as_view_mut(), 0.25, &mut rng);
+        let hits = m.data().iter().filter(|&&x| x).count();
+        assert!((200..300).contains(&hits), "{hits}");
+    }
+}
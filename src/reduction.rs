@@ -0,0 +1,221 @@
+// src/reduction.rs
+//
+// GEMM with the K mode split into blocks. Every K block contributes to every
+// C tile, so K blocks computed in parallel must be combined without racing
+// on C; `ReductionStrategy` picks how.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::bench_utils::KernelStats;
+use crate::blas::BlasBackend;
+use crate::copy::tensor_add_assign;
+use crate::gemm::{check_gemm_shapes, gemm_f32};
+use crate::hw::default_tile_for_gemm;
+use crate::layout::Layout;
+use crate::parallel::Parallelism;
+use crate::shape::coords;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tiled_tensor::{Tile, TiledTensorViewMut};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReductionStrategy {
+    /// Threads own C tiles and walk K blocks one after another
+    Serial,
+    /// Threads own K ranges, write private partial C buffers, and the
+    /// buffers are summed pairwise
+    TreeReduce,
+    /// Threads own K ranges and add their partial C into C with f32
+    /// compare-and-swap
+    Atomic,
+}
+
+/// K-blocked GEMM driver
+#[derive(Debug, Clone, Copy)]
+pub struct TiledReduction {
+    strategy: ReductionStrategy,
+    k_tile: usize,
+    parallelism: Parallelism,
+}
+
+impl TiledReduction {
+    pub fn new(strategy: ReductionStrategy, k_tile: usize) -> Self {
+        assert!(k_tile > 0, "TiledReduction: k_tile must be > 0");
+        Self { strategy, k_tile, parallelism: Parallelism::default() }
+    }
+
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.parallelism.threads = threads.max(1);
+        self
+    }
+
+    pub fn with_parallelism(mut self, parallelism: Parallelism) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// `C = alpha * A * B + beta * C`
+    pub fn gemm_f32<B: BlasBackend + Sync>(
+        &self,
+        backend: &B,
+        a: &TensorView<'_, f32>,
+        b: &TensorView<'_, f32>,
+        c: &mut TensorViewMut<'_, f32>,
+        alpha: f32,
+        beta: f32,
+    ) -> KernelStats {
+        let (m, n, k) = check_gemm_shapes("TiledReduction::gemm_f32", a.layout(), b.layout(), c.layout())
+            .unwrap_or_else(|e| panic!("{e}"));
+
+        let stats = KernelStats::gemm(m, n, k, std::mem::size_of::<f32>(), beta);
+        let blocks = k.div_ceil(self.k_tile);
+        let threads = self.parallelism.num_threads();
+        if blocks <= 1 || threads == 1 {
+            gemm_f32(backend, a, b, c, alpha, beta);
+            return stats;
+        }
+
+        match self.strategy {
+            ReductionStrategy::Serial => self.serial(backend, a, b, c, alpha, beta),
+            ReductionStrategy::TreeReduce | ReductionStrategy::Atomic => {
+                // Contiguous runs of K blocks, one per thread
+                let threads = threads.min(blocks);
+                let par = self.parallelism.with_threads(threads);
+                let ranges: Vec<(usize, usize)> = (0..threads)
+                    .map(|t| ((blocks * t / threads * self.k_tile).min(k), (blocks * (t + 1) / threads * self.k_tile).min(k)))
+                    .collect();
+                let partial = |(k0, k1): (usize, usize)| {
+                    let mut p = Tensor::new(vec![0.0f32; m * n], Layout::row_major([m, n]));
+                    let (a_k, b_k) = unsafe { (a.subview_2d(0, k0, m, k1 - k0), b.subview_2d(k0, 0, k1 - k0, n)) };
+                    gemm_f32(backend, &a_k, &b_k, &mut p.as_view_mut(), alpha, 0.0);
+                    p
+                };
+
+                if self.strategy == ReductionStrategy::TreeReduce {
+                    let mut partials: Vec<Option<Tensor<f32>>> = ranges.iter().map(|_| None).collect();
+                    let jobs: Vec<_> = ranges.iter().copied().zip(partials.iter_mut()).collect();
+                    par.run(jobs, |(r, slot)| *slot = Some(partial(r)));
+                    let sum = tree_sum(partials.into_iter().flatten().collect(), &par);
+                    for crd in coords(c.layout().shape()) {
+                        let x = unsafe { c.get_mut(&crd) };
+                        *x = sum.as_view()[[crd.flat_at(0), crd.flat_at(1)]] + if beta == 0.0 { 0.0 } else { beta * *x };
+                    }
+                } else {
+                    for (_, x) in c.indexed_iter_mut() {
+                        *x = if beta == 0.0 { 0.0 } else { beta * *x };
+                    }
+                    let c = &*c;
+                    par.run(ranges, |r| {
+                        let p = partial(r);
+                        for crd in coords(c.layout().shape()) {
+                            let v = p.as_view()[[crd.flat_at(0), crd.flat_at(1)]];
+                            // Every thread only touches C through these atomic adds
+                            unsafe { atomic_add(c.ptr_at_mut(&crd), v) };
+                        }
+                    });
+                }
+            }
+        }
+        stats
+    }
+
+    /// C tiles in parallel; each tile accumulates its K blocks in order
+    fn serial<B: BlasBackend + Sync>(
+        &self,
+        backend: &B,
+        a: &TensorView<'_, f32>,
+        b: &TensorView<'_, f32>,
+        c: &mut TensorViewMut<'_, f32>,
+        alpha: f32,
+        beta: f32,
+    ) {
+        let (m, n, k) = (c.layout().shape().flat_at(0), c.layout().shape().flat_at(1), a.layout().shape().flat_at(1));
+        let tiler = default_tile_for_gemm(m, n, k, std::mem::size_of::<f32>()).tiler();
+        let shape = c.layout().shape().clone();
+        let c_full = unsafe { c.subview_mut([0, 0], shape) };
+        let mut tiled = TiledTensorViewMut::new(c_full, tiler);
+        let jobs: Vec<(Tile, TensorViewMut<'_, f32>)> = tiled.tiles_mut().collect();
+
+        let k_tile = self.k_tile;
+        self.parallelism.run(jobs, |(tile, mut view)| {
+            let (m0, n0, tm, tn) = (tile.start(0), tile.start(1), tile.len(0), tile.len(1));
+            #[cfg(feature = "trace")]
+            let _span = crate::trace::TileSpan::new(
+                "TiledReduction",
+                &tile,
+                4 * (tm * k + k * tn + 2 * tm * tn),
+                2 * tm * tn * k,
+            );
+            for k0 in (0..k).step_by(k_tile) {
+                let kb = k_tile.min(k - k0);
+                let (a_sub, b_sub) = unsafe { (a.subview_2d(m0, k0, tm, kb), b.subview_2d(k0, n0, kb, tn)) };
+                gemm_f32(backend, &a_sub, &b_sub, &mut view, alpha, if k0 == 0 { beta } else { 1.0 });
+            }
+        });
+    }
+}
+
+/// Pairwise sum of equally shaped row-major buffers, one round per level
+fn tree_sum(mut partials: Vec<Tensor<f32>>, par: &Parallelism) -> Tensor<f32> {
+    while partials.len() > 1 {
+        let half = partials.len().div_ceil(2);
+        let (lo, hi) = partials.split_at_mut(half);
+        par.run(lo.iter_mut().zip(hi.iter()).collect(), |(dst, src)| {
+            tensor_add_assign(&mut dst.as_view_mut(), &src.as_view())
+        });
+        partials.truncate(half);
+    }
+    partials.pop().expect("tree_sum of no partials")
+}
+
+/// `*ptr += v` as an f32 compare-and-swap loop
+///
+/// # Safety
+/// `ptr` must be valid, 4-byte aligned, and only accessed atomically while
+/// other threads may touch it.
+unsafe fn atomic_add(ptr: *mut f32, v: f32) {
+    let cell = AtomicU32::from_ptr(ptr as *mut u32);
+    let mut cur = cell.load(Ordering::Relaxed);
+    loop {
+        let next = (f32::from_bits(cur) + v).to_bits();
+        match cell.compare_exchange_weak(cur, next, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => break,
+            Err(actual) => cur = actual,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+
+    #[test]
+    fn strategies_match_plain_gemm() {
+        let (m, k, n) = (6, 37, 5);
+        let a = Tensor::new((0..m * k).map(|x| (x % 5) as f32 - 2.0).collect(), Layout::row_major([m, k]));
+        let b = Tensor::new((0..k * n).map(|x| (x % 3) as f32).collect(), Layout::col_major([k, n]));
+        let init: Vec<f32> = (0..m * n).map(|x| x as f32).collect();
+
+        let mut expected = Tensor::new(init.clone(), Layout::row_major([m, n]));
+        gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 2.0, 0.5);
+
+        for strategy in [ReductionStrategy::Serial, ReductionStrategy::TreeReduce, ReductionStrategy::Atomic] {
+            let mut c = Tensor::new(init.clone(), Layout::row_major([m, n]));
+            TiledReduction::new(strategy, 4)
+                .with_threads(5)
+                .gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 2.0, 0.5);
+            assert_eq!(c.data(), expected.data(), "{strategy:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "TiledReduction")]
+    fn rejects_c_smaller_than_the_product() {
+        let a = Tensor::new(vec![0.0; 6 * 8], Layout::row_major([6, 8]));
+        let b = Tensor::new(vec![0.0; 8 * 5], Layout::row_major([8, 5]));
+        let mut c = Tensor::new(vec![0.0; 4], Layout::row_major([2, 2]));
+        TiledReduction::new(ReductionStrategy::Atomic, 2)
+            .with_threads(4)
+            .gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+    }
+}
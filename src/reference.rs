@@ -0,0 +1,169 @@
+// src/reference.rs
+//
+// Scalar reference implementations: plain loops over coordinates with no
+// tiling, packing, backend or parallelism, so they are easy to check by eye.
+// Tests of the optimized paths (and of user kernels plugged into them)
+// compare against these. They are slow and meant for small problems.
+
+use std::ops::{Add, Mul};
+
+use crate::error::{check_axis, check_rank, check_same_shape, Error, Result};
+use crate::gemm::check_gemm_shapes;
+use crate::shape::{for_each_flat_coord, Shape};
+use crate::tensor::{TensorView, TensorViewMut};
+use crate::tuple::Tuple;
+
+/// `C = alpha * A * B + beta * C` for rank-2 views of any layout. As in
+/// BLAS, C is not read when `beta` is zero.
+pub fn gemm<T>(a: &TensorView<'_, T>, b: &TensorView<'_, T>, c: &mut TensorViewMut<'_, T>, alpha: T, beta: T)
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    try_gemm(a, b, c, alpha, beta).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `gemm` returning mismatched operands as `Error::GemmOperands`
+pub fn try_gemm<T>(a: &TensorView<'_, T>, b: &TensorView<'_, T>, c: &mut TensorViewMut<'_, T>, alpha: T, beta: T) -> Result<()>
+where
+    T: Copy + Default + PartialEq + Add<Output = T> + Mul<Output = T>,
+{
+    let (m, n, k) = check_gemm_shapes("reference::gemm", a.layout(), b.layout(), c.layout())?;
+    for i in 0..m {
+        for j in 0..n {
+            let mut dot = T::default();
+            for p in 0..k {
+                dot = dot + a[[i, p]] * b[[p, j]];
+            }
+            let old = if beta == T::default() { T::default() } else { beta * c[[i, j]] };
+            c[[i, j]] = alpha * dot + old;
+        }
+    }
+    Ok(())
+}
+
+/// Valid-mode N-D cross-correlation (the "convolution" of neural networks):
+/// `out[o] = sum_w input[o + w] * weight[w]`, where all three views have
+/// the same flat rank and each extent of `out` is `input - weight + 1`
+pub fn conv<T>(input: &TensorView<'_, T>, weight: &TensorView<'_, T>, out: &mut TensorViewMut<'_, T>)
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    try_conv(input, weight, out).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `conv` returning rank and extent mismatches as errors
+pub fn try_conv<T>(input: &TensorView<'_, T>, weight: &TensorView<'_, T>, out: &mut TensorViewMut<'_, T>) -> Result<()>
+where
+    T: Copy + Default + Add<Output = T> + Mul<Output = T>,
+{
+    const OP: &str = "reference::conv";
+    let (di, dw) = (input.layout().flat_shape().to_vec(), weight.layout().flat_shape().to_vec());
+    check_rank(OP, di.len(), dw.len())?;
+    check_rank(OP, di.len(), out.layout().flat_shape().len())?;
+    if di.iter().zip(&dw).any(|(&i, &w)| w == 0 || w > i) {
+        return Err(Error::ShapeMismatch { op: OP, lhs: input.layout().shape().clone(), rhs: weight.layout().shape().clone() });
+    }
+    let expected = Shape::new(Tuple::int(di.iter().zip(&dw).map(|(&i, &w)| i - w + 1).collect()));
+    check_same_shape(OP, &expected, &Shape::new(Tuple::int(out.layout().flat_shape().to_vec())))?;
+
+    let w_shape = Shape::new(Tuple::int(dw));
+    for_each_flat_coord(&expected, |o| {
+        let mut sum = T::default();
+        for_each_flat_coord(&w_shape, |w| {
+            let at: Vec<usize> = o.iter().zip(w).map(|(o, w)| o + w).collect();
+            // SAFETY: `o < input - weight + 1` and `w < weight` per mode, so `o + w < input`
+            sum = sum + unsafe { *input.get_flat(&at) * *weight.get_flat(w) };
+        });
+        // SAFETY: `o` ranges over the shape of `out`
+        unsafe { *out.get_flat_mut(o) = sum };
+    });
+    Ok(())
+}
+
+/// Softmax of `x` along flat mode `axis`, written to `out` of the same
+/// shape. The maximum of each row is subtracted first and the sums are
+/// taken in `f64`.
+pub fn softmax(x: &TensorView<'_, f32>, axis: usize, out: &mut TensorViewMut<'_, f32>) {
+    try_softmax(x, axis, out).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// `softmax` returning a bad axis or mismatched shapes as errors
+pub fn try_softmax(x: &TensorView<'_, f32>, axis: usize, out: &mut TensorViewMut<'_, f32>) -> Result<()> {
+    const OP: &str = "reference::softmax";
+    let dims = x.layout().flat_shape().to_vec();
+    check_axis(OP, axis, dims.len())?;
+    check_same_shape(OP, &Shape::new(Tuple::int(dims.clone())), &Shape::new(Tuple::int(out.layout().flat_shape().to_vec())))?;
+
+    // Visit every row along `axis` by iterating the other modes with `axis` pinned to 0
+    let mut outer = dims.clone();
+    outer[axis] = 1;
+    for_each_flat_coord(&Shape::new(Tuple::int(outer)), |base| {
+        let mut crd = base.to_vec();
+        let mut row = Vec::with_capacity(dims[axis]);
+        for i in 0..dims[axis] {
+            crd[axis] = i;
+            // SAFETY: `crd` is within the shape of `x`
+            row.push(unsafe { *x.get_flat(&crd) } as f64);
+        }
+        let max = row.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let sum: f64 = row.iter().map(|v| (v - max).exp()).sum();
+        for (i, v) in row.iter().enumerate() {
+            crd[axis] = i;
+            // SAFETY: `out` has the shape of `x`
+            unsafe { *out.get_flat_mut(&crd) = ((v - max).exp() / sum) as f32 };
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    #[test]
+    fn gemm_handles_strided_operands_and_beta() {
+        let a = Tensor::new(vec![1, 2, 3, 4, 5, 6], Layout::col_major([2, 3]));
+        let b = Tensor::new(vec![1, 0, 2, 1, 0, 3], Layout::row_major([3, 2]));
+        let mut c = Tensor::new(vec![10, 20, 30, 40], Layout::row_major([2, 2]));
+        gemm(&a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1, 2);
+        // A = [[1, 3, 5], [2, 4, 6]]
+        assert_eq!(c.data(), &[27, 58, 70, 102]);
+
+        let mut c = Tensor::new(vec![f32::NAN; 4], Layout::row_major([2, 2]));
+        let (a, b) = (a.astype::<f32>(), b.astype::<f32>());
+        gemm(&a.as_view(), &b.as_view(), &mut c.as_view_mut(), 0.5, 0.0);
+        assert_eq!(c.data(), &[3.5, 9.0, 5.0, 11.0]);
+
+        let err = try_gemm(&b.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.0, 0.0);
+        assert!(matches!(err, Err(Error::GemmOperands { .. })));
+    }
+
+    #[test]
+    fn conv_is_valid_cross_correlation() {
+        let input = Tensor::new((0..12).collect(), Layout::row_major([3, 4]));
+        let weight = Tensor::new(vec![1, 0, 0, -1], Layout::row_major([2, 2]));
+        let mut out = Tensor::new(vec![0; 6], Layout::row_major([2, 3]));
+        conv(&input.as_view(), &weight.as_view(), &mut out.as_view_mut());
+        // input[i][j] - input[i + 1][j + 1] = -5 everywhere
+        assert_eq!(out.data(), &[-5; 6]);
+
+        let mut wrong = Tensor::new(vec![0; 4], Layout::row_major([2, 2]));
+        assert!(matches!(try_conv(&input.as_view(), &weight.as_view(), &mut wrong.as_view_mut()), Err(Error::ShapeMismatch { .. })));
+    }
+
+    #[test]
+    fn softmax_rows_sum_to_one() {
+        let x = Tensor::new(vec![1.0, 2.0, 3.0, 1000.0, 1000.0, 1000.0], Layout::row_major([2, 3]));
+        let mut out = Tensor::new(vec![0.0; 6], Layout::col_major([2, 3]));
+        softmax(&x.as_view(), 1, &mut out.as_view_mut());
+        let view = out.as_view();
+        assert!((view[[1, 0]] - 1.0 / 3.0).abs() < 1e-6);
+        assert!((view[[0, 2]] / view[[0, 1]] - std::f32::consts::E).abs() < 1e-5);
+        for i in 0..2 {
+            assert!(((0..3).map(|j| view[[i, j]]).sum::<f32>() - 1.0).abs() < 1e-6);
+        }
+        assert!(matches!(try_softmax(&x.as_view(), 2, &mut out.as_view_mut()), Err(Error::InvalidAxis { .. })));
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::ops::AddAssign;
+
+use crate::layout::Layout;
+use crate::shape::{coords, Shape};
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::tuple::Tuple;
+
+/// Replace the `axis` entry of a flattened coordinate
+fn with_axis(crd: &Tuple, axis: usize, value: usize) -> Tuple {
+    let mut flat = crd.flatten();
+    flat[axis] = value;
+    Tuple::int(flat)
+}
+
+/// Returns true if no index appears twice, i.e. writes through `indices`
+/// touch pairwise disjoint slices along the scattered axis. Expected O(n),
+/// stopping at the first repeat.
+pub fn indices_disjoint(indices: &[usize]) -> bool {
+    let mut seen = HashSet::with_capacity(indices.len());
+    indices.iter().all(|&i| seen.insert(i))
+}
+
+/// Select slices of `src` along flattened mode `axis`:
+/// `out[.., i, ..] = src[.., indices[i], ..]`. The result is row-major.
+pub fn gather<T: Copy>(src: &TensorView<'_, T>, indices: &[usize], axis: usize) -> Tensor<T> {
+    let extents = src.layout().shape().dims.flatten();
+    assert!(axis < extents.len(), "gather: axis {} out of range", axis);
+    assert!(
+        indices.iter().all(|&i| i < extents[axis]),
+        "gather: index out of bounds for axis of extent {}",
+        extents[axis]
+    );
+
+    let mut out_extents = extents;
+    out_extents[axis] = indices.len();
+    let out_shape = Shape::new(Tuple::int(out_extents));
+
+    let data = coords(&out_shape)
+        .map(|crd| {
+            let i = crd.flatten()[axis];
+            unsafe { *src.get(&with_axis(&crd, axis, indices[i])) }
+        })
+        .collect();
+
+    Tensor::new(data, Layout::row_major(out_shape))
+}
+
+fn check_scatter_shapes<T>(
+    dst: &TensorViewMut<'_, T>,
+    indices: &[usize],
+    src: &TensorView<'_, T>,
+    axis: usize,
+    op: &str,
+) {
+    let dst_ext = dst.layout().shape().dims.flatten();
+    let src_ext = src.layout().shape().dims.flatten();
+
+    assert!(axis < dst_ext.len(), "{}: axis {} out of range", op, axis);
+    assert_eq!(dst_ext.len(), src_ext.len(), "{}: rank mismatch", op);
+    assert_eq!(src_ext[axis], indices.len(), "{}: src extent must equal indices.len()", op);
+    for d in (0..dst_ext.len()).filter(|&d| d != axis) {
+        assert_eq!(dst_ext[d], src_ext[d], "{}: extent mismatch in mode {}", op, d);
+    }
+    assert!(
+        indices.iter().all(|&i| i < dst_ext[axis]),
+        "{}: index out of bounds for axis of extent {}",
+        op,
+        dst_ext[axis]
+    );
+}
+
+/// Inverse of `gather`: `dst[.., indices[i], ..] = src[.., i, ..]`.
+///
+/// # Panics
+/// Panics if `indices` contains duplicates, since the result would depend on write order.
+pub fn scatter<T: Copy>(
+    dst: &mut TensorViewMut<'_, T>,
+    indices: &[usize],
+    src: &TensorView<'_, T>,
+    axis: usize,
+) {
+    check_scatter_shapes(dst, indices, src, axis, "scatter");
+    assert!(indices_disjoint(indices), "scatter: indices are not disjoint");
+
+    for crd in coords(src.layout().shape()) {
+        let i = crd.flatten()[axis];
+        unsafe {
+            *dst.get_mut(&with_axis(&crd, axis, indices[i])) = *src.get(&crd);
+        }
+    }
+}
+
+/// Accumulating scatter: `dst[.., indices[i], ..] += src[.., i, ..]`.
+/// Duplicate indices accumulate; they are applied sequentially so no write is lost.
+pub fn scatter_add<T: Copy + AddAssign>(
+    dst: &mut TensorViewMut<'_, T>,
+    indices: &[usize],
+    src: &TensorView<'_, T>,
+    axis: usize,
+) {
+    check_scatter_shapes(dst, indices, src, axis, "scatter_add");
+
+    for crd in coords(src.layout().shape()) {
+        let i = crd.flatten()[axis];
+        unsafe {
+            *dst.get_mut(&with_axis(&crd, axis, indices[i])) += *src.get(&crd);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::matrix;
+
+    #[test]
+    fn gather_rows_and_cols() {
+        let t = matrix(3, 2, vec![1, 2, 3, 4, 5, 6]);
+
+        let rows = gather(&t.as_view(), &[2, 0, 2], 0);
+        assert_eq!(rows.layout().shape().to_string(), "(3,2)");
+        assert_eq!(rows.data(), &[5, 6, 1, 2, 5, 6]);
+
+        let cols = gather(&t.as_view(), &[1], 1);
+        assert_eq!(cols.data(), &[2, 4, 6]);
+    }
+
+    #[test]
+    fn scatter_add_accumulates_duplicates() {
+        let mut dst = matrix(2, 2, vec![0; 4]);
+        let src = matrix(3, 2, vec![1, 1, 2, 2, 3, 3]);
+
+        scatter_add(&mut dst.as_view_mut(), &[1, 0, 1], &src.as_view(), 0);
+        assert_eq!(dst.data(), &[2, 2, 4, 4]);
+    }
+
+    #[test]
+    fn scatter_inverts_gather() {
+        let t = matrix(2, 3, vec![1, 2, 3, 4, 5, 6]);
+        let perm = [2, 0, 1];
+        let g = gather(&t.as_view(), &perm, 1);
+
+        let mut back = matrix(2, 3, vec![0; 6]);
+        scatter(&mut back.as_view_mut(), &perm, &g.as_view(), 1);
+        assert_eq!(back.data(), t.data());
+    }
+
+    #[test]
+    #[should_panic(expected = "not disjoint")]
+    fn scatter_rejects_overlapping_indices() {
+        let mut dst = matrix(2, 2, vec![0; 4]);
+        let src = matrix(2, 2, vec![1; 4]);
+        scatter(&mut dst.as_view_mut(), &[1, 1], &src.as_view(), 0);
+    }
+}
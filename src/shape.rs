@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use crate::tuple::Tuple;
 
 /// Shape wraps Tuple and adds semantic meaning
@@ -18,10 +20,15 @@ impl Shape {
 
     /// Rank = number of top-level modes
     pub fn rank(&self) -> usize {
-        match &self.dims {
-            Tuple::Int(_) => 1,
-            Tuple::Tup(v) => v.len(),
-        }
+        self.dims.len()
+    }
+
+    /// Number of coordinates of top-level mode `mode`, i.e. the product of
+    /// its sub-tree. Unlike `flat_at`, the index counts modes, not leaves:
+    /// for `(2,(3,4))`, `extent(1)` is 12 while `flat_at(1)` is 3.
+    pub fn extent(&self, mode: usize) -> usize {
+        assert!(mode < self.rank(), "Shape::extent: mode {mode} out of range for rank {}", self.rank());
+        self.dims.get(mode).size()
     }
     
     pub fn flat_at(&self, i: usize) -> usize {
@@ -46,6 +53,158 @@ impl Shape {
     }
 }
 
+/* ---------- mode editing (see the `Tuple` methods) ---------- */
+
+impl Shape {
+    pub fn append_mode(&self, mode: impl Into<Tuple>) -> Shape {
+        Shape::new(self.dims.append_mode(mode))
+    }
+
+    pub fn prepend_mode(&self, mode: impl Into<Tuple>) -> Shape {
+        Shape::new(self.dims.prepend_mode(mode))
+    }
+
+    pub fn insert_mode(&self, i: usize, mode: impl Into<Tuple>) -> Shape {
+        Shape::new(self.dims.insert_mode(i, mode))
+    }
+
+    pub fn remove_mode(&self, i: usize) -> Shape {
+        Shape::new(self.dims.remove_mode(i))
+    }
+
+    pub fn group_modes(&self, range: Range<usize>) -> Shape {
+        Shape::new(self.dims.group_modes(range))
+    }
+
+    pub fn ungroup(&self, i: usize) -> Shape {
+        Shape::new(self.dims.ungroup(i))
+    }
+
+    pub fn select(&self, modes: &[usize]) -> Shape {
+        Shape::new(self.dims.select(modes))
+    }
+}
+
+/* ---------- conversions ---------- */
+
+impl From<Tuple> for Shape {
+    fn from(dims: Tuple) -> Self {
+        Shape::new(dims)
+    }
+}
+
+impl From<&Shape> for Shape {
+    fn from(s: &Shape) -> Self {
+        s.clone()
+    }
+}
+
+/// Everything that converts into a `Tuple` also converts into a `Shape`.
+macro_rules! impl_shape_from {
+    ($(impl[$($g:tt)*] $ty:ty;)+) => {
+        $(impl<$($g)*> From<$ty> for Shape {
+            fn from(v: $ty) -> Self {
+                Shape::new(Tuple::from(v))
+            }
+        })+
+    };
+}
+
+impl_shape_from! {
+    impl[] usize;
+    impl[const N: usize] [usize; N];
+    impl[] &[usize];
+    impl[] Vec<usize>;
+    impl[] &Tuple;
+    impl[A: Into<Tuple>, B: Into<Tuple>] (A, B);
+    impl[A: Into<Tuple>, B: Into<Tuple>, C: Into<Tuple>] (A, B, C);
+    impl[A: Into<Tuple>, B: Into<Tuple>, C: Into<Tuple>, D: Into<Tuple>] (A, B, C, D);
+}
+
+/* ---------- coordinate enumeration ---------- */
+
+/// Iterator over every coordinate of a shape, in lexicographic order
+/// (last flattened mode fastest). Each coordinate has the same nesting as the shape.
+pub struct Coords {
+    structure: Tuple,
+    extents: Vec<usize>,
+    current: Option<Vec<usize>>,
+}
+
+/// Enumerate the coordinate space of `shape`
+pub fn coords(shape: &Shape) -> Coords {
+    let extents = shape.dims.flatten();
+    let current = if extents.contains(&0) {
+        None
+    } else {
+        Some(vec![0; extents.len()])
+    };
+
+    Coords {
+        structure: shape.dims.clone(),
+        extents,
+        current,
+    }
+}
+
+/// Visit every flattened coordinate of `shape` in `coords` order, reusing
+/// one buffer instead of building a `Tuple` per coordinate
+pub fn for_each_flat_coord(shape: &Shape, mut f: impl FnMut(&[usize])) {
+    let extents = shape.dims.flatten();
+    if extents.contains(&0) {
+        return;
+    }
+    let mut crd = vec![0; extents.len()];
+    loop {
+        f(&crd);
+        let mut d = crd.len();
+        loop {
+            if d == 0 {
+                return;
+            }
+            d -= 1;
+            crd[d] += 1;
+            if crd[d] < extents[d] {
+                break;
+            }
+            crd[d] = 0;
+        }
+    }
+}
+
+/// Rebuild a tuple with the nesting of `like` from flattened leaf values
+fn unflatten(like: &Tuple, flat: &[usize], pos: &mut usize) -> Tuple {
+    match like {
+        Tuple::Int(v) => {
+            let out = flat[*pos..*pos + v.len()].to_vec();
+            *pos += v.len();
+            Tuple::Int(out)
+        }
+        Tuple::Tup(vs) => Tuple::Tup(vs.iter().map(|t| unflatten(t, flat, pos)).collect()),
+    }
+}
+
+impl Iterator for Coords {
+    type Item = Tuple;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let flat = self.current.take()?;
+        let crd = unflatten(&self.structure, &flat, &mut 0);
+
+        let mut next = flat;
+        for d in (0..next.len()).rev() {
+            next[d] += 1;
+            if next[d] < self.extents[d] {
+                self.current = Some(next);
+                break;
+            }
+            next[d] = 0;
+        }
+
+        Some(crd)
+    }
+}
+
 impl std::fmt::Display for Shape {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.dims)
@@ -81,6 +240,11 @@ mod tests {
         assert_eq!(s.rank(), 2);
         assert_eq!(s.depth(), 2);
         assert_eq!(s.to_string(), "(2,(3,4))");
+        assert_eq!((s.extent(0), s.extent(1)), (2, 12));
+        assert_eq!((s.flat_at(1), s.flat_len()), (3, 3));
+
+        let flat = Shape::new(Tuple::int(vec![5, 6, 7]));
+        assert_eq!((flat.rank(), flat.extent(2)), (3, 7));
     }
 
     #[test]
@@ -101,5 +265,32 @@ mod tests {
         assert_eq!(s1.rank(), 2);
         assert_eq!(s1.to_string(), "(4,2)");
     }
+
+    #[test]
+    fn coords_flat() {
+        let s = Shape::new(Tuple::int(vec![2, 3]));
+        let all: Vec<String> = coords(&s).map(|c| c.to_string()).collect();
+        assert_eq!(all, vec!["(0,0)", "(0,1)", "(0,2)", "(1,0)", "(1,1)", "(1,2)"]);
+    }
+
+    #[test]
+    fn coords_hierarchical_keeps_nesting() {
+        let s = Shape::new(Tuple::tup(vec![
+            Tuple::int1(2),
+            Tuple::int(vec![2, 2]),
+        ]));
+
+        let all: Vec<Tuple> = coords(&s).collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0].to_string(), "(0,(0,0))");
+        assert_eq!(all[3].to_string(), "(0,(1,1))");
+        assert_eq!(all[7].to_string(), "(1,(1,1))");
+    }
+
+    #[test]
+    fn coords_empty_shape() {
+        let s = Shape::new(Tuple::int(vec![3, 0]));
+        assert_eq!(coords(&s).count(), 0);
+    }
 }
 
@@ -0,0 +1,158 @@
+// src/shape_infer.rs
+//
+// Result shapes of common tensor operations, with NumPy's rules. Shapes are
+// taken by their flattened modes and results are flat.
+
+use crate::error::{check_axis, Error, Result};
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+fn flat(dims: Vec<usize>) -> Shape {
+    Shape::new(Tuple::int(dims))
+}
+
+/// Broadcast of two shapes: modes are aligned from the right, missing
+/// leading modes count as 1, and each pair must match or contain a 1.
+pub fn broadcast_shape(a: &Shape, b: &Shape) -> Result<Shape> {
+    broadcast_dims(&a.dims.flatten(), &b.dims.flatten())
+        .map(flat)
+        .ok_or_else(|| Error::ShapeMismatch { op: "broadcast_shape", lhs: a.clone(), rhs: b.clone() })
+}
+
+fn broadcast_dims(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    let rank = a.len().max(b.len());
+    let at = |d: &[usize], i: usize| if i < rank - d.len() { 1 } else { d[i - (rank - d.len())] };
+    (0..rank)
+        .map(|i| match (at(a, i), at(b, i)) {
+            (x, y) if x == y || y == 1 => Some(x),
+            (1, y) => Some(y),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Shape of `a @ b`. The last two modes multiply as matrices and any
+/// leading (batch) modes broadcast; a rank-1 operand is a row vector on
+/// the left or a column vector on the right, and its mode is dropped
+/// from the result.
+pub fn matmul_output_shape(a: &Shape, b: &Shape) -> Result<Shape> {
+    const OP: &str = "matmul_output_shape";
+    let (da, db) = (a.dims.flatten(), b.dims.flatten());
+    let mismatch = || Error::ShapeMismatch { op: OP, lhs: a.clone(), rhs: b.clone() };
+    if da.is_empty() || db.is_empty() {
+        return Err(mismatch());
+    }
+
+    let a_vec = da.len() == 1;
+    let b_vec = db.len() == 1;
+    let da = if a_vec { vec![1, da[0]] } else { da };
+    let db = if b_vec { vec![db[0], 1] } else { db };
+
+    let (m, k) = (da[da.len() - 2], da[da.len() - 1]);
+    let (kb, n) = (db[db.len() - 2], db[db.len() - 1]);
+    if k != kb {
+        return Err(mismatch());
+    }
+
+    let mut out = broadcast_dims(&da[..da.len() - 2], &db[..db.len() - 2]).ok_or_else(mismatch)?;
+    if !a_vec {
+        out.push(m);
+    }
+    if !b_vec {
+        out.push(n);
+    }
+    Ok(flat(out))
+}
+
+/// Shape after reducing over `axes`; reduced modes become 1 with
+/// `keep_dims` and are removed otherwise.
+pub fn reduce_shape(shape: &Shape, axes: &[usize], keep_dims: bool) -> Result<Shape> {
+    let dims = shape.dims.flatten();
+    for &axis in axes {
+        check_axis("reduce_shape", axis, dims.len())?;
+    }
+    let out = dims
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &d)| match (axes.contains(&i), keep_dims) {
+            (false, _) => Some(d),
+            (true, true) => Some(1),
+            (true, false) => None,
+        })
+        .collect();
+    Ok(flat(out))
+}
+
+/// Shape of `shapes` joined along `axis`: every other mode must match
+pub fn concat_shape(shapes: &[&Shape], axis: usize) -> Result<Shape> {
+    const OP: &str = "concat_shape";
+    let first = shapes.first().ok_or(Error::NoOperands { op: OP })?;
+    let mut out = first.dims.flatten();
+    check_axis(OP, axis, out.len())?;
+
+    for s in &shapes[1..] {
+        let dims = s.dims.flatten();
+        let same_elsewhere =
+            dims.len() == out.len() && dims.iter().zip(&out).enumerate().all(|(i, (x, y))| i == axis || x == y);
+        if !same_elsewhere {
+            return Err(Error::ShapeMismatch { op: OP, lhs: (*first).clone(), rhs: (*s).clone() });
+        }
+        out[axis] += dims[axis];
+    }
+    Ok(flat(out))
+}
+
+/// Shape of equally shaped `shapes` stacked along a new mode at `axis`
+pub fn stack_shape(shapes: &[&Shape], axis: usize) -> Result<Shape> {
+    const OP: &str = "stack_shape";
+    let first = shapes.first().ok_or(Error::NoOperands { op: OP })?;
+    let mut out = first.dims.flatten();
+    check_axis(OP, axis, out.len() + 1)?;
+
+    for s in &shapes[1..] {
+        if s.dims.flatten() != out {
+            return Err(Error::ShapeMismatch { op: OP, lhs: (*first).clone(), rhs: (*s).clone() });
+        }
+    }
+    out.insert(axis, shapes.len());
+    Ok(flat(out))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn s(dims: &[usize]) -> Shape {
+        flat(dims.to_vec())
+    }
+
+    #[test]
+    fn broadcasting_and_matmul() {
+        assert_eq!(broadcast_shape(&s(&[8, 1, 6]), &s(&[7, 1])), Ok(s(&[8, 7, 6])));
+        assert_eq!(broadcast_shape(&s(&[3]), &s(&[])), Ok(s(&[3])));
+        assert!(matches!(broadcast_shape(&s(&[2, 3]), &s(&[4])), Err(Error::ShapeMismatch { .. })));
+
+        assert_eq!(matmul_output_shape(&s(&[2, 3]), &s(&[3, 4])), Ok(s(&[2, 4])));
+        assert_eq!(matmul_output_shape(&s(&[5, 1, 2, 3]), &s(&[7, 3, 4])), Ok(s(&[5, 7, 2, 4])));
+        assert_eq!(matmul_output_shape(&s(&[3]), &s(&[3, 4])), Ok(s(&[4])));
+        assert_eq!(matmul_output_shape(&s(&[2, 3]), &s(&[3])), Ok(s(&[2])));
+        assert_eq!(matmul_output_shape(&s(&[3]), &s(&[3])), Ok(s(&[])));
+        let err = matmul_output_shape(&s(&[2, 3]), &s(&[4, 2])).unwrap_err();
+        assert_eq!(err.to_string(), "matmul_output_shape: shape mismatch between (2,3) and (4,2)");
+    }
+
+    #[test]
+    fn reductions_and_joins() {
+        assert_eq!(reduce_shape(&s(&[2, 3, 4]), &[0, 2], false), Ok(s(&[3])));
+        assert_eq!(reduce_shape(&s(&[2, 3, 4]), &[1], true), Ok(s(&[2, 1, 4])));
+        assert_eq!(reduce_shape(&s(&[2]), &[1], true), Err(Error::InvalidAxis { op: "reduce_shape", axis: 1, rank: 1 }));
+
+        let (a, b) = (s(&[2, 3]), s(&[2, 5]));
+        assert_eq!(concat_shape(&[&a, &b], 1), Ok(s(&[2, 8])));
+        assert!(concat_shape(&[&a, &b], 0).is_err());
+        assert_eq!(stack_shape(&[&a, &a, &a], 2), Ok(s(&[2, 3, 3])));
+        assert!(stack_shape(&[&a, &b], 0).is_err());
+        assert_eq!(stack_shape(&[], 0), Err(Error::NoOperands { op: "stack_shape" }));
+        assert_eq!(concat_shape(&[], 0).unwrap_err().to_string(), "concat_shape: needs at least one operand");
+    }
+}
@@ -0,0 +1,140 @@
+// src/static_layout.rs
+//
+// Layouts whose shape and stride are fixed-size arrays computed by `const
+// fn`s. A micro-kernel can name its register tile as a `const` and every
+// offset it takes folds to a constant, instead of going through the heap
+// tuples of `Layout`. Convert to a `Layout` to hand the tile to the rest of
+// the crate.
+
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+/// Strides of a compact row-major layout of `shape` (last mode fastest)
+pub const fn row_major_strides<const R: usize>(shape: [usize; R]) -> [usize; R] {
+    let mut stride = [0; R];
+    let mut acc = 1;
+    let mut i = R;
+    while i > 0 {
+        i -= 1;
+        stride[i] = acc;
+        acc *= shape[i];
+    }
+    stride
+}
+
+/// Strides of a compact column-major layout of `shape` (first mode fastest)
+pub const fn col_major_strides<const R: usize>(shape: [usize; R]) -> [usize; R] {
+    let mut stride = [0; R];
+    let mut acc = 1;
+    let mut i = 0;
+    while i < R {
+        stride[i] = acc;
+        acc *= shape[i];
+        i += 1;
+    }
+    stride
+}
+
+/// Flat rank-`R` layout known at compile time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticLayout<const R: usize> {
+    shape: [usize; R],
+    stride: [usize; R],
+}
+
+impl<const R: usize> StaticLayout<R> {
+    pub const fn row_major(shape: [usize; R]) -> Self {
+        Self { shape, stride: row_major_strides(shape) }
+    }
+
+    pub const fn col_major(shape: [usize; R]) -> Self {
+        Self { shape, stride: col_major_strides(shape) }
+    }
+
+    /// Same shape with explicit strides, e.g. a tile of a larger buffer
+    pub const fn with_stride(self, stride: [usize; R]) -> Self {
+        Self { shape: self.shape, stride }
+    }
+
+    pub const fn shape(&self) -> [usize; R] {
+        self.shape
+    }
+
+    pub const fn stride(&self) -> [usize; R] {
+        self.stride
+    }
+
+    /// Number of coordinates
+    pub const fn size(&self) -> usize {
+        let mut n = 1;
+        let mut i = 0;
+        while i < R {
+            n *= self.shape[i];
+            i += 1;
+        }
+        n
+    }
+
+    /// One past the largest offset the layout reaches (0 when empty)
+    pub const fn cosize(&self) -> usize {
+        if self.size() == 0 {
+            return 0;
+        }
+        let mut last = 0;
+        let mut i = 0;
+        while i < R {
+            last += (self.shape[i] - 1) * self.stride[i];
+            i += 1;
+        }
+        last + 1
+    }
+
+    /// Offset of `crd`; out-of-range coordinates fail const evaluation
+    /// and panic at runtime
+    #[inline(always)]
+    pub const fn crd2idx(&self, crd: [usize; R]) -> usize {
+        let mut idx = 0;
+        let mut i = 0;
+        while i < R {
+            assert!(crd[i] < self.shape[i], "StaticLayout::crd2idx: coordinate out of bounds");
+            idx += crd[i] * self.stride[i];
+            i += 1;
+        }
+        idx
+    }
+}
+
+impl<const R: usize> From<StaticLayout<R>> for Layout {
+    fn from(l: StaticLayout<R>) -> Layout {
+        Layout::with_shape_stride(Shape::new(Tuple::int(l.shape.to_vec())), Tuple::int(l.stride.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{ColMajor, RowMajor};
+
+    const TILE: StaticLayout<3> = StaticLayout::row_major([2, 3, 4]);
+
+    #[test]
+    fn strides_are_const() {
+        const S: [usize; 3] = TILE.stride();
+        const IDX: usize = TILE.crd2idx([1, 2, 3]);
+        assert_eq!(S, [12, 4, 1]);
+        assert_eq!(IDX, 23);
+        assert_eq!(col_major_strides([2, 3, 4]), [1, 2, 6]);
+        assert_eq!((TILE.size(), TILE.cosize()), (24, 24));
+        assert_eq!(StaticLayout::row_major([4, 4]).with_stride([1, 8]).cosize(), 28);
+        assert_eq!(StaticLayout::<2>::row_major([0, 4]).cosize(), 0);
+    }
+
+    #[test]
+    fn converts_to_the_matching_layout() {
+        assert_eq!(Layout::from(TILE), Layout::new::<RowMajor>(Shape::new(Tuple::int(vec![2, 3, 4]))));
+        let col = StaticLayout::col_major([5, 2]);
+        assert_eq!(Layout::from(col), Layout::new::<ColMajor>(Shape::new(Tuple::int(vec![5, 2]))));
+        assert_eq!(Layout::from(col).crd2idx_flat(&[3, 1]), col.crd2idx([3, 1]));
+    }
+}
@@ -0,0 +1,267 @@
+// src/strassen.rs
+//
+// Strassen GEMM. Each level splits the operands into 2 x 2 blocks and builds
+// the product from seven half-size products instead of eight, recursing
+// until a mode reaches the threshold, where the backend takes over. Odd
+// extents are peeled off and finished with plain GEMMs. All temporaries are
+// allocated once up front, one set per recursion level.
+
+use crate::bench_utils::KernelStats;
+use crate::copy::axpy_view;
+use crate::blas::{BlasBackend, BlasTranspose};
+use crate::gemm::{check_gemm_shapes, gemm_f32};
+use crate::kernel::op_stride;
+use crate::layout::Layout;
+use crate::tensor::{check_disjoint, Tensor, TensorView, TensorViewMut};
+
+/// Extent below which `gemm_strassen_f32` callers usually stop recursing;
+/// smaller blocks are faster through the backend directly.
+pub const DEFAULT_STRASSEN_THRESHOLD: usize = 256;
+
+/// `C = alpha * A * B + beta * C` by Strassen recursion. Levels are added
+/// while every one of `m`, `n` and `k` exceeds `threshold`; each saves an
+/// eighth of the multiplications at the cost of extra additions and
+/// slightly larger rounding error than a plain GEMM.
+pub fn gemm_strassen_f32<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    alpha: f32,
+    beta: f32,
+    threshold: usize,
+) -> KernelStats {
+    assert!(threshold > 0, "gemm_strassen_f32: threshold must be > 0");
+    let (m, n, k) = check_gemm_shapes("gemm_strassen_f32", a.layout(), b.layout(), c.layout()).unwrap_or_else(|e| panic!("{e}"));
+    if let Err(e) = check_disjoint("gemm_strassen_f32", a, c).and_then(|_| check_disjoint("gemm_strassen_f32", b, c)) {
+        panic!("{e}");
+    }
+
+    let stats = KernelStats::gemm(m, n, k, std::mem::size_of::<f32>(), beta);
+    let mut ws = workspace(m, n, k, threshold);
+    if ws.is_empty() {
+        gemm_f32(backend, a, b, c, alpha, beta);
+        return stats;
+    }
+
+    if alpha == 1.0 && beta == 0.0 {
+        product(backend, a, b, c, &mut ws);
+    } else {
+        let mut p = zeros(m, n);
+        product(backend, a, b, &mut p.as_view_mut(), &mut ws);
+        for i in 0..m {
+            for j in 0..n {
+                let old = if beta == 0.0 { 0.0 } else { beta * c[[i, j]] };
+                c[[i, j]] = alpha * p[[i, j]] + old;
+            }
+        }
+    }
+    stats
+}
+
+/// `gemm_strassen_f32` over row-major BLAS operands at the default
+/// threshold; `GemmAlgo::Strassen` on `NativeBlas`
+///
+/// # Safety
+/// The pointers must be valid for the extents and leading dimensions given,
+/// as for `BlasBackend::gemm_f32`, and C must not overlap A or B.
+#[allow(clippy::too_many_arguments)]
+pub(crate) unsafe fn gemm_strassen_blas<B: BlasBackend>(
+    backend: &B,
+    ta: BlasTranspose,
+    tb: BlasTranspose,
+    m: i32,
+    n: i32,
+    k: i32,
+    alpha: f32,
+    a: *const f32,
+    lda: i32,
+    b: *const f32,
+    ldb: i32,
+    beta: f32,
+    c: *mut f32,
+    ldc: i32,
+) {
+    let (m, n, k) = (m as usize, n as usize, k as usize);
+    let a = TensorView::from_raw(a, Layout::from_flat(&[m, k], &op_stride(ta, lda as usize)));
+    let b = TensorView::from_raw(b, Layout::from_flat(&[k, n], &op_stride(tb, ldb as usize)));
+    let mut c = TensorViewMut::from_raw(c, Layout::from_flat(&[m, n], &[ldc as usize, 1]));
+    gemm_strassen_f32(backend, &a, &b, &mut c, alpha, beta, DEFAULT_STRASSEN_THRESHOLD);
+}
+
+/// Temporaries for one recursion level: a left operand, a right operand and
+/// a product, each of the halved extents
+struct Level {
+    lhs: Tensor<f32>,
+    rhs: Tensor<f32>,
+    prod: Tensor<f32>,
+}
+
+fn workspace(mut m: usize, mut n: usize, mut k: usize, threshold: usize) -> Vec<Level> {
+    let mut levels = Vec::new();
+    while m.min(n).min(k) > threshold {
+        (m, n, k) = (m / 2, n / 2, k / 2);
+        levels.push(Level { lhs: zeros(m, k), rhs: zeros(k, n), prod: zeros(m, n) });
+    }
+    levels
+}
+
+fn zeros(rows: usize, cols: usize) -> Tensor<f32> {
+    Tensor::new(vec![0.0; rows * cols], Layout::row_major([rows, cols]))
+}
+
+fn extents(v: &TensorView<'_, f32>) -> (usize, usize) {
+    let shape = v.layout().shape();
+    assert_eq!(shape.flat_len(), 2, "gemm_strassen_f32: operands must be rank-2");
+    (shape.flat_at(0), shape.flat_at(1))
+}
+
+/// Block `(r0, c0)` of extents `rows x cols`; callers keep mutable blocks disjoint
+fn block<'a>(v: &TensorView<'a, f32>, r0: usize, c0: usize, rows: usize, cols: usize) -> TensorView<'a, f32> {
+    unsafe { v.subview([r0, c0], [rows, cols]) }
+}
+
+/// `out = x + sign * y`
+fn combine(x: &TensorView<'_, f32>, y: &TensorView<'_, f32>, sign: f32, out: &mut Tensor<f32>) {
+    let (rows, cols) = extents(x);
+    let mut out = out.as_view_mut();
+    for i in 0..rows {
+        for j in 0..cols {
+            out[[i, j]] = x[[i, j]] + sign * y[[i, j]];
+        }
+    }
+}
+
+/// `dst += sign * src`
+fn accumulate(dst: &mut TensorViewMut<'_, f32>, src: &TensorView<'_, f32>, sign: f32) {
+    axpy_view(dst, sign, src);
+}
+
+/// `c = a * b`, using `ws[0]` at this level and the rest below
+fn product<B: BlasBackend>(
+    backend: &B,
+    a: &TensorView<'_, f32>,
+    b: &TensorView<'_, f32>,
+    c: &mut TensorViewMut<'_, f32>,
+    ws: &mut [Level],
+) {
+    let Some((level, rest)) = ws.split_first_mut() else {
+        gemm_f32(backend, a, b, c, 1.0, 0.0);
+        return;
+    };
+    let ((m, k), n) = (extents(a), extents(b).1);
+    let (h, p, w) = (m / 2, k / 2, n / 2);
+
+    let (a11, a12, a21, a22) = (block(a, 0, 0, h, p), block(a, 0, p, h, p), block(a, h, 0, h, p), block(a, h, p, h, p));
+    let (b11, b12, b21, b22) = (block(b, 0, 0, p, w), block(b, 0, w, p, w), block(b, p, 0, p, w), block(b, p, w, p, w));
+    let mut even = c.tile_mut(0, 0, 2 * h, 2 * w);
+    let (mut top, mut bottom) = even.split_at_row_mut(h);
+    let (mut c11, mut c12) = top.split_at_col_mut(w);
+    let (mut c21, mut c22) = bottom.split_at_col_mut(w);
+
+    // M1 = (A11 + A22)(B11 + B22) -> C11, C22
+    combine(&a11, &a22, 1.0, &mut level.lhs);
+    combine(&b11, &b22, 1.0, &mut level.rhs);
+    product(backend, &level.lhs.as_view(), &level.rhs.as_view(), &mut c11, rest);
+    let m1 = c11.as_view();
+    for i in 0..h {
+        for j in 0..w {
+            c22[[i, j]] = m1[[i, j]];
+        }
+    }
+
+    // M2 = (A21 + A22) B11 -> C21, -C22
+    combine(&a21, &a22, 1.0, &mut level.lhs);
+    product(backend, &level.lhs.as_view(), &b11, &mut c21, rest);
+    accumulate(&mut c22, &c21.as_view(), -1.0);
+
+    // M3 = A11 (B12 - B22) -> C12, C22
+    combine(&b12, &b22, -1.0, &mut level.rhs);
+    product(backend, &a11, &level.rhs.as_view(), &mut c12, rest);
+    accumulate(&mut c22, &c12.as_view(), 1.0);
+
+    // M4 = A22 (B21 - B11) -> C11, C21
+    combine(&b21, &b11, -1.0, &mut level.rhs);
+    product(backend, &a22, &level.rhs.as_view(), &mut level.prod.as_view_mut(), rest);
+    accumulate(&mut c11, &level.prod.as_view(), 1.0);
+    accumulate(&mut c21, &level.prod.as_view(), 1.0);
+
+    // M5 = (A11 + A12) B22 -> -C11, C12
+    combine(&a11, &a12, 1.0, &mut level.lhs);
+    product(backend, &level.lhs.as_view(), &b22, &mut level.prod.as_view_mut(), rest);
+    accumulate(&mut c11, &level.prod.as_view(), -1.0);
+    accumulate(&mut c12, &level.prod.as_view(), 1.0);
+
+    // M6 = (A21 - A11)(B11 + B12) -> C22
+    combine(&a21, &a11, -1.0, &mut level.lhs);
+    combine(&b11, &b12, 1.0, &mut level.rhs);
+    product(backend, &level.lhs.as_view(), &level.rhs.as_view(), &mut level.prod.as_view_mut(), rest);
+    accumulate(&mut c22, &level.prod.as_view(), 1.0);
+
+    // M7 = (A12 - A22)(B21 + B22) -> C11
+    combine(&a12, &a22, -1.0, &mut level.lhs);
+    combine(&b21, &b22, 1.0, &mut level.rhs);
+    product(backend, &level.lhs.as_view(), &level.rhs.as_view(), &mut level.prod.as_view_mut(), rest);
+    accumulate(&mut c11, &level.prod.as_view(), 1.0);
+
+    // Odd extents: the last column of A / row of B, then the last column and row of C
+    let (m2, k2, n2) = (2 * h, 2 * p, 2 * w);
+    if k2 < k {
+        let mut c_even = c.tile_mut(0, 0, m2, n2);
+        gemm_f32(backend, &block(a, 0, k2, m2, 1), &block(b, k2, 0, 1, n2), &mut c_even, 1.0, 1.0);
+    }
+    if n2 < n {
+        let mut c_col = c.tile_mut(0, n2, m2, 1);
+        gemm_f32(backend, &block(a, 0, 0, m2, k), &block(b, 0, n2, k, 1), &mut c_col, 1.0, 0.0);
+    }
+    if m2 < m {
+        let mut c_row = c.tile_mut(m2, 0, 1, n);
+        gemm_f32(backend, &block(a, m2, 0, 1, k), b, &mut c_row, 1.0, 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::test_util::seeded_matrix;
+    use crate::blas::GemmAlgo;
+
+    #[test]
+    fn native_blas_runs_strassen_as_an_algo() {
+        let n = DEFAULT_STRASSEN_THRESHOLD + 3;
+        let (a, b) = (seeded_matrix(n, n, 1), seeded_matrix(n, n, 4));
+        let mut expected = seeded_matrix(n, n, 2);
+        let mut c = seeded_matrix(n, n, 2);
+        let ni = n as i32;
+        unsafe {
+            NativeBlas.gemm_f32(BlasTranspose::Trans, BlasTranspose::NoTrans, ni, ni, ni, 2.0, a.data().as_ptr(), ni, b.data().as_ptr(), ni, 0.5, expected.data_mut().as_mut_ptr(), ni);
+            NativeBlas.gemm_f32_algo(GemmAlgo::Strassen, BlasTranspose::Trans, BlasTranspose::NoTrans, ni, ni, ni, 2.0, a.data().as_ptr(), ni, b.data().as_ptr(), ni, 0.5, c.data_mut().as_mut_ptr(), ni);
+        }
+        assert_eq!(c.data(), expected.data());
+    }
+
+    #[test]
+    fn matches_plain_gemm_on_odd_shapes() {
+        for (m, k, n, threshold) in [(16, 16, 16, 4), (13, 9, 11, 2), (33, 20, 17, 3), (5, 5, 5, 8)] {
+            let (a, b) = (seeded_matrix(m, k, 1), seeded_matrix(k, n, 3));
+            let init = seeded_matrix(m, n, 2);
+
+            let mut expected = Tensor::new(init.data().to_vec(), Layout::row_major([m, n]));
+            gemm_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut expected.as_view_mut(), 1.5, -0.5);
+
+            let mut c = Tensor::new(init.data().to_vec(), Layout::row_major([m, n]));
+            let stats = gemm_strassen_f32(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), 1.5, -0.5, threshold);
+            assert_eq!(c.data(), expected.data(), "{m}x{k}x{n}");
+            assert_eq!(stats.flops, 2 * m * n * k);
+        }
+    }
+
+    #[test]
+    fn workspace_has_one_level_per_halving() {
+        let levels = workspace(100, 64, 40, 8);
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels[2].lhs.layout().shape().dims.flatten(), vec![12, 5]);
+        assert_eq!(levels[2].prod.layout().shape().dims.flatten(), vec![12, 8]);
+    }
+}
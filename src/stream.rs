@@ -0,0 +1,111 @@
+// src/stream.rs
+//
+// Tiles of a mutable tiled view as a `futures` Stream, so async
+// applications (tokio and friends) can interleave tile computations with
+// IO. At most `max_in_flight` tiles are handed out at once: the stream
+// stays pending until a `TileTask` is dropped, which is the backpressure a
+// consumer running tasks concurrently (e.g. `for_each_concurrent`) needs.
+
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use futures_core::Stream;
+
+use crate::tensor::TensorViewMut;
+use crate::tiled_tensor::{Tile, TiledTensorViewMut};
+
+#[derive(Default)]
+struct Gate {
+    in_flight: usize,
+    waker: Option<Waker>,
+}
+
+/// One tile to work on. Dropping it frees its slot in the stream.
+pub struct TileTask<'a, T> {
+    pub tile: Tile,
+    pub view: TensorViewMut<'a, T>,
+    gate: Arc<Mutex<Gate>>,
+}
+
+impl<T> Drop for TileTask<'_, T> {
+    fn drop(&mut self) {
+        let mut gate = self.gate.lock().unwrap();
+        gate.in_flight -= 1;
+        if let Some(waker) = gate.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Stream of `TileTask`s; see `TiledTensorViewMut::tiles_stream`
+pub struct TileStream<'s, 'a, T> {
+    tiled: &'s mut TiledTensorViewMut<'a, T>,
+    max_in_flight: usize,
+    gate: Arc<Mutex<Gate>>,
+}
+
+impl<'a, T> TiledTensorViewMut<'a, T> {
+    /// The remaining tiles as a stream with at most `max_in_flight` tasks
+    /// alive at a time
+    pub fn tiles_stream(&mut self, max_in_flight: usize) -> TileStream<'_, 'a, T> {
+        assert!(max_in_flight > 0, "tiles_stream: max_in_flight must be > 0");
+        TileStream { tiled: self, max_in_flight, gate: Arc::default() }
+    }
+}
+
+impl<'a, T> Stream for TileStream<'_, 'a, T> {
+    type Item = TileTask<'a, T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let mut gate = this.gate.lock().unwrap();
+        if gate.in_flight >= this.max_in_flight {
+            gate.waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        let Some((tile, view)) = this.tiled.tiles_mut().next() else {
+            return Poll::Ready(None);
+        };
+        gate.in_flight += 1;
+        Poll::Ready(Some(TileTask { tile, view, gate: this.gate.clone() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.tiled.tiles_left();
+        (n, Some(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+
+    fn poll<S: Stream + Unpin>(s: &mut S) -> Poll<Option<S::Item>> {
+        Pin::new(s).poll_next(&mut Context::from_waker(Waker::noop()))
+    }
+
+    #[test]
+    fn stream_waits_for_tasks_to_finish() {
+        let mut t = Tensor::new(vec![0i32; 16], Layout::row_major([4, 4]));
+        let mut tiled = TiledTensorViewMut::new(t.as_view_mut(), Layout::row_major([2, 4]));
+        let mut stream = tiled.tiles_stream(1);
+        assert_eq!(stream.size_hint(), (2, Some(2)));
+
+        let Poll::Ready(Some(mut first)) = poll(&mut stream) else { panic!("first tile not ready") };
+        assert!(poll(&mut stream).is_pending());
+        first.view[[1, 3]] = 1;
+        drop(first);
+
+        let Poll::Ready(Some(mut second)) = poll(&mut stream) else { panic!("second tile not ready") };
+        assert_eq!(second.tile.index(), 1);
+        second.view[[0, 0]] = 2;
+        drop(second);
+        assert!(matches!(poll(&mut stream), Poll::Ready(None)));
+        drop(stream);
+
+        assert_eq!((t.data()[7], t.data()[8]), (1, 2));
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::tuple::Tuple;
+
+/// CuTe-style XOR swizzle on linear offsets.
+///
+/// - `B`: number of bits in the mask
+/// - `M`: number of least-significant bits kept constant
+/// - `S`: distance to shift the YYY mask onto the ZZZ mask (may be negative)
+///
+/// `apply(o) = o ^ ((o & yyy_mask) >> S)`, which is a bijection on offsets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Swizzle<const B: u32, const M: u32, const S: i32>;
+
+impl<const B: u32, const M: u32, const S: i32> Swizzle<B, M, S> {
+    const BIT_MASK: usize = (1usize << B) - 1;
+    const YYY_MASK: usize = Self::BIT_MASK << (M as i32 + if S > 0 { S } else { 0 });
+    const ZZZ_MASK: usize = Self::BIT_MASK << (M as i32 - if S < 0 { S } else { 0 });
+
+    pub const fn new() -> Self {
+        Swizzle
+    }
+
+    pub const fn yyy_mask(&self) -> usize {
+        Self::YYY_MASK
+    }
+
+    pub const fn zzz_mask(&self) -> usize {
+        Self::ZZZ_MASK
+    }
+
+    #[inline(always)]
+    pub const fn apply(&self, offset: usize) -> usize {
+        let moved = offset & Self::YYY_MASK;
+        let moved = if S >= 0 { moved >> S } else { moved << -S };
+        offset ^ moved
+    }
+}
+
+impl<const B: u32, const M: u32, const S: i32> fmt::Display for Swizzle<B, M, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Sw<{},{},{}>", B, M, S)
+    }
+}
+
+/// `Swizzle ∘ Layout`: coordinates map through the layout, then the swizzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwizzledLayout<const B: u32, const M: u32, const S: i32> {
+    swizzle: Swizzle<B, M, S>,
+    layout: Layout,
+}
+
+impl<const B: u32, const M: u32, const S: i32> SwizzledLayout<B, M, S> {
+    pub fn new(swizzle: Swizzle<B, M, S>, layout: Layout) -> Self {
+        Self { swizzle, layout }
+    }
+
+    pub fn layout(&self) -> &Layout {
+        &self.layout
+    }
+
+    pub fn swizzle(&self) -> Swizzle<B, M, S> {
+        self.swizzle
+    }
+
+    pub fn shape(&self) -> &Shape {
+        self.layout.shape()
+    }
+
+    pub fn size(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Codomain size, rounded up so every swizzled offset stays inside it
+    pub fn cosize(&self) -> usize {
+        let c = self.layout.cosize();
+        let span = (self.swizzle.yyy_mask() | self.swizzle.zzz_mask()) + 1;
+        c.div_ceil(span) * span
+    }
+
+    pub fn crd2idx(&self, crd: &Tuple) -> usize {
+        self.swizzle.apply(self.layout.crd2idx(crd))
+    }
+}
+
+/// Compose a swizzle with a layout
+pub fn composition<const B: u32, const M: u32, const S: i32>(
+    swizzle: Swizzle<B, M, S>,
+    layout: &Layout,
+) -> SwizzledLayout<B, M, S> {
+    SwizzledLayout::new(swizzle, layout.clone())
+}
+
+impl<const B: u32, const M: u32, const S: i32> fmt::Display for SwizzledLayout<B, M, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} o {}:{}", self.swizzle, self.layout.shape(), self.layout.stride())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::coords;
+
+    #[test]
+    fn masks_match_cute() {
+        let sw = Swizzle::<3, 3, 3>::new();
+        assert_eq!(sw.yyy_mask(), 0b111_000_000);
+        assert_eq!(sw.zzz_mask(), 0b000_111_000);
+        assert_eq!(sw.apply(0b001_000_000), 0b001_001_000);
+        assert_eq!(sw.to_string(), "Sw<3,3,3>");
+    }
+
+    #[test]
+    fn swizzle_is_bijective_on_codomain() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![8, 64])));
+        let sl = composition(Swizzle::<3, 3, 3>::new(), &layout);
+
+        let mut seen = vec![false; sl.cosize()];
+        for crd in coords(layout.shape()) {
+            let idx = sl.crd2idx(&crd);
+            assert!(!seen[idx], "offset {} hit twice", idx);
+            seen[idx] = true;
+        }
+    }
+
+    #[test]
+    fn column_reads_spread_across_banks() {
+        // 8 rows of 8 x 8-element chunks: reading one column of chunks
+        // should touch 8 distinct chunk slots after swizzling.
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![8, 64])));
+        let sl = composition(Swizzle::<3, 3, 3>::new(), &layout);
+
+        let mut chunks: Vec<usize> = (0..8)
+            .map(|r| (sl.crd2idx(&Tuple::int(vec![r, 0])) % 64) / 8)
+            .collect();
+        chunks.sort();
+        chunks.dedup();
+        assert_eq!(chunks.len(), 8);
+    }
+
+    #[test]
+    fn negative_shift() {
+        let sw = Swizzle::<2, 0, -2>::new();
+        assert_eq!(sw.yyy_mask(), 0b0011);
+        assert_eq!(sw.zzz_mask(), 0b1100);
+        assert_eq!(sw.apply(0b0001), 0b0101);
+    }
+}
@@ -1,31 +1,182 @@
+use std::alloc::Layout as AllocLayout;
 use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 use std::ptr::NonNull;
 
-use crate::layout::Layout;
-use crate::shape::Shape;
+use crate::allocator::TensorAlloc;
+
+use crate::copy::tensor_copy;
+use crate::error::{check_axis, check_rank, Error, Result};
+use crate::layout::{Layout, LayoutPolicy, RowMajor};
+use crate::provenance::Provenance;
+use crate::shape::{coords, Shape};
 use crate::tuple::Tuple;
+#[cfg(feature = "mmap")]
+use crate::mmap::{Mapping, Pod};
+#[cfg(feature = "mmap")]
+use std::path::Path;
+
+/* ========================= Storage ========================= */
+
+/// Owned element buffer: a plain `Vec`, memory from a `TensorAlloc`, or a
+/// read-only file mapping (elements are `Pod`, so nothing needs dropping)
+enum Storage<T> {
+    Vec(Vec<T>),
+    Alloc {
+        ptr: NonNull<T>,
+        len: usize,
+        layout: AllocLayout,
+        alloc: Box<dyn TensorAlloc>,
+    },
+    #[cfg(feature = "mmap")]
+    Mapped { len: usize, map: Mapping },
+}
+
+unsafe impl<T: Send> Send for Storage<T> {}
+unsafe impl<T: Sync> Sync for Storage<T> {}
+
+impl<T> Storage<T> {
+    fn as_slice(&self) -> &[T] {
+        match self {
+            Storage::Vec(v) => v,
+            Storage::Alloc { ptr, len, .. } => unsafe { std::slice::from_raw_parts(ptr.as_ptr(), *len) },
+            #[cfg(feature = "mmap")]
+            Storage::Mapped { len, map } => unsafe { std::slice::from_raw_parts(map.as_ptr().cast(), *len) },
+        }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [T] {
+        // The mapping is read-only: the first write moves it onto the heap
+        #[cfg(feature = "mmap")]
+        if let Storage::Mapped { len, map } = self {
+            let mut out = Vec::with_capacity(*len);
+            unsafe {
+                std::ptr::copy_nonoverlapping(map.as_ptr().cast(), out.as_mut_ptr(), *len);
+                out.set_len(*len);
+            }
+            *self = Storage::Vec(out);
+        }
+        match self {
+            Storage::Vec(v) => v,
+            Storage::Alloc { ptr, len, .. } => unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), *len) },
+            #[cfg(feature = "mmap")]
+            Storage::Mapped { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<T> Drop for Storage<T> {
+    fn drop(&mut self) {
+        if let Storage::Alloc { ptr, len, layout, alloc } = self {
+            unsafe {
+                std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(ptr.as_ptr(), *len));
+                if layout.size() != 0 {
+                    alloc.deallocate(ptr.as_ptr().cast(), *layout);
+                }
+            }
+        }
+    }
+}
 
 /* ========================= Tensor ========================= */
 
+/// Panics unless `layout` fits in `len` elements and can back a mutable tensor
+fn check_storage(op: &str, len: usize, layout: &Layout) {
+    assert!(!layout.has_reversed_modes(), "Tensor layouts cannot have reversed modes; flip a view instead");
+    assert!(layout.cosize() <= len, "{op}: layout reaches {} elements but the buffer holds {len}", layout.cosize());
+    assert!(layout.is_injective(), "{op}: layout maps several coordinates to one element");
+}
+
 pub struct Tensor<T> {
-    data: Vec<T>,
+    data: Storage<T>,
     layout: Layout,
 }
 
 impl<T> Tensor<T> {
+    /// Tensor over `data` indexed through `layout`. A layout with gaps
+    /// (e.g. a padded leading dimension) needs `layout.cosize()` elements;
+    /// layouts that send two coordinates to one element are rejected, as
+    /// the tensor hands out mutable views.
     pub fn new(data: Vec<T>, layout: Layout) -> Self {
-        assert_eq!(data.len(), layout.size());
-        Self { data, layout }
+        check_storage("Tensor::new", data.len(), &layout);
+        Self { data: Storage::Vec(data), layout: layout.with_offset(0) }
+    }
+
+    /// Like `new`, but the elements are moved into memory obtained from `alloc`
+    /// (e.g. `PageAligned` or `Pinned` staging buffers).
+    pub fn new_in<A: TensorAlloc + 'static>(data: Vec<T>, layout: Layout, alloc: A) -> Self {
+        check_storage("Tensor::new_in", data.len(), &layout);
+
+        let len = data.len();
+        let align = alloc.align(std::mem::align_of::<T>());
+        let mem = AllocLayout::from_size_align(std::mem::size_of::<T>() * len, align)
+            .expect("Tensor::new_in: allocation too large");
+
+        let ptr = if mem.size() == 0 {
+            NonNull::dangling()
+        } else {
+            let raw = alloc.allocate(mem).cast::<T>();
+            NonNull::new(raw).unwrap_or_else(|| std::alloc::handle_alloc_error(mem))
+        };
+
+        unsafe {
+            let mut data = std::mem::ManuallyDrop::new(data);
+            std::ptr::copy_nonoverlapping(data.as_ptr(), ptr.as_ptr(), len);
+            // Elements were moved out; free only the Vec's buffer
+            data.set_len(0);
+            std::mem::ManuallyDrop::drop(&mut data);
+        }
+
+        Self {
+            data: Storage::Alloc { ptr, len, layout: mem, alloc: Box::new(alloc) },
+            layout: layout.with_offset(0),
+        }
     }
 
     pub fn layout(&self) -> &Layout {
         &self.layout
     }
 
+    /// Tensor over the first `layout.cosize()` elements of the file at `path`,
+    /// mapped read-only rather than read. Pages load on first touch; the
+    /// first mutable access copies the elements into memory.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(path: impl AsRef<Path>, layout: Layout) -> Result<Self>
+    where
+        T: Pod,
+    {
+        Self::from_mmap_at(path, 0, layout)
+    }
+
+    /// Like `from_mmap`, starting `offset` bytes into the file, e.g. past
+    /// the header of an `.npy` or `.safetensors` file.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap_at(path: impl AsRef<Path>, offset: usize, layout: Layout) -> Result<Self>
+    where
+        T: Pod,
+    {
+        check_storage("Tensor::from_mmap", layout.cosize(), &layout);
+        let path = path.as_ref();
+        if !offset.is_multiple_of(std::mem::align_of::<T>()) {
+            return Err(Error::Io(format!(
+                "{}: offset {} is not aligned for {}",
+                path.display(),
+                offset,
+                std::any::type_name::<T>()
+            )));
+        }
+        let len = layout.cosize();
+        let map = Mapping::new(path, offset, len * std::mem::size_of::<T>())?;
+        Ok(Self { data: Storage::Mapped { len, map }, layout: layout.with_offset(0) })
+    }
+
+    // The pointer keeps the provenance of a shared borrow of the whole
+    // buffer, which is all a read-only view needs
     pub fn as_view(&self) -> TensorView<'_, T> {
         TensorView {
-            ptr: unsafe { NonNull::new_unchecked(self.data.as_ptr() as *mut T) },
+            ptr: NonNull::from(self.data.as_slice()).cast(),
             layout: self.layout.clone(),
+            provenance: Provenance::default(),
             _marker: PhantomData,
         }
     }
@@ -33,20 +184,59 @@ impl<T> Tensor<T> {
 
     pub fn as_view_mut(&mut self) -> TensorViewMut<'_, T> {
         TensorViewMut {
-            ptr: unsafe { NonNull::new_unchecked(self.data.as_mut_ptr()) },
+            ptr: NonNull::from(self.data.as_mut_slice()).cast(),
             layout: self.layout.clone(),
+            provenance: Provenance::default(),
             _marker: PhantomData,
         }
     }
 
     #[inline(always)]
     pub fn data(&self) -> &[T] {
-        &self.data
+        self.data.as_slice()
     }
 
     #[inline(always)]
     pub fn data_mut(&mut self) -> &mut [T] {
-        &mut self.data
+        self.data.as_mut_slice()
+    }
+
+    /// Backing storage in memory order (same as `data`)
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+
+    /// Take the backing storage in memory order. Storage from a
+    /// `TensorAlloc` is moved into a fresh `Vec` and released.
+    pub fn into_vec(mut self) -> Vec<T> {
+        // `Storage` implements Drop, so fields are moved out by `ptr::read`
+        let storage = std::mem::ManuallyDrop::new(std::mem::replace(&mut self.data, Storage::Vec(Vec::new())));
+        match &*storage {
+            Storage::Vec(v) => unsafe { std::ptr::read(v) },
+            Storage::Alloc { ptr, len, layout, alloc } => unsafe {
+                let alloc = std::ptr::read(alloc);
+                let mut out = Vec::with_capacity(*len);
+                std::ptr::copy_nonoverlapping(ptr.as_ptr(), out.as_mut_ptr(), *len);
+                out.set_len(*len);
+                // Elements now live in `out`; free only the raw memory
+                if layout.size() != 0 {
+                    alloc.deallocate(ptr.as_ptr().cast(), *layout);
+                }
+                out
+            },
+            #[cfg(feature = "mmap")]
+            Storage::Mapped { len, map } => unsafe {
+                let mut out = Vec::with_capacity(*len);
+                std::ptr::copy_nonoverlapping(map.as_ptr().cast(), out.as_mut_ptr(), *len);
+                out.set_len(*len);
+                drop(std::ptr::read(map));
+                out
+            },
+        }
     }
 
 }
@@ -56,23 +246,104 @@ impl<T> Tensor<T> {
 pub struct TensorView<'a, T> {
     pub(crate) ptr: NonNull<T>,
     layout: Layout,
+    provenance: Provenance,
     _marker: PhantomData<&'a T>,
 }
 
 pub struct TensorViewMut<'a, T> {
     pub(crate) ptr: NonNull<T>,
     layout: Layout,
+    provenance: Provenance,
     _marker: PhantomData<&'a mut T>,
 }
 
+// Views follow the rules of the references they stand in for; `NonNull`
+// alone would make them !Send/!Sync.
+// - `TensorView` is a shared borrow (`&'a T`): it may cross threads when `T: Sync`.
+// - `TensorViewMut` is an exclusive borrow (`&'a mut T`): moving it is fine when
+//   `T: Send`, sharing `&TensorViewMut` only exposes reads, so it needs `T: Sync`.
+// Disjointness of sibling mutable views is the obligation of the unsafe
+// `subview_mut` calls (or `TiledTensorViewMut`) that created them.
+unsafe impl<T: Sync> Send for TensorView<'_, T> {}
+unsafe impl<T: Sync> Sync for TensorView<'_, T> {}
+unsafe impl<T: Send> Send for TensorViewMut<'_, T> {}
+unsafe impl<T: Sync> Sync for TensorViewMut<'_, T> {}
+
 impl<'a, T> TensorView<'a, T> {
+    /// Reinterpret the memory behind `self` through another layout
+    ///
+    /// # Safety
+    /// Every index reachable through `layout` must be in-bounds for this view's allocation.
+    pub(crate) unsafe fn with_layout(&self, layout: Layout) -> TensorView<'a, T> {
+        TensorView {
+            ptr: self.ptr,
+            layout: layout.with_offset(self.layout.offset()),
+            provenance: self.provenance.then("with_layout", &self.layout, 0),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Like `with_layout`, with the origin moved by `offset` elements
+    ///
+    /// # Safety
+    /// Every index reachable through `layout` from the new origin must be in-bounds.
+    pub(crate) unsafe fn at_offset(&self, offset: isize, layout: Layout) -> TensorView<'a, T> {
+        TensorView {
+            ptr: self.ptr.offset(offset),
+            layout: layout.with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("at_offset", &self.layout, offset),
+            _marker: PhantomData,
+        }
+    }
+
+    /// View over raw memory, e.g. a BLAS operand pointer
+    ///
+    /// # Safety
+    /// `ptr` must be non-null and every index reachable through `layout`
+    /// must be valid for reads for `'a`.
+    pub(crate) unsafe fn from_raw(ptr: *const T, layout: Layout) -> TensorView<'a, T> {
+        Self::from_non_null(NonNull::new_unchecked(ptr.cast_mut()), layout)
+    }
+
+    /// `from_raw` for a pointer already derived from another view's
+    ///
+    /// # Safety
+    /// As for `from_raw`.
+    pub(crate) unsafe fn from_non_null(ptr: NonNull<T>, layout: Layout) -> TensorView<'a, T> {
+        TensorView { ptr, layout, provenance: Provenance::default(), _marker: PhantomData }
+    }
+
     pub fn layout(&self) -> &Layout {
         &self.layout
     }
 
+    /// How this view was derived, from the layout of the tensor it came
+    /// from to its own, e.g.
+    /// `(8,8):(8,1) -> subview[+18] (4,4):(8,1) -> split[+2] (4,2):(8,1)`
+    #[cfg(feature = "provenance")]
+    pub fn debug_path(&self) -> String {
+        self.provenance.path(&self.layout)
+    }
+
+    /// Name the step that produced this view `op` in `debug_path`
+    pub(crate) fn labelled(mut self, op: &'static str) -> Self {
+        self.provenance = self.provenance.renamed(op);
+        self
+    }
+
     pub unsafe fn get(&self, crd: &Tuple) -> &'a T {
-        let idx = self.layout.crd2idx(crd);
-        &*self.ptr.as_ptr().add(idx)
+        debug_check_tuple("get", &self.layout, crd);
+        &*self.ptr.as_ptr().offset(self.layout.crd2offset(crd))
+    }
+
+    /// `get` with a flattened coordinate
+    ///
+    /// # Safety
+    /// `crd` must be in-bounds in every flattened mode.
+    #[inline]
+    pub unsafe fn get_flat(&self, crd: &[usize]) -> &'a T {
+        debug_check_coord("get_flat", &self.layout, crd);
+        &*self.ptr.as_ptr().offset(self.layout.crd2offset_flat(crd))
     }
 
     #[inline]
@@ -91,22 +362,94 @@ impl<'a, T> TensorView<'a, T> {
     /// Caller must ensure `idx` is in-bounds.
     #[inline(always)]
     pub unsafe fn ptr_at(&self, idx: &Tuple) -> *const T {
-        let offset = idx.dot(&self.layout.stride());
-        self.ptr.as_ptr().add(offset)
+        debug_check_tuple("ptr_at", &self.layout, idx);
+        self.ptr.as_ptr().offset(self.layout.crd2offset(idx))
     }
 
 
     /* ---------- N-D subview ---------- */
 
-    pub unsafe fn subview(&self, start: &Tuple, subshape: &Shape) -> TensorView<'a, T> {
-        let offset = self.layout.crd2idx(start);
+    pub unsafe fn subview(&self, start: impl Into<Tuple>, subshape: impl Into<Shape>) -> TensorView<'a, T> {
+        let (start, subshape) = (start.into(), subshape.into());
+        debug_check_subview(&self.layout, &start, &subshape);
+        let offset = self.layout.crd2offset(&start);
 
         TensorView {
-            ptr: NonNull::new_unchecked(self.ptr.as_ptr().add(offset)),
-            layout: Layout::with_shape_stride(
-                subshape.clone(),
+            ptr: self.ptr.offset(offset),
+            layout: self.layout.reversed_like(Layout::with_shape_stride(
+                subshape,
                 self.layout.stride().clone(),
-            ),
+            )).with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("subview", &self.layout, offset),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Coordinate of this view's origin inside `parent`, or `None` if the
+    /// two views do not share a root or the origin is not an element of `parent`.
+    pub fn position_in(&self, parent: &TensorView<'_, T>) -> Option<Tuple> {
+        let root = self.ptr.as_ptr().wrapping_offset(-self.layout.offset());
+        let parent_root = parent.ptr.as_ptr().wrapping_offset(-parent.layout.offset());
+        if root != parent_root {
+            return None;
+        }
+        self.layout.position_in(&parent.layout)
+    }
+
+    /// Bounds-checked `subview`: `start + subshape` must fit inside `self`
+    /// in every flattened mode.
+    pub fn try_subview(&self, start: impl Into<Tuple>, subshape: impl Into<Shape>) -> Result<TensorView<'a, T>> {
+        let (start, subshape) = (start.into(), subshape.into());
+        check_subview(&self.layout, &start, &subshape)?;
+        Ok(unsafe { self.subview(start, subshape) })
+    }
+
+    /// `chunks` consecutive pieces of flattened mode `axis`, as even as
+    /// possible: the first `extent % chunks` pieces get one extra index,
+    /// and pieces past the extent are empty.
+    pub fn split(&self, axis: usize, chunks: usize) -> Vec<TensorView<'a, T>> {
+        split_ranges("split", &self.layout, axis, chunks)
+            .into_iter()
+            .map(|(start, shape)| unsafe { self.subview(start, shape) }.labelled("split"))
+            .collect()
+    }
+
+    /// NumPy `tile` without copying, when every repetition falls on an
+    /// extent-1 mode: those modes get extent `reps[i]` and stride 0. Shorter
+    /// `reps` are padded with leading 1s and a lower-rank `self` with leading
+    /// extent-1 modes, as in NumPy. `None` if some mode with extent > 1 is
+    /// repeated; `copy::repeat` materializes those.
+    pub fn repeat_view(&self, reps: &[usize]) -> Option<TensorView<'a, T>> {
+        let (dims, stride) = (self.layout.shape().dims.flatten(), self.layout.stride().flatten());
+        let rank = dims.len().max(reps.len());
+        let (lead, rep_lead) = (rank - dims.len(), rank - reps.len());
+
+        let (mut shape, mut strides) = (Vec::with_capacity(rank), Vec::with_capacity(rank));
+        for i in 0..rank {
+            let r = if i < rep_lead { 1 } else { reps[i - rep_lead] };
+            let (d, s) = if i < lead { (1, 0) } else { (dims[i - lead], stride[i - lead]) };
+            if r != 1 && d != 1 {
+                return None;
+            }
+            shape.push(d * r);
+            strides.push(if r == 1 { s } else { 0 });
+        }
+
+        let mut layout = Layout::with_shape_stride(Shape::new(Tuple::int(shape)), Tuple::int(strides));
+        for i in (0..dims.len()).filter(|&i| self.layout.is_reversed(i)) {
+            layout = layout.flip(i + lead);
+        }
+        Some(unsafe { self.with_layout(layout) }.labelled("repeat_view"))
+    }
+
+    /// Reverse flattened mode `mode`: coordinate `i` of the result is
+    /// coordinate `extent - 1 - i` of `self`. No data is copied.
+    pub fn flip(&self, mode: usize) -> TensorView<'a, T> {
+        let offset = flip_origin(&self.layout, mode);
+        TensorView {
+            ptr: unsafe { self.ptr.offset(offset) },
+            layout: self.layout.flip(mode).with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("flip", &self.layout, offset),
             _marker: PhantomData,
         }
     }
@@ -132,6 +475,283 @@ impl<'a, T> TensorView<'a, T> {
 
         self.subview(&start, &shape)
     }
+
+    /* ---------- materialization ---------- */
+
+    /// Clone the elements in layout order (last flattened mode fastest),
+    /// gathering through the strides of a possibly non-contiguous view.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.indexed_iter().map(|(_, v)| v.clone()).collect()
+    }
+
+    /// Copy into a freshly allocated, compact tensor whose strides follow `P`
+    /// (e.g. `view.to_tensor::<RowMajor>()` to own a strided subview).
+    pub fn to_tensor<P: LayoutPolicy>(&self) -> Tensor<T>
+    where
+        T: Copy,
+    {
+        let shape = self.layout.shape().clone();
+        let stride = P::make_stride(&shape);
+        let layout = Layout::with_shape_stride(shape, stride);
+
+        let n = layout.size();
+        if n == 0 {
+            return Tensor::new(Vec::new(), layout);
+        }
+
+        // Any initialized value works as filler; every slot is overwritten below
+        let fill = unsafe { *self.ptr.as_ptr() };
+        let mut out = Tensor::new(vec![fill; n], layout);
+        tensor_copy(self, &mut out.as_view_mut());
+        out
+    }
+
+    /* ---------- iteration ---------- */
+
+    /// Iterate over `(coordinate, &element)` pairs in layout order
+    /// (last flattened mode fastest).
+    pub fn indexed_iter(&self) -> IndexedIter<'a, T> {
+        IndexedIter {
+            ptr: self.ptr,
+            counter: CoordCounter::new(&self.layout),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Iterate over the elements in ascending address order: modes are
+    /// visited from the largest stride to the smallest, whatever their
+    /// logical position, so a transposed view is still read front to back.
+    /// Use it when the order of elements does not matter (sums, maxima).
+    pub fn iter_memory_order(&self) -> MemoryOrderIter<'a, T> {
+        MemoryOrderIter::new(self.ptr, &self.layout)
+    }
+
+    /* ---------- padding ---------- */
+
+    /// Logical view of `self` grown to `pad_to`; coordinates outside the
+    /// original extents read `pad_value`. Call `to_tensor` to materialize it.
+    ///
+    /// # Panics
+    /// Panics if `pad_to` has a different flattened rank or is smaller than `self` in any mode.
+    pub fn padded(&self, pad_to: &Shape, pad_value: T) -> PaddedView<'a, T>
+    where
+        T: Copy,
+    {
+        let inner = self.layout.shape().dims.flatten();
+        let outer = pad_to.dims.flatten();
+        assert_eq!(inner.len(), outer.len(), "padded: rank mismatch");
+        assert!(
+            inner.iter().zip(outer.iter()).all(|(i, o)| i <= o),
+            "padded: pad_to {} is smaller than {}",
+            pad_to,
+            self.layout.shape()
+        );
+
+        PaddedView {
+            base: TensorView {
+                ptr: self.ptr,
+                layout: self.layout.clone(),
+                provenance: self.provenance.clone(),
+                _marker: PhantomData,
+            },
+            inner,
+            shape: pad_to.clone(),
+            pad_value,
+        }
+    }
+}
+
+/* ========================= Aliasing ========================= */
+
+/// Whether views at `pa` and `pb` can reach a common element, i.e. whether
+/// `sum x_i a_i - sum y_j b_j = pb - pa` has a solution with every coordinate
+/// in range. Modes are grouped by |stride| and solved from the largest
+/// stride down, pruning with the reachable range of the remaining modes.
+/// This is exact for subviews of one tensor; when the search budget runs out
+/// the answer is a conservative `true`.
+fn may_overlap<T>(pa: *const T, la: &Layout, pb: *const T, lb: &Layout) -> bool {
+    let elem = std::mem::size_of::<T>() as isize;
+    if elem == 0 || la.size() == 0 || lb.size() == 0 {
+        return false;
+    }
+    let d = pb.addr() as isize - pa.addr() as isize;
+    if d % elem != 0 {
+        return true;
+    }
+
+    // (|stride|, lowest, highest) multiple of the stride each group contributes
+    let mut terms: Vec<(isize, isize, isize)> = Vec::new();
+    for (layout, sign) in [(la, 1), (lb, -1)] {
+        for (&n, s) in layout.shape().dims.iter_flat().zip(layout.signed_stride()) {
+            if n <= 1 || s == 0 {
+                continue;
+            }
+            let n = n as isize - 1;
+            let (lo, hi) = if s * sign > 0 { (0, n) } else { (-n, 0) };
+            match terms.iter_mut().find(|t| t.0 == s.abs()) {
+                Some(t) => {
+                    t.1 += lo;
+                    t.2 += hi;
+                }
+                None => terms.push((s.abs(), lo, hi)),
+            }
+        }
+    }
+    terms.sort_by_key(|t| std::cmp::Reverse(t.0));
+
+    let mut budget = 4096;
+    solve_offsets(&terms, d / elem, &mut budget)
+}
+
+/// Whether `target = sum t_k c_k` has a solution with `t_k` in `[lo_k, hi_k]`
+fn solve_offsets(terms: &[(isize, isize, isize)], target: isize, budget: &mut usize) -> bool {
+    let Some((&(c, lo, hi), rest)) = terms.split_first() else {
+        return target == 0;
+    };
+    if *budget == 0 {
+        return true;
+    }
+    *budget -= 1;
+
+    let (rlo, rhi) = rest.iter().fold((0, 0), |(a, b), &(c, lo, hi)| (a + lo * c, b + hi * c));
+    // The remainder `target - t c` must stay within [rlo, rhi]
+    let first = lo.max(-(rhi - target).div_euclid(c));
+    let last = hi.min((target - rlo).div_euclid(c));
+    (first..=last).any(|t| solve_offsets(rest, target - t * c, budget))
+}
+
+impl<T> TensorView<'_, T> {
+    /// Whether `self` and `other` may share an element (see `may_overlap`)
+    pub fn overlaps(&self, other: &TensorView<'_, T>) -> bool {
+        may_overlap(self.ptr.as_ptr(), &self.layout, other.ptr.as_ptr(), &other.layout)
+    }
+}
+
+/// Debug-build check that the output `dst` of `op` does not overlap `src`.
+/// Release builds skip it; the `*_unchecked` entry points always do.
+pub(crate) fn check_disjoint<T>(op: &'static str, src: &TensorView<'_, T>, dst: &TensorViewMut<'_, T>) -> Result<()> {
+    if cfg!(debug_assertions) {
+        check_disjoint_always(op, src, dst)?;
+    }
+    Ok(())
+}
+
+/// `check_disjoint` in every build, for callers that cannot trust their
+/// inputs (e.g. the C ABI)
+pub(crate) fn check_disjoint_always<T>(op: &'static str, src: &TensorView<'_, T>, dst: &TensorViewMut<'_, T>) -> Result<()> {
+    if may_overlap(src.ptr.as_ptr(), &src.layout, dst.ptr.as_ptr(), &dst.layout) {
+        return Err(Error::Aliasing { op });
+    }
+    Ok(())
+}
+
+/* ========================= TensorCow ========================= */
+
+/// Either a borrowed view or an owned tensor; the borrow is copied into a
+/// row-major tensor the first time mutable access is requested.
+pub enum TensorCow<'a, T> {
+    Borrowed(TensorView<'a, T>),
+    Owned(Tensor<T>),
+}
+
+impl<'a, T> TensorCow<'a, T> {
+    pub fn is_owned(&self) -> bool {
+        matches!(self, TensorCow::Owned(_))
+    }
+
+    pub fn layout(&self) -> &Layout {
+        match self {
+            TensorCow::Borrowed(v) => v.layout(),
+            TensorCow::Owned(t) => t.layout(),
+        }
+    }
+
+    /// Read-only view of the current data, borrowed or owned
+    pub fn view(&self) -> TensorView<'_, T> {
+        match self {
+            TensorCow::Borrowed(v) => unsafe { v.with_layout(v.layout.clone()) },
+            TensorCow::Owned(t) => t.as_view(),
+        }
+    }
+}
+
+impl<'a, T: Copy> TensorCow<'a, T> {
+    /// Mutable view, materializing a borrowed view first
+    pub fn to_mut(&mut self) -> TensorViewMut<'_, T> {
+        if let TensorCow::Borrowed(v) = self {
+            *self = TensorCow::Owned(v.to_tensor::<RowMajor>());
+        }
+        match self {
+            TensorCow::Owned(t) => t.as_view_mut(),
+            TensorCow::Borrowed(_) => unreachable!(),
+        }
+    }
+
+    pub fn into_owned(self) -> Tensor<T> {
+        match self {
+            TensorCow::Borrowed(v) => v.to_tensor::<RowMajor>(),
+            TensorCow::Owned(t) => t,
+        }
+    }
+}
+
+impl<'a, T> From<TensorView<'a, T>> for TensorCow<'a, T> {
+    fn from(v: TensorView<'a, T>) -> Self {
+        TensorCow::Borrowed(v)
+    }
+}
+
+impl<T> From<Tensor<T>> for TensorCow<'_, T> {
+    fn from(t: Tensor<T>) -> Self {
+        TensorCow::Owned(t)
+    }
+}
+
+/* ========================= PaddedView ========================= */
+
+pub struct PaddedView<'a, T> {
+    base: TensorView<'a, T>,
+    inner: Vec<usize>,
+    shape: Shape,
+    pad_value: T,
+}
+
+impl<'a, T: Copy> PaddedView<'a, T> {
+    pub fn shape(&self) -> &Shape {
+        &self.shape
+    }
+
+    /// Extents of the underlying (unpadded) view
+    pub fn inner_shape(&self) -> &Shape {
+        self.base.layout.shape()
+    }
+
+    /// Read the element at `crd`, or the pad value outside the original extents
+    pub fn get(&self, crd: &Tuple) -> T {
+        let flat = crd.flatten();
+        let outer = self.shape.dims.flatten();
+        assert!(
+            flat.len() == outer.len() && flat.iter().zip(outer.iter()).all(|(c, o)| c < o),
+            "PaddedView::get: coordinate {} out of bounds for {}",
+            crd,
+            self.shape
+        );
+
+        if flat.iter().zip(self.inner.iter()).all(|(c, i)| c < i) {
+            unsafe { *self.base.ptr_at(crd) }
+        } else {
+            self.pad_value
+        }
+    }
+
+    /// Materialize into a row-major tensor of the padded shape
+    pub fn to_tensor(&self) -> Tensor<T> {
+        let data = coords(&self.shape).map(|crd| self.get(&crd)).collect();
+        Tensor::new(data, Layout::row_major(self.shape.clone()))
+    }
 }
 
 impl<'a, T> TensorViewMut<'a, T> {
@@ -139,24 +759,208 @@ impl<'a, T> TensorViewMut<'a, T> {
         &self.layout
     }
 
-    pub unsafe fn get_mut(&mut self, crd: &Tuple) -> &'a mut T {
-        let idx = self.layout.crd2idx(crd);
-        &mut *self.ptr.as_ptr().add(idx)
+    /// See `TensorView::debug_path`
+    #[cfg(feature = "provenance")]
+    pub fn debug_path(&self) -> String {
+        self.provenance.path(&self.layout)
+    }
+
+    pub(crate) fn labelled(mut self, op: &'static str) -> Self {
+        self.provenance = self.provenance.renamed(op);
+        self
+    }
+
+    /// Mutable counterpart of `TensorView::at_offset`, consuming `self`
+    ///
+    /// # Safety
+    /// Every index reachable through `layout` from the new origin must be in-bounds.
+    pub(crate) unsafe fn into_offset(self, offset: isize, layout: Layout) -> TensorViewMut<'a, T> {
+        TensorViewMut {
+            ptr: self.ptr.offset(offset),
+            layout: layout.with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("at_offset", &self.layout, offset),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Mutable counterpart of `TensorView::at_offset`, keeping `'a`
+    ///
+    /// # Safety
+    /// Every index reachable through `layout` from the new origin must be
+    /// in-bounds, and views handed out this way must not overlap.
+    pub(crate) unsafe fn at_offset_mut(&mut self, offset: isize, layout: Layout) -> TensorViewMut<'a, T> {
+        TensorViewMut {
+            ptr: self.ptr.offset(offset),
+            layout: layout.with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("at_offset", &self.layout, offset),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Mutable counterpart of `TensorView::from_raw`
+    ///
+    /// # Safety
+    /// `ptr` must be non-null, every index reachable through `layout` must be
+    /// valid for reads and writes for `'a`, and nothing else may access them.
+    pub(crate) unsafe fn from_raw(ptr: *mut T, layout: Layout) -> TensorViewMut<'a, T> {
+        TensorViewMut {
+            ptr: NonNull::new_unchecked(ptr),
+            layout,
+            provenance: Provenance::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Give up write access, keeping the full lifetime
+    pub fn into_view(self) -> TensorView<'a, T> {
+        TensorView {
+            ptr: self.ptr,
+            layout: self.layout,
+            provenance: self.provenance,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Element at `crd`. The reference borrows `self`, so two of them
+    /// cannot be held at once; `split_at_mut` gives disjoint views for that.
+    ///
+    /// ```compile_fail
+    /// use rutilelib::{layout::Layout, tensor::Tensor, tuple::Tuple};
+    /// let mut t = Tensor::new(vec![0; 4], Layout::row_major([2, 2]));
+    /// let mut v = t.as_view_mut();
+    /// let crd = Tuple::int(vec![0, 0]);
+    /// let (a, b) = unsafe { (v.get_mut(&crd), v.get_mut(&crd)) };
+    /// *a = *b;
+    /// ```
+    ///
+    /// # Safety
+    /// `crd` must be in-bounds.
+    pub unsafe fn get_mut(&mut self, crd: &Tuple) -> &mut T {
+        debug_check_tuple("get_mut", &self.layout, crd);
+        &mut *self.ptr.as_ptr().offset(self.layout.crd2offset(crd))
+    }
+
+    /// `get_mut` with a flattened coordinate
+    ///
+    /// # Safety
+    /// `crd` must be in-bounds in every flattened mode.
+    #[inline]
+    pub unsafe fn get_flat_mut(&mut self, crd: &[usize]) -> &mut T {
+        debug_check_coord("get_flat_mut", &self.layout, crd);
+        &mut *self.ptr.as_ptr().offset(self.layout.crd2offset_flat(crd))
     }
 
-    pub unsafe fn subview_mut(&mut self, start: &Tuple, subshape: &Shape) -> TensorViewMut<'a, T> {
-        let offset = self.layout.crd2idx(start);
+    /// Unchecked subview that keeps `'a` rather than borrowing `self`, for
+    /// handing out several disjoint blocks at once; `try_subview_mut`,
+    /// `tile_mut` and `split_at_mut` are the safe, reborrowing versions.
+    ///
+    /// # Safety
+    /// The subview must lie inside `self`, and no two views obtained this
+    /// way (or `self` itself) may access the same element while both are used.
+    pub unsafe fn subview_mut(&mut self, start: impl Into<Tuple>, subshape: impl Into<Shape>) -> TensorViewMut<'a, T> {
+        let (start, subshape) = (start.into(), subshape.into());
+        debug_check_subview(&self.layout, &start, &subshape);
+        let offset = self.layout.crd2offset(&start);
 
         TensorViewMut {
-            ptr: NonNull::new_unchecked(self.ptr.as_ptr().add(offset)),
-            layout: Layout::with_shape_stride( 
-                subshape.clone(),
+            ptr: self.ptr.offset(offset),
+            layout: self.layout.reversed_like(Layout::with_shape_stride(
+                subshape,
                 self.layout.stride().clone(),
-            ),
+            )).with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("subview", &self.layout, offset),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Bounds-checked `subview_mut`. The result borrows `self`, so unlike
+    /// the unchecked version it cannot be used to create aliasing views.
+    pub fn try_subview_mut(
+        &mut self,
+        start: impl Into<Tuple>,
+        subshape: impl Into<Shape>,
+    ) -> Result<TensorViewMut<'_, T>> {
+        let (start, subshape) = (start.into(), subshape.into());
+        check_subview(&self.layout, &start, &subshape)?;
+        Ok(unsafe { self.subview_mut(start, subshape) })
+    }
+
+    /// Mutable counterpart of `TensorView::split`. The pieces cover disjoint
+    /// index ranges of one mode, so they never alias; they borrow `self`.
+    pub fn split_mut(&mut self, axis: usize, chunks: usize) -> Vec<TensorViewMut<'_, T>> {
+        split_ranges("split_mut", &self.layout, axis, chunks)
+            .into_iter()
+            .map(|(start, shape)| unsafe { self.subview_mut(start, shape) }.labelled("split"))
+            .collect()
+    }
+
+    /// Read-only view of the same elements, borrowing `self`
+    pub fn as_view(&self) -> TensorView<'_, T> {
+        TensorView { ptr: self.ptr, layout: self.layout.clone(), provenance: self.provenance.clone(), _marker: PhantomData }
+    }
+
+    /// Mutable view of the same elements for a shorter borrow, e.g. to
+    /// hand to a function while keeping `self`
+    pub fn reborrow(&mut self) -> TensorViewMut<'_, T> {
+        TensorViewMut { ptr: self.ptr, layout: self.layout.clone(), provenance: self.provenance.clone(), _marker: PhantomData }
+    }
+
+    /// `r x c` block at `(r0, c0)` of a rank-2 view, borrowing `self`.
+    /// Panics if the block does not fit.
+    pub fn tile_mut(&mut self, r0: usize, c0: usize, r: usize, c: usize) -> TensorViewMut<'_, T> {
+        match self.try_subview_mut([r0, c0], [r, c]) {
+            Ok(tile) => tile.labelled("tile"),
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Split flattened mode `axis` at `mid` into `0..mid` and `mid..extent`.
+    /// The halves never alias, so both may be written at once; they borrow
+    /// `self`.
+    pub fn split_at_mut(&mut self, axis: usize, mid: usize) -> (TensorViewMut<'_, T>, TensorViewMut<'_, T>) {
+        const OP: &str = "split_at_mut";
+        let rank = self.layout.flat_shape().len();
+        if let Err(e) = check_axis(OP, axis, rank) {
+            panic!("{e}");
+        }
+        let extent = self.layout.flat_shape()[axis];
+        assert!(mid <= extent, "{OP}: mid {mid} is past extent {extent} of mode {axis}");
+
+        let dims = &self.layout.shape().dims;
+        let (head, tail) = (with_flat_at(dims, axis, mid), with_flat_at(dims, axis, extent - mid));
+        let mut start = vec![0; rank];
+        start[axis] = mid;
+        let (head, tail) = unsafe { (self.subview_mut(vec![0; rank], Shape::new(head)), self.subview_mut(start, Shape::new(tail))) };
+        (head.labelled("split_at"), tail.labelled("split_at"))
+    }
+
+    /// Rows `0..mid` and `mid..` of a matrix view; see `split_at_mut`
+    pub fn split_at_row_mut(&mut self, mid: usize) -> (TensorViewMut<'_, T>, TensorViewMut<'_, T>) {
+        self.split_at_mut(0, mid)
+    }
+
+    /// Columns `0..mid` and `mid..` of a matrix view; see `split_at_mut`
+    pub fn split_at_col_mut(&mut self, mid: usize) -> (TensorViewMut<'_, T>, TensorViewMut<'_, T>) {
+        self.split_at_mut(1, mid)
+    }
+
+    /// Mutable counterpart of `TensorView::flip`
+    pub fn flip(self, mode: usize) -> TensorViewMut<'a, T> {
+        let offset = flip_origin(&self.layout, mode);
+        TensorViewMut {
+            ptr: unsafe { self.ptr.offset(offset) },
+            layout: self.layout.flip(mode).with_offset(self.layout.offset() + offset),
+            provenance: self.provenance.then("flip", &self.layout, offset),
             _marker: PhantomData,
         }
     }
 
+    /// Unchecked `r x c` block at `(r0, c0)`. The result keeps `'a`, so it
+    /// can outlive the borrow of `self`; `tile_mut` is the checked,
+    /// reborrowing version.
+    ///
+    /// # Safety
+    /// The block must lie inside `self` and must not overlap any other live view.
     pub unsafe fn subview_2d_mut(
         &mut self,
         r0: usize,
@@ -183,8 +987,365 @@ impl<'a, T> TensorViewMut<'a, T> {
     /// Caller must ensure `idx` is in-bounds and unique.
     #[inline(always)]
     pub unsafe fn ptr_at_mut(&self, idx: &Tuple) -> *mut T {
-        let offset = idx.dot(&self.layout.stride());
-        self.ptr.as_ptr().add(offset)
+        debug_check_tuple("ptr_at_mut", &self.layout, idx);
+        self.ptr.as_ptr().offset(self.layout.crd2offset(idx))
+    }
+
+    /// Iterate over `(coordinate, &mut element)` pairs in layout order
+    /// (last flattened mode fastest).
+    pub fn indexed_iter_mut(&mut self) -> IndexedIterMut<'_, T> {
+        IndexedIterMut {
+            ptr: self.ptr,
+            counter: CoordCounter::new(&self.layout),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Validate a subview request against the parent layout
+fn check_subview(parent: &Layout, start: &Tuple, subshape: &Shape) -> Result<()> {
+    const OP: &str = "subview";
+    let rank = parent.shape().flat_len();
+    check_rank(OP, rank, start.flat_len())?;
+    check_rank(OP, rank, subshape.flat_len())?;
+
+    let fits = start
+        .iter_flat()
+        .zip(subshape.dims.iter_flat())
+        .zip(parent.shape().dims.iter_flat())
+        .all(|((&s, &len), &extent)| s.checked_add(len).is_some_and(|end| end <= extent));
+    if !fits {
+        return Err(Error::OutOfBounds { op: OP, coord: start.clone(), shape: parent.shape().clone() });
+    }
+
+    // The per-mode check implies this for well-formed layouts; it also
+    // catches subshapes whose nesting makes the parent strides span further.
+    if subshape.size() > 0 && !parent.has_reversed_modes() {
+        let sub = Layout::with_shape_stride(subshape.clone(), parent.stride().clone());
+        if parent.crd2idx(start) + sub.cosize() > parent.cosize() {
+            return Err(Error::OutOfBounds { op: OP, coord: start.clone(), shape: parent.shape().clone() });
+        }
+    }
+    Ok(())
+}
+
+/// Start coordinate and shape of each `split` piece
+fn split_ranges(op: &'static str, layout: &Layout, axis: usize, chunks: usize) -> Vec<(Tuple, Shape)> {
+    let rank = layout.shape().flat_len();
+    if let Err(e) = check_axis(op, axis, rank) {
+        panic!("{e}");
+    }
+    assert!(chunks > 0, "{op}: chunks must be > 0");
+
+    let extent = layout.shape().flat_at(axis);
+    let (base, extra) = (extent / chunks, extent % chunks);
+    let mut begin = 0;
+    (0..chunks)
+        .map(|i| {
+            let len = base + usize::from(i < extra);
+            let mut start = vec![0; rank];
+            start[axis] = begin;
+            begin += len;
+            (Tuple::int(start), Shape::new(with_flat_at(&layout.shape().dims, axis, len)))
+        })
+        .collect()
+}
+
+/// `t` with flattened entry `i` replaced by `v`, keeping the nesting
+fn with_flat_at(t: &Tuple, mut i: usize, v: usize) -> Tuple {
+    fn recur(t: &Tuple, i: &mut usize, v: usize) -> Tuple {
+        match t {
+            Tuple::Int(xs) => Tuple::Int(
+                xs.iter()
+                    .map(|&x| {
+                        let out = if *i == 0 { v } else { x };
+                        *i = i.wrapping_sub(1);
+                        out
+                    })
+                    .collect(),
+            ),
+            Tuple::Tup(ts) => Tuple::Tup(ts.iter().map(|t| recur(t, i, v)).collect()),
+        }
+    }
+    recur(t, &mut i, v)
+}
+
+/// Offset from the current origin to the last element along `mode`,
+/// which becomes the origin once that mode is reversed.
+fn flip_origin(layout: &Layout, mode: usize) -> isize {
+    let extent = layout.shape().dims.iter_flat().nth(mode).copied().unwrap_or(0);
+    if extent == 0 {
+        return 0;
+    }
+    (extent as isize - 1) * layout.signed_stride()[mode]
+}
+
+/* ========================= Indexing operators ========================= */
+
+/* ---------- `debug-checks` ---------- */
+
+// With the `debug-checks` feature the unchecked accessors (`get`, `ptr_at`,
+// `subview`, ...) validate their arguments like the safe ones and panic with
+// the coordinate and layout; without it these compile to nothing.
+
+#[inline(always)]
+fn debug_check_coord(op: &'static str, layout: &Layout, crd: &[usize]) {
+    #[cfg(feature = "debug-checks")]
+    {
+        let dims = layout.flat_shape();
+        if crd.len() != dims.len() || crd.iter().zip(dims).any(|(c, d)| c >= d) {
+            panic!("{op}: coordinate {crd:?} out of bounds for layout {}:{}", layout.shape(), layout.stride());
+        }
+    }
+    #[cfg(not(feature = "debug-checks"))]
+    let _ = (op, layout, crd);
+}
+
+#[inline(always)]
+fn debug_check_tuple(op: &'static str, layout: &Layout, crd: &Tuple) {
+    #[cfg(feature = "debug-checks")]
+    debug_check_coord(op, layout, &crd.flatten());
+    #[cfg(not(feature = "debug-checks"))]
+    let _ = (op, layout, crd);
+}
+
+#[inline(always)]
+fn debug_check_subview(layout: &Layout, start: &Tuple, subshape: &Shape) {
+    // `subview` accepts any nesting of its arguments and the parent; compare flattened
+    #[cfg(feature = "debug-checks")]
+    if let Err(e) = check_subview(
+        &Layout::with_shape_stride(Shape::new(Tuple::int(layout.flat_shape().to_vec())), Tuple::int(layout.flat_stride().to_vec())),
+        &Tuple::int(start.flatten()),
+        &Shape::new(Tuple::int(subshape.dims.flatten())),
+    ) {
+        panic!("{e} (subshape {subshape}, layout {}:{})", layout.shape(), layout.stride());
+    }
+    #[cfg(not(feature = "debug-checks"))]
+    let _ = (layout, start, subshape);
+}
+
+/// Linear offset of the flat coordinate `crd`, panicking if any
+/// coordinate is outside its extent.
+fn checked_offset(layout: &Layout, crd: &[usize]) -> isize {
+    let rank = layout.shape().flat_len();
+    assert_eq!(crd.len(), rank, "index rank {} does not match tensor rank {rank}", crd.len());
+
+    let extents = layout.shape().dims.iter_flat();
+    let mut offset = 0;
+    for (d, ((&c, &extent), stride)) in crd.iter().zip(extents).zip(layout.signed_stride()).enumerate() {
+        assert!(c < extent, "index {c} out of bounds for mode {d} of extent {extent}");
+        offset += c as isize * stride;
+    }
+    offset
+}
+
+macro_rules! impl_index {
+    ($($ty:ty),+) => {$(
+        impl<T, const N: usize> Index<[usize; N]> for $ty {
+            type Output = T;
+
+            fn index(&self, crd: [usize; N]) -> &T {
+                let off = checked_offset(&self.layout, &crd);
+                self.element(off)
+            }
+        }
+
+        impl<T> Index<(usize, usize)> for $ty {
+            type Output = T;
+
+            fn index(&self, (i, j): (usize, usize)) -> &T {
+                &self[[i, j]]
+            }
+        }
+    )+};
+}
+
+macro_rules! impl_index_mut {
+    ($($ty:ty),+) => {$(
+        impl<T, const N: usize> IndexMut<[usize; N]> for $ty {
+            fn index_mut(&mut self, crd: [usize; N]) -> &mut T {
+                let off = checked_offset(&self.layout, &crd);
+                self.element_mut(off)
+            }
+        }
+
+        impl<T> IndexMut<(usize, usize)> for $ty {
+            fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+                &mut self[[i, j]]
+            }
+        }
+    )+};
+}
+
+impl<T> Tensor<T> {
+    // Owned tensors never have reversed modes, so offsets are non-negative
+    fn element(&self, off: isize) -> &T {
+        &self.data()[off as usize]
+    }
+
+    fn element_mut(&mut self, off: isize) -> &mut T {
+        &mut self.data_mut()[off as usize]
+    }
+}
+
+impl<T> TensorView<'_, T> {
+    fn element(&self, off: isize) -> &T {
+        // `off` is in the view's codomain, which its creator guaranteed in-bounds
+        unsafe { &*self.ptr.as_ptr().offset(off) }
+    }
+}
+
+impl<T> TensorViewMut<'_, T> {
+    fn element(&self, off: isize) -> &T {
+        unsafe { &*self.ptr.as_ptr().offset(off) }
+    }
+
+    fn element_mut(&mut self, off: isize) -> &mut T {
+        unsafe { &mut *self.ptr.as_ptr().offset(off) }
+    }
+}
+
+impl_index!(Tensor<T>, TensorView<'_, T>, TensorViewMut<'_, T>);
+impl_index_mut!(Tensor<T>, TensorViewMut<'_, T>);
+
+/* ========================= Indexed iteration ========================= */
+
+/// Lexicographic counter over the flattened coordinate space of a layout
+struct CoordCounter {
+    shape: Vec<usize>,
+    stride: Vec<isize>,
+    current: Option<Vec<usize>>,
+}
+
+impl CoordCounter {
+    fn new(layout: &Layout) -> Self {
+        let shape = layout.shape().dims.flatten();
+        let stride = layout.signed_stride();
+        let current = if shape.contains(&0) {
+            None
+        } else {
+            Some(vec![0; shape.len()])
+        };
+        Self { shape, stride, current }
+    }
+
+    /// Return the current coordinate with its linear offset, then advance
+    fn next(&mut self) -> Option<(Vec<usize>, isize)> {
+        let crd = self.current.take()?;
+        let offset = crd.iter().zip(self.stride.iter()).map(|(&c, s)| c as isize * s).sum();
+
+        let mut next = crd.clone();
+        for d in (0..next.len()).rev() {
+            next[d] += 1;
+            if next[d] < self.shape[d] {
+                self.current = Some(next);
+                break;
+            }
+            next[d] = 0;
+        }
+
+        Some((crd, offset))
+    }
+}
+
+pub struct IndexedIter<'a, T> {
+    ptr: NonNull<T>,
+    counter: CoordCounter,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> Iterator for IndexedIter<'a, T> {
+    type Item = (Vec<usize>, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (crd, offset) = self.counter.next()?;
+        Some((crd, unsafe { &*self.ptr.as_ptr().offset(offset) }))
+    }
+}
+
+/// Elements of a view in ascending address order; see `TensorView::iter_memory_order`
+pub struct MemoryOrderIter<'a, T> {
+    // Lowest address the view reaches
+    ptr: NonNull<T>,
+    // Modes sorted from the largest stride (outermost) to the smallest
+    extents: Vec<usize>,
+    steps: Vec<isize>,
+    crd: Vec<usize>,
+    offset: isize,
+    remaining: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T> MemoryOrderIter<'a, T> {
+    fn new(ptr: NonNull<T>, layout: &Layout) -> Self {
+        let shape = layout.flat_shape();
+        let stride = layout.signed_stride();
+        let remaining = shape.iter().product();
+
+        // Reversed modes are walked from their far end, which sits lowest in memory
+        let start: isize = if remaining == 0 {
+            0
+        } else {
+            shape.iter().zip(&stride).filter(|(_, &s)| s < 0).map(|(&n, &s)| (n as isize - 1) * s).sum()
+        };
+
+        let mut order: Vec<usize> = (0..shape.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(stride[i].unsigned_abs()));
+        Self {
+            ptr: unsafe { NonNull::new_unchecked(ptr.as_ptr().wrapping_offset(start)) },
+            extents: order.iter().map(|&i| shape[i]).collect(),
+            steps: order.iter().map(|&i| stride[i].abs()).collect(),
+            crd: vec![0; order.len()],
+            offset: 0,
+            remaining,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T> Iterator for MemoryOrderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let item = unsafe { &*self.ptr.as_ptr().offset(self.offset) };
+
+        for d in (0..self.crd.len()).rev() {
+            self.crd[d] += 1;
+            self.offset += self.steps[d];
+            if self.crd[d] < self.extents[d] {
+                break;
+            }
+            self.offset -= self.steps[d] * self.extents[d] as isize;
+            self.crd[d] = 0;
+        }
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T> ExactSizeIterator for MemoryOrderIter<'_, T> {}
+
+pub struct IndexedIterMut<'a, T> {
+    ptr: NonNull<T>,
+    counter: CoordCounter,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for IndexedIterMut<'a, T> {
+    type Item = (Vec<usize>, &'a mut T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Each coordinate is visited once, so the returned references are disjoint
+        // as long as the layout is injective.
+        let (crd, offset) = self.counter.next()?;
+        Some((crd, unsafe { &mut *self.ptr.as_ptr().offset(offset) }))
     }
 }
 
@@ -260,6 +1421,290 @@ mod tests {
         assert_eq!(sub.layout().shape().size(), 6);
     }
 
+    #[test]
+    fn index_operators() {
+        let mut t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::col_major([3, 4]));
+        assert_eq!(t[[2, 1]], 5);
+        assert_eq!(t[(0, 3)], 9);
+
+        t[[1, 1]] = 42;
+        assert_eq!(t.data()[4], 42);
+
+        let mut v = t.as_view_mut();
+        let mut sub = unsafe { v.subview_mut([1, 1], [2, 2]) };
+        sub[(0, 0)] += 1;
+        assert_eq!(sub[[0, 0]], 43);
+        assert_eq!(sub.layout().stride().to_string(), "(1,3)");
+        assert_eq!(t.as_view()[[2, 2]], 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for mode 1")]
+    fn index_checks_each_mode() {
+        let t = Tensor::new(vec![0u8; 6], Layout::row_major([2, 3]));
+        // Offset 1*3 + 0 would be in range, but column 3 is not
+        let _ = t[[0, 3]];
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match tensor rank")]
+    fn index_checks_rank() {
+        let t = Tensor::new(vec![0u8; 6], Layout::row_major([2, 3]));
+        let _ = t[[1]];
+    }
+
+    #[test]
+    fn slice_accessors_and_into_vec() {
+        let mut t = Tensor::new(vec![1, 2, 3, 4], Layout::row_major([2, 2]));
+        t.as_mut_slice()[3] = 9;
+        assert_eq!(t.as_slice(), &[1, 2, 3, 9]);
+        assert_eq!(t.into_vec(), vec![1, 2, 3, 9]);
+
+        let t = Tensor::new_in(vec![String::from("a"), String::from("b")], Layout::row_major(2), crate::allocator::PageAligned);
+        assert_eq!(t.into_vec(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn view_to_vec_gathers_strided_elements() {
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::row_major([3, 4]));
+        let v = t.as_view();
+        let sub = unsafe { v.subview([1, 1], [2, 2]) };
+        assert_eq!(sub.to_vec(), vec![5, 6, 9, 10]);
+    }
+
+    #[test]
+    fn view_to_tensor_owns_subview() {
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::row_major([3, 4]));
+        let v = t.as_view();
+        let sub = unsafe { v.subview([1, 1], [2, 3]) };
+
+        let owned = sub.to_tensor::<RowMajor>();
+        assert_eq!(owned.data(), &[5, 6, 7, 9, 10, 11]);
+        assert_eq!(owned.layout().stride().to_string(), "(3,1)");
+
+        let owned = sub.to_tensor::<crate::layout::ColMajor>();
+        assert_eq!(owned.data(), &[5, 9, 6, 10, 7, 11]);
+        assert_eq!(owned[[1, 2]], 11);
+    }
+
+    #[test]
+    fn cow_borrows_until_mutated() {
+        let t = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::row_major([2, 3]));
+        let v = t.as_view();
+        let sub = unsafe { v.subview([0, 1], [2, 2]) };
+
+        let mut cow = TensorCow::from(sub);
+        assert!(!cow.is_owned());
+        assert_eq!(cow.view()[[1, 0]], 4);
+
+        cow.to_mut()[[1, 0]] = 40;
+        assert!(cow.is_owned());
+        assert_eq!(cow.view().to_vec(), vec![1, 2, 40, 5]);
+        assert_eq!(t.data(), &[0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn cow_owned_mutates_in_place() {
+        let mut cow = TensorCow::from(Tensor::new(vec![1.0f32, 2.0], Layout::row_major(2)));
+        cow.to_mut()[[0]] = 3.0;
+        assert_eq!(cow.into_owned().into_vec(), vec![3.0, 2.0]);
+    }
+
+    #[test]
+    fn views_cross_thread_boundaries() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<TensorView<'_, f32>>();
+        assert_send_sync::<TensorViewMut<'_, f32>>();
+        assert_send_sync::<crate::tiled_tensor::Tile>();
+
+        let mut t = Tensor::new(vec![0u32; 8], Layout::row_major([2, 4]));
+        let mut v = t.as_view_mut();
+        let top = unsafe { v.subview_mut([0, 0], [1, 4]) };
+        let bottom = unsafe { v.subview_mut([1, 0], [1, 4]) };
+
+        std::thread::scope(|s| {
+            for (row, mut half) in [(1, top), (2, bottom)] {
+                s.spawn(move || {
+                    for (_, x) in half.indexed_iter_mut() {
+                        *x = row;
+                    }
+                });
+            }
+        });
+        assert_eq!(t.data(), &[1, 1, 1, 1, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn flipped_views_reverse_without_copying() {
+        let mut t = Tensor::new((0..6).collect::<Vec<i32>>(), Layout::row_major([2, 3]));
+        let v = t.as_view();
+
+        let cols = v.flip(1);
+        assert_eq!(cols.to_vec(), vec![2, 1, 0, 5, 4, 3]);
+        assert_eq!(cols[[1, 0]], 5);
+
+        let both = cols.flip(0);
+        assert_eq!(both.to_vec(), vec![5, 4, 3, 2, 1, 0]);
+        assert_eq!(both.flip(0).flip(1).to_vec(), v.to_vec());
+
+        // Subviews of a flipped view stay reversed
+        let sub = unsafe { cols.subview([0, 1], [2, 2]) };
+        assert_eq!(sub.to_vec(), vec![1, 0, 4, 3]);
+        assert_eq!(sub.to_tensor::<RowMajor>().data(), &[1, 0, 4, 3]);
+
+        let mut rows = t.as_view_mut().flip(0);
+        rows[[0, 0]] = 30;
+        unsafe { *rows.get_mut(&Tuple::int(vec![1, 2])) = 20 };
+        assert_eq!(t.data(), &[0, 1, 20, 30, 4, 5]);
+    }
+
+    #[test]
+    fn try_subview_checks_bounds() {
+        let mut t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::row_major([3, 4]));
+        let v = t.as_view();
+
+        let sub = v.try_subview([1, 2], [2, 2]).unwrap();
+        assert_eq!(sub.to_vec(), vec![6, 7, 10, 11]);
+
+        // Offsets 7 and 8 are inside the buffer, but column 4 is outside the parent
+        assert!(matches!(v.try_subview([1, 3], [1, 2]), Err(Error::OutOfBounds { .. })));
+        assert!(matches!(v.try_subview([0, 0], [4, 1]), Err(Error::OutOfBounds { .. })));
+        assert!(matches!(v.try_subview([0], [1, 1]), Err(Error::RankMismatch { .. })));
+        assert!(v.try_subview([3, 4], [0, 0]).is_ok());
+
+        let mut vm = t.as_view_mut();
+        vm.try_subview_mut([2, 0], [1, 4]).unwrap()[[0, 3]] = -1;
+        assert!(vm.try_subview_mut([2, 1], [2, 1]).is_err());
+        assert_eq!(t.data()[11], -1);
+    }
+
+    #[test]
+    fn split_at_mut_gives_disjoint_halves() {
+        let mut t = Tensor::new(vec![0; 12], Layout::row_major([3, 4]));
+        let mut v = t.as_view_mut();
+        {
+            let (mut top, mut bottom) = v.split_at_row_mut(1);
+            let (mut left, mut right) = bottom.split_at_col_mut(3);
+            assert_eq!(left.layout().shape().dims.flatten(), vec![2, 3]);
+            top[[0, 0]] = 1;
+            left[[1, 2]] = 2;
+            right[[0, 0]] = 3;
+        }
+        v.tile_mut(0, 2, 1, 2)[[0, 1]] = 4;
+        v.reborrow()[[2, 0]] = 5;
+        assert_eq!(v.as_view().to_vec(), vec![1, 0, 0, 4, 0, 0, 0, 3, 5, 0, 2, 0]);
+
+        let (empty, all) = v.split_at_mut(0, 0);
+        assert_eq!((empty.layout().size(), all.layout().size()), (0, 12));
+    }
+
+    #[test]
+    fn gapped_layouts_need_their_cosize() {
+        let padded = Layout::row_major([2, 2]).with_stride([4, 1]);
+        let t = Tensor::new((0..6).collect(), padded);
+        assert_eq!(t.as_view().to_vec(), vec![0, 1, 4, 5]);
+
+        assert!(Layout::row_major([3, 2]).with_stride([2, 3]).is_injective());
+        assert!(!Layout::row_major([2, 3]).with_stride([0, 1]).is_injective());
+    }
+
+    #[test]
+    #[should_panic(expected = "layout reaches 6 elements but the buffer holds 4")]
+    fn gapped_layout_over_a_short_buffer_panics() {
+        Tensor::new(vec![1.0f32; 4], Layout::row_major([2, 2]).with_stride([4, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "maps several coordinates to one element")]
+    fn overlapping_layout_panics() {
+        Tensor::new(vec![0; 4], Layout::row_major([2, 2]).with_stride([1, 1]));
+    }
+
+    #[test]
+    fn flipped_views_reach_back_to_the_buffer_start() {
+        // Origins move to the far end and elements are reached with negative
+        // offsets; under Miri these must stay within the buffer's provenance
+        let mut t = Tensor::new((0..6).collect(), Layout::row_major([2, 3]));
+        {
+            let mut f = t.as_view_mut().flip(1).flip(0);
+            f[[0, 0]] += 10;
+            f[[1, 2]] += 20;
+        }
+        assert_eq!(t.data(), &[20, 1, 2, 3, 4, 15]);
+        let v = t.as_view().flip(0);
+        assert_eq!((v[[0, 2]], v[[1, 0]]), (15, 20));
+    }
+
+    #[test]
+    #[should_panic(expected = "past extent 4")]
+    fn split_at_mut_rejects_mid_past_extent() {
+        let mut t = Tensor::new(vec![0; 12], Layout::row_major([3, 4]));
+        t.as_view_mut().split_at_col_mut(5);
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "get_flat: coordinate [1, 3] out of bounds for layout (2,3):(3,1)")]
+    fn debug_checks_catch_unchecked_reads() {
+        let t = Tensor::new(vec![0; 6], Layout::row_major([2, 3]));
+        let v = t.as_view();
+        unsafe { v.get_flat(&[1, 3]) };
+    }
+
+    #[cfg(feature = "debug-checks")]
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn debug_checks_catch_unchecked_subviews() {
+        let t = Tensor::new(vec![0; 6], Layout::row_major([2, 3]));
+        unsafe { t.as_view().subview([1, 1], [1, 3]) };
+    }
+
+    #[cfg(feature = "provenance")]
+    #[test]
+    fn debug_path_lists_derivation_steps() {
+        let mut t = Tensor::new(vec![0; 64], Layout::row_major([8, 8]));
+        let mut v = t.as_view_mut();
+        assert_eq!(v.debug_path(), "(8,8):(8,1)");
+
+        let mut block = v.try_subview_mut([2, 2], [4, 4]).unwrap();
+        let (_, mut right) = block.split_at_col_mut(2);
+        let tile = right.tile_mut(1, 0, 2, 2);
+        assert_eq!(
+            tile.debug_path(),
+            "(8,8):(8,1) -> subview[+18] (4,4):(8,1) -> split_at[+2] (4,2):(8,1) -> tile[+8] (2,2):(8,1)"
+        );
+        assert_eq!(tile.as_view().flip(0).debug_path().rsplit(" -> ").next(), Some("flip[+8] (2,2):(8,1)"));
+    }
+
+    #[test]
+    fn views_remember_their_position() {
+        let t = Tensor::new((0..24).collect::<Vec<i32>>(), Layout::row_major([4, 6]));
+        let v = t.as_view();
+        assert_eq!(v.layout().offset(), 0);
+
+        let block = unsafe { v.subview([1, 2], [3, 4]) };
+        let inner = unsafe { block.subview([1, 1], [2, 2]) };
+        assert_eq!(block.layout().offset(), 8);
+        assert_eq!(inner.position_in(&block), Some(Tuple::int(vec![1, 1])));
+        assert_eq!(inner.position_in(&v), Some(Tuple::int(vec![2, 3])));
+
+        let flipped = v.flip(1);
+        assert_eq!(inner.position_in(&flipped), Some(Tuple::int(vec![2, 2])));
+
+        // Owned copies start a new root
+        let owned = inner.to_tensor::<RowMajor>();
+        assert_eq!(owned.as_view().position_in(&v), None);
+    }
+
+    #[test]
+    fn tensorview_subview_from_arrays() {
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::row_major([3, 4]));
+        let v = t.as_view();
+
+        let sub = unsafe { v.subview([1, 1], [2, 2]) };
+        assert_eq!(unsafe { *sub.get(&Tuple::from([1, 1])) }, 10);
+    }
+
     #[test]
     fn tensorview_mut_write() {
         let shape = Shape::new(Tuple::tup(vec![
@@ -287,5 +1732,143 @@ mod tests {
 
         assert_eq!(*val, 42);
     }
-}
 
+    #[test]
+    fn indexed_iter_strided_subview() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![3, 4])));
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), layout);
+        let v = t.as_view();
+        let sub = unsafe { v.subview_2d(1, 1, 2, 2) };
+
+        let items: Vec<_> = sub.indexed_iter().map(|(c, x)| (c, *x)).collect();
+        assert_eq!(
+            items,
+            vec![
+                (vec![0, 0], 5),
+                (vec![0, 1], 6),
+                (vec![1, 0], 9),
+                (vec![1, 1], 10),
+            ]
+        );
+    }
+
+    #[test]
+    fn iter_memory_order_follows_addresses() {
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::col_major([3, 4]));
+        let v = t.as_view();
+        assert_eq!(v.to_vec()[..4], [0, 3, 6, 9]);
+        assert_eq!(v.iter_memory_order().copied().collect::<Vec<_>>(), (0..12).collect::<Vec<_>>());
+
+        // A flipped, strided window is still read front to back
+        let sub = unsafe { v.subview_2d(1, 1, 2, 2) }.flip(1);
+        assert_eq!(sub.iter_memory_order().copied().collect::<Vec<_>>(), vec![4, 5, 7, 8]);
+        assert_eq!(sub.iter_memory_order().len(), 4);
+
+        let empty = Tensor::new(Vec::<i32>::new(), Layout::row_major([0, 3]));
+        assert_eq!(empty.as_view().iter_memory_order().count(), 0);
+    }
+
+    #[test]
+    fn new_in_page_aligned_and_pinned() {
+        use crate::allocator::{PageAligned, Pinned, PAGE_SIZE};
+
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![4, 8])));
+        let mut t = Tensor::new_in((0..32).map(|x| x as f32).collect(), layout.clone(), PageAligned);
+        assert_eq!(t.data().as_ptr().addr() % PAGE_SIZE, 0);
+        assert_eq!(t.data()[31], 31.0);
+
+        t.data_mut()[0] = -1.0;
+        assert_eq!(unsafe { *t.as_view().get(&Tuple::int(vec![0, 0])) }, -1.0);
+
+        let p = Tensor::new_in(vec![String::from("a"); 32], layout, Pinned);
+        assert_eq!(p.data().as_ptr().addr() % PAGE_SIZE, 0);
+        assert_eq!(p.data()[7], "a");
+    }
+
+    #[test]
+    fn padded_view_reads_pad_value() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let t = Tensor::new((1..=6).collect::<Vec<i32>>(), layout);
+        let v = t.as_view();
+
+        let padded = v.padded(&Shape::new(Tuple::int(vec![4, 4])), 0);
+        assert_eq!(padded.get(&Tuple::int(vec![1, 2])), 6);
+        assert_eq!(padded.get(&Tuple::int(vec![1, 3])), 0);
+        assert_eq!(padded.get(&Tuple::int(vec![3, 0])), 0);
+
+        let p = padded.to_tensor();
+        assert_eq!(p.data(), &[1, 2, 3, 0, 4, 5, 6, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn padded_view_rejects_shrinking() {
+        let layout = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let t = Tensor::new(vec![0; 6], layout);
+        let _ = t.as_view().padded(&Shape::new(Tuple::int(vec![2, 2])), 0);
+    }
+
+    #[test]
+    fn indexed_iter_mut_writes_coordinates() {
+        let layout = Layout::col_major(Shape::new(Tuple::int(vec![2, 3])));
+        let mut t = Tensor::new(vec![0usize; 6], layout);
+
+        for (c, x) in t.as_view_mut().indexed_iter_mut() {
+            *x = c[0] * 10 + c[1];
+        }
+
+        assert_eq!(t.data(), &[0, 10, 1, 11, 2, 12]);
+    }
+
+    #[test]
+    fn overlap_analysis() {
+        let t = Tensor::new((0..16).collect::<Vec<i32>>(), Layout::row_major([4, 4]));
+        let v = t.as_view();
+        let (top, bottom) = unsafe { (v.subview([0, 0], [2, 4]), v.subview([2, 0], [2, 4])) };
+        assert!(!top.overlaps(&bottom));
+        assert!(top.overlaps(&v));
+
+        // Even and odd columns interleave in memory but never share an element
+        let even = unsafe { v.with_layout(Layout::row_major([8]).with_stride(2)) };
+        let odd = unsafe { v.at_offset(1, Layout::row_major([8]).with_stride(2)) };
+        assert!(!even.overlaps(&odd));
+        assert!(even.overlaps(&v.flip(0)));
+    }
+    #[test]
+    fn split_into_uneven_chunks() {
+        let t = Tensor::new((0..12).collect::<Vec<i32>>(), Layout::row_major([2, 6]));
+        let pieces = t.as_view().split(1, 4);
+        let extents: Vec<usize> = pieces.iter().map(|p| p.layout().shape().flat_at(1)).collect();
+        assert_eq!(extents, vec![2, 2, 1, 1]);
+        assert_eq!(pieces[1].to_vec(), vec![2, 3, 8, 9]);
+        assert_eq!(pieces[3].to_vec(), vec![5, 11]);
+        assert_eq!(t.as_view().split(0, 3)[2].layout().size(), 0);
+
+        let mut t = Tensor::new(vec![0; 12], Layout::row_major([2, 6]));
+        let mut view = t.as_view_mut();
+        for (i, mut piece) in view.split_mut(0, 2).into_iter().enumerate() {
+            for (_, x) in piece.indexed_iter_mut() {
+                *x = i as i32 + 1;
+            }
+        }
+        assert_eq!(t.data(), &[1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "split: axis 2 out of range for rank 2")]
+    fn split_rejects_bad_axis() {
+        let t = Tensor::new(vec![0; 4], Layout::row_major([2, 2]));
+        t.as_view().split(2, 2);
+    }
+
+    #[test]
+    fn repeat_view_broadcasts_unit_modes() {
+        let t = Tensor::new(vec![1, 2, 3], Layout::row_major([3, 1]));
+        let r = t.as_view().repeat_view(&[2, 1, 2]).unwrap();
+        assert_eq!(r.layout().shape().dims.flatten(), vec![2, 3, 2]);
+        assert_eq!(r.layout().stride().flatten(), vec![0, 1, 0]);
+        assert_eq!(r.to_vec(), vec![1, 1, 2, 2, 3, 3, 1, 1, 2, 2, 3, 3]);
+        assert!(t.as_view().repeat_view(&[2, 2]).is_none());
+    }
+
+}
@@ -0,0 +1,22 @@
+// src/test_util.rs
+//
+// Fixtures shared by the unit tests.
+
+use crate::layout::Layout;
+use crate::tensor::Tensor;
+
+/// Row-major `rows x cols` tensor over `data`
+pub(crate) fn matrix<T>(rows: usize, cols: usize, data: Vec<T>) -> Tensor<T> {
+    Tensor::new(data, Layout::row_major([rows, cols]))
+}
+
+/// Row-major `rows x cols` tensor of small integers picked by `seed`, so
+/// f32 GEMMs over it are exact whatever the summation order
+pub(crate) fn seeded_matrix(rows: usize, cols: usize, seed: usize) -> Tensor<f32> {
+    matrix(rows, cols, (0..rows * cols).map(|x| ((x * 7 + seed) % 5) as f32 - 2.0).collect())
+}
+
+/// Row-major `rows x cols` tensor counting up from `first`
+pub(crate) fn iota<T: From<u16>>(rows: usize, cols: usize, first: u16) -> Tensor<T> {
+    matrix(rows, cols, (first..).take(rows * cols).map(T::from).collect())
+}
@@ -10,8 +10,16 @@
 //
 // ============================================================
 
-use crate::tensor::{TensorView, TensorViewMut};
-use crate::layout::Layout;
+use std::marker::PhantomData;
+use std::ops::{Deref, Range};
+use std::ptr::NonNull;
+use std::sync::Mutex;
+
+use crate::copy::tensor_copy;
+use crate::shape::for_each_flat_coord;
+use crate::tensor::{Tensor, TensorView, TensorViewMut};
+use crate::layout::{Layout, INLINE_RANK};
+use crate::static_layout::StaticLayout;
 use crate::layout_algebra::flat_divide;
 use crate::tuple::Tuple;
 use crate::shape::Shape;
@@ -20,10 +28,63 @@ use crate::shape::Shape;
    Tile descriptor
    ============================================================ */
 
+/// Per-mode values of one tile: inline up to `INLINE_RANK` modes, so
+/// iterating tiles of low-rank views never touches the allocator
+#[derive(Clone, PartialEq, Eq)]
+enum TileDims {
+    Inline { len: u8, dims: [usize; INLINE_RANK] },
+    Heap(Box<[usize]>),
+}
+
+impl TileDims {
+    fn from_fn(rank: usize, mut f: impl FnMut(usize) -> usize) -> Self {
+        if rank <= INLINE_RANK {
+            let mut dims = [0; INLINE_RANK];
+            for (i, d) in dims[..rank].iter_mut().enumerate() {
+                *d = f(i);
+            }
+            TileDims::Inline { len: rank as u8, dims }
+        } else {
+            TileDims::Heap((0..rank).map(f).collect())
+        }
+    }
+}
+
+impl Deref for TileDims {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        match self {
+            TileDims::Inline { len, dims } => &dims[..*len as usize],
+            TileDims::Heap(dims) => dims,
+        }
+    }
+}
+
+impl std::fmt::Debug for TileDims {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+impl PartialEq<Vec<usize>> for TileDims {
+    fn eq(&self, other: &Vec<usize>) -> bool {
+        self.deref() == other.as_slice()
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for TileDims {
+    fn from(dims: [usize; N]) -> Self {
+        TileDims::from_fn(N, |i| dims[i])
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Tile {
-    start: Vec<usize>,
-    len:   Vec<usize>,
+    start: TileDims,
+    len:   TileDims,
+    index: usize,
+    coord: TileDims,
 }
 
 impl Tile {
@@ -38,6 +99,76 @@ impl Tile {
     pub fn ndim(&self) -> usize {
         self.start.len()
     }
+
+    /// Linear id of the tile in row-major order over the tile grid
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Position of the tile in the tile grid
+    pub fn tile_coord(&self) -> &[usize] {
+        &self.coord
+    }
+
+    /// Extents of this (possibly partial) tile
+    pub fn shape(&self) -> Shape {
+        Shape::new(Tuple::int(self.len.to_vec()))
+    }
+
+    /// `shape` as a slice, without building a `Shape`
+    pub fn extents(&self) -> &[usize] {
+        &self.len
+    }
+
+    /// Layout of the tile inside `parent`: the tile's shape with the
+    /// parent's flattened strides, positioned at the tile start. Does not
+    /// allocate up to rank `INLINE_RANK`.
+    pub fn to_layout(&self, parent: &Layout) -> Layout {
+        assert_eq!(parent.flat_shape().len(), self.ndim(), "Tile::to_layout: rank mismatch");
+        parent
+            .reversed_like(Layout::from_flat(&self.len, parent.flat_stride()))
+            .with_offset(parent.offset() + parent.crd2offset_flat(&self.start))
+    }
+
+    /// Global coordinate of the tile-local coordinate `local`
+    pub fn global_coord(&self, local: impl Into<Tuple>) -> Tuple {
+        let local = local.into();
+        assert_eq!(local.flat_len(), self.ndim(), "Tile::global_coord: rank mismatch");
+        Tuple::int(
+            local
+                .iter_flat()
+                .zip(self.start.iter().zip(self.len.iter()))
+                .map(|(&l, (&s, &n))| {
+                    assert!(l < n, "Tile::global_coord: local coordinate {l} outside tile extent {n}");
+                    s + l
+                })
+                .collect(),
+        )
+    }
+
+    /// Tile-local coordinate of `global`, or `None` if it lies outside the tile
+    pub fn local_coord(&self, global: impl Into<Tuple>) -> Option<Tuple> {
+        let global = global.into();
+        if global.flat_len() != self.ndim() {
+            return None;
+        }
+        global
+            .iter_flat()
+            .zip(self.start.iter().zip(self.len.iter()))
+            .map(|(&g, (&s, &n))| g.checked_sub(s).filter(|l| *l < n))
+            .collect::<Option<Vec<_>>>()
+            .map(Tuple::int)
+    }
+
+    pub fn contains(&self, global: impl Into<Tuple>) -> bool {
+        self.local_coord(global).is_some()
+    }
+
+    /// Linear index of the tile's first element in `parent`
+    pub fn linear_index(&self, parent: &Layout) -> usize {
+        assert_eq!(parent.flat_shape().len(), self.ndim(), "Tile::linear_index: rank mismatch");
+        parent.crd2idx_flat(&self.start)
+    }
 }
 
 /* ============================================================
@@ -47,52 +178,93 @@ impl Tile {
 pub struct TileIter {
     tile_shape: Vec<usize>,
     full_shape: Vec<usize>,
-    current: Vec<usize>,
-    done: bool,
+    counts: Vec<usize>,
+    // Tiles spanned by one step along each grid mode
+    pitch: Vec<usize>,
+    remaining: Range<usize>,
 }
 
 impl TileIter {
     pub fn new(tile_shape: Vec<usize>, full_shape: Vec<usize>) -> Self {
-        let ndim = tile_shape.len();
+        // Partial edge tiles count, so the grid extent rounds up
+        let counts: Vec<usize> = full_shape.iter().zip(tile_shape.iter()).map(|(f, t)| f.div_ceil(*t)).collect();
+        let mut pitch = vec![1; counts.len()];
+        for i in (1..counts.len()).rev() {
+            pitch[i - 1] = pitch[i] * counts[i];
+        }
         Self {
             tile_shape,
             full_shape,
-            current: vec![0; ndim],
-            done: false,
+            remaining: 0..counts.iter().product(),
+            counts,
+            pitch,
         }
     }
+
+    /// Total number of tiles, including the ones already yielded
+    pub fn num_tiles(&self) -> usize {
+        self.counts.iter().product()
+    }
+
+    /// Tile number `index`, decoded row-major over the grid (last mode fastest)
+    fn tile(&self, index: usize) -> Tile {
+        let rank = self.counts.len();
+        let coord = TileDims::from_fn(rank, |i| index / self.pitch[i] % self.counts[i]);
+        let start = TileDims::from_fn(rank, |i| coord[i] * self.tile_shape[i]);
+        let len = TileDims::from_fn(rank, |i| self.tile_shape[i].min(self.full_shape[i] - start[i]));
+        Tile { start, len, index, coord }
+    }
 }
 
 impl Iterator for TileIter {
     type Item = Tile;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.done {
-            return None;
-        }
+        let index = self.remaining.next()?;
+        Some(self.tile(index))
+    }
 
-        let start = self.current.clone();
-        let mut len = Vec::with_capacity(start.len());
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
 
-        for d in 0..start.len() {
-            let remaining = self.full_shape[d] - start[d];
-            len.push(self.tile_shape[d].min(remaining));
-        }
+impl DoubleEndedIterator for TileIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.remaining.next_back()?;
+        Some(self.tile(index))
+    }
+}
 
-        // advance multi-dimensional counter
-        for d in (0..self.current.len()).rev() {
-            self.current[d] += self.tile_shape[d];
-            if self.current[d] < self.full_shape[d] {
-                break;
-            } else {
-                self.current[d] = 0;
-                if d == 0 {
-                    self.done = true;
-                }
-            }
-        }
+impl ExactSizeIterator for TileIter {}
+
+/// What every tile view of one base shares, computed once: the base's
+/// signed strides and the layout of a full tile. Only partial edge tiles
+/// build a layout of their own; up to rank `INLINE_RANK` neither that nor
+/// cloning the full one allocates.
+struct TileViews {
+    stride: Vec<isize>,
+    full: Layout,
+}
+
+impl TileViews {
+    fn new(base: &Layout, tile_shape: &[usize]) -> Self {
+        Self { stride: base.signed_stride(), full: Self::layout_of(base, tile_shape) }
+    }
+
+    fn layout_of(base: &Layout, extents: &[usize]) -> Layout {
+        base.reversed_like(Layout::from_flat(extents, base.flat_stride()))
+    }
 
-        Some(Tile { start, len })
+    /// Offset of `tile`'s origin from the base origin, and the tile's layout
+    fn place(&self, base: &Layout, tile: &Tile) -> (isize, Layout) {
+        let offset = tile.start.iter().zip(&self.stride).map(|(&s, &d)| s as isize * d).sum();
+        let layout = if self.full.flat_shape() == &*tile.len {
+            self.full.clone()
+        } else {
+            Self::layout_of(base, &tile.len)
+        };
+        (offset, layout)
     }
 }
 
@@ -104,6 +276,7 @@ pub struct TiledTensorView<'a, T> {
     base: TensorView<'a, T>,
     tile_layout: Layout,
     tile_iter: TileIter,
+    views: TileViews,
 }
 
 impl<'a, T> TiledTensorView<'a, T> {
@@ -119,23 +292,92 @@ impl<'a, T> TiledTensorView<'a, T> {
             _ => panic!("flat_divide must produce flat Int tuple"),
         };
 
-        let tile_iter = TileIter::new(tile_dims.clone(), base.layout().shape().dims.flatten());
+        let views = TileViews::new(base.layout(), &tile_dims);
+        let tile_iter = TileIter::new(tile_dims, base.layout().flat_shape().to_vec());
 
         Self {
             base,
             tile_layout: tiler,
             tile_iter,
+            views,
         }
     }
 
-    pub fn tiles(&mut self) -> impl Iterator<Item = (Tile, TensorView<'a, T>)> + '_ {
-        self.tile_iter.by_ref().map(|tile| {
-            let sub = unsafe {
-                self.base.subview(&Tuple::int(tile.start.clone()), &Shape::new(Tuple::int(tile.len.clone())))
-            };
+    /// Number of tiles covering the base view, partial edge tiles included
+    pub fn num_tiles(&self) -> usize {
+        self.tile_iter.num_tiles()
+    }
+
+    pub fn tiles(&mut self) -> impl ExactSizeIterator<Item = (Tile, TensorView<'a, T>)> + DoubleEndedIterator + '_ {
+        let (base, views) = (&self.base, &self.views);
+        self.tile_iter.by_ref().map(move |tile| {
+            let (offset, layout) = views.place(base.layout(), &tile);
+            let sub = unsafe { base.at_offset(offset, layout) }.labelled("tile");
             (tile, sub)
         })
     }
+
+    /// Every tile copied into its own row-major tensor, for kernels that
+    /// need contiguous operands. Storage comes from `pool`; hand finished
+    /// tiles back with `TileBufferPool::recycle` and a steady loop stops
+    /// allocating after its first few tiles.
+    pub fn tiles_copied<'p>(
+        &'p mut self,
+        pool: &'p TileBufferPool<T>,
+    ) -> impl ExactSizeIterator<Item = (Tile, Tensor<T>)> + DoubleEndedIterator + use<'a, 'p, T>
+    where
+        T: Copy,
+    {
+        self.tiles().map(move |(tile, view)| {
+            let layout = Layout::row_major(tile.extents().to_vec());
+            let mut data = pool.take(layout.size());
+            gather(&view, &mut data);
+            (tile, Tensor::new(data, layout))
+        })
+    }
+}
+
+/// Append the elements of `view` to `data` in row-major order
+fn gather<T: Copy>(view: &TensorView<'_, T>, data: &mut Vec<T>) {
+    for_each_flat_coord(view.layout().shape(), |crd| data.push(unsafe { *view.get_flat(crd) }));
+}
+
+/// Free buffers for tile copies, shared by reference so tiles can be
+/// recycled while an iterator still borrows the pool, from any thread
+#[derive(Debug)]
+pub struct TileBufferPool<T> {
+    free: Mutex<Vec<Vec<T>>>,
+}
+
+impl<T> Default for TileBufferPool<T> {
+    fn default() -> Self {
+        Self { free: Mutex::new(Vec::new()) }
+    }
+}
+
+impl<T> TileBufferPool<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep `tile`'s storage for the next copy
+    pub fn recycle(&self, tile: Tensor<T>) {
+        let mut data = tile.into_vec();
+        data.clear();
+        self.free.lock().unwrap().push(data);
+    }
+
+    /// Number of buffers waiting to be reused
+    pub fn available(&self) -> usize {
+        self.free.lock().unwrap().len()
+    }
+
+    /// An empty buffer with room for `len` elements
+    fn take(&self, len: usize) -> Vec<T> {
+        let mut data = self.free.lock().unwrap().pop().unwrap_or_default();
+        data.reserve(len);
+        data
+    }
 }
 
 /* ============================================================
@@ -146,6 +388,7 @@ pub struct TiledTensorViewMut<'a, T> {
     base: TensorViewMut<'a, T>,
     tile_layout: Layout,
     tile_iter: TileIter,
+    views: TileViews,
 }
 
 impl<'a, T> TiledTensorViewMut<'a, T> {
@@ -160,23 +403,299 @@ impl<'a, T> TiledTensorViewMut<'a, T> {
             _ => panic!("flat_divide must produce flat Int tuple"),
         };
 
-        let tile_iter = TileIter::new(tile_dims.clone(), base.layout().shape().dims.flatten());
+        let views = TileViews::new(base.layout(), &tile_dims);
+        let tile_iter = TileIter::new(tile_dims, base.layout().flat_shape().to_vec());
 
         Self {
             base,
             tile_layout: tiler,
             tile_iter,
+            views,
         }
     }
 
-    pub fn tiles_mut(&mut self) -> impl Iterator<Item = (Tile, TensorViewMut<'a, T>)> + '_ {
-        self.tile_iter.by_ref().map(|tile| {
-            let sub = unsafe {
-                self.base.subview_mut(&Tuple::int(tile.start.clone()), &Shape::new(Tuple::int(tile.len.clone())))
-            };
+    pub fn num_tiles(&self) -> usize {
+        self.tile_iter.num_tiles()
+    }
+
+    /// Tiles not yet handed out by `tiles_mut`
+    #[cfg(feature = "async")]
+    pub(crate) fn tiles_left(&self) -> usize {
+        self.tile_iter.len()
+    }
+
+    pub fn tiles_mut(&mut self) -> impl ExactSizeIterator<Item = (Tile, TensorViewMut<'a, T>)> + DoubleEndedIterator + '_ {
+        let (base, views) = (&mut self.base, &self.views);
+        self.tile_iter.by_ref().map(move |tile| {
+            let (offset, layout) = views.place(base.layout(), &tile);
+            // SAFETY: the tiles of one tiler lie inside the base and are
+            // disjoint, and `tile_iter` yields each tile once, even across
+            // calls, so views outliving this borrow never overlap
+            let sub = unsafe { base.at_offset_mut(offset, layout) }.labelled("tile");
             (tile, sub)
         })
     }
+
+    /// Load-compute-store over every remaining tile: the tile is copied into
+    /// a row-major `scratch` tensor, `f` updates it in place, and the result
+    /// is written back. One buffer is reused for all tiles.
+    pub fn for_each_tile(&mut self, mut f: impl FnMut(&Tile, &mut Tensor<T>))
+    where
+        T: Copy,
+    {
+        let mut data = Vec::new();
+        for (tile, mut view) in self.tiles_mut() {
+            let layout = Layout::row_major(tile.extents().to_vec());
+            data.clear();
+            gather(&view.as_view(), &mut data);
+
+            let mut scratch = Tensor::new(data, layout);
+            f(&tile, &mut scratch);
+            tensor_copy(&scratch.as_view(), &mut view);
+            data = scratch.into_vec();
+        }
+    }
+}
+
+/* ============================================================
+   Static tiling (compile-time tile extents)
+   ============================================================ */
+
+/// 2-D tiler whose tile extents are const generics. Full tiles come out as
+/// `StaticTile`s, whose loops have constant trip counts the compiler can
+/// unroll; the partial tiles along the bottom and right edges are handed
+/// out separately as ordinary views.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StaticTiler<const TM: usize, const TN: usize>;
+
+impl<const TM: usize, const TN: usize> StaticTiler<TM, TN> {
+    /// A compact row-major `TM x TN` tile, as a compile-time constant
+    pub const TILE: StaticLayout<2> = StaticLayout::row_major([TM, TN]);
+
+    /// The same tiling as a dynamic tiler layout for `TiledTensorView::new`
+    pub fn layout(&self) -> Layout {
+        Self::TILE.into()
+    }
+}
+
+/// Row/column extents and signed strides of a rank-2 layout
+fn matrix_geometry(op: &str, layout: &Layout) -> ([usize; 2], [isize; 2]) {
+    let shape = layout.flat_shape();
+    assert_eq!(shape.len(), 2, "{op}: static tiling needs a rank-2 view, got {}", layout.shape());
+    let stride = layout.signed_stride();
+    ([shape[0], shape[1]], [stride[0], stride[1]])
+}
+
+/// Grid of `TM x TN` tiles over `[rows, cols]`: full tiles first, then the
+/// partial ones. Tiles are numbered row-major over the whole grid, as in `TileIter`.
+struct StaticGrid<const TM: usize, const TN: usize> {
+    extents: [usize; 2],
+}
+
+impl<const TM: usize, const TN: usize> StaticGrid<TM, TN> {
+    fn counts(&self) -> [usize; 2] {
+        [self.extents[0].div_ceil(TM), self.extents[1].div_ceil(TN)]
+    }
+
+    fn full(&self) -> impl Iterator<Item = [usize; 2]> {
+        let (full_m, full_n) = (self.extents[0] / TM, self.extents[1] / TN);
+        (0..full_m).flat_map(move |i| (0..full_n).map(move |j| [i, j]))
+    }
+
+    fn edges(&self) -> impl Iterator<Item = Tile> {
+        let [rows, cols] = self.extents;
+        let [count_m, count_n] = self.counts();
+        let (full_m, full_n) = (rows / TM, cols / TN);
+        (0..count_m)
+            .flat_map(move |i| (0..count_n).map(move |j| [i, j]))
+            .filter(move |&[i, j]| i >= full_m || j >= full_n)
+            .map(move |[i, j]| {
+                let start = [i * TM, j * TN];
+                let len = [TM.min(rows - start[0]), TN.min(cols - start[1])];
+                Tile { start: start.into(), len: len.into(), index: i * count_n + j, coord: [i, j].into() }
+            })
+    }
+}
+
+/// A full `TM x TN` tile of a `StaticTiledTensorView`
+pub struct StaticTile<'a, T, const TM: usize, const TN: usize> {
+    ptr: NonNull<T>,
+    stride: [isize; 2],
+    coord: [usize; 2],
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T, const TM: usize, const TN: usize> StaticTile<'a, T, TM, TN> {
+    /// Position of the tile in the tile grid
+    pub fn tile_coord(&self) -> [usize; 2] {
+        self.coord
+    }
+
+    /// Global coordinate of the tile's first element
+    pub fn start(&self) -> [usize; 2] {
+        [self.coord[0] * TM, self.coord[1] * TN]
+    }
+
+    #[inline(always)]
+    pub fn get(&self, i: usize, j: usize) -> &'a T {
+        debug_assert!(i < TM && j < TN, "StaticTile::get: ({i}, {j}) outside {TM}x{TN}");
+        unsafe { self.ptr.offset(i as isize * self.stride[0] + j as isize * self.stride[1]).as_ref() }
+    }
+
+    /// Copy the tile into a stack array with fully unrolled loops
+    #[inline(always)]
+    pub fn load(&self) -> [[T; TN]; TM]
+    where
+        T: Copy,
+    {
+        std::array::from_fn(|i| std::array::from_fn(|j| *self.get(i, j)))
+    }
+
+    /// The tile as an ordinary view
+    pub fn view(&self) -> TensorView<'a, T> {
+        let mut layout = Layout::row_major([TM, TN]).with_stride(self.stride.map(|s| s.unsigned_abs()));
+        for mode in (0..2).filter(|&m| self.stride[m] < 0) {
+            layout = layout.flip(mode);
+        }
+        unsafe { TensorView::from_non_null(self.ptr, layout) }
+    }
+}
+
+/// Mutable counterpart of `StaticTile`
+pub struct StaticTileMut<'a, T, const TM: usize, const TN: usize> {
+    ptr: NonNull<T>,
+    stride: [isize; 2],
+    coord: [usize; 2],
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T, const TM: usize, const TN: usize> StaticTileMut<'a, T, TM, TN> {
+    pub fn tile_coord(&self) -> [usize; 2] {
+        self.coord
+    }
+
+    pub fn start(&self) -> [usize; 2] {
+        [self.coord[0] * TM, self.coord[1] * TN]
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self, i: usize, j: usize) -> &mut T {
+        debug_assert!(i < TM && j < TN, "StaticTileMut::get_mut: ({i}, {j}) outside {TM}x{TN}");
+        unsafe { self.ptr.offset(i as isize * self.stride[0] + j as isize * self.stride[1]).as_mut() }
+    }
+
+    /// Write a whole tile, e.g. a register block computed on the stack
+    #[inline(always)]
+    pub fn store(&mut self, values: &[[T; TN]; TM])
+    where
+        T: Copy,
+    {
+        for (i, row) in values.iter().enumerate() {
+            for (j, &v) in row.iter().enumerate() {
+                *self.get_mut(i, j) = v;
+            }
+        }
+    }
+}
+
+// Tiles stand in for borrows of disjoint blocks of the base view
+unsafe impl<T: Sync, const TM: usize, const TN: usize> Send for StaticTile<'_, T, TM, TN> {}
+unsafe impl<T: Send, const TM: usize, const TN: usize> Send for StaticTileMut<'_, T, TM, TN> {}
+
+pub struct StaticTiledTensorView<'a, T, const TM: usize, const TN: usize> {
+    base: TensorView<'a, T>,
+    stride: [isize; 2],
+    grid: StaticGrid<TM, TN>,
+}
+
+impl<'a, T> TiledTensorView<'a, T> {
+    /// Tile a rank-2 view by the compile-time extents of `StaticTiler<TM, TN>`
+    pub fn new_static<const TM: usize, const TN: usize>(
+        base: TensorView<'a, T>,
+        _tiler: StaticTiler<TM, TN>,
+    ) -> StaticTiledTensorView<'a, T, TM, TN> {
+        const { assert!(TM > 0 && TN > 0, "StaticTiler extents must be positive") };
+        let (extents, stride) = matrix_geometry("TiledTensorView::new_static", base.layout());
+        StaticTiledTensorView { base, stride, grid: StaticGrid { extents } }
+    }
+}
+
+impl<'a, T, const TM: usize, const TN: usize> StaticTiledTensorView<'a, T, TM, TN> {
+    /// Number of tiles, full and partial
+    pub fn num_tiles(&self) -> usize {
+        self.grid.counts().iter().product()
+    }
+
+    /// Every full `TM x TN` tile, row-major over the grid
+    pub fn full_tiles(&self) -> impl Iterator<Item = StaticTile<'a, T, TM, TN>> + '_ {
+        let (base, stride) = (self.base.ptr, self.stride);
+        self.grid.full().map(move |coord| {
+            let offset = (coord[0] * TM) as isize * stride[0] + (coord[1] * TN) as isize * stride[1];
+            StaticTile { ptr: unsafe { base.offset(offset) }, stride, coord, _marker: PhantomData }
+        })
+    }
+
+    /// The partial tiles along the bottom and right edges
+    pub fn edge_tiles(&self) -> impl Iterator<Item = (Tile, TensorView<'a, T>)> + '_ {
+        self.grid.edges().map(|tile| {
+            let (offset, layout) = edge_placement(self.base.layout(), self.stride, &tile);
+            let view = unsafe { self.base.at_offset(offset, layout) }.labelled("tile");
+            (tile, view)
+        })
+    }
+}
+
+pub struct StaticTiledTensorViewMut<'a, T, const TM: usize, const TN: usize> {
+    base: TensorViewMut<'a, T>,
+    stride: [isize; 2],
+    grid: StaticGrid<TM, TN>,
+}
+
+impl<'a, T> TiledTensorViewMut<'a, T> {
+    /// Mutable counterpart of `TiledTensorView::new_static`
+    pub fn new_static<const TM: usize, const TN: usize>(
+        base: TensorViewMut<'a, T>,
+        _tiler: StaticTiler<TM, TN>,
+    ) -> StaticTiledTensorViewMut<'a, T, TM, TN> {
+        const { assert!(TM > 0 && TN > 0, "StaticTiler extents must be positive") };
+        let (extents, stride) = matrix_geometry("TiledTensorViewMut::new_static", base.layout());
+        StaticTiledTensorViewMut { base, stride, grid: StaticGrid { extents } }
+    }
+}
+
+impl<'a, T, const TM: usize, const TN: usize> StaticTiledTensorViewMut<'a, T, TM, TN> {
+    pub fn num_tiles(&self) -> usize {
+        self.grid.counts().iter().product()
+    }
+
+    /// Every full tile; tiles are disjoint, so they may be processed in parallel
+    pub fn full_tiles_mut(&mut self) -> impl Iterator<Item = StaticTileMut<'_, T, TM, TN>> + '_ {
+        let (base, stride) = (self.base.ptr, self.stride);
+        self.grid.full().map(move |coord| {
+            let offset = (coord[0] * TM) as isize * stride[0] + (coord[1] * TN) as isize * stride[1];
+            StaticTileMut { ptr: unsafe { base.offset(offset) }, stride, coord, _marker: PhantomData }
+        })
+    }
+
+    /// The partial tiles along the bottom and right edges
+    pub fn edge_tiles_mut(&mut self) -> impl Iterator<Item = (Tile, TensorViewMut<'_, T>)> + '_ {
+        let (mut base, stride) = (self.base.reborrow(), self.stride);
+        self.grid.edges().map(move |tile| {
+            let (offset, layout) = edge_placement(base.layout(), stride, &tile);
+            // SAFETY: the edge tiles lie inside the base and are disjoint, and
+            // each is yielded once per call; the views borrow `self`, so a
+            // later call cannot hand them out again while they are alive
+            let view = unsafe { base.at_offset_mut(offset, layout) }.labelled("tile");
+            (tile, view)
+        })
+    }
+}
+
+/// Offset of an edge tile's origin in a rank-2 base with signed `stride`,
+/// and the tile's layout
+fn edge_placement(base: &Layout, stride: [isize; 2], tile: &Tile) -> (isize, Layout) {
+    let offset = tile.start(0) as isize * stride[0] + tile.start(1) as isize * stride[1];
+    (offset, base.reversed_like(Layout::from_flat(tile.extents(), base.flat_stride())))
 }
 
 /* ============================================================
@@ -195,18 +714,13 @@ impl<'a, T> TiledTensorViewMut<'a, T> {
 //   - zero-cost tiling
 //
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::tensor::Tensor;
     use crate::layout::Layout;
     use crate::shape::Shape;
     use crate::tuple::Tuple;
-
-    fn make_tensor_2d(m: usize, n: usize) -> Tensor<f32> {
-        let data: Vec<f32> = (0..m*n).map(|x| x as f32).collect();
-        let shape = Shape::new(Tuple::int(vec![m, n]));
-        Tensor::new(data, Layout::row_major(shape))
-    }
+    use crate::test_util::iota;
 
     #[test]
     fn tile_iter_covers_entire_tensor() {
@@ -214,7 +728,7 @@ mod tests {
         let n = 6;
         let tile = vec![3, 4];
 
-        let t = make_tensor_2d(m, n);
+        let t = iota::<f32>(m, n, 0);
         let tiler = Layout::row_major(Shape::new(Tuple::int(tile.clone())));
 
         let mut tiled = TiledTensorView::new(t.as_view(), tiler);
@@ -249,7 +763,7 @@ mod tests {
         let n = 5;
         let tile = vec![4, 3];
 
-        let t = make_tensor_2d(m, n);
+        let t = iota::<f32>(m, n, 0);
         let tiler = Layout::row_major(Shape::new(Tuple::int(tile.clone())));
 
         let mut tiled = TiledTensorView::new(t.as_view(), tiler);
@@ -273,7 +787,7 @@ mod tests {
         let n = 6;
         let tile = vec![2, 3];
 
-        let mut t = make_tensor_2d(m, n);
+        let mut t = iota::<f32>(m, n, 0);
         let tiler = Layout::row_major(Shape::new(Tuple::int(tile.clone())));
 
         {
@@ -297,12 +811,276 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tile_coordinate_helpers() {
+        let t = iota::<f32>(7, 5, 0);
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![4, 3])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+
+        // Bottom-right edge tile: start (4,3), extent (3,2)
+        let (tile, view) = tiled.tiles().last().unwrap();
+        assert_eq!(tile.shape().to_string(), "(3,2)");
+        assert_eq!(tile.global_coord([2, 1]), Tuple::int(vec![6, 4]));
+        assert_eq!(tile.local_coord([5, 3]), Some(Tuple::int(vec![1, 0])));
+        assert_eq!(tile.local_coord([3, 3]), None);
+        assert!(!tile.contains([6, 5]));
+
+        assert_eq!(tile.linear_index(t.layout()), 23);
+        let layout = tile.to_layout(t.layout());
+        assert_eq!(&layout, view.layout());
+        assert_eq!(layout.offset(), 23);
+        assert_eq!(layout.crd2idx([1, 1]) + tile.linear_index(t.layout()), 29);
+    }
+
+    #[test]
+    fn tile_iter_is_exact_size_and_reversible() {
+        let t = iota::<f32>(7, 5, 0);
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![4, 3])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+        assert_eq!(tiled.num_tiles(), 4);
+
+        let mut tiles = tiled.tiles();
+        assert_eq!(tiles.len(), 4);
+        let (last, _) = tiles.next_back().unwrap();
+        assert_eq!((last.start.to_vec(), last.len.to_vec()), (vec![4, 3], vec![3, 2]));
+        let (first, _) = tiles.next().unwrap();
+        assert_eq!(first.start, vec![0, 0]);
+        assert_eq!(tiles.len(), 2);
+        drop(tiles);
+
+        // The total is unaffected by consumed tiles
+        assert_eq!(tiled.num_tiles(), 4);
+    }
+
+    #[test]
+    fn tiles_carry_grid_index() {
+        let t = iota::<f32>(7, 5, 0);
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+
+        let tiles: Vec<Tile> = tiled.tiles().map(|(tile, _)| tile).collect();
+        assert_eq!(tiles.len(), 8);
+        for (i, tile) in tiles.iter().enumerate() {
+            assert_eq!(tile.index(), i);
+            assert_eq!(tile.tile_coord(), &[i / 2, i % 2]);
+        }
+
+        // Reverse iteration reports the same ids
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+        let ids: Vec<usize> = tiled.tiles().rev().map(|(tile, _)| tile.index()).collect();
+        assert_eq!(ids, (0..8).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn copied_tiles_are_contiguous_and_reuse_buffers() {
+        let t = iota::<f32>(5, 4, 0);
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+        let pool = TileBufferPool::new();
+
+        let mut seen = Vec::new();
+        for (tile, copy) in tiled.tiles_copied(&pool) {
+            assert_eq!(copy.layout(), &Layout::row_major(tile.shape()));
+            seen.push((tile.index(), copy.data().as_ptr(), copy.data().to_vec()));
+            pool.recycle(copy);
+        }
+        assert_eq!(seen.len(), 6);
+        assert_eq!(seen[1].2, vec![3.0, 7.0]);
+        assert_eq!(seen[4].2, vec![16.0, 17.0, 18.0]);
+        // One buffer serves every tile
+        assert!(seen.iter().all(|s| s.1 == seen[0].1));
+        assert_eq!(pool.available(), 1);
+    }
+
+    #[test]
+    fn for_each_tile_writes_scratch_back() {
+        let mut t = iota::<f32>(5, 4, 0);
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![2, 3])));
+        let mut tiled = TiledTensorViewMut::new(t.as_view_mut(), tiler);
+
+        let mut shapes = Vec::new();
+        tiled.for_each_tile(|tile, scratch| {
+            assert!(scratch.layout().is_contiguous());
+            shapes.push(scratch.layout().shape().dims.flatten());
+            for x in scratch.data_mut() {
+                *x = -*x - tile.index() as f32;
+            }
+        });
+        assert_eq!(shapes[1], vec![2, 1]);
+        assert_eq!(shapes[5], vec![1, 1]);
+        // Row 4 lies in tiles 4 and 5
+        assert_eq!(&t.data()[16..], &[-20.0, -21.0, -22.0, -24.0]);
+        assert_eq!(t.data()[3], -4.0);
+    }
+
+    #[test]
+    fn tile_views_match_subviews() {
+        let t = Tensor::new((0..35).map(|x| x as f32).collect(), Layout::col_major(Shape::new(Tuple::int(vec![7, 5]))));
+        let tiler = Layout::row_major(Shape::new(Tuple::int(vec![3, 2])));
+        let mut tiled = TiledTensorView::new(t.as_view(), tiler);
+        for (tile, view) in tiled.tiles() {
+            let expected = unsafe { t.as_view().subview(tile.start.to_vec(), tile.shape()) };
+            assert_eq!(view.layout(), expected.layout());
+            assert_eq!(view.to_vec(), expected.to_vec());
+        }
+
+        // Past the inline rank, coordinates spill to the heap
+        let tiles: Vec<Tile> = TileIter::new(vec![1, 1, 1, 1, 2], vec![1, 1, 1, 2, 3]).collect();
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[3].tile_coord(), &[0, 0, 0, 1, 1]);
+        assert_eq!((tiles[3].start(4), tiles[3].len(4)), (2, 1));
+    }
+
+    #[test]
+    fn static_tiles_split_full_and_edge_tiles() {
+        let t = iota::<f32>(7, 5, 0);
+        let tiled = TiledTensorView::new_static(t.as_view(), StaticTiler::<4, 2>);
+        assert_eq!(tiled.num_tiles(), 6);
+
+        let full: Vec<_> = tiled.full_tiles().collect();
+        assert_eq!(full.iter().map(|tile| tile.start()).collect::<Vec<_>>(), vec![[0, 0], [0, 2]]);
+        assert_eq!(full[1].load(), [[2.0, 3.0], [7.0, 8.0], [12.0, 13.0], [17.0, 18.0]]);
+        assert_eq!(full[1].view().to_vec(), vec![2.0, 3.0, 7.0, 8.0, 12.0, 13.0, 17.0, 18.0]);
+
+        let edges: Vec<_> = tiled.edge_tiles().map(|(tile, view)| (tile.index(), tile.shape().to_string(), view.to_vec())).collect();
+        assert_eq!(edges.len(), 4);
+        assert_eq!(edges[0], (2, "(4,1)".to_string(), vec![4.0, 9.0, 14.0, 19.0]));
+        assert_eq!(edges[3], (5, "(3,1)".to_string(), vec![24.0, 29.0, 34.0]));
+
+        // The dynamic tiler of the same extents covers the same tiles
+        let mut dynamic = TiledTensorView::new(t.as_view(), StaticTiler::<4, 2>.layout());
+        assert_eq!(dynamic.tiles().count(), full.len() + edges.len());
+    }
+
+    #[test]
+    fn static_tiles_write_every_element() {
+        let mut t = Tensor::new(vec![0i32; 30], Layout::col_major([5, 6]));
+        {
+            let mut tiled = TiledTensorViewMut::new_static(t.as_view_mut(), StaticTiler::<2, 3>);
+            for mut tile in tiled.full_tiles_mut() {
+                let [r, c] = tile.start();
+                tile.store(&std::array::from_fn(|i| std::array::from_fn(|j| ((r + i) * 10 + c + j) as i32)));
+            }
+            for (tile, mut view) in tiled.edge_tiles_mut() {
+                for (crd, x) in view.indexed_iter_mut() {
+                    let g = tile.global_coord(crd);
+                    *x = (g.flat_at(0) * 10 + g.flat_at(1)) as i32;
+                }
+            }
+        }
+        let view = t.as_view();
+        for (crd, &x) in view.indexed_iter() {
+            assert_eq!(x as usize, crd[0] * 10 + crd[1]);
+        }
+    }
+
+    #[test]
+    fn collected_mutable_tiles_are_disjoint() {
+        let mut t = Tensor::new(vec![-1i32; 35], Layout::col_major([7, 5]));
+        let mut seen = Vec::new();
+        {
+            let mut tiled = TiledTensorViewMut::new(t.as_view_mut(), Layout::row_major([3, 2]));
+            let mut tiles: Vec<_> = tiled.tiles_mut().collect();
+            assert!(tiled.tiles_mut().next().is_none());
+            for (tile, view) in &mut tiles {
+                for (_, x) in view.indexed_iter_mut() {
+                    assert_eq!(*x, -1, "element written by two tiles");
+                    *x = tile.index() as i32;
+                }
+            }
+            seen.extend(tiles.into_iter().map(|(tile, _)| tile));
+        }
+        for (crd, &x) in t.as_view().indexed_iter() {
+            let owner = seen.iter().find(|tile| tile.contains(crd.clone())).unwrap();
+            assert_eq!(x, owner.index() as i32);
+        }
+
+        let mut t = Tensor::new(vec![-1i32; 35], Layout::row_major([7, 5]));
+        let mut tiled = TiledTensorViewMut::new_static(t.as_view_mut(), StaticTiler::<4, 2>);
+        let mut edges: Vec<_> = tiled.edge_tiles_mut().collect();
+        for (tile, view) in &mut edges {
+            for (_, x) in view.indexed_iter_mut() {
+                assert_eq!(*x, -1, "element written by two tiles");
+                *x = tile.index() as i32;
+            }
+        }
+        drop(edges);
+        // Column 4 and rows 4..7 of columns 0..4 are edge elements
+        assert_eq!(t.data()[4], 2);
+        assert_eq!(t.data()[4 * 5], 3);
+        assert_eq!(t.data()[6 * 5 + 3], 4);
+        assert_eq!(t.data()[6 * 5 + 4], 5);
+        assert_eq!(t.data()[0], -1);
+    }
+
+    /// Counts this thread's allocations, so tests running in parallel do
+    /// not disturb each other
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: std::alloc::Layout, size: usize) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+            std::alloc::System.realloc(ptr, layout, size)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+    #[cfg(not(feature = "provenance"))]
+    pub(crate) fn allocations() -> usize {
+        ALLOCATIONS.with(|n| n.get())
+    }
+
+    // Provenance chains are reference-counted, so tiles allocate with that feature
+    #[cfg(not(feature = "provenance"))]
+    #[test]
+    fn tiles_do_not_allocate() {
+        let mut t = Tensor::new((0..6 * 7 * 5 * 3).map(|x| x as f32).collect(), Layout::col_major([6, 7, 5, 3]));
+        let mut tiled = TiledTensorView::new(t.as_view(), Layout::row_major([4, 3, 2, 2]));
+        let mut sum = 0.0;
+        let before = allocations();
+        for (tile, view) in tiled.tiles() {
+            let layout = tile.to_layout(view.layout());
+            sum += unsafe { *view.get_flat(&[0; 4]) } + layout.cosize() as f32 + tile.extents()[0] as f32;
+        }
+        assert_eq!(allocations(), before, "tiles allocated");
+        assert!(sum > 0.0);
+
+        let mut tiled = TiledTensorViewMut::new(t.as_view_mut(), Layout::row_major([4, 3, 2, 2]));
+        let before = allocations();
+        for (_, mut view) in tiled.tiles_mut() {
+            unsafe { *view.get_flat_mut(&[0; 4]) = 0.0 };
+        }
+        assert_eq!(allocations(), before, "mutable tiles allocated");
+
+        let m = iota::<f32>(7, 5, 0);
+        let tiled = TiledTensorView::new_static(m.as_view(), StaticTiler::<4, 3>);
+        let before = allocations();
+        assert_eq!(tiled.edge_tiles().map(|(_, view)| view.layout().size()).sum::<usize>(), 35 - 12);
+        assert_eq!(allocations(), before, "edge tiles allocated");
+    }
+
     #[test]
     fn single_tile_equals_whole_tensor() {
         let m = 4;
         let n = 4;
 
-        let t = make_tensor_2d(m, n);
+        let t = iota::<f32>(m, n, 0);
         let tiler = Layout::row_major(Shape::new(Tuple::int(vec![m, n])));
 
         let mut tiled = TiledTensorView::new(t.as_view(), tiler);
@@ -0,0 +1,196 @@
+// src/trace.rs
+//
+// In-memory recorder of per-tile events from the tiled drivers, with a
+// Chrome trace exporter (load the JSON in chrome://tracing or Perfetto).
+// Drivers open a `TileSpan` around each tile; spans are dropped on the floor
+// unless a recording is running.
+
+use std::cell::Cell;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::error::{Error, Result};
+use crate::tiled_tensor::Tile;
+
+/// One tile executed by a driver
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TileEvent {
+    /// Driver that ran the tile
+    pub name: &'static str,
+    /// `Tile::index` in the driver's tile grid
+    pub tile: usize,
+    /// Small per-process thread number, in order of first event
+    pub thread: usize,
+    /// Nanoseconds since the first recording started
+    pub start_ns: u64,
+    pub end_ns: u64,
+    /// Operand bytes read plus result bytes written
+    pub bytes: usize,
+    pub flops: usize,
+}
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static EVENTS: Mutex<Vec<TileEvent>> = Mutex::new(Vec::new());
+static EPOCH: OnceLock<Instant> = OnceLock::new();
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+/// Bumped by every `start`, so spans left over from an earlier recording
+/// are dropped instead of landing in the current one
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static THREAD: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Start recording tile events from every thread, discarding anything an
+/// earlier recording left behind
+pub fn start() {
+    EPOCH.get_or_init(Instant::now);
+    let mut events = EVENTS.lock().unwrap();
+    events.clear();
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    RECORDING.store(true, Ordering::Release);
+}
+
+/// Stop recording and return the events recorded so far
+pub fn stop() -> Vec<TileEvent> {
+    RECORDING.store(false, Ordering::Release);
+    std::mem::take(&mut *EVENTS.lock().unwrap())
+}
+
+pub fn is_recording() -> bool {
+    RECORDING.load(Ordering::Acquire)
+}
+
+fn now_ns() -> u64 {
+    EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u64
+}
+
+fn thread_number() -> usize {
+    THREAD.with(|t| match t.get() {
+        Some(n) => n,
+        None => {
+            let n = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+            t.set(Some(n));
+            n
+        }
+    })
+}
+
+/// Records a `TileEvent` covering its lifetime
+pub struct TileSpan {
+    event: Option<TileEvent>,
+    generation: usize,
+}
+
+impl TileSpan {
+    pub fn new(name: &'static str, tile: &Tile, bytes: usize, flops: usize) -> Self {
+        let generation = GENERATION.load(Ordering::Acquire);
+        let event = is_recording().then(|| TileEvent {
+            name,
+            tile: tile.index(),
+            thread: thread_number(),
+            start_ns: now_ns(),
+            end_ns: 0,
+            bytes,
+            flops,
+        });
+        Self { event, generation }
+    }
+}
+
+impl Drop for TileSpan {
+    fn drop(&mut self) {
+        if let Some(mut event) = self.event.take() {
+            event.end_ns = now_ns();
+            let mut events = EVENTS.lock().unwrap();
+            if is_recording() && GENERATION.load(Ordering::Acquire) == self.generation {
+                events.push(event);
+            }
+        }
+    }
+}
+
+/// Chrome trace JSON: one complete ("X") event per tile, one row per thread
+pub fn to_chrome_json(events: &[TileEvent]) -> String {
+    let mut out = String::from("{\"traceEvents\":[");
+    for (i, e) in events.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        let _ = write!(
+            out,
+            "{{\"name\":\"{}\",\"ph\":\"X\",\"pid\":0,\"tid\":{},\"ts\":{:.3},\"dur\":{:.3},\
+             \"args\":{{\"tile\":{},\"bytes\":{},\"flops\":{}}}}}",
+            e.name,
+            e.thread,
+            e.start_ns as f64 / 1e3,
+            e.end_ns.saturating_sub(e.start_ns) as f64 / 1e3,
+            e.tile,
+            e.bytes,
+            e.flops
+        );
+    }
+    out.push_str("]}");
+    out
+}
+
+pub fn write_chrome_trace(path: impl AsRef<Path>, events: &[TileEvent]) -> Result<()> {
+    let path = path.as_ref();
+    std::fs::write(path, to_chrome_json(events)).map_err(|e| Error::Io(format!("{}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+    use crate::gemm::gemm_f32_tiled_parallel;
+    use crate::layout::Layout;
+    use crate::tensor::Tensor;
+    use crate::tiled_tensor::TileIter;
+
+    // Recordings are process-wide; tests that start one take turns
+    static RECORDER: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn spans_from_an_aborted_recording_are_dropped() {
+        let _turn = RECORDER.lock().unwrap();
+        let tile = TileIter::new(vec![1], vec![1]).next().unwrap();
+
+        // The first recording is never stopped; a new one replaces it
+        start();
+        drop(TileSpan::new("stale", &tile, 0, 0));
+        let open = TileSpan::new("stale", &tile, 0, 0);
+        start();
+        drop(open);
+        assert!(stop().iter().all(|e| e.name != "stale"));
+    }
+
+    #[test]
+    fn tiled_gemm_records_one_event_per_tile() {
+        let _turn = RECORDER.lock().unwrap();
+        let (m, k, n) = (8, 97, 8);
+        let a = Tensor::new(vec![1.0f32; m * k], Layout::row_major([m, k]));
+        let b = Tensor::new(vec![1.0f32; k * n], Layout::row_major([k, n]));
+        let mut c = Tensor::new(vec![0.0f32; m * n], Layout::row_major([m, n]));
+        let tiler = Layout::row_major([4, 4]);
+
+        start();
+        gemm_f32_tiled_parallel(&NativeBlas, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Some(&tiler), 1.0, 0.0);
+        let events = stop();
+
+        // Other tests may run tiled GEMMs concurrently; keep this one's tiles
+        let ours: Vec<_> = events.iter().filter(|e| e.flops == 2 * 4 * 4 * k).collect();
+        let mut tiles: Vec<usize> = ours.iter().map(|e| e.tile).collect();
+        tiles.sort();
+        assert_eq!(tiles, vec![0, 1, 2, 3]);
+        assert!(ours.iter().all(|e| e.end_ns >= e.start_ns && e.bytes == 4 * (4 * k * 2 + 2 * 16)));
+
+        let json = to_chrome_json(&events);
+        assert!(json.starts_with("{\"traceEvents\":[{\"name\":"));
+        assert!(json.contains("\"name\":\"gemm_f32_tiled_parallel\",\"ph\":\"X\""));
+        assert!(json.ends_with("}}]}"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::bench_utils::{random_matrix_f32, time_kernel};
+use crate::blas::{BlasBackend, BlasTranspose, GemmAlgo};
+use crate::gemm::gemm_f32_tiled_parallel;
+use crate::hw::topology;
+use crate::layout::Layout;
+use crate::shape::Shape;
+use crate::tensor::Tensor;
+use crate::tuple::Tuple;
+
+/// C tile extents for the tiled GEMM drivers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileConfig {
+    pub tile_m: usize,
+    pub tile_n: usize,
+}
+
+impl TileConfig {
+    pub fn new(tile_m: usize, tile_n: usize) -> Self {
+        assert!(tile_m > 0 && tile_n > 0, "TileConfig: tile extents must be > 0");
+        Self { tile_m, tile_n }
+    }
+
+    /// Tiler layout accepted by `gemm_f32_tiled_parallel` / `TiledTensorView`
+    pub fn tiler(&self) -> Layout {
+        Layout::row_major(Shape::new(Tuple::int(vec![self.tile_m, self.tile_n])))
+    }
+
+    /// Bytes touched by one tile step: an A panel, a B panel and the C tile
+    pub fn working_set_bytes(&self, k: usize, dtype_size: usize) -> usize {
+        (self.tile_m * k + k * self.tile_n + self.tile_m * self.tile_n) * dtype_size
+    }
+}
+
+/// Tuning knobs
+#[derive(Debug, Clone, Copy)]
+pub struct TuneOptions {
+    pub warmup: usize,
+    pub iters: usize,
+    /// Per-core cache budget the working set of a tile should fit in
+    pub cache_bytes: usize,
+}
+
+impl Default for TuneOptions {
+    fn default() -> Self {
+        Self {
+            warmup: 1,
+            iters: 3,
+            cache_bytes: topology().caches.l2,
+        }
+    }
+}
+
+const EXTENTS: [usize; 6] = [16, 32, 64, 128, 256, 512];
+
+/// Candidate tile shapes for an `m x k` by `k x n` GEMM: square and 1:2
+/// rectangles whose working set fits in `cache_bytes`, clamped to the matrix.
+/// Always returns at least one candidate.
+pub fn candidate_tiles(m: usize, n: usize, k: usize, cache_bytes: usize) -> Vec<TileConfig> {
+    let mut out: Vec<TileConfig> = Vec::new();
+
+    for &t in &EXTENTS {
+        for (tm, tn) in [(t, t), (t, 2 * t), (2 * t, t)] {
+            let cfg = TileConfig::new(tm.min(m.max(1)), tn.min(n.max(1)));
+            if cfg.working_set_bytes(k, 4) <= cache_bytes && !out.contains(&cfg) {
+                out.push(cfg);
+            }
+        }
+    }
+
+    if out.is_empty() {
+        out.push(TileConfig::new(EXTENTS[0].min(m.max(1)), EXTENTS[0].min(n.max(1))));
+    }
+    out
+}
+
+/// Measure every candidate on random `m x k`, `k x n` operands and return the fastest.
+pub fn tune_gemm_f32<B: BlasBackend + Sync>(
+    backend: &B,
+    m: usize,
+    n: usize,
+    k: usize,
+    opts: &TuneOptions,
+) -> TileConfig {
+    let a = random_matrix_f32(m, k, 0x5eed);
+    let b = random_matrix_f32(k, n, 0x5eed + 1);
+    let mut c = Tensor::new(vec![0.0f32; m * n], Layout::row_major(Shape::new(Tuple::int(vec![m, n]))));
+
+    candidate_tiles(m, n, k, opts.cache_bytes)
+        .into_iter()
+        .map(|cfg| {
+            let tiler = cfg.tiler();
+            let timing = time_kernel(opts.warmup, opts.iters, || {
+                gemm_f32_tiled_parallel(
+                    backend, &a.as_view(), &b.as_view(), &mut c.as_view_mut(), Some(&tiler), 1.0, 0.0,
+                );
+            });
+            (cfg, timing.median)
+        })
+        .min_by_key(|(_, t)| *t)
+        .map(|(cfg, _)| cfg)
+        .expect("candidate_tiles returns at least one tile")
+}
+
+/// Time every algorithm `backend` offers on random row-major operands and
+/// return the fastest, for `GemmConfig::algo`.
+pub fn tune_gemm_algo<B: BlasBackend>(backend: &B, m: usize, n: usize, k: usize, opts: &TuneOptions) -> GemmAlgo {
+    let a = random_matrix_f32(m, k, 0x5eed);
+    let b = random_matrix_f32(k, n, 0x5eed + 1);
+    let mut c = vec![0.0f32; m * n];
+    let (mi, ni, ki) = (m as i32, n as i32, k as i32);
+
+    backend
+        .gemm_algos()
+        .iter()
+        .map(|&algo| {
+            let timing = time_kernel(opts.warmup, opts.iters, || {
+                let (a, b, c) = (a.data().as_ptr(), b.data().as_ptr(), c.as_mut_ptr());
+                let nt = BlasTranspose::NoTrans;
+                // SAFETY: a, b and c are distinct row-major m x k, k x n and m x n buffers
+                unsafe { backend.gemm_f32_algo(algo, nt, nt, mi, ni, ki, 1.0, a, ki.max(1), b, ni.max(1), 0.0, c, ni.max(1)) };
+            });
+            (algo, timing.median)
+        })
+        .min_by_key(|(_, t)| *t)
+        .map(|(algo, _)| algo)
+        .expect("gemm_algos always includes Default")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blas::NativeBlas;
+
+    #[test]
+    fn candidates_fit_cache_and_matrix() {
+        let cands = candidate_tiles(100, 40, 64, 64 * 1024);
+        assert!(!cands.is_empty());
+        for c in &cands {
+            assert!(c.tile_m <= 100 && c.tile_n <= 40);
+            assert!(c.working_set_bytes(64, 4) <= 64 * 1024);
+        }
+    }
+
+    #[test]
+    fn candidates_never_empty() {
+        let cands = candidate_tiles(4096, 4096, 1 << 20, 1024);
+        assert_eq!(cands, vec![TileConfig::new(16, 16)]);
+    }
+
+    #[test]
+    fn tune_returns_a_candidate() {
+        let opts = TuneOptions { warmup: 0, iters: 1, cache_bytes: 32 * 1024 };
+        let best = tune_gemm_f32(&NativeBlas, 24, 20, 8, &opts);
+        assert!(candidate_tiles(24, 20, 8, opts.cache_bytes).contains(&best));
+
+        let algo = tune_gemm_algo(&NativeBlas, 24, 20, 8, &opts);
+        assert!(NativeBlas.gemm_algos().contains(&algo));
+    }
+}
@@ -1,4 +1,5 @@
 use std::fmt;
+use std::ops::Range;
 
 /// Recursive integer tuple (CuTe-style)
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +24,16 @@ impl Tuple {
         Tuple::Tup(v)
     }
 
+    /// Create a tuple from its top-level modes.
+    /// Modes that are all scalars collapse into a single Int tuple.
+    pub fn from_modes(modes: Vec<Tuple>) -> Self {
+        if modes.iter().all(|m| matches!(m, Tuple::Int(v) if v.len() == 1)) {
+            Tuple::Int(modes.into_iter().flat_map(|m| m.flatten()).collect())
+        } else {
+            Tuple::Tup(modes)
+        }
+    }
+
     /// Total number of leaf elements
     pub fn size(&self) -> usize {
         match self {
@@ -71,6 +82,66 @@ impl Tuple {
         }
     }
 
+    /// Element reached by taking `path[0]` at the top level, `path[1]` in
+    /// that mode, and so on; the empty path is the whole tuple. A scalar
+    /// mode is its own only element, so a trailing 0 on it is allowed.
+    pub fn get_path(&self, path: &[usize]) -> Tuple {
+        match path.split_first() {
+            None => self.clone(),
+            Some((&i, rest)) => {
+                assert!(i < self.len(), "Index {i} out of bounds in Tuple::get_path on {self}");
+                self.get(i).get_path(rest)
+            }
+        }
+    }
+
+    /// Replace the element at `path` (as in `get_path`) with `value`,
+    /// keeping the rest of the nesting. A scalar inside an `Int` tuple that
+    /// becomes a nested tuple turns its parent into a `Tup`.
+    pub fn set_path(&mut self, path: &[usize], value: Tuple) {
+        let Some((&i, rest)) = path.split_first() else {
+            *self = value;
+            return;
+        };
+        assert!(i < self.len(), "Index {i} out of bounds in Tuple::set_path on {self}");
+        match self {
+            Tuple::Tup(vs) => vs[i].set_path(rest, value),
+            Tuple::Int(v) if v.len() == 1 => self.set_path(rest, value),
+            Tuple::Int(v) => {
+                let mut mode = Tuple::int1(v[i]);
+                mode.set_path(rest, value);
+                match mode {
+                    Tuple::Int(x) if x.len() == 1 => v[i] = x[0],
+                    mode => {
+                        let mut modes: Vec<Tuple> = v.iter().map(|&x| Tuple::int1(x)).collect();
+                        modes[i] = mode;
+                        *self = Tuple::Tup(modes);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Replace flattened leaf `i` (the `i`-th entry of `flatten`) with `value`
+    pub fn replace_leaf(&mut self, i: usize, value: usize) {
+        fn recur(t: &mut Tuple, i: &mut usize, value: usize) -> bool {
+            match t {
+                Tuple::Int(v) if *i < v.len() => {
+                    v[*i] = value;
+                    true
+                }
+                Tuple::Int(v) => {
+                    *i -= v.len();
+                    false
+                }
+                Tuple::Tup(vs) => vs.iter_mut().any(|m| recur(m, i, value)),
+            }
+        }
+        if !recur(self, &mut { i }, value) {
+            panic!("Leaf {i} out of bounds in Tuple::replace_leaf on {self}");
+        }
+    }
+
     /// Recursive flattened iterator over leaf integers
     pub fn iter_flat(&self) -> Box<dyn Iterator<Item = &usize> + '_> {
         match self {
@@ -148,6 +219,167 @@ impl Tuple {
     }
 }
 
+/* ===== Congruence ===== */
+
+impl Tuple {
+    /// Normal form for comparing structure:
+    /// - a single-mode Tup is replaced by its mode,
+    /// - a Tup of scalars becomes one Int tuple.
+    pub fn canonicalize(&self) -> Tuple {
+        match self {
+            Tuple::Int(v) => Tuple::Int(v.clone()),
+            Tuple::Tup(vs) if vs.len() == 1 => vs[0].canonicalize(),
+            Tuple::Tup(vs) => Tuple::from_modes(vs.iter().map(|t| t.canonicalize()).collect()),
+        }
+    }
+
+    /// True if both tuples have the same nesting and rank at every level,
+    /// ignoring representation differences removed by `canonicalize`.
+    pub fn is_congruent(&self, other: &Tuple) -> bool {
+        fn recur(a: &Tuple, b: &Tuple) -> bool {
+            match (a, b) {
+                (Tuple::Int(x), Tuple::Int(y)) => x.len() == y.len(),
+                (Tuple::Tup(x), Tuple::Tup(y)) => {
+                    x.len() == y.len() && x.iter().zip(y.iter()).all(|(a, b)| recur(a, b))
+                }
+                _ => false,
+            }
+        }
+        recur(&self.canonicalize(), &other.canonicalize())
+    }
+}
+
+/* ===== Mode editing ===== */
+
+// Each edit rebuilds the top level with `from_modes`, so a result whose
+// modes are all scalars is an `Int` tuple again. Nested modes are moved
+// as a whole.
+
+impl Tuple {
+    /// Top-level modes, a scalar of an `Int` tuple becoming `int1`
+    pub fn modes(&self) -> Vec<Tuple> {
+        (0..self.len()).map(|i| self.get(i)).collect()
+    }
+
+    /// `mode` added after the last mode
+    pub fn append_mode(&self, mode: impl Into<Tuple>) -> Tuple {
+        self.insert_mode(self.len(), mode)
+    }
+
+    /// `mode` added before the first mode
+    pub fn prepend_mode(&self, mode: impl Into<Tuple>) -> Tuple {
+        self.insert_mode(0, mode)
+    }
+
+    /// `mode` added so that it becomes mode `i`
+    pub fn insert_mode(&self, i: usize, mode: impl Into<Tuple>) -> Tuple {
+        assert!(i <= self.len(), "Tuple::insert_mode: position {i} past rank {}", self.len());
+        let mut modes = self.modes();
+        modes.insert(i, mode.into());
+        Tuple::from_modes(modes)
+    }
+
+    /// Mode `i` left out
+    pub fn remove_mode(&self, i: usize) -> Tuple {
+        assert!(i < self.len(), "Tuple::remove_mode: mode {i} out of range for rank {}", self.len());
+        let mut modes = self.modes();
+        modes.remove(i);
+        Tuple::from_modes(modes)
+    }
+
+    /// Modes `range` nested into a single mode at `range.start`, like
+    /// CuTe's `group_modes<B, E>`; `(2,3,4).group_modes(0..2)` is `((2,3),4)`
+    pub fn group_modes(&self, range: Range<usize>) -> Tuple {
+        let Range { start, end } = range;
+        assert!(start < end && end <= self.len(), "Tuple::group_modes: bad range {start}..{end} for rank {}", self.len());
+        let mut modes = self.modes();
+        let group = Tuple::from_modes(modes.drain(start..end).collect());
+        modes.insert(start, group);
+        Tuple::from_modes(modes)
+    }
+
+    /// Top-level modes `modes`, in that order, like CuTe's `select`. Modes
+    /// may be repeated or left out.
+    pub fn select(&self, modes: &[usize]) -> Tuple {
+        let all = self.modes();
+        Tuple::from_modes(modes.iter().map(|&i| {
+            assert!(i < all.len(), "Tuple::select: mode {i} out of range for rank {}", all.len());
+            all[i].clone()
+        }).collect())
+    }
+
+    /// The sub-modes of mode `i` spliced into the top level, undoing
+    /// `group_modes`; a scalar mode stays as it is
+    pub fn ungroup(&self, i: usize) -> Tuple {
+        assert!(i < self.len(), "Tuple::ungroup: mode {i} out of range for rank {}", self.len());
+        let mut modes = self.modes();
+        let inner = modes.remove(i).modes();
+        modes.splice(i..i, inner);
+        Tuple::from_modes(modes)
+    }
+}
+
+/* ===== Hierarchical arithmetic ===== */
+
+impl Tuple {
+    /// Apply `f` leaf-wise over two congruent tuples.
+    fn zip_with(&self, rhs: &Tuple, op: &str, f: &impl Fn(usize, usize) -> usize) -> Tuple {
+        match (self, rhs) {
+            (Tuple::Int(a), Tuple::Int(b)) => {
+                assert_eq!(a.len(), b.len(), "Tuple::{op} rank mismatch: {self} vs {rhs}");
+                Tuple::Int(a.iter().zip(b.iter()).map(|(&x, &y)| f(x, y)).collect())
+            }
+            (Tuple::Tup(a), Tuple::Tup(b)) => {
+                assert_eq!(a.len(), b.len(), "Tuple::{op} rank mismatch: {self} vs {rhs}");
+                Tuple::Tup(a.iter().zip(b.iter()).map(|(x, y)| x.zip_with(y, op, f)).collect())
+            }
+            _ if self.is_congruent(rhs) => self.canonicalize().zip_with(&rhs.canonicalize(), op, f),
+            _ => panic!("Tuple::{op} requires congruent tuples: {self} vs {rhs}"),
+        }
+    }
+
+    /// Element-wise sum
+    pub fn add(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "add", &|x, y| x + y)
+    }
+
+    /// Element-wise difference (panics on underflow)
+    pub fn sub(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "sub", &|x, y| x.checked_sub(y).expect("Tuple::sub underflow"))
+    }
+
+    /// Element-wise product
+    pub fn mul(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "mul", &|x, y| x * y)
+    }
+
+    /// Element-wise floor division
+    pub fn div(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "div", &|x, y| x / y)
+    }
+
+    /// Element-wise minimum
+    pub fn min(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "min", &|x, y| x.min(y))
+    }
+
+    /// Element-wise ceiling division
+    pub fn ceil_div(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "ceil_div", &|x, y| x.div_ceil(y))
+    }
+
+    /// CuTe `shape_div`: ceiling division where one side must divide the other.
+    pub fn shape_div(&self, rhs: &Tuple) -> Tuple {
+        self.zip_with(rhs, "shape_div", &|x, y| {
+            assert!(
+                x % y == 0 || y % x == 0,
+                "Tuple::shape_div requires divisibility: {x} vs {y}"
+            );
+            x.div_ceil(y)
+        })
+    }
+}
+
 impl fmt::Display for Tuple {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -183,6 +415,54 @@ impl fmt::Display for Tuple {
     }
 }
 
+/* ===== Conversions ===== */
+
+impl From<usize> for Tuple {
+    fn from(x: usize) -> Self {
+        Tuple::int1(x)
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for Tuple {
+    fn from(v: [usize; N]) -> Self {
+        Tuple::Int(v.to_vec())
+    }
+}
+
+impl From<&[usize]> for Tuple {
+    fn from(v: &[usize]) -> Self {
+        Tuple::Int(v.to_vec())
+    }
+}
+
+impl From<Vec<usize>> for Tuple {
+    fn from(v: Vec<usize>) -> Self {
+        Tuple::Int(v)
+    }
+}
+
+impl From<&Tuple> for Tuple {
+    fn from(t: &Tuple) -> Self {
+        t.clone()
+    }
+}
+
+/// Rust tuples map to modes: `(2, (3, 4))` becomes `(2,(3,4))`.
+macro_rules! impl_from_rust_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Into<Tuple>),+> From<($($name,)+)> for Tuple {
+            #[allow(non_snake_case)]
+            fn from(($($name,)+): ($($name,)+)) -> Self {
+                Tuple::from_modes(vec![$($name.into()),+])
+            }
+        }
+    };
+}
+
+impl_from_rust_tuple!(A, B);
+impl_from_rust_tuple!(A, B, C);
+impl_from_rust_tuple!(A, B, C, D);
+
 pub type Stride = Tuple;
 pub type Indices = Tuple;
 
@@ -224,6 +504,54 @@ mod tests {
         assert_eq!(t.get(1).to_string(), "(3,4)");
     }
 
+    #[test]
+    fn paths_read_and_edit_nested_modes() {
+        // (2,(3,(4,5)))
+        let mut t = Tuple::tup(vec![
+            Tuple::int1(2),
+            Tuple::tup(vec![Tuple::int1(3), Tuple::int(vec![4, 5])]),
+        ]);
+        assert_eq!(t.get_path(&[]), t);
+        assert_eq!(t.get_path(&[1, 1]).to_string(), "(4,5)");
+        assert_eq!(t.get_path(&[1, 1, 0]), Tuple::int1(4));
+        assert_eq!(t.get_path(&[0, 0]), Tuple::int1(2));
+
+        t.set_path(&[1, 1, 1], Tuple::int1(7));
+        assert_eq!(t.to_string(), "(2,(3,(4,7)))");
+        t.set_path(&[1, 1, 0], Tuple::int(vec![2, 2]));
+        assert_eq!(t.to_string(), "(2,(3,((2,2),7)))");
+        t.set_path(&[0], Tuple::int(vec![1, 2]));
+        assert_eq!(t.to_string(), "((1,2),(3,((2,2),7)))");
+
+        t.replace_leaf(4, 9);
+        assert_eq!(t.flatten(), vec![1, 2, 3, 2, 9, 7]);
+        let mut flat = Tuple::int(vec![8, 6]);
+        flat.replace_leaf(1, 3);
+        assert_eq!(flat, Tuple::int(vec![8, 3]));
+    }
+
+    #[test]
+    fn mode_edits_match_cute() {
+        let t = Tuple::int(vec![2, 3, 4]);
+        assert_eq!(t.append_mode(5).to_string(), "(2,3,4,5)");
+        assert_eq!(t.prepend_mode(Tuple::int(vec![6, 7])).to_string(), "((6,7),2,3,4)");
+        assert_eq!(t.insert_mode(1, 9).to_string(), "(2,9,3,4)");
+        assert_eq!(t.remove_mode(1), Tuple::int(vec![2, 4]));
+
+        let grouped = t.group_modes(0..2);
+        assert_eq!(grouped.to_string(), "((2,3),4)");
+        assert_eq!(t.group_modes(1..3).to_string(), "(2,(3,4))");
+        assert_eq!(grouped.ungroup(0), t);
+        assert_eq!(grouped.ungroup(1), grouped);
+        assert_eq!(grouped.remove_mode(0), Tuple::int1(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds in Tuple::replace_leaf")]
+    fn replace_leaf_past_the_end_panics() {
+        Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 4])]).replace_leaf(3, 1);
+    }
+
     #[test]
     fn tuple_iter_flat() {
         let t = Tuple::tup(vec![
@@ -233,5 +561,86 @@ mod tests {
         let elems: Vec<usize> = t.iter_flat().copied().collect();
         assert_eq!(elems, vec![2,3,4,5]);
     }
+
+    #[test]
+    fn tuple_arithmetic_is_hierarchical() {
+        let a = Tuple::tup(vec![Tuple::int1(8), Tuple::int(vec![6, 5])]);
+        let b = Tuple::tup(vec![Tuple::int1(3), Tuple::int(vec![2, 4])]);
+
+        assert_eq!(a.add(&b).to_string(), "(11,(8,9))");
+        assert_eq!(a.sub(&b).to_string(), "(5,(4,1))");
+        assert_eq!(a.mul(&b).to_string(), "(24,(12,20))");
+        assert_eq!(a.div(&b).to_string(), "(2,(3,1))");
+        assert_eq!(a.min(&b).to_string(), "(3,(2,4))");
+        assert_eq!(a.ceil_div(&b).to_string(), "(3,(3,2))");
+    }
+
+    #[test]
+    fn tuple_shape_div() {
+        let a = Tuple::int(vec![8, 2]);
+        let b = Tuple::int(vec![4, 4]);
+        assert_eq!(a.shape_div(&b).to_string(), "(2,1)");
+    }
+
+    #[test]
+    #[should_panic(expected = "divisibility")]
+    fn tuple_shape_div_rejects_coprime() {
+        Tuple::int1(6).shape_div(&Tuple::int1(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "congruent")]
+    fn tuple_arithmetic_rejects_mismatched_nesting() {
+        let a = Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 4])]);
+        let b = Tuple::int(vec![2, 3, 4]);
+        a.add(&b);
+    }
+
+    #[test]
+    fn tuple_arithmetic_accepts_equivalent_nesting() {
+        let a = Tuple::tup(vec![Tuple::int1(2), Tuple::int1(3)]);
+        let b = Tuple::int(vec![2, 3]);
+        assert_eq!(a.add(&b), Tuple::int(vec![4, 6]));
+    }
+
+    #[test]
+    fn tuple_conversions() {
+        assert_eq!(Tuple::from(5), Tuple::int1(5));
+        assert_eq!(Tuple::from([2, 3]), Tuple::int(vec![2, 3]));
+        assert_eq!(Tuple::from(&[2usize, 3][..]), Tuple::int(vec![2, 3]));
+        assert_eq!(Tuple::from((2, 3, 4)), Tuple::int(vec![2, 3, 4]));
+        assert_eq!(
+            Tuple::from((2, (3, 4))),
+            Tuple::tup(vec![Tuple::int1(2), Tuple::int(vec![3, 4])])
+        );
+        assert_eq!(
+            Tuple::from(([1, 2], 3)),
+            Tuple::tup(vec![Tuple::int(vec![1, 2]), Tuple::int1(3)])
+        );
+    }
+
+    #[test]
+    fn tuple_canonicalize() {
+        let t = Tuple::tup(vec![Tuple::tup(vec![Tuple::int1(2), Tuple::int1(3)])]);
+        assert_eq!(t.canonicalize(), Tuple::int(vec![2, 3]));
+
+        let nested = Tuple::tup(vec![
+            Tuple::tup(vec![Tuple::int1(4)]),
+            Tuple::tup(vec![Tuple::int1(5), Tuple::int1(6)]),
+        ]);
+        assert_eq!(
+            nested.canonicalize(),
+            Tuple::tup(vec![Tuple::int1(4), Tuple::int(vec![5, 6])])
+        );
+    }
+
+    #[test]
+    fn tuple_congruence() {
+        let a = Tuple::tup(vec![Tuple::int1(8), Tuple::int(vec![2, 4])]);
+        let b = Tuple::tup(vec![Tuple::int1(1), Tuple::tup(vec![Tuple::int1(9), Tuple::int1(7)])]);
+        assert!(a.is_congruent(&b));
+        assert!(!a.is_congruent(&Tuple::int(vec![8, 2, 4])));
+        assert!(!Tuple::int(vec![1, 2]).is_congruent(&Tuple::int(vec![1, 2, 3])));
+    }
 }
 